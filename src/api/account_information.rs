@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
 use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 use crate::api::routes::RobloxApi;
 use crate::errors::RoboltError;
 use crate::utils::client::Authenticated;
-use crate::utils::response::CountResponse;
+use crate::utils::response::{CountResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
 use crate::Robolt;
 
 impl<State> Robolt<State> {
@@ -47,6 +51,21 @@ impl Robolt<Authenticated> {
 			.map(|res| res.description)
 	}
 
+	/// Changes the profile description of the authenticated user, returning
+	/// the description as saved, which Roblox may have filtered
+	pub async fn set_description(&self, description: &str) -> Result<String, RoboltError> {
+		let user_id = self.authenticated_user_id().await?;
+		let body = HashMap::from([("description", description)]);
+
+		self.request(RobloxApi::AccountInformation, "/v1/description")
+			.method(Method::POST)
+			.invalidates(RobloxApi::Users, format!("/v1/users/{user_id}"))
+			.idempotency(Idempotency::Idempotent)
+			.send_body::<_, ProfileDescription>(body)
+			.await
+			.map(|res| res.description)
+	}
+
 	pub async fn gender(&self) -> Result<Gender, RoboltError> {
 		self.request(RobloxApi::AccountInformation, "/v1/gender")
 			.send::<GenderResponse>()
@@ -54,6 +73,18 @@ impl Robolt<Authenticated> {
 			.map(|res| res.gender)
 	}
 
+	pub async fn set_gender(&self, gender: Gender) -> Result<(), RoboltError> {
+		let body = HashMap::from([("gender", (gender as u8).to_string())]);
+
+		self.request(RobloxApi::AccountInformation, "/v1/gender")
+			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.send_body::<_, EmptyResponse>(body)
+			.await?;
+
+		Ok(())
+	}
+
 	pub async fn consecutive_xbox_logins(&self) -> Result<u16, RoboltError> {
 		self.request(RobloxApi::AccountInformation, "/v1/xbox-live/consecutive-login-days")
 			.send::<CountResponse<u16>>()
@@ -1,46 +1,175 @@
+use std::any::type_name;
 use std::error::Error;
-use std::intrinsics::type_name;
+use std::fmt;
 use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use reqwest::header::{CONTENT_LENGTH, COOKIE};
-use reqwest::StatusCode;
+use reqwest::header::{HeaderValue, CONTENT_LENGTH, COOKIE};
+use reqwest::{Method, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
 
-use crate::api::routes::RobloxApi;
+use crate::api::routes::{encode_query_value, RobloxApi};
+use crate::errors::{RoboltError, RoboltErrorKind};
 use crate::utils::client::{default_client_headers, Authenticated, Unauthenticated};
+#[cfg(feature = "test-util")]
+use crate::utils::middleware::RequestParts;
+use crate::utils::response::EmptyResponse;
+use crate::utils::retry::Idempotency;
 use crate::Robolt;
 
 impl<State> Robolt<State> {
 	pub fn is_authenticated(&self) -> bool {
 		type_name::<State>() == type_name::<Authenticated>()
 	}
+
+	/// Checks whether a username can be used to sign up, `birthday` being an
+	/// ISO 8601 date
+	pub async fn validate_username(
+		&self,
+		username: &str,
+		birthday: &str,
+	) -> Result<UsernameValidation, RoboltError> {
+		self.request(
+			RobloxApi::Auth,
+			format!(
+				"/v1/usernames/validate?request.username={}&request.birthday={}&request.context=Signup",
+				encode_query_value(username),
+				encode_query_value(birthday)
+			),
+		)
+		.send()
+		.await
+	}
+
+	/// Checks whether a password can be used to sign up with a username,
+	/// without creating an account
+	///
+	/// The password is sent in the request body rather than the URL so that it
+	/// is never logged, and it is redacted from the [Debug] output of
+	/// [RequestParts](crate::middleware::RequestParts).
+	pub async fn validate_password(
+		&self,
+		username: &str,
+		password: &str,
+	) -> Result<PasswordValidation, RoboltError> {
+		let body = json!({
+			"username": username,
+			"password": password,
+		});
+
+		self.request(RobloxApi::Auth, "/v2/passwords/validate")
+			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.read_only()
+			.send_body(body)
+			.await
+	}
 }
 
 impl Robolt<Unauthenticated> {
-	pub async fn set_cookie(self, roblox_cookie: String) -> Result<Robolt<Authenticated>, Box<dyn Error>> {
-		let cookie = format!(".ROBLOSECURITY={roblox_cookie}");
+	/// Authenticates the client with a `.ROBLOSECURITY` cookie, see
+	/// [RobloxCookie::parse] for the accepted formats
+	///
+	/// Fails with [RoboltErrorKind::InvalidCookie] if Roblox rejects the
+	/// cookie.
+	pub async fn set_cookie(self, roblox_cookie: RobloxCookie) -> Result<Robolt<Authenticated>, RoboltError> {
+		let cookie = roblox_cookie.header_value();
 		let mut headers = default_client_headers();
 
-		headers.insert(COOKIE, cookie.parse()?);
-		headers.insert(CONTENT_LENGTH, "0".parse()?);
+		headers.insert(
+			COOKIE,
+			cookie
+				.parse()
+				.map_err(|_| RoboltError::invalid_cookie("Cookie is not a valid header value".to_string()))?,
+		);
+		headers.insert(CONTENT_LENGTH, HeaderValue::from_static("0"));
 
-		let res = self
-			.http
-			.post(format!("https://{}/v2/logout", RobloxApi::Auth.url()))
-			.headers(headers.clone())
-			.send()
-			.await?;
+		let url = self.endpoints.url(RobloxApi::Auth, "/v2/logout");
+
+		#[cfg(feature = "test-util")]
+		if let Some(transport) = &self.mock_transport {
+			let parts = RequestParts {
+				method: Method::POST,
+				url: url.clone(),
+				headers: headers.clone(),
+				body: None,
+				is_retry: false,
+				idempotency: Idempotency::NonIdempotent,
+			};
 
-		if !res.status().is_success() && res.status() != StatusCode::FORBIDDEN {
-			return Err("Invalid cookie".into());
+			let res = transport.respond(&parts)?;
+			return self.authenticate(cookie, res);
 		}
 
-		let csrf_token = res.headers().get("x-csrf-token").ok_or("No CSRF token found")?;
+		let res = self.http.post(url).headers(headers).send().await?;
+
+		self.authenticate(cookie, res)
+	}
+
+	/// Authenticates the client with a `.ROBLOSECURITY` cookie like
+	/// [Robolt::set_cookie], then verifies the cookie by fetching the
+	/// authenticated user, which is cached on the returned client
+	///
+	/// Roblox hands out a CSRF token even for cookies that expired or were
+	/// logged out, which [Robolt::set_cookie] therefore accepts. Such cookies
+	/// fail here with [RoboltErrorKind::InvalidCookie] instead of on the first
+	/// authenticated request.
+	pub async fn login(self, roblox_cookie: RobloxCookie) -> Result<Robolt<Authenticated>, RoboltError> {
+		let client = self.set_cookie(roblox_cookie).await?;
+
+		match client.authenticated_user().await {
+			Ok(_) => Ok(client),
+			Err(err) if err.status() == Some(StatusCode::UNAUTHORIZED.as_u16()) => Err(
+				RoboltError::invalid_cookie(format!("Cookie rejected with {}", StatusCode::UNAUTHORIZED)),
+			),
+			Err(err) => Err(err),
+		}
+	}
+
+	/// Builds the authenticated client from the response of the logout
+	/// endpoint, which hands out a CSRF token without logging out when called
+	/// without one
+	fn authenticate(self, cookie: String, res: Response) -> Result<Robolt<Authenticated>, RoboltError> {
+		let status = res.status();
+
+		if !status.is_success() && status != StatusCode::FORBIDDEN {
+			return Err(RoboltError::invalid_cookie(format!("Cookie rejected with {status}")));
+		}
+
+		let csrf_token = res
+			.headers()
+			.get("x-csrf-token")
+			.and_then(|token| token.to_str().ok())
+			.ok_or_else(|| RoboltError::from("No CSRF token found".to_string()))?;
 
 		Ok(Robolt {
 			state: PhantomData::<Authenticated>,
 			http: self.http,
 			cookie: Some(cookie),
-			xcsrf: Some(csrf_token.to_str()?.to_string()),
+			xcsrf: Arc::new(RwLock::new(Some(csrf_token.to_string()))),
+			api_key: self.api_key,
+			middleware: self.middleware,
+			retry: self.retry,
+			locked_groups: self.locked_groups,
+			max_response_size: self.max_response_size,
+			max_pages_per_crawl: self.max_pages_per_crawl,
+			schema_watch: self.schema_watch,
+			moderation_log: self.moderation_log,
+			dry_run: self.dry_run,
+			capture_error_bodies: self.capture_error_bodies,
+			cache: self.cache,
+			endpoints: self.endpoints,
+			user: Arc::default(),
+			raw_hosts: self.raw_hosts,
+			clock: self.clock,
+			sleeper: self.sleeper,
+			#[cfg(feature = "test-util")]
+			mock_transport: self.mock_transport,
 		})
 	}
 }
@@ -51,7 +180,431 @@ impl Robolt<Authenticated> {
 			state: PhantomData::<Unauthenticated>,
 			http: self.http,
 			cookie: None,
-			xcsrf: None,
+			xcsrf: Arc::default(),
+			api_key: self.api_key,
+			middleware: self.middleware,
+			retry: self.retry,
+			locked_groups: self.locked_groups,
+			max_response_size: self.max_response_size,
+			max_pages_per_crawl: self.max_pages_per_crawl,
+			schema_watch: self.schema_watch,
+			moderation_log: self.moderation_log,
+			dry_run: self.dry_run,
+			capture_error_bodies: self.capture_error_bodies,
+			cache: self.cache,
+			endpoints: self.endpoints,
+			user: Arc::default(),
+			raw_hosts: self.raw_hosts,
+			clock: self.clock,
+			sleeper: self.sleeper,
+			#[cfg(feature = "test-util")]
+			mock_transport: self.mock_transport,
 		}
 	}
+
+	/// Logs the session out on Roblox, invalidating the cookie, and returns
+	/// the client without it
+	///
+	/// See [Robolt::remove_cookie] to drop the cookie while keeping the
+	/// session valid.
+	pub async fn logout(self) -> Result<Robolt<Unauthenticated>, RoboltError> {
+		self.request(RobloxApi::Auth, "/v2/logout")
+			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.send::<EmptyResponse>()
+			.await?;
+
+		Ok(self.remove_cookie())
+	}
+
+	/// Checks whether the cookie is still accepted by Roblox using a
+	/// lightweight authenticated request
+	pub async fn auth_health(&self) -> AuthHealth {
+		let res = self
+			.request(RobloxApi::Users, "/v1/users/authenticated")
+			.send::<EmptyResponse>()
+			.await;
+
+		match res {
+			Ok(_) => AuthHealth::Healthy,
+			Err(err) if matches!(err.kind(), RoboltErrorKind::ChallengeRequired { .. }) => AuthHealth::Challenge,
+			Err(err) if matches!(err.status(), Some(401 | 403)) => AuthHealth::CookieInvalid,
+			Err(_) => AuthHealth::NetworkError,
+		}
+	}
+
+	/// Periodically runs [Robolt::auth_health] on a background task and
+	/// invokes `callback` with the previous and new state whenever the state
+	/// changes
+	///
+	/// The first check only establishes the initial state. The task is
+	/// stopped when the returned [AuthWatchdog] is dropped. A panic in the
+	/// callback is caught and ignored, it never stops the task.
+	///
+	/// # Panics
+	///
+	/// Panics if called outside of a Tokio runtime.
+	pub fn auth_watchdog<F>(&self, interval: Duration, callback: F) -> AuthWatchdog
+	where
+		F: Fn(AuthHealth, AuthHealth) + Send + 'static,
+	{
+		let client = self.clone();
+
+		let handle = tokio::spawn(async move {
+			let mut previous: Option<AuthHealth> = None;
+
+			loop {
+				let current = client.auth_health().await;
+
+				if let Some(previous) = previous.replace(current.clone()) {
+					if previous != current {
+						let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(previous, current)));
+					}
+				}
+
+				client.sleeper.sleep(interval).await;
+			}
+		});
+
+		AuthWatchdog {
+			handle,
+		}
+	}
+}
+
+impl Drop for AuthWatchdog {
+	fn drop(&mut self) {
+		self.handle.abort();
+	}
+}
+
+const COOKIE_NAME: &str = ".ROBLOSECURITY";
+const WARNING_PREFIX: &str = "_|WARNING:";
+const WARNING_SUFFIX: &str = "|_";
+
+#[derive(Clone, PartialEq, Eq)]
+/// A normalized `.ROBLOSECURITY` cookie
+pub struct RobloxCookie {
+	value: String,
+}
+
+impl RobloxCookie {
+	/// Parses a cookie in any of the formats it is commonly copied in:
+	///
+	/// - the raw token, with or without the `_|WARNING:...|_` prefix
+	/// - the `.ROBLOSECURITY=<token>` pair, or a whole `Cookie` header
+	///   containing it
+	/// - any of the above wrapped in quotes or as a JSON string
+	/// - a JSON cookie export such as `{"name": ".ROBLOSECURITY", "value":
+	///   "..."}`
+	/// - any of the above URL-encoded
+	pub fn parse(input: &str) -> Result<Self, CookieParseError> {
+		let input = unquote(input.trim())?;
+		let input = match input.starts_with('{') {
+			true => json_cookie_value(&input)?,
+			false => input,
+		};
+		let input = match input.contains('%') {
+			true => url_decode(&input)?,
+			false => input,
+		};
+		let input = input
+			.strip_prefix("Cookie:")
+			.or_else(|| input.strip_prefix("cookie:"))
+			.unwrap_or(&input)
+			.trim();
+
+		let value = match input.contains('=') {
+			true => unquote(find_cookie_pair(input)?.trim())?,
+			false => input.to_string(),
+		};
+
+		let token = match value.strip_prefix(WARNING_PREFIX) {
+			Some(rest) => match rest.split_once(WARNING_SUFFIX) {
+				Some((_, token)) => token,
+				None => return Err(CookieParseError::UnterminatedWarning),
+			},
+			None => value.as_str(),
+		};
+
+		if token.is_empty() {
+			return Err(CookieParseError::Empty);
+		}
+
+		if let Some(character) = value.chars().find(|c| !is_cookie_char(*c)) {
+			return Err(CookieParseError::InvalidCharacter(character));
+		}
+
+		Ok(Self {
+			value,
+		})
+	}
+
+	/// The cookie value, including the warning prefix if it had one
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+
+	/// The cookie value without the warning prefix
+	pub fn token(&self) -> &str {
+		self.value
+			.strip_prefix(WARNING_PREFIX)
+			.and_then(|rest| rest.split_once(WARNING_SUFFIX))
+			.map_or(&self.value, |(_, token)| token)
+	}
+
+	/// The value of the `Cookie` header sent with authenticated requests
+	pub fn header_value(&self) -> String {
+		format!("{COOKIE_NAME}={}", self.value)
+	}
+}
+
+impl FromStr for RobloxCookie {
+	type Err = CookieParseError;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		Self::parse(input)
+	}
+}
+
+#[doc(hidden)]
+impl fmt::Debug for RobloxCookie {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("RobloxCookie(..)")
+	}
+}
+
+/// Strips matching surrounding quotes, decoding JSON escapes in double
+/// quoted strings
+fn unquote(input: &str) -> Result<String, CookieParseError> {
+	match input.chars().next() {
+		Some('"') => serde_json::from_str::<String>(input).map_err(|_| CookieParseError::InvalidJson),
+		Some('\'') => input
+			.strip_prefix('\'')
+			.and_then(|rest| rest.strip_suffix('\''))
+			.map(str::to_string)
+			.ok_or(CookieParseError::UnterminatedQuote),
+		_ => Ok(input.to_string()),
+	}
+}
+
+/// Extracts the value of a cookie exported as a JSON object such as
+/// `{"name": ".ROBLOSECURITY", "value": "..."}`
+fn json_cookie_value(input: &str) -> Result<String, CookieParseError> {
+	let cookie = serde_json::from_str::<Value>(input).map_err(|_| CookieParseError::InvalidJson)?;
+
+	if let Some(name) = cookie["name"].as_str().filter(|name| *name != COOKIE_NAME) {
+		return Err(CookieParseError::MissingCookie(vec![name.to_string()]));
+	}
+
+	cookie["value"]
+		.as_str()
+		.map(str::to_string)
+		.ok_or(CookieParseError::InvalidJson)
+}
+
+/// Finds the value of the `.ROBLOSECURITY` pair in a `name=value; ...` list
+fn find_cookie_pair(input: &str) -> Result<String, CookieParseError> {
+	let mut names = Vec::new();
+
+	for pair in input.split(';').map(str::trim).filter(|pair| !pair.is_empty()) {
+		let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+		if name.trim() == COOKIE_NAME {
+			return Ok(value.to_string());
+		}
+
+		names.push(name.trim().to_string());
+	}
+
+	Err(CookieParseError::MissingCookie(names))
+}
+
+fn url_decode(input: &str) -> Result<String, CookieParseError> {
+	let bytes = input.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+
+	while i < bytes.len() {
+		if bytes[i] == b'%' {
+			let byte = input
+				.get(i + 1..i + 3)
+				.and_then(|hex| u8::from_str_radix(hex, 16).ok())
+				.ok_or(CookieParseError::InvalidUrlEncoding)?;
+
+			decoded.push(byte);
+			i += 3;
+		} else {
+			decoded.push(bytes[i]);
+			i += 1;
+		}
+	}
+
+	String::from_utf8(decoded).map_err(|_| CookieParseError::InvalidUrlEncoding)
+}
+
+/// Whether a character is allowed in a cookie value (RFC 6265 cookie-octet)
+fn is_cookie_char(c: char) -> bool {
+	c.is_ascii_graphic() && !matches!(c, '"' | ',' | ';' | '\\')
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieParseError {
+	/// The input contains no token
+	Empty,
+	/// The input is a `name=value` list without a `.ROBLOSECURITY` pair, the
+	/// names of the cookies found being included
+	MissingCookie(Vec<String>),
+	/// The input starts with a single quote that is never closed
+	UnterminatedQuote,
+	/// The input starts with a double quote or brace but is not a valid JSON
+	/// string or cookie object
+	InvalidJson,
+	/// The input contains a `%` that is not a valid URL escape sequence
+	InvalidUrlEncoding,
+	/// The `_|WARNING:` prefix is never closed with `|_`
+	UnterminatedWarning,
+	/// The token contains a character that is not allowed in a cookie
+	InvalidCharacter(char),
+}
+
+impl fmt::Display for CookieParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Empty => write!(f, "Expected a {COOKIE_NAME} token, found an empty cookie"),
+			Self::MissingCookie(names) => write!(
+				f,
+				"Expected a `{COOKIE_NAME}=<token>` pair, found only: {}",
+				names.join(", ")
+			),
+			Self::UnterminatedQuote => write!(f, "Expected a quoted cookie, but the closing quote is missing"),
+			Self::InvalidJson => write!(
+				f,
+				"Expected a cookie as a JSON string or a JSON object with a `value`, but the JSON is invalid"
+			),
+			Self::InvalidUrlEncoding => write!(
+				f,
+				"Expected a URL-encoded cookie, but it has an invalid escape sequence"
+			),
+			Self::UnterminatedWarning => write!(
+				f,
+				"Expected the `{WARNING_PREFIX}` prefix to be closed with `{WARNING_SUFFIX}` before the token"
+			),
+			Self::InvalidCharacter(c) => {
+				write!(f, "Expected a {COOKIE_NAME} token, found invalid character {c:?}")
+			},
+		}
+	}
+}
+
+impl Error for CookieParseError {}
+
+impl From<CookieParseError> for RoboltError {
+	fn from(error: CookieParseError) -> Self {
+		RoboltError::invalid_cookie(error.to_string())
+	}
+}
+
+#[derive(Deserialize)]
+/// The response of the signup validation endpoints, `code` being 0 when the
+/// value is valid
+struct ValidationResponse {
+	code: i32,
+	message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "ValidationResponse")]
+pub enum UsernameValidation {
+	Valid,
+	AlreadyInUse,
+	Inappropriate,
+	/// Usernames must be between 3 and 20 characters long
+	InvalidLength,
+	/// Usernames cannot start or end with `_`
+	InvalidUnderscorePosition,
+	/// Usernames can have at most one `_`
+	TooManyUnderscores,
+	/// Usernames can only contain letters, digits and `_`
+	InvalidCharacters,
+	/// The username might contain private information
+	PrivateInformation,
+	Unknown {
+		code: i32,
+		message: String,
+	},
+}
+
+impl From<ValidationResponse> for UsernameValidation {
+	fn from(res: ValidationResponse) -> Self {
+		match res.code {
+			0 => Self::Valid,
+			1 => Self::AlreadyInUse,
+			2 => Self::Inappropriate,
+			3 => Self::InvalidLength,
+			4 => Self::InvalidUnderscorePosition,
+			5 => Self::TooManyUnderscores,
+			6 => Self::InvalidCharacters,
+			7 => Self::PrivateInformation,
+			code => Self::Unknown {
+				code,
+				message: res.message,
+			},
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "ValidationResponse")]
+pub enum PasswordValidation {
+	Valid,
+	/// Passwords must be at least 8 characters long
+	TooShort,
+	/// The password is too common or too simple
+	Forbidden,
+	SameAsUsername,
+	Unknown {
+		code: i32,
+		message: String,
+	},
+}
+
+impl PasswordValidation {
+	pub fn is_valid(&self) -> bool {
+		*self == Self::Valid
+	}
+}
+
+impl From<ValidationResponse> for PasswordValidation {
+	fn from(res: ValidationResponse) -> Self {
+		match res.code {
+			0 => Self::Valid,
+			1 => Self::TooShort,
+			2 => Self::Forbidden,
+			3 => Self::SameAsUsername,
+			code => Self::Unknown {
+				code,
+				message: res.message,
+			},
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthHealth {
+	/// The cookie is valid
+	Healthy,
+	/// The cookie has expired or was revoked
+	CookieInvalid,
+	/// Roblox requires a challenge (e.g. captcha or 2SV) to be solved
+	Challenge,
+	/// Roblox could not be reached or returned an unexpected response
+	NetworkError,
+}
+
+/// Handle to a running [Robolt::auth_watchdog] task, the task stops when this
+/// is dropped
+#[derive(Debug)]
+pub struct AuthWatchdog {
+	handle: JoinHandle<()>,
 }
\ No newline at end of file
@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 
 use reqwest::Method;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::api::routes::RobloxApi;
 use crate::api::Limit;
-use crate::errors::RoboltError;
+use crate::errors::{ResourceKind, RoboltError};
 use crate::utils::client::Authenticated;
+use crate::utils::paging::{NumberPaging, NumberedPage, PageIterator};
 use crate::utils::response::{DataResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
 use crate::Robolt;
 
 impl Robolt<Authenticated> {
@@ -19,6 +21,7 @@ impl Robolt<Authenticated> {
 	pub async fn add_wearing_asset(&self, asset_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/avatar/assets/{asset_id}/wear"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -30,6 +33,7 @@ impl Robolt<Authenticated> {
 
 		self.request(RobloxApi::Avatar, "/v1/avatar/set-wearing-assets")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body(body)
 			.await
 	}
@@ -37,6 +41,7 @@ impl Robolt<Authenticated> {
 	pub async fn remove_wearing_asset(&self, asset_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/avatar/assets/{asset_id}/remove"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -46,6 +51,7 @@ impl Robolt<Authenticated> {
 	pub async fn redraw_avatar_thumbnail(&self) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, "/v1/avatar/redraw-thumbnail")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -53,10 +59,11 @@ impl Robolt<Authenticated> {
 	}
 
 	pub async fn set_avatar_type(&self, avatar_type: BodyType) -> Result<(), RoboltError> {
-		let body = HashMap::from([("playerAvatarType", avatar_type as u8)]);
+		let body = HashMap::from([("playerAvatarType", avatar_type)]);
 
 		self.request(RobloxApi::Avatar, "/v1/avatar/set-player-avatar-type")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body::<_, EmptyResponse>(body)
 			.await?;
 
@@ -66,6 +73,7 @@ impl Robolt<Authenticated> {
 	pub async fn set_body_colors(&self, body_colors: BodyColors) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, "/v1/avatar/set-body-colors")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body::<_, EmptyResponse>(body_colors)
 			.await?;
 
@@ -75,6 +83,7 @@ impl Robolt<Authenticated> {
 	pub async fn set_scales(&self, scales: BodyScale) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, "/v1/avatar/set-scales")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body::<_, EmptyResponse>(scales)
 			.await?;
 
@@ -84,6 +93,7 @@ impl Robolt<Authenticated> {
 	pub async fn delete_outfit(&self, outfit_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/outfits/{outfit_id}/delete"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -93,6 +103,7 @@ impl Robolt<Authenticated> {
 	pub async fn wear_outfit(&self, outfit_id: u64) -> Result<InvalidAssets, RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/outfits/{outfit_id}/wear"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send()
 			.await
 	}
@@ -100,6 +111,7 @@ impl Robolt<Authenticated> {
 	pub async fn create_outfit(&self, new_outfit: OutfitV1) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, "/v1/outfits/create")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body::<_, EmptyResponse>(new_outfit)
 			.await?;
 
@@ -109,6 +121,7 @@ impl Robolt<Authenticated> {
 	pub async fn update_outfit(&self, outfit_id: u64, updated_outfit: OutfitV2) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v2/outfits/{outfit_id}/update"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body::<_, EmptyResponse>(updated_outfit)
 			.await?;
 
@@ -133,18 +146,20 @@ impl<State> Robolt<State> {
 
 	pub async fn avatar(&self, user_id: u64) -> Result<Avatar, RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/users/{user_id}/avatar"))
+			.resource(ResourceKind::User, user_id)
 			.send()
 			.await
 	}
 
 	pub async fn currently_wearing(&self, user_id: u64) -> Result<Vec<u64>, RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/users/{user_id}/currently-wearing"))
+			.resource(ResourceKind::User, user_id)
 			.send::<AssetIdsResponse>()
 			.await
 			.map(|res| res.asset_ids)
 	}
 
-	pub fn outfits(&self, user_id: u64) -> OutfitFilterBuilder<State> {
+	pub fn outfits(&self, user_id: u64) -> OutfitFilterBuilder<'_, State> {
 		OutfitFilterBuilder::new(user_id, self)
 	}
 
@@ -153,12 +168,14 @@ impl<State> Robolt<State> {
 			RobloxApi::Avatar,
 			format!("/v1/game-start-info?universeId={universe_id}",),
 		)
+		.resource(ResourceKind::Universe, universe_id)
 		.send()
 		.await
 	}
 
 	pub async fn outfit(&self, outfit_id: u64) -> Result<DetailedOutfit, RoboltError> {
 		self.request(RobloxApi::Avatar, format!("/v1/outfits/{outfit_id}/details"))
+			.resource(ResourceKind::Outfit, outfit_id)
 			.send()
 			.await
 	}
@@ -191,19 +208,61 @@ impl<'a, State> OutfitFilterBuilder<'a, State> {
 	}
 
 	pub async fn send(self) -> Result<FilteredOutfitResponse, RoboltError> {
-		self.client
-			.request(
-				RobloxApi::Avatar,
-				format!(
-					"/v1/users/{}/outfits?page={}&itemsPerPage={}&isEditable={}",
-					self.user_id, self.page, self.items_per_page as u8, self.is_editable
-				),
-			)
-			.send()
-			.await
+		let page = u64::from(self.page);
+		fetch_outfits(
+			self.client,
+			self.user_id,
+			page,
+			self.items_per_page as u8,
+			self.is_editable,
+		)
+		.await
+	}
+
+	/// Pages through the outfits starting at [OutfitFilterBuilder::page]
+	pub fn pages(self) -> PageIterator<'a, OutfitInfo>
+	where
+		State: Sync,
+	{
+		let client = self.client;
+		let (user_id, items_per_page, is_editable) = (self.user_id, self.items_per_page as u8, self.is_editable);
+		let paging = NumberPaging::new(u64::from(items_per_page)).first_page(u64::from(self.page));
+
+		PageIterator::new(paging.clone(), move |cursor| {
+			let page = paging.page_number(cursor.as_deref());
+
+			async move {
+				let res = fetch_outfits(client, user_id, page?, items_per_page, is_editable).await?;
+
+				Ok(NumberedPage {
+					items: res.data,
+					total: Some(res.total),
+					final_page: None,
+				})
+			}
+		})
+		.max_pages(client.max_pages_per_crawl)
 	}
 }
 
+async fn fetch_outfits<State>(
+	client: &Robolt<State>,
+	user_id: u64,
+	page: u64,
+	items_per_page: u8,
+	is_editable: bool,
+) -> Result<FilteredOutfitResponse, RoboltError> {
+	client
+		.request(
+			RobloxApi::Avatar,
+			format!(
+				"/v1/users/{user_id}/outfits?page={page}&itemsPerPage={items_per_page}&isEditable={is_editable}"
+			),
+		)
+		.send()
+		.await
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DetailedOutfit {
@@ -309,14 +368,25 @@ pub struct OutfitInfo {
 #[serde(rename_all = "camelCase")]
 pub struct Avatar {
 	pub player_avatar_type: BodyType,
+	#[serde(default)]
 	pub default_shirt_applied: bool,
+	#[serde(default)]
 	pub default_pants_applied: bool,
 	pub scales: BodyScale,
 	pub body_colors: BodyColors,
+	#[serde(default)]
 	pub assets: Vec<AvatarAsset>,
+	#[serde(default)]
 	pub emotes: Vec<AvatarEmotes>,
 }
 
+impl Avatar {
+	/// The IDs of the assets currently worn
+	pub fn asset_ids(&self) -> Vec<u64> {
+		self.assets.iter().map(|asset| asset.id).collect()
+	}
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BodyScale {
@@ -356,6 +426,7 @@ pub struct AvatarAsset {
 	pub name: String,
 	pub asset_type: AvatarAssetInfo,
 	pub meta: Option<AvatarAssetMeta>,
+	#[serde(default)]
 	pub current_version_id: u64,
 }
 
@@ -382,13 +453,40 @@ pub struct AvatarAssetMeta {
 	pub version: u32,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+/// The avatar type, sent as `R6` or `R15`
+///
+/// Endpoints return it either by name or as its numeric value.
 pub enum BodyType {
 	#[default]
 	R6 = 1,
 	R15 = 3,
 }
 
+impl<'de> Deserialize<'de> for BodyType {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum AvatarType {
+			Number(u8),
+			String(String),
+		}
+
+		match AvatarType::deserialize(deserializer)? {
+			AvatarType::Number(1) => Ok(BodyType::R6),
+			AvatarType::Number(3) => Ok(BodyType::R15),
+			AvatarType::Number(avatar_type) => {
+				Err(serde::de::Error::custom(format!("invalid avatar type `{avatar_type}`")))
+			},
+			AvatarType::String(avatar_type) => match avatar_type.as_str() {
+				"R6" => Ok(BodyType::R6),
+				"R15" => Ok(BodyType::R15),
+				_ => Err(serde::de::Error::custom(format!("invalid avatar type `{avatar_type}`"))),
+			},
+		}
+	}
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AvatarMetadata {
@@ -1,16 +1,28 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
 use crate::api::routes::RobloxApi;
-use crate::api::Limit;
-use crate::errors::RoboltError;
+use crate::api::{Limit, Page};
+use crate::errors::{ResourceKind, RoboltError};
 use crate::utils::client::Authenticated;
 use crate::utils::response::DataResponse;
+use crate::utils::retry::Idempotency;
+#[cfg(feature = "chrono")]
+use crate::utils::timestamp;
 use crate::Robolt;
 
+const AWARDED_DATES_BATCH_SIZE: usize = 100;
+const BADGE_STATS_SNAPSHOT_VERSION: u32 = 1;
+
 impl<State> Robolt<State> {
 	pub async fn badge(&self, badge_id: u64) -> Result<Badge, RoboltError> {
 		self.request(RobloxApi::Badges, format!("/v1/badges/{badge_id}"))
+			.resource(ResourceKind::Badge, badge_id)
 			.send()
 			.await
 	}
@@ -20,6 +32,7 @@ impl<State> Robolt<State> {
 			RobloxApi::Badges,
 			format!("/v1/universes/{universe_id}/badges?limit={}", limit as u8),
 		)
+		.resource(ResourceKind::Universe, universe_id)
 		.send::<DataResponse<Badge>>()
 		.await
 		.map(|res| res.data)
@@ -30,40 +43,98 @@ impl<State> Robolt<State> {
 			RobloxApi::Badges,
 			format!("/v1/users/{user_id}/badges?limit={}", limit as u8),
 		)
+		.resource(ResourceKind::User, user_id)
 		.send::<DataResponse<Badge>>()
 		.await
 		.map(|res| res.data)
 	}
 
+	/// A page of the badges awarded to a user, newest first
+	///
+	/// The dates at which they were awarded are fetched with
+	/// [Robolt::awarded_badge_timestamps].
+	pub async fn user_badges_page(
+		&self,
+		user_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<Badge>, RoboltError> {
+		let mut path = format!("/v1/users/{user_id}/badges?limit={}&sortOrder=Desc", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Badges, path)
+			.resource(ResourceKind::User, user_id)
+			.send()
+			.await
+	}
+
+	/// Fetches the dates at which a user was awarded the given badges
+	///
+	/// Badge IDs are requested in batches of 100, so any number of IDs can be
+	/// passed. Badges the user does not own are listed in
+	/// [AwardedBadges::not_awarded].
 	pub async fn awarded_badge_timestamps(
 		&self,
 		user_id: u64,
 		badge_ids: Vec<u64>,
-	) -> Result<Vec<AwardedBadgeTimestamp>, RoboltError> {
-		let badge_ids = badge_ids
-			.iter()
-			.map(|id| id.to_string())
-			.collect::<Vec<String>>()
-			.join(",");
+	) -> Result<AwardedBadges, RoboltError> {
+		let mut awarded = Vec::new();
 
-		self.request(
-			RobloxApi::Badges,
-			format!("/v1/users/{user_id}/badges/awarded-dates?badgeIds={badge_ids}"),
-		)
-		.send::<DataResponse<AwardedBadgeTimestamp>>()
-		.await
-		.map(|res| res.data)
+		for chunk in badge_ids.chunks(AWARDED_DATES_BATCH_SIZE) {
+			let chunk = chunk.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(",");
+
+			let timestamps = self
+				.request(
+					RobloxApi::Badges,
+					format!("/v1/users/{user_id}/badges/awarded-dates?badgeIds={chunk}"),
+				)
+				.send::<DataResponse<AwardedBadgeTimestamp>>()
+				.await?
+				.data;
+
+			awarded.extend(timestamps);
+		}
+
+		let not_awarded = badge_ids
+			.into_iter()
+			.filter(|id| !awarded.iter().any(|timestamp| timestamp.badge_id == *id))
+			.collect();
+
+		Ok(AwardedBadges {
+			awarded,
+			not_awarded,
+		})
+	}
+
+	/// Fetches the award statistics of the given badges, keyed by badge ID
+	///
+	/// There is no batch endpoint for statistics, so every badge is fetched
+	/// individually with at most `concurrency` requests in flight.
+	pub async fn badge_statistics(
+		&self,
+		badge_ids: &[u64],
+		concurrency: usize,
+	) -> Result<HashMap<u64, BadgeStats>, RoboltError> {
+		stream::iter(badge_ids)
+			.map(|badge_id| async move { self.badge(*badge_id).await.map(|badge| (badge.id, badge.statistics)) })
+			.buffered(concurrency.max(1))
+			.try_collect()
+			.await
 	}
 }
 
 impl Robolt<Authenticated> {
-	pub fn update_badge(&self, badge_id: u64) -> BadgeUpdateBuilder {
+	pub fn update_badge(&self, badge_id: u64) -> BadgeUpdateBuilder<'_> {
 		BadgeUpdateBuilder::new(badge_id, self)
 	}
 
 	pub async fn remove_badge(&self, badge_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Badges, format!("/v1/user/badges/{badge_id}"))
 			.method(Method::DELETE)
+			.idempotency(Idempotency::NonIdempotent)
 			.send()
 			.await
 	}
@@ -99,11 +170,89 @@ impl<'a> BadgeUpdateBuilder<'a> {
 		self.client
 			.request(RobloxApi::Badges, format!("/v1/badges/{}", self.id))
 			.method(Method::PATCH)
+			.idempotency(Idempotency::NonIdempotent)
 			.send_body(self)
 			.await
 	}
 }
 
+impl Badge {
+	/// When the badge was created
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn created_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.created)
+	}
+
+	/// When the badge was last updated
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn updated_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.updated)
+	}
+}
+
+impl AwardedBadgeTimestamp {
+	/// When the badge was awarded
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn awarded_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.awarded_date)
+	}
+}
+
+impl BadgeStatsSnapshot {
+	pub fn new(statistics: &HashMap<u64, BadgeStats>) -> Self {
+		Self {
+			version: BADGE_STATS_SNAPSHOT_VERSION,
+			awarded_counts: statistics
+				.iter()
+				.map(|(badge_id, stats)| (*badge_id, stats.awarded_count))
+				.collect(),
+		}
+	}
+}
+
+impl BadgeStatsTracker {
+	/// Flags any badge awarded more than `threshold` times between two
+	/// snapshots as an anomaly
+	pub fn new(threshold: u64) -> Self {
+		Self {
+			threshold,
+		}
+	}
+
+	/// Computes the awarded count deltas between a stored snapshot and freshly
+	/// fetched statistics, sorted by badge ID
+	///
+	/// Badges missing from `previous` (e.g. an older snapshot taken before the
+	/// badge was tracked) have no delta and are never flagged.
+	pub fn compare(
+		&self,
+		previous: &BadgeStatsSnapshot,
+		current: &HashMap<u64, BadgeStats>,
+	) -> Vec<BadgeStatsDelta> {
+		let mut deltas = current
+			.iter()
+			.map(|(badge_id, stats)| {
+				let previous = previous.awarded_counts.get(badge_id).copied();
+				let delta = previous.map_or(0, |previous| stats.awarded_count.saturating_sub(previous));
+
+				BadgeStatsDelta {
+					badge_id: *badge_id,
+					previous,
+					current: stats.awarded_count,
+					delta,
+					anomalous: delta > self.threshold,
+				}
+			})
+			.collect::<Vec<BadgeStatsDelta>>();
+
+		deltas.sort_by_key(|delta| delta.badge_id);
+		deltas
+	}
+}
+
 #[derive(Serialize)]
 pub struct BadgeUpdateBuilder<'a> {
 	#[serde(skip_serializing)]
@@ -118,6 +267,13 @@ pub struct BadgeUpdateBuilder<'a> {
 	enabled: Option<bool>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AwardedBadges {
+	pub awarded: Vec<AwardedBadgeTimestamp>,
+	/// The requested badge IDs that the user has not been awarded
+	pub not_awarded: Vec<u64>,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AwardedBadgeTimestamp {
@@ -133,11 +289,13 @@ pub struct Badge {
 	pub description: Option<String>,
 	pub display_name: String,
 	pub display_description: Option<String>,
+	#[serde(default)]
 	pub enabled: bool,
 	pub icon_image_id: u64,
 	pub display_icon_image_id: u64,
 	pub created: String,
 	pub updated: String,
+	#[serde(default)]
 	pub statistics: BadgeStats,
 	pub awarding_universe: Option<AwardingUniverse>,
 }
@@ -150,6 +308,33 @@ pub struct BadgeStats {
 	pub win_rate_percentage: f32,
 }
 
+/// The awarded counts of a set of badges at a point in time, meant to be
+/// stored by the caller and compared against with [BadgeStatsTracker]
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeStatsSnapshot {
+	#[serde(default)]
+	pub version: u32,
+	#[serde(default)]
+	pub awarded_counts: BTreeMap<u64, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BadgeStatsTracker {
+	pub threshold: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct BadgeStatsDelta {
+	pub badge_id: u64,
+	/// `None` if the badge was not part of the previous snapshot
+	pub previous: Option<u64>,
+	pub current: u64,
+	pub delta: u64,
+	/// Whether the delta exceeds the tracker's threshold
+	pub anomalous: bool,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AwardingUniverse {
@@ -4,10 +4,11 @@ use serde_json::json;
 use serde_repr::Deserialize_repr;
 
 use crate::api::routes::RobloxApi;
-use crate::api::Limit;
-use crate::errors::RoboltError;
+use crate::api::{deserialize_creator, Creator, FlatCreator, Limit, Robux, TypedCreator};
+use crate::errors::{ResourceKind, RoboltError};
 use crate::utils::client::Authenticated;
 use crate::utils::response::{DataResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
 use crate::Robolt;
 
 impl<State> Robolt<State> {
@@ -16,6 +17,7 @@ impl<State> Robolt<State> {
 			RobloxApi::Catalog,
 			format!("/v1/assets/{asset_id}/bundles?limit={}", limit as u8),
 		)
+		.resource(ResourceKind::Asset, asset_id)
 		.send::<DataResponse<Bundle>>()
 		.await
 		.map(|res| res.data)
@@ -23,6 +25,7 @@ impl<State> Robolt<State> {
 
 	pub async fn bundle(&self, bundle_id: u64) -> Result<Bundle, RoboltError> {
 		self.request(RobloxApi::Catalog, format!("/v1/bundles/{bundle_id}/details"))
+			.resource(ResourceKind::Bundle, bundle_id)
 			.send()
 			.await
 	}
@@ -38,6 +41,7 @@ impl<State> Robolt<State> {
 			RobloxApi::Catalog,
 			format!("/v1/bundles/{bundle_id}/recommendations?numItems={amount}"),
 		)
+		.resource(ResourceKind::Bundle, bundle_id)
 		.send::<DataResponse<Bundle>>()
 		.await
 		.map(|res| res.data)
@@ -60,6 +64,7 @@ impl<State> Robolt<State> {
 
 	pub async fn user_bundles(&self, user_id: u64) -> Result<Vec<OwnedBundle>, RoboltError> {
 		self.request(RobloxApi::Catalog, format!("/v1/users/{user_id}/bundles"))
+			.resource(ResourceKind::User, user_id)
 			.send::<DataResponse<OwnedBundle>>()
 			.await
 			.map(|res| res.data)
@@ -78,6 +83,7 @@ impl<State> Robolt<State> {
 				bundle_type as u8, limit as u8
 			),
 		)
+		.resource(ResourceKind::User, user_id)
 		.send::<DataResponse<OwnedBundle>>()
 		.await
 		.map(|res| res.data)
@@ -85,12 +91,14 @@ impl<State> Robolt<State> {
 
 	pub async fn asset_favorite_count(&self, asset_id: u64) -> Result<u64, RoboltError> {
 		self.request(RobloxApi::Catalog, format!("/v1/favorites/assets/{asset_id}/count"))
+			.resource(ResourceKind::Asset, asset_id)
 			.send()
 			.await
 	}
 
 	pub async fn bundle_favorite_count(&self, bundle_id: u64) -> Result<u64, RoboltError> {
 		self.request(RobloxApi::Catalog, format!("/v1/favorites/bundles/{bundle_id}/count"))
+			.resource(ResourceKind::Bundle, bundle_id)
 			.send()
 			.await
 	}
@@ -103,6 +111,8 @@ impl Robolt<Authenticated> {
 			format!("/v1/favorites/users/{user_id}/assets/{asset_id}/favorite"),
 		)
 		.method(Method::POST)
+		.invalidates(RobloxApi::Catalog, format!("/v1/favorites/assets/{asset_id}/count"))
+		.idempotency(Idempotency::NonIdempotent)
 		.send::<EmptyResponse>()
 		.await?;
 
@@ -115,6 +125,8 @@ impl Robolt<Authenticated> {
 			format!("/v1/favorites/users/{user_id}/assets/{asset_id}/favorite"),
 		)
 		.method(Method::DELETE)
+		.invalidates(RobloxApi::Catalog, format!("/v1/favorites/assets/{asset_id}/count"))
+		.idempotency(Idempotency::NonIdempotent)
 		.send::<EmptyResponse>()
 		.await?;
 
@@ -127,6 +139,8 @@ impl Robolt<Authenticated> {
 			format!("/v1/favorites/users/{user_id}/bundles/{bundle_id}/favorite"),
 		)
 		.method(Method::POST)
+		.invalidates(RobloxApi::Catalog, format!("/v1/favorites/bundles/{bundle_id}/count"))
+		.idempotency(Idempotency::NonIdempotent)
 		.send::<EmptyResponse>()
 		.await?;
 
@@ -139,12 +153,18 @@ impl Robolt<Authenticated> {
 			format!("/v1/favorites/users/{user_id}/bundles/{bundle_id}/favorite"),
 		)
 		.method(Method::DELETE)
+		.invalidates(RobloxApi::Catalog, format!("/v1/favorites/bundles/{bundle_id}/count"))
+		.idempotency(Idempotency::NonIdempotent)
 		.send::<EmptyResponse>()
 		.await?;
 
 		Ok(())
 	}
 
+	/// Fetches when a user favorited an asset, `None` if they have not
+	///
+	/// Roblox responds with `null` rather than an error for items that are not
+	/// favorited.
 	pub async fn asset_favorite_model(
 		&self,
 		user_id: u64,
@@ -158,6 +178,8 @@ impl Robolt<Authenticated> {
 		.await
 	}
 
+	/// Fetches when a user favorited a bundle, `None` if they have not, see
+	/// [Robolt::asset_favorite_model]
 	pub async fn bundle_favorite_model(
 		&self,
 		user_id: u64,
@@ -171,6 +193,18 @@ impl Robolt<Authenticated> {
 		.await
 	}
 
+	pub async fn is_asset_favorited(&self, user_id: u64, asset_id: u64) -> Result<bool, RoboltError> {
+		self.asset_favorite_model(user_id, asset_id)
+			.await
+			.map(|favorite| favorite.is_some())
+	}
+
+	pub async fn is_bundle_favorited(&self, user_id: u64, bundle_id: u64) -> Result<bool, RoboltError> {
+		self.bundle_favorite_model(user_id, bundle_id)
+			.await
+			.map(|favorite| favorite.is_some())
+	}
+
 	pub async fn favorite_bundles(
 		&self,
 		user_id: u64,
@@ -194,6 +228,8 @@ impl Robolt<Authenticated> {
 
 		self.request(RobloxApi::Catalog, "/v1/catalog/items/details")
 			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.read_only()
 			.send_body::<_, DataResponse<CatalogSearchResponse>>(body)
 			.await
 			.map(|res| res.data)
@@ -215,22 +251,26 @@ pub struct CatalogSearchResponse {
 	pub asset_type: Option<AssetType>,
 	pub bundle_type: Option<BundleType>,
 	pub name: String,
+	#[serde(default)]
 	pub description: String,
 	pub product_id: u64,
 	pub genres: Option<Vec<CatalogItemGenre>>,
 	pub bundled_items: Option<Vec<BundleItem>>,
+	#[serde(default)]
 	pub item_status: Vec<CatalogItemStatus>,
+	#[serde(default)]
 	pub item_restrictions: Vec<CatalogItemRestriction>,
+	#[serde(default)]
 	pub creator_has_verified_badge: bool,
-	pub creator_type: CreatorType,
-	pub creator_target_id: u64,
-	pub creator_name: String,
-	pub price: Option<u64>,
+	#[serde(flatten, deserialize_with = "deserialize_creator::<_, FlatCreator>")]
+	pub creator: Creator,
+	pub price: Option<Robux>,
 	pub premium_pricing: Option<PremiumPricing>,
-	pub lowest_price: Option<u64>,
+	pub lowest_price: Option<Robux>,
 	pub price_status: Option<String>,
 	pub units_available_for_consumption: Option<u64>,
 	pub purchase_count: Option<u64>,
+	#[serde(default)]
 	pub favorite_count: u64,
 	pub off_sale_deadline: Option<String>,
 	pub collectible_item_id: Option<String>,
@@ -238,12 +278,6 @@ pub struct CatalogSearchResponse {
 	pub sale_location_type: SaleLocationType,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
-pub enum CreatorType {
-	Group,
-	User,
-}
-
 #[derive(Debug, Clone, PartialEq, Deserialize_repr)]
 #[repr(u8)]
 pub enum AssetType {
@@ -366,9 +400,9 @@ pub enum CatalogItemStatus {
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetFavorite {
-	user_id: u64,
-	asset_id: u64,
-	created: String,
+	pub user_id: u64,
+	pub asset_id: u64,
+	pub created: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
@@ -381,9 +415,9 @@ pub struct FavoriteBundles {
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BundleFavorite {
-	user_id: u64,
-	bundle_id: u64,
-	created: String,
+	pub user_id: u64,
+	pub bundle_id: u64,
+	pub created: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
@@ -391,11 +425,15 @@ pub struct BundleFavorite {
 pub struct Bundle {
 	pub id: u64,
 	pub name: String,
+	#[serde(default)]
 	pub description: String,
 	pub bundle_type: String,
+	#[serde(default)]
 	pub items: Vec<BundleItem>,
-	pub creator: BundleCreator,
+	#[serde(deserialize_with = "deserialize_creator::<_, TypedCreator>")]
+	pub creator: Creator,
 	pub product: BundleProduct,
+	#[serde(default)]
 	pub item_restrictions: Vec<CatalogItemRestriction>,
 }
 
@@ -405,7 +443,8 @@ pub struct OwnedBundle {
 	pub id: u64,
 	pub name: String,
 	pub bundle_type: String,
-	pub creator: BundleCreator,
+	#[serde(deserialize_with = "deserialize_creator::<_, TypedCreator>")]
+	pub creator: Creator,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
@@ -418,25 +457,18 @@ pub struct BundleItem {
 	pub item_type: String,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BundleCreator {
-	pub id: u64,
-	pub name: String,
-	#[serde(rename = "type")]
-	pub creator_type: String,
-	pub has_verified_badge: bool,
-}
-
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BundleProduct {
 	pub id: u64,
 	#[serde(rename = "type")]
 	pub product_type: String,
+	#[serde(default)]
 	pub is_public_domain: bool,
+	#[serde(default)]
 	pub is_for_sale: bool,
-	pub price_in_robux: Option<u64>,
+	pub price_in_robux: Option<Robux>,
+	#[serde(default)]
 	pub is_free: bool,
 	pub no_price_text: Option<String>,
 	pub premium_pricing: Option<PremiumPricing>,
@@ -446,7 +478,7 @@ pub struct BundleProduct {
 #[serde(rename_all = "camelCase")]
 pub struct PremiumPricing {
 	pub premium_discount_percentage: u64,
-	pub premium_price_in_robux: u64,
+	pub premium_price_in_robux: Robux,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize_repr)]
@@ -0,0 +1,407 @@
+use std::fmt;
+
+use reqwest::Method;
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+
+use crate::api::routes::{encode_query_value, RobloxApi};
+use crate::errors::{ResourceKind, RoboltError};
+use crate::utils::retry::Idempotency;
+use crate::Robolt;
+
+impl<State> Robolt<State> {
+	/// Fetches a user through Open Cloud, requires an API key to be set with
+	/// [Robolt::set_api_key]
+	pub async fn cloud_user(&self, user_id: u64) -> Result<CloudUser, RoboltError> {
+		self.request(RobloxApi::Cloud, format!("/cloud/v2/users/{user_id}"))
+			.resource(ResourceKind::User, user_id)
+			.send()
+			.await
+	}
+
+	/// Fetches a group through Open Cloud, requires an API key to be set with
+	/// [Robolt::set_api_key]
+	pub async fn cloud_group(&self, group_id: u64) -> Result<CloudGroup, RoboltError> {
+		self.request(RobloxApi::Cloud, format!("/cloud/v2/groups/{group_id}"))
+			.resource(ResourceKind::Group, group_id)
+			.send()
+			.await
+	}
+
+	/// Lists the members of a group through Open Cloud, optionally narrowed
+	/// down with a [MembershipFilter]
+	///
+	/// ### Arguments
+	/// * `max_page_size` - The maximum number of memberships to return (<= 100)
+	/// * `page_token` - The [CloudPage::next_page_token] of the previous page
+	pub async fn cloud_group_memberships(
+		&self,
+		group_id: u64,
+		filter: Option<MembershipFilter>,
+		max_page_size: u8,
+		page_token: Option<&str>,
+	) -> Result<CloudPage<CloudMembership>, RoboltError> {
+		let mut path = format!("/cloud/v2/groups/{group_id}/memberships?maxPageSize={max_page_size}");
+
+		if let Some(filter) = filter {
+			path.push_str(&format!("&filter={}", encode_query_value(&filter.to_string())));
+		}
+
+		if let Some(page_token) = page_token {
+			path.push_str(&format!("&pageToken={}", encode_query_value(page_token)));
+		}
+
+		self.request(RobloxApi::Cloud, path).send().await
+	}
+
+	/// Changes the role of a group member through Open Cloud
+	pub async fn update_cloud_membership(
+		&self,
+		group_id: u64,
+		membership_id: &str,
+		role_id: u64,
+	) -> Result<CloudMembership, RoboltError> {
+		let body = json!({ "role": format!("groups/{group_id}/roles/{role_id}") });
+
+		self.request(
+			RobloxApi::Cloud,
+			format!("/cloud/v2/groups/{group_id}/memberships/{membership_id}"),
+		)
+		.method(Method::PATCH)
+		.idempotency(Idempotency::NonIdempotent)
+		.send_body(body)
+		.await
+	}
+
+	/// Lists the items in a user's inventory through Open Cloud
+	///
+	/// Private inventories can be read as long as the user has consented to
+	/// the owner of the API key.
+	///
+	/// ### Arguments
+	/// * `max_page_size` - The maximum number of items to return (<= 100)
+	/// * `page_token` - The [CloudPage::next_page_token] of the previous page
+	pub async fn cloud_inventory_items(
+		&self,
+		user_id: u64,
+		filter: InventoryFilter,
+		max_page_size: u8,
+		page_token: Option<&str>,
+	) -> Result<CloudPage<InventoryItem>, RoboltError> {
+		let mut path = format!("/cloud/v2/users/{user_id}/inventory-items?maxPageSize={max_page_size}");
+		let filter = filter.to_string();
+
+		if !filter.is_empty() {
+			path.push_str(&format!("&filter={}", encode_query_value(&filter)));
+		}
+
+		if let Some(page_token) = page_token {
+			path.push_str(&format!("&pageToken={}", encode_query_value(page_token)));
+		}
+
+		self.request(RobloxApi::Cloud, path).send().await
+	}
+}
+
+impl MembershipFilter {
+	/// Matches the membership of a single user
+	pub fn user(user_id: u64) -> Self {
+		MembershipFilter(format!("user == 'users/{user_id}'"))
+	}
+
+	/// Matches the memberships of any of the given users
+	pub fn users(user_ids: &[u64]) -> Self {
+		let users = user_ids
+			.iter()
+			.map(|id| format!("'users/{id}'"))
+			.collect::<Vec<String>>()
+			.join(", ");
+
+		MembershipFilter(format!("user in [{users}]"))
+	}
+
+	/// Matches every member with the given role
+	pub fn role(group_id: u64, role_id: u64) -> Self {
+		MembershipFilter(format!("role == 'groups/{group_id}/roles/{role_id}'"))
+	}
+}
+
+impl fmt::Display for MembershipFilter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+impl InventoryFilter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn asset_ids(mut self, asset_ids: Vec<u64>) -> Self {
+		self.asset_ids = asset_ids;
+		self
+	}
+
+	pub fn badge_ids(mut self, badge_ids: Vec<u64>) -> Self {
+		self.badge_ids = badge_ids;
+		self
+	}
+
+	pub fn game_pass_ids(mut self, game_pass_ids: Vec<u64>) -> Self {
+		self.game_pass_ids = game_pass_ids;
+		self
+	}
+
+	/// Only return items of the given asset types, e.g. `HAT` or
+	/// `CLASSIC_SHIRT`
+	pub fn inventory_item_asset_types(mut self, asset_types: Vec<String>) -> Self {
+		self.inventory_item_asset_types = asset_types;
+		self
+	}
+
+	pub fn only_collectibles(mut self, only_collectibles: bool) -> Self {
+		self.only_collectibles = only_collectibles;
+		self
+	}
+}
+
+impl fmt::Display for InventoryFilter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fn join<T: ToString>(values: &[T]) -> String {
+			values.iter().map(T::to_string).collect::<Vec<String>>().join(",")
+		}
+
+		let mut parts = Vec::new();
+
+		if !self.asset_ids.is_empty() {
+			parts.push(format!("assetIds={}", join(&self.asset_ids)));
+		}
+
+		if !self.badge_ids.is_empty() {
+			parts.push(format!("badgeIds={}", join(&self.badge_ids)));
+		}
+
+		if !self.game_pass_ids.is_empty() {
+			parts.push(format!("gamePassIds={}", join(&self.game_pass_ids)));
+		}
+
+		if !self.inventory_item_asset_types.is_empty() {
+			parts.push(format!(
+				"inventoryItemAssetTypes={}",
+				join(&self.inventory_item_asset_types)
+			));
+		}
+
+		if self.only_collectibles {
+			parts.push("onlyCollectibles=true".to_string());
+		}
+
+		f.write_str(&parts.join(";"))
+	}
+}
+
+/// Parses the ID out of an Open Cloud resource name such as `users/123`
+pub fn parse_resource_id(resource: &str) -> Option<u64> {
+	resource.rsplit('/').next()?.parse().ok()
+}
+
+/// Parses the last segment out of an Open Cloud resource name such as
+/// `groups/7/memberships/abc`
+pub(crate) fn deserialize_resource_name<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let resource = String::deserialize(deserializer)?;
+
+	match resource.rsplit('/').next() {
+		Some(name) if !name.is_empty() => Ok(name.to_string()),
+		_ => Err(serde::de::Error::custom(format!("invalid resource name `{resource}`"))),
+	}
+}
+
+pub(crate) fn deserialize_string_id<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let id = String::deserialize(deserializer)?;
+	id.parse().map_err(serde::de::Error::custom)
+}
+
+pub(crate) fn deserialize_resource_id<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let resource = Option::<String>::deserialize(deserializer)?;
+
+	resource
+		.map(|resource| {
+			parse_resource_id(&resource)
+				.ok_or_else(|| serde::de::Error::custom(format!("invalid resource name `{resource}`")))
+		})
+		.transpose()
+}
+
+/// A user returned by Open Cloud
+///
+/// Fields marked as scope-gated are only present when the API key has the
+/// `user.advanced:read` or `user.social:read` scopes.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudUser {
+	#[serde(deserialize_with = "deserialize_string_id")]
+	pub id: u64,
+	pub name: String,
+	pub display_name: String,
+	#[serde(default)]
+	pub about: String,
+	/// RFC 3339 timestamp of the account's creation
+	pub create_time: String,
+	pub locale: Option<String>,
+	/// Scope-gated
+	pub premium: Option<bool>,
+	/// Scope-gated
+	pub id_verified: Option<bool>,
+	/// Scope-gated
+	pub social_network_profiles: Option<CloudSocialProfiles>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudSocialProfiles {
+	pub facebook: Option<String>,
+	pub twitter: Option<String>,
+	pub youtube: Option<String>,
+	pub twitch: Option<String>,
+	pub guilded: Option<String>,
+	pub visibility: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudGroup {
+	#[serde(deserialize_with = "deserialize_string_id")]
+	pub id: u64,
+	pub display_name: String,
+	#[serde(default)]
+	pub description: String,
+	/// The user ID of the owner, `None` if the group has no owner
+	#[serde(default, deserialize_with = "deserialize_resource_id")]
+	pub owner: Option<u64>,
+	/// RFC 3339 timestamp of the group's creation
+	pub create_time: String,
+	pub update_time: Option<String>,
+	#[serde(default)]
+	pub member_count: u64,
+	#[serde(default)]
+	pub public_entry_allowed: bool,
+	#[serde(default)]
+	pub locked: bool,
+	#[serde(default)]
+	pub verified: bool,
+}
+
+/// A page of an Open Cloud listing, continued by passing
+/// [CloudPage::next_page_token] back as the page token
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudPage<T> {
+	#[serde(alias = "groupMemberships", alias = "inventoryItems")]
+	pub items: Vec<T>,
+	/// `None` (or empty) once the last page has been reached
+	#[serde(default, deserialize_with = "deserialize_page_token")]
+	pub next_page_token: Option<String>,
+}
+
+fn deserialize_page_token<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let token = Option::<String>::deserialize(deserializer)?;
+	Ok(token.filter(|token| !token.is_empty()))
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudMembership {
+	/// The membership ID, used to update the member's role
+	#[serde(rename = "path", deserialize_with = "deserialize_resource_name")]
+	pub id: String,
+	#[serde(rename = "user", deserialize_with = "deserialize_string_resource_id")]
+	pub user_id: u64,
+	#[serde(rename = "role", deserialize_with = "deserialize_string_resource_id")]
+	pub role_id: u64,
+	pub create_time: Option<String>,
+	pub update_time: Option<String>,
+}
+
+fn deserialize_string_resource_id<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	deserialize_resource_id(deserializer)?.ok_or_else(|| serde::de::Error::custom("missing resource name"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A filter for [Robolt::cloud_group_memberships]
+pub struct MembershipFilter(String);
+
+/// A filter for [Robolt::cloud_inventory_items], an empty filter returns
+/// every item
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct InventoryFilter {
+	asset_ids: Vec<u64>,
+	badge_ids: Vec<u64>,
+	game_pass_ids: Vec<u64>,
+	inventory_item_asset_types: Vec<String>,
+	only_collectibles: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct InventoryItem {
+	#[serde(rename = "path", deserialize_with = "deserialize_resource_name")]
+	pub id: String,
+	#[serde(flatten)]
+	pub details: InventoryItemDetails,
+}
+
+/// What an [InventoryItem] refers to
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InventoryItemDetails {
+	AssetDetails(InventoryAsset),
+	#[serde(rename_all = "camelCase")]
+	BadgeDetails {
+		#[serde(deserialize_with = "deserialize_string_id")]
+		badge_id: u64,
+	},
+	#[serde(rename_all = "camelCase")]
+	GamePassDetails {
+		#[serde(deserialize_with = "deserialize_string_id")]
+		game_pass_id: u64,
+	},
+	#[serde(rename_all = "camelCase")]
+	PrivateServerDetails {
+		#[serde(deserialize_with = "deserialize_string_id")]
+		private_server_id: u64,
+	},
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryAsset {
+	#[serde(deserialize_with = "deserialize_string_id")]
+	pub asset_id: u64,
+	pub inventory_item_asset_type: String,
+	pub instance_id: Option<String>,
+	/// Only present for limited items
+	pub collectible_details: Option<CollectibleDetails>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectibleDetails {
+	pub item_id: String,
+	pub instance_id: String,
+	pub instance_state: String,
+	pub serial_number: Option<u64>,
+}
@@ -0,0 +1,179 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::RobloxApi;
+use crate::api::Robux;
+use crate::errors::{ResourceKind, RoboltError};
+use crate::utils::client::Authenticated;
+use crate::utils::retry::Idempotency;
+use crate::Robolt;
+
+impl Robolt<Authenticated> {
+	pub async fn universe_configuration(&self, universe_id: u64) -> Result<UniverseConfiguration, RoboltError> {
+		self.request(RobloxApi::Develop, format!("/v2/universes/{universe_id}/configuration"))
+			.resource(ResourceKind::Universe, universe_id)
+			.send()
+			.await
+	}
+
+	pub fn update_universe_configuration(&self, universe_id: u64) -> UniverseConfigurationBuilder<'_> {
+		UniverseConfigurationBuilder::new(universe_id, self)
+	}
+
+	/// Fails with a descriptive error if Studio access to API services is
+	/// disabled for the universe, which would otherwise surface as a 403 from
+	/// the datastore and messaging APIs
+	pub async fn assert_api_access_enabled(&self, universe_id: u64) -> Result<(), RoboltError> {
+		let configuration = self.universe_configuration(universe_id).await?;
+
+		match configuration.studio_access_to_apis_allowed {
+			Some(true) => Ok(()),
+			_ => Err(format!(
+				"Studio access to API services is disabled for universe {universe_id}, enable it in the game \
+				 settings or with `update_universe_configuration`"
+			)
+			.into()),
+		}
+	}
+}
+
+impl<'a> UniverseConfigurationBuilder<'a> {
+	fn new(id: u64, client: &'a Robolt<Authenticated>) -> Self {
+		Self {
+			id,
+			client,
+			name: None,
+			description: None,
+			universe_avatar_type: None,
+			playable_devices: None,
+			studio_access_to_apis_allowed: None,
+			allow_third_party_sales: None,
+			permissions: None,
+		}
+	}
+
+	pub fn name(mut self, name: &str) -> Self {
+		self.name = Some(name.to_string());
+		self
+	}
+
+	pub fn description(mut self, description: &str) -> Self {
+		self.description = Some(description.to_string());
+		self
+	}
+
+	pub fn avatar_type(mut self, avatar_type: UniverseAvatarType) -> Self {
+		self.universe_avatar_type = Some(avatar_type);
+		self
+	}
+
+	pub fn playable_devices(mut self, devices: Vec<PlayableDevice>) -> Self {
+		self.playable_devices = Some(devices);
+		self
+	}
+
+	pub fn studio_access_to_apis_allowed(mut self, allowed: bool) -> Self {
+		self.studio_access_to_apis_allowed = Some(allowed);
+		self
+	}
+
+	pub fn allow_third_party_sales(mut self, allowed: bool) -> Self {
+		self.allow_third_party_sales = Some(allowed);
+		self
+	}
+
+	pub fn permissions(mut self, permissions: UniversePermissions) -> Self {
+		self.permissions = Some(permissions);
+		self
+	}
+
+	pub async fn update(self) -> Result<UniverseConfiguration, RoboltError> {
+		self.client
+			.request(RobloxApi::Develop, format!("/v2/universes/{}/configuration", self.id))
+			.method(Method::PATCH)
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body(self)
+			.await
+	}
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniverseConfigurationBuilder<'a> {
+	#[serde(skip_serializing)]
+	id: u64,
+	#[serde(skip_serializing)]
+	client: &'a Robolt<Authenticated>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	name: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	description: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	universe_avatar_type: Option<UniverseAvatarType>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	playable_devices: Option<Vec<PlayableDevice>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	studio_access_to_apis_allowed: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	allow_third_party_sales: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	permissions: Option<UniversePermissions>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniverseConfiguration {
+	pub id: u64,
+	pub name: String,
+	pub description: Option<String>,
+	pub universe_avatar_type: UniverseAvatarType,
+	#[serde(default)]
+	pub playable_devices: Vec<PlayableDevice>,
+	pub studio_access_to_apis_allowed: Option<bool>,
+	pub allow_third_party_sales: Option<bool>,
+	pub permissions: Option<UniversePermissions>,
+	#[serde(default)]
+	pub is_archived: bool,
+	#[serde(default)]
+	pub is_friends_only: bool,
+	#[serde(default)]
+	pub allow_private_servers: bool,
+	pub private_server_price: Option<Robux>,
+	#[serde(default)]
+	pub is_for_sale: bool,
+	pub price: Option<Robux>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UniversePermissions {
+	#[serde(rename = "IsThirdPartyTeleportAllowed")]
+	pub third_party_teleport_allowed: bool,
+	#[serde(rename = "IsThirdPartyAssetAllowed")]
+	pub third_party_asset_allowed: bool,
+	#[serde(rename = "IsThirdPartyPurchaseAllowed")]
+	pub third_party_purchase_allowed: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum UniverseAvatarType {
+	MorphToR6,
+	MorphToR15,
+	#[default]
+	PlayerChoice,
+	/// An avatar type added after this version of the crate
+	#[serde(other)]
+	Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum PlayableDevice {
+	Computer,
+	Phone,
+	Tablet,
+	Console,
+	#[serde(rename = "VR")]
+	Vr,
+	/// A device added after this version of the crate
+	#[serde(other)]
+	Other,
+}
@@ -1,20 +1,320 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::api::routes::RobloxApi;
-use crate::errors::RoboltError;
+use crate::api::{Limit, Page, Robux};
+use crate::errors::{ResourceKind, RoboltError};
 use crate::utils::client::Authenticated;
 use crate::Robolt;
 
 impl Robolt<Authenticated> {
-	pub async fn balance(&self) -> Result<u64, RoboltError> {
+	pub async fn balance(&self) -> Result<Robux, RoboltError> {
 		self.request(RobloxApi::Economy, "/v1/user/currency")
 			.send::<UserBalance>()
 			.await
 			.map(|res| res.robux)
 	}
+
+	/// The Robux held by a group
+	///
+	/// Requires the authenticated user to have permission to view the group's
+	/// finances, otherwise fails with
+	/// [RoboltErrorKind::PermissionDenied](crate::errors::RoboltErrorKind::PermissionDenied)
+	pub async fn group_balance(&self, group_id: u64) -> Result<Robux, RoboltError> {
+		self.request(RobloxApi::Economy, format!("/v1/groups/{group_id}/currency"))
+			.send::<UserBalance>()
+			.await
+			.map(|res| res.robux)
+	}
+
+	/// Fetches a page of a user's transaction history, newest first
+	///
+	/// Only the authenticated user's own history can be fetched.
+	pub async fn transactions(
+		&self,
+		user_id: u64,
+		transaction_type: TransactionType,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<Transaction>, RoboltError> {
+		let mut path = format!(
+			"/v2/users/{user_id}/transactions?transactionType={}&limit={}",
+			transaction_type.as_str(),
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Economy, path)
+			.resource(ResourceKind::User, user_id)
+			.send()
+			.await
+	}
+
+	/// Fetches a page of a group's transaction history
+	///
+	/// Requires the authenticated user to have permission to view the group's
+	/// finances, otherwise fails with
+	/// [RoboltErrorKind::PermissionDenied](crate::errors::RoboltErrorKind::PermissionDenied)
+	pub async fn group_transactions(
+		&self,
+		group_id: u64,
+		transaction_type: TransactionType,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<Transaction>, RoboltError> {
+		let mut path = format!(
+			"/v2/groups/{group_id}/transactions?transactionType={}&limit={}",
+			transaction_type.as_str(),
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Economy, path).send().await
+	}
+
+	/// Fetches the revenue summary of a group over the given time frame
+	///
+	/// Requires the authenticated user to have permission to view the group's
+	/// finances, otherwise fails with
+	/// [RoboltErrorKind::PermissionDenied](crate::errors::RoboltErrorKind::PermissionDenied)
+	pub async fn group_revenue_summary(
+		&self,
+		group_id: u64,
+		time_frame: TimeFrame,
+	) -> Result<RevenueSummary, RoboltError> {
+		self.request(
+			RobloxApi::Economy,
+			format!("/v1/groups/{group_id}/revenue/summary/{time_frame:?}"),
+		)
+		.send()
+		.await
+	}
+
+	/// Fetches the sales chart of a limited asset
+	pub async fn resale_data(&self, asset_id: u64) -> Result<ResaleData, RoboltError> {
+		self.request(RobloxApi::Economy, format!("/v1/assets/{asset_id}/resale-data"))
+			.resource(ResourceKind::Asset, asset_id)
+			.send()
+			.await
+	}
+
+	/// Writes the price history of a limited asset to `writer` as CSV, oldest
+	/// sale first, see [ResaleData::price_points]
+	#[cfg(feature = "csv")]
+	#[doc(cfg(feature = "csv"))]
+	pub async fn resale_history_csv<W: std::io::Write>(
+		&self,
+		asset_id: u64,
+		writer: W,
+	) -> Result<(), RoboltError> {
+		let resale_data = self.resale_data(asset_id).await?;
+
+		crate::csv::write_rows(writer, resale_data.price_points()).map_err(|err| err.to_string().into())
+	}
+}
+
+impl ResaleData {
+	/// The average sale price of each day in the chart, oldest first
+	///
+	/// Roblox returns the chart newest first, with the prices and volumes in
+	/// separate lists. Days without a matching volume point have a volume of
+	/// `0`.
+	pub fn price_points(&self) -> Vec<PricePoint> {
+		let mut points = self
+			.price_data_points
+			.iter()
+			.map(|price| PricePoint {
+				date: price.date.clone(),
+				price: Robux(price.value),
+				volume: self
+					.volume_data_points
+					.iter()
+					.find(|volume| volume.date == price.date)
+					.map_or(0, |volume| volume.value),
+			})
+			.collect::<Vec<PricePoint>>();
+
+		points.sort_by(|a, b| a.date.cmp(&b.date));
+		points
+	}
+}
+
+impl Transaction {
+	/// The item the transaction is for, e.g. the asset that was bought or
+	/// sold, `None` for transactions without one such as stipends
+	pub fn item(&self) -> Option<TransactionItem> {
+		TransactionItem::deserialize(&self.details).ok()
+	}
+
+	/// The place an item was bought in, `None` outside of in-game purchases
+	pub fn place(&self) -> Option<TransactionPlace> {
+		TransactionPlace::deserialize(self.details.get("place")?).ok()
+	}
+}
+
+impl TransactionType {
+	fn as_str(&self) -> &'static str {
+		match self {
+			TransactionType::Sale => "Sale",
+			TransactionType::Purchase => "Purchase",
+			TransactionType::AffiliateSale => "AffiliateSale",
+			TransactionType::DevEx => "DevEx",
+			TransactionType::GroupPayout => "GroupPayout",
+			TransactionType::AdImpressionPayout => "AdImpressionPayout",
+			TransactionType::PremiumPayout => "EngagementPayout",
+			TransactionType::GroupPremiumPayout => "GroupEngagementPayout",
+			TransactionType::CurrencyPurchase => "CurrencyPurchase",
+			TransactionType::TradeRobux => "TradeRobux",
+			TransactionType::PremiumStipend => "PremiumStipend",
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum TransactionType {
+	#[default]
+	Sale,
+	Purchase,
+	/// Commissions earned on sales made through the user's games or links
+	AffiliateSale,
+	DevEx,
+	GroupPayout,
+	AdImpressionPayout,
+	PremiumPayout,
+	GroupPremiumPayout,
+	CurrencyPurchase,
+	TradeRobux,
+	PremiumStipend,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum TimeFrame {
+	#[default]
+	Day,
+	Week,
+	Month,
+	Year,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+	pub id: u64,
+	pub id_hash: Option<String>,
+	pub created: String,
+	#[serde(default)]
+	pub is_pending: bool,
+	pub agent: TransactionAgent,
+	/// The transaction details, the shape of which depends on the transaction
+	/// type
+	#[serde(default)]
+	pub details: Value,
+	pub currency: TransactionCurrency,
+	pub purchase_token: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionAgent {
+	pub id: u64,
+	#[serde(rename = "type")]
+	pub agent_type: String,
+	pub name: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The item of a transaction, see [Transaction::item]
+pub struct TransactionItem {
+	pub id: u64,
+	pub name: String,
+	/// The kind of item, e.g. `Asset`, `GamePass` or `DeveloperProduct`
+	#[serde(rename = "type")]
+	pub item_type: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The place of an in-game purchase, see [Transaction::place]
+pub struct TransactionPlace {
+	pub place_id: u64,
+	pub universe_id: u64,
+	pub name: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionCurrency {
+	/// Negative for Robux spent, and for payouts that were reversed
+	pub amount: i64,
+	#[serde(rename = "type")]
+	pub currency_type: String,
+}
+
+/// Robux earned by a group, grouped by source
+///
+/// Roblox adds and removes buckets over time, missing buckets default to `0`
+/// and unknown buckets are kept in `extra`.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RevenueSummary {
+	pub pending_robux: i64,
+	pub item_sale_robux: i64,
+	pub premium_payouts: i64,
+	pub group_premium_payouts: i64,
+	pub recurring_robux_stipend: i64,
+	pub purchased_robux: i64,
+	pub trade_system_robux: i64,
+	pub group_payout_robux: i64,
+	pub individual_to_group_robux: i64,
+	pub adjustment_robux: i64,
+	#[serde(flatten)]
+	pub extra: HashMap<String, Value>,
+}
+
+/// The sales chart of a limited asset
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResaleData {
+	/// The number of copies in circulation, `None` for non-unique limiteds
+	pub asset_stock: Option<u64>,
+	#[serde(default)]
+	pub sales: u64,
+	pub number_remaining: Option<u64>,
+	#[serde(default)]
+	pub recent_average_price: Robux,
+	pub original_price: Option<Robux>,
+	#[serde(default)]
+	pub price_data_points: Vec<ChartPoint>,
+	#[serde(default)]
+	pub volume_data_points: Vec<ChartPoint>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct ChartPoint {
+	pub value: u64,
+	pub date: String,
+}
+
+/// A single day of a sales chart, see [ResaleData::price_points]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PricePoint {
+	pub date: String,
+	/// The average price the asset sold for that day
+	pub price: Robux,
+	/// The number of copies sold that day
+	pub volume: u64,
 }
 
 #[derive(Deserialize)]
 struct UserBalance {
-	robux: u64,
+	robux: Robux,
 }
\ No newline at end of file
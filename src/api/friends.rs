@@ -1,18 +1,22 @@
 use reqwest::Method;
 use serde::Deserialize;
+use serde_json::json;
 
 use crate::api::presence::UserPresence;
 use crate::api::routes::RobloxApi;
 use crate::api::users::{PartialUser, User};
-use crate::api::Limit;
-use crate::errors::RoboltError;
+use crate::api::{Limit, Page};
+use crate::errors::{ResourceKind, RoboltError};
+use crate::utils::batch::{self, BatchReport};
 use crate::utils::client::Authenticated;
 use crate::utils::response::{CountResponse, DataResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
 use crate::Robolt;
 
 impl<State> Robolt<State> {
 	pub async fn follower_count(&self, user_id: u64) -> Result<u64, RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/followers/count"))
+			.resource(ResourceKind::User, user_id)
 			.send::<CountResponse<u64>>()
 			.await
 			.map(|res| res.count)
@@ -20,6 +24,7 @@ impl<State> Robolt<State> {
 
 	pub async fn following_count(&self, user_id: u64) -> Result<u64, RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/followings/count"))
+			.resource(ResourceKind::User, user_id)
 			.send::<CountResponse<u64>>()
 			.await
 			.map(|res| res.count)
@@ -27,6 +32,7 @@ impl<State> Robolt<State> {
 
 	pub async fn friend_count(&self, user_id: u64) -> Result<u64, RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/friends/count"))
+			.resource(ResourceKind::User, user_id)
 			.send::<CountResponse<u64>>()
 			.await
 			.map(|res| res.count)
@@ -34,29 +40,46 @@ impl<State> Robolt<State> {
 
 	pub async fn friends(&self, user_id: u64) -> Result<Vec<User>, RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/friends"))
+			.resource(ResourceKind::User, user_id)
 			.send::<DataResponse<User>>()
 			.await
 			.map(|res| res.data)
 	}
 
-	pub async fn followers(&self, user_id: u64, limit: Limit) -> Result<Vec<User>, RoboltError> {
-		self.request(
-			RobloxApi::Friends,
-			format!("/v1/users/{user_id}/followers?limit={}", limit as u8),
-		)
-		.send::<DataResponse<User>>()
-		.await
-		.map(|res| res.data)
+	pub async fn followers(
+		&self,
+		user_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<User>, RoboltError> {
+		let mut path = format!("/v1/users/{user_id}/followers?limit={}", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Friends, path)
+			.resource(ResourceKind::User, user_id)
+			.send()
+			.await
 	}
 
-	pub async fn followings(&self, user_id: u64, limit: Limit) -> Result<Vec<User>, RoboltError> {
-		self.request(
-			RobloxApi::Friends,
-			format!("/v1/users/{user_id}/followings?limit={}", limit as u8),
-		)
-		.send::<DataResponse<User>>()
-		.await
-		.map(|res| res.data)
+	pub async fn followings(
+		&self,
+		user_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<User>, RoboltError> {
+		let mut path = format!("/v1/users/{user_id}/followings?limit={}", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Friends, path)
+			.resource(ResourceKind::User, user_id)
+			.send()
+			.await
 	}
 }
 
@@ -88,6 +111,7 @@ impl Robolt<Authenticated> {
 	pub async fn unfriend(&self, user_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/unfriend"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -97,6 +121,7 @@ impl Robolt<Authenticated> {
 	pub async fn unfollow(&self, user_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/unfollow"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -109,24 +134,38 @@ impl Robolt<Authenticated> {
 			format!("/v1/users/{user_id}/decline-friend-request"),
 		)
 		.method(Method::POST)
+		.idempotency(Idempotency::NonIdempotent)
 		.send::<EmptyResponse>()
 		.await?;
 
 		Ok(())
 	}
 
+	pub async fn send_friend_request(&self, user_id: u64) -> Result<(), RoboltError> {
+		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/request-friendship"))
+			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body::<_, EmptyResponse>(json!({ "friendshipOriginSourceType": "UserProfile" }))
+			.await?;
+
+		Ok(())
+	}
+
 	pub async fn accept_friend_request(&self, user_id: u64) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Friends, format!("/v1/users/{user_id}/accept-friend-request"))
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
 		Ok(())
 	}
 
+	/// Declines every pending friend request at once
 	pub async fn decline_all_friend_requests(&self) -> Result<(), RoboltError> {
 		self.request(RobloxApi::Friends, "/v1/user/friend-requests/decline-all")
 			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
 			.send::<EmptyResponse>()
 			.await?;
 
@@ -143,6 +182,77 @@ impl Robolt<Authenticated> {
 			.map(|res| res.data)
 	}
 
+	/// Unfriends every user in `user_ids`, with at most `concurrency` requests
+	/// in flight
+	///
+	/// With `dry_run` set, nothing is sent and the report lists which of the
+	/// users are currently friends (and would therefore be unfriended).
+	pub async fn unfriend_many(
+		&self,
+		user_ids: Vec<u64>,
+		concurrency: usize,
+		dry_run: bool,
+	) -> Result<BatchReport<u64>, RoboltError> {
+		if dry_run {
+			let mut report = BatchReport {
+				dry_run: true,
+				..Default::default()
+			};
+
+			for relationship in self.friendship_statuses(user_ids).await? {
+				match relationship.status {
+					FriendshipStatus::Friends => report.succeeded.push(relationship.id),
+					_ => report.skipped.push(relationship.id),
+				}
+			}
+
+			return Ok(report);
+		}
+
+		Ok(batch::run(user_ids, concurrency, |user_id| self.unfriend(user_id)).await)
+	}
+
+	/// Unfollows every user in `user_ids`, with at most `concurrency` requests
+	/// in flight
+	///
+	/// With `dry_run` set, nothing is sent and the report lists which of the
+	/// users are currently followed (and would therefore be unfollowed).
+	pub async fn unfollow_many(
+		&self,
+		user_ids: Vec<u64>,
+		concurrency: usize,
+		dry_run: bool,
+	) -> Result<BatchReport<u64>, RoboltError> {
+		if dry_run {
+			let mut report = BatchReport {
+				dry_run: true,
+				..Default::default()
+			};
+
+			for status in self.following_statuses(user_ids).await? {
+				match status.is_following {
+					true => report.succeeded.push(status.user_id),
+					false => report.skipped.push(status.user_id),
+				}
+			}
+
+			return Ok(report);
+		}
+
+		Ok(batch::run(user_ids, concurrency, |user_id| self.unfollow(user_id)).await)
+	}
+
+	/// Whether the authenticated user follows each of `user_ids`
+	pub async fn following_statuses(&self, user_ids: Vec<u64>) -> Result<Vec<FollowingStatus>, RoboltError> {
+		self.request(RobloxApi::Friends, "/v1/user/following-exists")
+			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.read_only()
+			.send_body::<_, FollowingExistsResponse>(json!({ "targetUserIds": user_ids }))
+			.await
+			.map(|res| res.followings)
+	}
+
 	pub async fn friendship_statuses(&self, user_ids: Vec<u64>) -> Result<Vec<UserRelationship>, RoboltError> {
 		let user_id = self.me().await?.id;
 		let user_ids = user_ids
@@ -182,6 +292,21 @@ pub struct UserRelationship {
 	pub status: FriendshipStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowingStatus {
+	pub user_id: u64,
+	/// Whether the authenticated user follows this user
+	pub is_following: bool,
+	/// Whether this user follows the authenticated user
+	pub is_followed: bool,
+}
+
+#[derive(Deserialize)]
+struct FollowingExistsResponse {
+	followings: Vec<FollowingStatus>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct OnlineFriend {
 	#[serde(rename = "userPresence")]
@@ -191,20 +316,78 @@ pub struct OnlineFriend {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(from = "FriendRequestResponse")]
+/// A pending friend request received by the authenticated user
 pub struct FriendRequest {
+	/// The user who sent the request
+	pub user: PartialUser,
+	pub origin: FriendRequestOrigin,
+	/// The experience the request was sent from, if any
+	pub source_universe_id: Option<u64>,
+	pub sent_at: String,
+	/// The names of the friends shared with the sender, only returned by some
+	/// variants of the endpoint
+	pub mutual_friends: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+/// Where a friend request was sent from
+pub enum FriendRequestOrigin {
+	UserProfile,
+	InGame,
+	PlayerSearch,
+	QrCode,
+	FriendRecommendations,
+	#[default]
+	Unknown,
+	Other {
+		source_type: String,
+	},
+}
+
+impl From<String> for FriendRequestOrigin {
+	fn from(source_type: String) -> Self {
+		match source_type.as_str() {
+			"UserProfile" => Self::UserProfile,
+			"InGame" => Self::InGame,
+			"PlayerSearch" => Self::PlayerSearch,
+			"QrCode" => Self::QrCode,
+			"FriendRecommendations" => Self::FriendRecommendations,
+			"" | "Unknown" => Self::Unknown,
+			_ => Self::Other {
+				source_type,
+			},
+		}
+	}
+}
+
+impl From<FriendRequestResponse> for FriendRequest {
+	fn from(res: FriendRequestResponse) -> Self {
+		Self {
+			user: res.user,
+			origin: res.friend_request.origin_source_type.into(),
+			source_universe_id: res.friend_request.source_universe_id,
+			sent_at: res.friend_request.sent_at,
+			mutual_friends: res.mutual_friends_list,
+		}
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FriendRequestResponse {
 	#[serde(flatten)]
-	pub user: User,
-	pub friend_request: FriendRequestInfo,
-	pub mutual_friends_list: Vec<String>,
+	user: PartialUser,
+	friend_request: FriendRequestInfo,
+	#[serde(default)]
+	mutual_friends_list: Vec<String>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct FriendRequestInfo {
-	pub sent_at: String,
-	pub sender_id: u64,
-	pub source_universe_id: Option<u64>,
-	pub origin_source_type: String,
-	pub contact_name: Option<String>,
+struct FriendRequestInfo {
+	sent_at: String,
+	source_universe_id: Option<u64>,
+	#[serde(default)]
+	origin_source_type: String,
 }
\ No newline at end of file
@@ -0,0 +1,737 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::Future;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::api::routes::RobloxApi;
+use crate::api::{deserialize_creator, Creator, Limit, Page, Robux, TypedCreator};
+use crate::errors::{ResourceKind, RoboltError, RoboltErrorKind};
+use crate::utils::client::Authenticated;
+use crate::utils::response::{DataResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
+use crate::Robolt;
+
+const DEFAULT_MAX_SERVER_PAGES: usize = 100;
+/// The maximum number of universes per playability status request
+const PLAYABILITY_BATCH_SIZE: usize = 50;
+/// The maximum number of universes or places per details request
+const DETAILS_BATCH_SIZE: usize = 50;
+/// The maximum number of universes per votes request
+const VOTES_BATCH_SIZE: usize = 50;
+
+/// The error code returned when adding a Discord link from an account that is
+/// under 13 or unverified
+const SOCIAL_LINK_AGE_RESTRICTED_CODE: i32 = 9;
+
+type ServerPredicate = dyn Fn(&GameServer) -> bool + Send + Sync;
+
+impl<State> Robolt<State> {
+	/// A page of the public servers of a place, see [Robolt::servers]
+	pub async fn game_servers(
+		&self,
+		place_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<GameServer>, RoboltError> {
+		self.servers(place_id, ServerType::Public, limit, cursor).await
+	}
+
+	/// A page of the running servers of a place, with their player counts
+	/// and ping
+	///
+	/// [ServerType::Friend] lists the servers that friends of the
+	/// authenticated user are in, and is empty for unauthenticated clients.
+	pub async fn servers(
+		&self,
+		place_id: u64,
+		server_type: ServerType,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<GameServer>, RoboltError> {
+		let mut path = format!("/v1/games/{place_id}/servers/{server_type}?limit={}", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Games, path)
+			.resource(ResourceKind::Place, place_id)
+			.send()
+			.await
+	}
+
+	/// The details of each universe in `universe_ids`
+	///
+	/// Universes are requested in batches of 50, and universes that do not
+	/// exist are missing from the result.
+	pub async fn universe_details(&self, universe_ids: Vec<u64>) -> Result<Vec<GameDetails>, RoboltError> {
+		let mut details = Vec::new();
+
+		for chunk in universe_ids.chunks(DETAILS_BATCH_SIZE) {
+			let ids = chunk.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+			let res = self
+				.request(RobloxApi::Games, format!("/v1/games?universeIds={ids}"))
+				.send::<DataResponse<GameDetails>>()
+				.await?;
+
+			details.extend(res.data);
+		}
+
+		Ok(details)
+	}
+
+	/// A page of the game passes of a universe, oldest first
+	pub async fn game_passes(
+		&self,
+		universe_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<GamePass>, RoboltError> {
+		let mut path = format!(
+			"/v1/games/{universe_id}/game-passes?limit={}&sortOrder=Asc",
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Games, path)
+			.resource(ResourceKind::Universe, universe_id)
+			.send()
+			.await
+	}
+
+	/// Lists the developer products of a universe
+	///
+	/// The endpoint is paginated by page number, which is passed around as the
+	/// cursor of the returned [Page].
+	pub async fn developer_products(
+		&self,
+		universe_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<DeveloperProduct>, RoboltError> {
+		let page = match cursor {
+			Some(cursor) => cursor
+				.parse::<u32>()
+				.map_err(|_| format!("Invalid developer products cursor: {cursor}"))?,
+			None => 1,
+		};
+
+		let res = self
+			.request(
+				RobloxApi::Cloud,
+				format!(
+					"/developer-products/v1/universes/{universe_id}/developerproducts?pageNumber={page}&pageSize={}",
+					limit as u8
+				),
+			)
+			.send::<DeveloperProductsResponse>()
+			.await?;
+
+		Ok(Page {
+			previous_page_cursor: (page > 1).then(|| (page - 1).to_string()),
+			next_page_cursor: (!res.final_page).then(|| (page + 1).to_string()),
+			data: res.developer_products,
+		})
+	}
+
+	pub async fn developer_product(&self, id: DeveloperProductId) -> Result<DeveloperProduct, RoboltError> {
+		self.request(
+			RobloxApi::Cloud,
+			format!("/developer-products/v1/developer-products/{id}"),
+		)
+		.resource(ResourceKind::DeveloperProduct, id.0)
+		.send()
+		.await
+	}
+
+	/// Lists the social links shown on a universe's game page
+	///
+	/// Fails with [RoboltErrorKind::PermissionDenied] when Roblox restricts
+	/// the links to the owner of the universe.
+	pub async fn social_links(&self, universe_id: u64) -> Result<Vec<SocialLink>, RoboltError> {
+		self.request(RobloxApi::Games, format!("/v1/games/{universe_id}/social-links/list"))
+			.resource(ResourceKind::Universe, universe_id)
+			.send::<DataResponse<SocialLink>>()
+			.await
+			.map(|res| res.data)
+	}
+
+	/// The up and down votes of each universe in `universe_ids`
+	///
+	/// Universes are requested in batches of 50, and universes that do not
+	/// exist are missing from the map.
+	pub async fn votes(&self, universe_ids: Vec<u64>) -> Result<HashMap<u64, Votes>, RoboltError> {
+		let mut votes = HashMap::new();
+
+		for chunk in universe_ids.chunks(VOTES_BATCH_SIZE) {
+			let ids = chunk.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+			let res = self
+				.request(RobloxApi::Games, format!("/v1/games/votes?universeIds={ids}"))
+				.send::<DataResponse<Votes>>()
+				.await?;
+
+			votes.extend(res.data.into_iter().map(|entry| (entry.id, entry)));
+		}
+
+		Ok(votes)
+	}
+
+	/// The number of users who favorited a universe
+	pub async fn favorites_count(&self, universe_id: u64) -> Result<u64, RoboltError> {
+		self.request(RobloxApi::Games, format!("/v1/games/{universe_id}/favorites/count"))
+			.resource(ResourceKind::Universe, universe_id)
+			.send::<FavoritesCountResponse>()
+			.await
+			.map(|res| res.favorites_count)
+	}
+
+	/// Crawls every public server of a place, see [crawl_servers]
+	pub async fn all_servers(
+		&self,
+		place_id: u64,
+		options: ServerCrawlOptions,
+	) -> Result<ServerSnapshot, RoboltError> {
+		crawl_servers(options, |cursor| async move {
+			self.game_servers(place_id, Limit::Max, cursor.as_deref()).await
+		})
+		.await
+	}
+}
+
+impl Robolt<Authenticated> {
+	/// Checks whether the authenticated user can join each universe, and why
+	/// not if they cannot
+	///
+	/// Universes are requested in batches of 50, and universes missing from
+	/// the response are missing from the map.
+	pub async fn playability(&self, universe_ids: &[u64]) -> Result<HashMap<u64, Playability>, RoboltError> {
+		let mut playability = HashMap::new();
+
+		for chunk in universe_ids.chunks(PLAYABILITY_BATCH_SIZE) {
+			let ids = chunk.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+			let res = self
+				.request(
+					RobloxApi::Games,
+					format!("/v1/games/multiget-playability-status?universeIds={ids}"),
+				)
+				.send::<Vec<Playability>>()
+				.await?;
+
+			playability.extend(res.into_iter().map(|status| (status.universe_id, status)));
+		}
+
+		Ok(playability)
+	}
+
+	/// The details of each place in `place_ids`
+	///
+	/// Places are requested in batches of 50, and places that do not exist
+	/// are missing from the result. Unlike the universe endpoint, the place
+	/// IDs are passed as repeated `placeIds` parameters.
+	pub async fn place_details(&self, place_ids: Vec<u64>) -> Result<Vec<PlaceDetails>, RoboltError> {
+		let mut details = Vec::new();
+
+		for chunk in place_ids.chunks(DETAILS_BATCH_SIZE) {
+			let query = chunk
+				.iter()
+				.map(|id| format!("placeIds={id}"))
+				.collect::<Vec<String>>()
+				.join("&");
+			let res = self
+				.request(RobloxApi::Games, format!("/v1/games/multiget-place-details?{query}"))
+				.send::<Vec<PlaceDetails>>()
+				.await?;
+
+			details.extend(res);
+		}
+
+		Ok(details)
+	}
+
+	/// Favorites or unfavorites a universe for the authenticated user
+	pub async fn set_favorite(&self, universe_id: u64, favorited: bool) -> Result<(), RoboltError> {
+		let body = HashMap::from([("isFavorited", favorited)]);
+
+		self.request(RobloxApi::Games, format!("/v1/games/{universe_id}/favorites"))
+			.method(Method::POST)
+			.invalidates(RobloxApi::Games, format!("/v1/games/{universe_id}/favorites/count"))
+			.idempotency(Idempotency::Idempotent)
+			.resource(ResourceKind::Universe, universe_id)
+			.send_body::<_, EmptyResponse>(body)
+			.await?;
+
+		Ok(())
+	}
+
+	/// Adds a social link to a universe the authenticated user can edit
+	///
+	/// Discord links fail with [RoboltErrorKind::AgeRestricted] unless the
+	/// account is 13 or older and verified.
+	pub async fn create_social_link(
+		&self,
+		universe_id: u64,
+		link: &SocialLinkRequest,
+	) -> Result<SocialLink, RoboltError> {
+		self.request(RobloxApi::Develop, format!("/v1/universes/{universe_id}/social-links"))
+			.method(Method::POST)
+			.invalidates(RobloxApi::Games, format!("/v1/games/{universe_id}/social-links/list"))
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body(link)
+			.await
+			.map_err(social_link_error)
+	}
+
+	/// Replaces the type, title and URL of an existing social link
+	pub async fn update_social_link(
+		&self,
+		universe_id: u64,
+		social_link_id: u64,
+		link: &SocialLinkRequest,
+	) -> Result<(), RoboltError> {
+		self.request(
+			RobloxApi::Develop,
+			format!("/v1/universes/{universe_id}/social-links/{social_link_id}"),
+		)
+		.method(Method::PATCH)
+		.invalidates(RobloxApi::Games, format!("/v1/games/{universe_id}/social-links/list"))
+		.idempotency(Idempotency::Idempotent)
+		.send_body::<_, EmptyResponse>(link)
+		.await
+		.map_err(social_link_error)?;
+
+		Ok(())
+	}
+
+	pub async fn delete_social_link(&self, universe_id: u64, social_link_id: u64) -> Result<(), RoboltError> {
+		self.request(
+			RobloxApi::Develop,
+			format!("/v1/universes/{universe_id}/social-links/{social_link_id}"),
+		)
+		.method(Method::DELETE)
+		.invalidates(RobloxApi::Games, format!("/v1/games/{universe_id}/social-links/list"))
+		.idempotency(Idempotency::Idempotent)
+		.send::<EmptyResponse>()
+		.await?;
+
+		Ok(())
+	}
+}
+
+fn social_link_error(err: RoboltError) -> RoboltError {
+	match err.code {
+		SOCIAL_LINK_AGE_RESTRICTED_CODE => err.with_kind(RoboltErrorKind::AgeRestricted),
+		_ => err,
+	}
+}
+
+/// Pages through servers with `fetch` until there are no pages left
+///
+/// The servers endpoint is known to return the same server on multiple pages
+/// and to occasionally hand out a cursor that was already visited, so servers
+/// are de-duplicated by ID and the crawl stops as soon as a cursor repeats.
+/// The crawl also stops once [ServerCrawlOptions::max_pages] pages have been
+/// fetched or a server matches [ServerCrawlOptions::stop_when].
+pub async fn crawl_servers<F, Fut>(options: ServerCrawlOptions, fetch: F) -> Result<ServerSnapshot, RoboltError>
+where
+	F: Fn(Option<String>) -> Fut,
+	Fut: Future<Output = Result<Page<GameServer>, RoboltError>>,
+{
+	let mut snapshot = ServerSnapshot::default();
+	let mut server_ids = HashSet::new();
+	let mut cursors = HashSet::new();
+	let mut cursor = None;
+
+	loop {
+		if snapshot.pages_fetched >= options.max_pages {
+			snapshot.truncated = true;
+			return Ok(snapshot);
+		}
+
+		let page = fetch(cursor.clone()).await?;
+		snapshot.pages_fetched += 1;
+
+		for server in page.data {
+			if !server_ids.insert(server.id.clone()) {
+				snapshot.duplicates_dropped += 1;
+				continue;
+			}
+
+			let matched = options.stop_when.as_ref().is_some_and(|stop_when| stop_when(&server));
+			snapshot.servers.push(server);
+
+			if matched {
+				snapshot.stopped_early = true;
+				return Ok(snapshot);
+			}
+		}
+
+		cursor = match page.next_page_cursor {
+			Some(next) if !cursors.insert(next.clone()) => {
+				snapshot.cursor_loop = true;
+				snapshot.truncated = true;
+				return Ok(snapshot);
+			},
+			Some(next) => Some(next),
+			None => return Ok(snapshot),
+		};
+	}
+}
+
+impl Default for ServerCrawlOptions {
+	fn default() -> Self {
+		Self {
+			max_pages: DEFAULT_MAX_SERVER_PAGES,
+			stop_when: None,
+		}
+	}
+}
+
+impl ServerCrawlOptions {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The maximum number of pages to fetch, 100 by default
+	pub fn max_pages(mut self, max_pages: usize) -> Self {
+		self.max_pages = max_pages;
+		self
+	}
+
+	/// Stops the crawl once a server matching `predicate` has been found, e.g.
+	/// to look for a specific player
+	pub fn stop_when<F>(mut self, predicate: F) -> Self
+	where
+		F: Fn(&GameServer) -> bool + Send + Sync + 'static,
+	{
+		self.stop_when = Some(Box::new(predicate));
+		self
+	}
+}
+
+impl fmt::Debug for ServerCrawlOptions {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ServerCrawlOptions")
+			.field("max_pages", &self.max_pages)
+			.field("stop_when", &self.stop_when.is_some())
+			.finish()
+	}
+}
+
+pub struct ServerCrawlOptions {
+	max_pages: usize,
+	stop_when: Option<Box<ServerPredicate>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// The servers found by [crawl_servers], along with how the crawl went
+pub struct ServerSnapshot {
+	pub servers: Vec<GameServer>,
+	pub pages_fetched: usize,
+	/// The number of servers that were returned more than once
+	pub duplicates_dropped: usize,
+	/// Whether there were pages left when the crawl stopped, because of the
+	/// page budget or a cursor loop
+	pub truncated: bool,
+	pub cursor_loop: bool,
+	/// Whether the crawl stopped because a server matched
+	/// [ServerCrawlOptions::stop_when]
+	pub stopped_early: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameServer {
+	/// The job ID of the server
+	pub id: String,
+	pub max_players: u32,
+	#[serde(default)]
+	pub playing: u32,
+	#[serde(default)]
+	pub player_tokens: Vec<String>,
+	#[serde(default)]
+	pub fps: f32,
+	pub ping: Option<u32>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of servers listed by [Robolt::servers]
+pub enum ServerType {
+	#[default]
+	Public,
+	/// Servers that friends of the authenticated user are in
+	Friend,
+}
+
+impl fmt::Display for ServerType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ServerType::Public => write!(f, "Public"),
+			ServerType::Friend => write!(f, "Friend"),
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The details of a universe, see [Robolt::universe_details]
+pub struct GameDetails {
+	/// The ID of the universe
+	pub id: u64,
+	pub root_place_id: u64,
+	pub name: String,
+	pub description: Option<String>,
+	#[serde(deserialize_with = "deserialize_creator::<_, TypedCreator>")]
+	pub creator: Creator,
+	/// `None` unless the universe requires paid access
+	pub price: Option<Robux>,
+	/// The number of players currently in game
+	#[serde(default)]
+	pub playing: u64,
+	#[serde(default)]
+	pub visits: u64,
+	pub max_players: u32,
+	#[serde(default)]
+	pub favorited_count: u64,
+	pub genre: Option<String>,
+	pub created: String,
+	pub updated: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The details of a place, see [Robolt::place_details]
+pub struct PlaceDetails {
+	pub place_id: u64,
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	pub url: String,
+	/// The name of the user or group that owns the place
+	pub builder: String,
+	pub builder_id: u64,
+	#[serde(default)]
+	pub is_playable: bool,
+	/// Why the place cannot be joined, e.g. `GuestProhibited`
+	pub reason_prohibited: Option<String>,
+	pub universe_id: u64,
+	pub universe_root_place_id: u64,
+	pub price: Option<Robux>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A game pass sold in a universe
+pub struct GamePass {
+	pub id: u64,
+	pub name: String,
+	pub display_name: String,
+	/// The marketplace-wide product ID, `None` if the pass was never for sale
+	pub product_id: Option<u64>,
+	/// `None` if the pass is not for sale
+	pub price: Option<Robux>,
+	pub seller_name: String,
+	pub seller_id: Option<u64>,
+	/// Whether the authenticated user owns the pass, always `false` for
+	/// unauthenticated clients
+	#[serde(default)]
+	pub is_owned: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The votes of a universe, see [Robolt::votes]
+pub struct Votes {
+	/// The ID of the universe
+	pub id: u64,
+	#[serde(default)]
+	pub up_votes: u64,
+	#[serde(default)]
+	pub down_votes: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FavoritesCountResponse {
+	favorites_count: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SocialLink {
+	pub id: u64,
+	#[serde(rename = "type")]
+	pub link_type: SocialLinkType,
+	pub title: String,
+	pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// A social link to add to a universe, or to replace an existing one with
+pub struct SocialLinkRequest {
+	#[serde(rename = "type")]
+	pub link_type: SocialLinkType,
+	pub title: String,
+	pub url: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum SocialLinkType {
+	Facebook,
+	/// Also parsed from `X`, the platform's current name
+	#[serde(alias = "X")]
+	Twitter,
+	YouTube,
+	Twitch,
+	/// Requires the account to be 13 or older and verified
+	Discord,
+	RobloxGroup,
+	Guilded,
+	/// A platform added after this version of the crate, which cannot be used
+	/// to create links
+	#[default]
+	#[serde(other)]
+	Other,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "PlayabilityResponse")]
+/// Whether the authenticated user can join a universe, see
+/// [Robolt::playability]
+pub struct Playability {
+	pub universe_id: u64,
+	pub is_playable: bool,
+	pub status: PlayabilityStatus,
+	/// The reason shown to the user when the universe cannot be joined
+	pub unplayable_display_text: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+/// Why a universe can or cannot be joined
+pub enum PlayabilityStatus {
+	#[default]
+	Playable,
+	/// Guests are not allowed to join
+	GuestProhibited,
+	/// The universe is private or has no public root place
+	GameUnapproved,
+	UnderReview,
+	/// The universe requires paid access, the price being in Robux when
+	/// returned by the endpoint
+	PurchaseRequired {
+		price: Option<Robux>,
+	},
+	/// The account is not allowed to join, e.g. because of its age or
+	/// moderation status
+	AccountRestricted,
+	InsufficientPermissionFriendsOnly,
+	InsufficientPermissionGroupOnly,
+	DeviceRestricted,
+	ContextualPlayabilityRegionalAvailability,
+	/// A status added after this version of the crate
+	Other {
+		status: String,
+	},
+}
+
+impl From<PlayabilityResponse> for Playability {
+	fn from(res: PlayabilityResponse) -> Self {
+		let status = match res.playability_status.as_str() {
+			"Playable" => PlayabilityStatus::Playable,
+			"GuestProhibited" => PlayabilityStatus::GuestProhibited,
+			"GameUnapproved" => PlayabilityStatus::GameUnapproved,
+			"UnderReview" => PlayabilityStatus::UnderReview,
+			"PurchaseRequired" => PlayabilityStatus::PurchaseRequired {
+				price: res.price,
+			},
+			"AccountRestricted" => PlayabilityStatus::AccountRestricted,
+			"InsufficientPermissionFriendsOnly" => PlayabilityStatus::InsufficientPermissionFriendsOnly,
+			"InsufficientPermissionGroupOnly" => PlayabilityStatus::InsufficientPermissionGroupOnly,
+			"DeviceRestricted" => PlayabilityStatus::DeviceRestricted,
+			"ContextualPlayabilityRegionalAvailability" => {
+				PlayabilityStatus::ContextualPlayabilityRegionalAvailability
+			},
+			_ => PlayabilityStatus::Other {
+				status: res.playability_status,
+			},
+		};
+
+		Self {
+			universe_id: res.universe_id,
+			is_playable: res.is_playable,
+			status,
+			unplayable_display_text: res.unplayable_display_text.filter(|text| !text.is_empty()),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlayabilityResponse {
+	universe_id: u64,
+	#[serde(default)]
+	is_playable: bool,
+	#[serde(default)]
+	playability_status: String,
+	unplayable_display_text: Option<String>,
+	price: Option<Robux>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(transparent)]
+/// The ID of a developer product within its universe, used by the developer
+/// product endpoints and `MarketplaceService.ProcessReceipt`
+pub struct DeveloperProductId(pub u64);
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(transparent)]
+/// The marketplace-wide product ID of a developer product, used for
+/// purchases and `MarketplaceService:GetProductInfo`
+pub struct ProductId(pub u64);
+
+impl fmt::Display for DeveloperProductId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl fmt::Display for ProductId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeveloperProductsResponse {
+	developer_products: Vec<DeveloperProduct>,
+	#[serde(rename = "FinalPage")]
+	final_page: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+/// An in-game purchase of a universe
+///
+/// Developer products have two IDs that are easily confused, see
+/// [DeveloperProductId] and [ProductId].
+pub struct DeveloperProduct {
+	#[serde(alias = "productId")]
+	pub product_id: ProductId,
+	#[serde(alias = "id", alias = "developerProductId")]
+	pub developer_product_id: DeveloperProductId,
+	#[serde(alias = "name")]
+	pub name: String,
+	#[serde(default, alias = "description")]
+	pub description: Option<String>,
+	#[serde(default, alias = "iconImageAssetId")]
+	pub icon_image_asset_id: Option<u64>,
+	/// `None` if the product is not for sale
+	#[serde(default, alias = "priceInRobux")]
+	pub price_in_robux: Option<Robux>,
+}
@@ -0,0 +1,1886 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::io::{self, Write};
+use std::sync::PoisonError;
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+
+use crate::api::cloud::MembershipFilter;
+use crate::api::routes::{encode_query_value, RobloxApi};
+use crate::api::users::PartialUser;
+use crate::api::{Limit, Page, SearchResults};
+use crate::errors::{NotFoundReason, ResourceKind, RoboltError, RoboltErrorKind};
+use crate::utils::batch::BatchReport;
+use crate::utils::client::Authenticated;
+use crate::utils::csv::escape_field;
+use crate::utils::moderation::{ModerationOutcome, ModerationTarget, Recorder};
+use crate::utils::paging::{Collected, CursorPaging, ExhaustionReason, PageIterator};
+use crate::utils::response::{DataResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
+#[cfg(feature = "chrono")]
+use crate::utils::timestamp;
+use crate::Robolt;
+
+const SHOUT_MAX_LENGTH: usize = 255;
+const WALL_POST_MAX_LENGTH: usize = 500;
+/// The number of members whose role is looked up per Open Cloud request by
+/// [Robolt::rank_many]
+const RANK_LOOKUP_BATCH_SIZE: usize = 50;
+/// The maximum number of users per batch join request action
+const JOIN_REQUESTS_BATCH_SIZE: usize = 100;
+
+impl<State> Robolt<State> {
+	pub async fn group(&self, group_id: u64) -> Result<Group, RoboltError> {
+		let group = self
+			.request(RobloxApi::Groups, format!("/v1/groups/{group_id}"))
+			.resource(ResourceKind::Group, group_id)
+			.send::<Group>()
+			.await?;
+
+		self.locked_groups
+			.cache
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.insert(group_id, group.is_locked.unwrap_or(false));
+
+		Ok(group)
+	}
+
+	/// Fetches the current shout of a group, `None` if it has none or it has
+	/// been cleared
+	pub async fn group_shout(&self, group_id: u64) -> Result<Option<GroupShout>, RoboltError> {
+		self.group(group_id)
+			.await
+			.map(|group| group.shout.filter(|shout| !shout.body.is_empty()))
+	}
+
+	/// Checks whether a group has been locked by moderation
+	pub async fn is_group_locked(&self, group_id: u64) -> Result<bool, RoboltError> {
+		self.group(group_id).await.map(|group| group.is_locked.unwrap_or(false))
+	}
+
+	/// Fails with [RoboltErrorKind::GroupLocked] if the group is locked and
+	/// [Robolt::guard_locked_groups] is enabled, as done by every group write
+	/// helper before sending its request
+	///
+	/// Failed fetches are not cached.
+	pub async fn ensure_group_unlocked(&self, group_id: u64) -> Result<(), RoboltError> {
+		if !self.locked_groups.guard {
+			return Ok(());
+		}
+
+		let cached = self
+			.locked_groups
+			.cache
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(&group_id)
+			.copied();
+		let locked = match cached {
+			Some(locked) => locked,
+			None => self.is_group_locked(group_id).await?,
+		};
+
+		match locked {
+			true => Err(RoboltError::group_locked(group_id)),
+			false => Ok(()),
+		}
+	}
+
+	/// Searches groups by name, with exact matches first
+	pub async fn search_groups(
+		&self,
+		keyword: &str,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<SearchResults<GroupSearchResult>, RoboltError> {
+		let mut path = format!(
+			"/v1/groups/search?keyword={}&prioritizeExactMatch=true&limit={}",
+			encode_query_value(keyword),
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Groups, path).send().await
+	}
+
+	pub async fn group_roles(&self, group_id: u64) -> Result<Vec<GroupRole>, RoboltError> {
+		self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/roles"))
+			.resource(ResourceKind::Group, group_id)
+			.send::<GroupRolesResponse>()
+			.await
+			.map(|res| res.roles)
+	}
+
+	/// Fetches the member count of every role in a group, keyed by role ID
+	pub async fn group_role_counts(&self, group_id: u64) -> Result<HashMap<u64, u64>, RoboltError> {
+		self.group_roles(group_id)
+			.await
+			.map(|roles| roles.into_iter().map(|role| (role.id, role.member_count)).collect())
+	}
+
+	/// Compares the current role member counts of a group against a
+	/// previously fetched [Robolt::group_role_counts] result
+	pub async fn group_roles_changed_since(
+		&self,
+		group_id: u64,
+		previous: &HashMap<u64, u64>,
+	) -> Result<Vec<RoleCountDelta>, RoboltError> {
+		let current = self.group_role_counts(group_id).await?;
+		Ok(RoleCountDelta::between(previous, &current))
+	}
+
+	pub async fn group_members(
+		&self,
+		group_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<GroupMember>, RoboltError> {
+		let mut path = format!("/v1/groups/{group_id}/users?limit={}&sortOrder=Asc", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Groups, path)
+			.resource(ResourceKind::Group, group_id)
+			.send()
+			.await
+	}
+
+	/// Fetches the members of a single role of a group
+	pub async fn role_members(
+		&self,
+		group_id: u64,
+		role_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<PartialUser>, RoboltError> {
+		let mut path = format!(
+			"/v1/groups/{group_id}/roles/{role_id}/users?limit={}&sortOrder=Asc",
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Groups, path)
+			.resource(ResourceKind::Group, group_id)
+			.send()
+			.await
+	}
+
+	/// Fetches the groups a user is a member of, along with their role in each
+	pub async fn user_groups(&self, user_id: u64) -> Result<Vec<UserGroup>, RoboltError> {
+		self.request(RobloxApi::Groups, format!("/v2/users/{user_id}/groups/roles"))
+			.send::<DataResponse<UserGroup>>()
+			.await
+			.map(|res| res.data)
+	}
+
+	/// Fetches groups with the v2 multi-get endpoint, which includes their
+	/// creation date but not their member count or shout
+	pub async fn groups_v2(&self, group_ids: &[u64]) -> Result<Vec<GroupV2>, RoboltError> {
+		let group_ids = group_ids.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+
+		self.request(RobloxApi::Groups, format!("/v2/groups?groupIds={group_ids}"))
+			.send::<DataResponse<GroupV2>>()
+			.await
+			.map(|res| res.data)
+	}
+
+	/// Fetches a group from both the v1 and v2 endpoints and merges the
+	/// results, see [GroupFull] for which fields come from which endpoint
+	///
+	/// If only one of the calls fails, the fields it provides are left empty
+	/// and [GroupFull::partial] records the failure. An error is only returned
+	/// if both calls fail.
+	pub async fn group_full(&self, group_id: u64) -> Result<GroupFull, RoboltError> {
+		let group_ids = [group_id];
+		let (v1, v2) = futures::join!(self.group(group_id), self.groups_v2(&group_ids));
+		let v2 = v2.and_then(|groups| {
+			groups
+				.into_iter()
+				.find(|group| group.id == group_id)
+				.ok_or_else(|| RoboltError::from(format!("Group {group_id} was not returned by the v2 endpoint")))
+		});
+
+		match (v1, v2) {
+			(Ok(v1), Ok(v2)) => Ok(GroupFull::merge(Some(v1), Some(v2), None)),
+			(Ok(v1), Err(error)) => Ok(GroupFull::merge(
+				Some(v1),
+				None,
+				Some(PartialGroup {
+					missing: GroupSource::V2,
+					error,
+				}),
+			)),
+			(Err(error), Ok(v2)) => Ok(GroupFull::merge(
+				None,
+				Some(v2),
+				Some(PartialGroup {
+					missing: GroupSource::V1,
+					error,
+				}),
+			)),
+			(Err(error), Err(_)) => Err(error),
+		}
+	}
+
+	/// Lists the posts on a group's wall, newest first
+	pub async fn wall_posts(
+		&self,
+		group_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<WallPost>, RoboltError> {
+		let mut path = format!("/v2/groups/{group_id}/wall/posts?limit={}&sortOrder=Desc", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Groups, path)
+			.resource(ResourceKind::Group, group_id)
+			.send()
+			.await
+	}
+
+	/// Lists the posts on a group's wall along with the current role of each
+	/// poster, requires an API key to be set with [Robolt::set_api_key]
+	///
+	/// The current roles of every poster on the page are resolved together,
+	/// with one Open Cloud membership lookup and one role list per page rather
+	/// than a request per post. Posters who have left the group have no
+	/// current role.
+	pub async fn wall_posts_enriched(
+		&self,
+		group_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<EnrichedWallPost>, RoboltError> {
+		let page = self.wall_posts(group_id, limit, cursor).await?;
+		let posters = page
+			.data
+			.iter()
+			.filter_map(|post| post.poster.as_ref().map(|poster| poster.user.id))
+			.collect::<BTreeSet<u64>>()
+			.into_iter()
+			.collect::<Vec<u64>>();
+
+		let mut role_ids = HashMap::new();
+		let mut page_token = None;
+
+		while !posters.is_empty() {
+			let memberships = self
+				.cloud_group_memberships(
+					group_id,
+					Some(MembershipFilter::users(&posters)),
+					100,
+					page_token.as_deref(),
+				)
+				.await?;
+
+			role_ids.extend(
+				memberships
+					.items
+					.into_iter()
+					.map(|membership| (membership.user_id, membership.role_id)),
+			);
+
+			page_token = match memberships.next_page_token {
+				Some(next) => Some(next),
+				None => break,
+			};
+		}
+
+		let roles = match role_ids.is_empty() {
+			true => HashMap::new(),
+			false => self
+				.group_roles(group_id)
+				.await?
+				.into_iter()
+				.map(|role| (role.id, role))
+				.collect::<HashMap<u64, GroupRole>>(),
+		};
+
+		Ok(Page {
+			previous_page_cursor: page.previous_page_cursor,
+			next_page_cursor: page.next_page_cursor,
+			data: page
+				.data
+				.into_iter()
+				.map(|post| {
+					let current_role = post
+						.poster
+						.as_ref()
+						.and_then(|poster| role_ids.get(&poster.user.id))
+						.and_then(|role_id| roles.get(role_id))
+						.cloned();
+
+					EnrichedWallPost {
+						post,
+						current_role,
+					}
+				})
+				.collect(),
+		})
+	}
+}
+
+impl Robolt<Authenticated> {
+	pub async fn set_group_shout(&self, group_id: u64, message: &str) -> Result<GroupShout, RoboltError> {
+		self.ensure_group_unlocked(group_id).await?;
+
+		self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/status"))
+			.method(Method::PATCH)
+			.invalidates(RobloxApi::Groups, format!("/v1/groups/{group_id}"))
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body(json!({ "message": message }))
+			.await
+	}
+
+	/// Changes the role of a member, `role_id` being the ID of the role rather
+	/// than its rank
+	pub async fn set_member_role(&self, group_id: u64, user_id: u64, role_id: u64) -> Result<(), RoboltError> {
+		self.ensure_group_unlocked(group_id).await?;
+
+		self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/users/{user_id}"))
+			.method(Method::PATCH)
+			.invalidates(RobloxApi::Groups, format!("/v2/users/{user_id}/groups/roles"))
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body::<_, EmptyResponse>(json!({ "roleId": role_id }))
+			.await?;
+
+		Ok(())
+	}
+
+	/// Removes a member from a group
+	pub async fn kick_member(&self, group_id: u64, user_id: u64) -> Result<(), RoboltError> {
+		self.ensure_group_unlocked(group_id).await?;
+
+		self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/users/{user_id}"))
+			.method(Method::DELETE)
+			.invalidates(RobloxApi::Groups, format!("/v2/users/{user_id}/groups/roles"))
+			.idempotency(Idempotency::Idempotent)
+			.send::<EmptyResponse>()
+			.await?;
+
+		Ok(())
+	}
+
+	/// Deletes a post from a group's wall
+	///
+	/// When a [ModerationLog](crate::moderation::ModerationLog) is set, the
+	/// post is first looked up on the wall so it can be recorded, see
+	/// [Robolt::delete_fetched_wall_post] to skip the lookup. The lookup fails
+	/// with [RoboltErrorKind::NotFound] if the post is not on the wall.
+	pub async fn delete_wall_post(&self, group_id: u64, post_id: u64) -> Result<(), RoboltError> {
+		if self.moderation_log.is_none() {
+			self.ensure_group_unlocked(group_id).await?;
+			return self.send_wall_post_deletion(group_id, post_id).await;
+		}
+
+		let post = self.find_wall_post(group_id, post_id).await?;
+		self.delete_fetched_wall_post(group_id, post).await
+	}
+
+	/// Deletes a post fetched with [Robolt::wall_posts], recording it to the
+	/// [ModerationLog](crate::moderation::ModerationLog) if one is set
+	///
+	/// Fails with [RoboltErrorKind::NotRecorded] if the post was deleted but
+	/// the outcome could not be recorded.
+	pub async fn delete_fetched_wall_post(&self, group_id: u64, post: WallPost) -> Result<(), RoboltError> {
+		self.ensure_group_unlocked(group_id).await?;
+
+		let Some(recorder) = self.moderation_recorder(group_id).await? else {
+			return self.send_wall_post_deletion(group_id, post.id).await;
+		};
+
+		recorder.record(
+			ModerationTarget::DeletedWallPost(post.clone()),
+			ModerationOutcome::Pending,
+		)?;
+
+		let res = self.send_wall_post_deletion(group_id, post.id).await;
+		let recorded = recorder.record(ModerationTarget::DeletedWallPost(post), outcome(&res));
+
+		recorded_result(res, recorded)
+	}
+
+	/// Deletes every post of a user from a group's wall
+	///
+	/// When a [ModerationLog](crate::moderation::ModerationLog) is set, every
+	/// page of the wall is fetched first and each post of the user is recorded
+	/// before they are deleted. Walls with more pages than
+	/// [Robolt::max_pages_per_crawl] are not deleted from, as posts past the
+	/// limit could not be recorded. Fails with [RoboltErrorKind::NotRecorded]
+	/// if the posts were deleted but the outcome could not be recorded.
+	pub async fn delete_wall_posts_by_user(&self, group_id: u64, user_id: u64) -> Result<(), RoboltError> {
+		self.ensure_group_unlocked(group_id).await?;
+
+		let Some(recorder) = self.moderation_recorder(group_id).await? else {
+			return self.send_user_wall_posts_deletion(group_id, user_id).await;
+		};
+
+		let posts = self
+			.group_wall(group_id)
+			.await?
+			.into_iter()
+			.filter(|post| post.poster.as_ref().is_some_and(|poster| poster.user.id == user_id))
+			.collect::<Vec<WallPost>>();
+
+		for post in posts.iter() {
+			recorder.record(
+				ModerationTarget::DeletedWallPost(post.clone()),
+				ModerationOutcome::Pending,
+			)?;
+		}
+
+		let res = self.send_user_wall_posts_deletion(group_id, user_id).await;
+		let recorded = posts
+			.into_iter()
+			.try_for_each(|post| recorder.record(ModerationTarget::DeletedWallPost(post), outcome(&res)));
+
+		recorded_result(res, recorded)
+	}
+
+	pub async fn post_to_group_wall(&self, group_id: u64, body: &str) -> Result<WallPost, RoboltError> {
+		self.ensure_group_unlocked(group_id).await?;
+
+		self.request(RobloxApi::Groups, format!("/v2/groups/{group_id}/wall/posts"))
+			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body(json!({ "body": body }))
+			.await
+	}
+
+	/// Posts an announcement to each of the channels set on `announcement`,
+	/// see [Announcement::send]
+	pub async fn announce(&self, group_id: u64, announcement: Announcement) -> AnnounceReport {
+		announcement
+			.send(|channel, text| async move {
+				match channel {
+					AnnounceChannel::Shout => self.set_group_shout(group_id, &text).await.map(|shout| shout.body),
+					AnnounceChannel::Wall => self.post_to_group_wall(group_id, &text).await.map(|post| post.body),
+				}
+			})
+			.await
+	}
+
+	pub async fn join_requests(
+		&self,
+		group_id: u64,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<JoinRequest>, RoboltError> {
+		let mut path = format!("/v1/groups/{group_id}/join-requests?limit={}", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Groups, path).send().await
+	}
+
+	/// Fetches the pending join request of a user, `None` if they have none
+	pub async fn join_request(&self, group_id: u64, user_id: u64) -> Result<Option<JoinRequest>, RoboltError> {
+		let res = self
+			.request(
+				RobloxApi::Groups,
+				format!("/v1/groups/{group_id}/join-requests/users/{user_id}"),
+			)
+			.resource(ResourceKind::Group, group_id)
+			.send::<Option<JoinRequest>>()
+			.await;
+
+		match res {
+			Err(err) if err.status() == Some(404) => Ok(None),
+			res => res,
+		}
+	}
+
+	/// Accepts the pending join request of a user
+	///
+	/// Like the other join request helpers, fails with
+	/// [RoboltErrorKind::PermissionDenied] if the authenticated user cannot
+	/// manage join requests of the group.
+	pub async fn accept_join_request(&self, group_id: u64, user_id: u64) -> Result<(), RoboltError> {
+		self.request(
+			RobloxApi::Groups,
+			format!("/v1/groups/{group_id}/join-requests/users/{user_id}"),
+		)
+		.method(Method::POST)
+		.idempotency(Idempotency::NonIdempotent)
+		.send::<EmptyResponse>()
+		.await?;
+
+		Ok(())
+	}
+
+	pub async fn decline_join_request(&self, group_id: u64, user_id: u64) -> Result<(), RoboltError> {
+		self.request(
+			RobloxApi::Groups,
+			format!("/v1/groups/{group_id}/join-requests/users/{user_id}"),
+		)
+		.method(Method::DELETE)
+		.idempotency(Idempotency::Idempotent)
+		.send::<EmptyResponse>()
+		.await?;
+
+		Ok(())
+	}
+
+	/// Accepts the pending join requests of the given users
+	///
+	/// Users are sent in batches of 100, so any number of users can be passed.
+	/// Batches sent before a failing one stay accepted.
+	pub async fn accept_join_requests(&self, group_id: u64, user_ids: Vec<u64>) -> Result<(), RoboltError> {
+		for chunk in user_ids.chunks(JOIN_REQUESTS_BATCH_SIZE) {
+			let body = json!({ "UserIds": chunk });
+
+			self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/join-requests"))
+				.method(Method::POST)
+				.idempotency(Idempotency::NonIdempotent)
+				.send_body::<_, EmptyResponse>(body)
+				.await?;
+		}
+
+		Ok(())
+	}
+
+	/// Declines the pending join requests of the given users, in batches of
+	/// 100 like [Robolt::accept_join_requests]
+	///
+	/// Declining a request twice has no further effect, so batches are
+	/// retried after transient server errors like
+	/// [Robolt::decline_join_request].
+	pub async fn decline_join_requests(&self, group_id: u64, user_ids: Vec<u64>) -> Result<(), RoboltError> {
+		for chunk in user_ids.chunks(JOIN_REQUESTS_BATCH_SIZE) {
+			let body = json!({ "UserIds": chunk });
+
+			self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/join-requests"))
+				.method(Method::DELETE)
+				.idempotency(Idempotency::Idempotent)
+				.send_body::<_, EmptyResponse>(body)
+				.await?;
+		}
+
+		Ok(())
+	}
+
+	/// Fetches a page of a group's audit log, newest entries first
+	///
+	/// ### Arguments
+	/// * `action_type` - Only return entries of this type, e.g. `ChangeRank`
+	pub async fn group_audit_log(
+		&self,
+		group_id: u64,
+		action_type: Option<&str>,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<AuditLogEntry>, RoboltError> {
+		let mut path = format!("/v1/groups/{group_id}/audit-log?limit={}&sortOrder=Desc", limit as u8);
+
+		if let Some(action_type) = action_type {
+			path.push_str(&format!("&actionType={action_type}"));
+		}
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Groups, path).send().await
+	}
+
+	/// Reconstructs the shout history of a group from the `PostStatus`
+	/// entries of its audit log, newest shouts first
+	///
+	/// Stops once `max_entries` shouts have been found, or after
+	/// [Robolt::max_pages_per_crawl] pages, see [ShoutHistory::truncated].
+	/// Shouts older than the audit log's retention period cannot be recovered,
+	/// see [ShoutHistory::truncated_by_retention].
+	pub async fn shout_history(&self, group_id: u64, max_entries: usize) -> Result<ShoutHistory, RoboltError> {
+		let mut pages = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+			self.group_audit_log(group_id, Some("PostStatus"), Limit::Max, cursor.as_deref())
+				.await
+		})
+		.max_pages(self.max_pages_per_crawl);
+		let mut records = Vec::new();
+
+		while records.len() < max_entries {
+			match pages.next_page().await {
+				Some(page) => records.extend(page?.iter().filter_map(AuditLogEntry::shout)),
+				None => break,
+			}
+		}
+
+		let reason = pages.exhaustion_reason();
+		let truncated_by_retention = reason == Some(ExhaustionReason::Completed) && records.len() <= max_entries;
+		let truncated = reason == Some(ExhaustionReason::CrawlTruncated) && records.len() < max_entries;
+		records.truncate(max_entries);
+
+		Ok(ShoutHistory {
+			records,
+			truncated_by_retention,
+			truncated,
+		})
+	}
+
+	/// Builds a [ComplianceReport] of a group by crawling every member and
+	/// pending join request, and up to [ComplianceOptions::audit_log_pages]
+	/// pages of rank changes from the audit log
+	///
+	/// Members and join requests are crawled up to
+	/// [Robolt::max_pages_per_crawl] pages, see [ComplianceReport::truncated].
+	pub async fn compliance_report(
+		&self,
+		group_id: u64,
+		options: ComplianceOptions,
+	) -> Result<ComplianceReport, RoboltError> {
+		let members = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+			self.group_members(group_id, Limit::Max, cursor.as_deref()).await
+		})
+		.max_pages(self.max_pages_per_crawl)
+		.collect_all()
+		.await?;
+
+		let rank_changes = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+			self.group_audit_log(group_id, Some("ChangeRank"), Limit::Max, cursor.as_deref())
+				.await
+		})
+		.max_pages(options.audit_log_pages.min(self.max_pages_per_crawl))
+		.collect_all()
+		.await?;
+
+		let join_requests = match options.include_join_requests {
+			true => {
+				PageIterator::new(CursorPaging::new(), move |cursor| async move {
+					self.join_requests(group_id, Limit::Max, cursor.as_deref()).await
+				})
+				.max_pages(self.max_pages_per_crawl)
+				.collect_all()
+				.await?
+			},
+			false => Collected::default(),
+		};
+
+		let mut report =
+			ComplianceReport::from_sources(group_id, members.items, &rank_changes.items, join_requests.items);
+		report.truncated = members.truncated || join_requests.truncated;
+
+		Ok(report)
+	}
+
+	/// Changes the role of many members, see [Robolt::resume_rank_many]
+	pub async fn rank_many(
+		&self,
+		group_id: u64,
+		changes: Vec<(u64, RoleTarget)>,
+		options: &RankOptions,
+	) -> RankReport {
+		let progress = RankProgress::new(group_id, changes);
+		self.resume_rank_many(progress, options).await
+	}
+
+	/// Sends the role changes left in `progress` one at a time, at most one
+	/// every [RankOptions::interval]
+	///
+	/// If an API key is set, the current roles of the members are looked up
+	/// through Open Cloud first, and members already at their target role are
+	/// skipped without sending anything. Rate limited changes are sent again
+	/// after the `Retry-After` delay (or [RankOptions::rate_limit_pause]), and
+	/// the run stops once [RankOptions::max_rate_limit_pauses] pauses in a row
+	/// have not helped, or at the first permission error. Either way,
+	/// [RankReport::progress] holds the changes that were not sent and can be
+	/// saved and passed back in to resume the run.
+	///
+	/// In a [dry run](crate::dry_run), every change is planned without waiting
+	/// in between and reported as succeeded, with the report marked as a dry
+	/// run.
+	pub async fn resume_rank_many(&self, progress: RankProgress, options: &RankOptions) -> RankReport {
+		let group_id = progress.group_id;
+		let mut remaining = VecDeque::from(progress.remaining);
+		let mut report = RankReport::default();
+
+		let roles = match self.group_roles(group_id).await {
+			Ok(roles) => roles,
+			Err(err) => return report.stopped(group_id, remaining, err),
+		};
+
+		let current_roles = match self.api_key.is_some() {
+			true => match self.current_role_ids(group_id, &remaining).await {
+				Ok(current_roles) => current_roles,
+				Err(err) => return report.stopped(group_id, remaining, err),
+			},
+			false => HashMap::new(),
+		};
+
+		// Whether a change was sent since the last wait
+		let mut wait_interval = false;
+		let mut pauses = 0;
+
+		while let Some(change) = remaining.front() {
+			let user_id = change.user_id;
+			let Some(role) = change.role.find(&roles) else {
+				let err = RoboltError::from(format!("Group {group_id} has no role {}", change.role));
+				report.report.failed.push((user_id, err));
+				remaining.pop_front();
+				continue;
+			};
+
+			if current_roles.get(&user_id) == Some(&role.id) {
+				report.report.skipped.push(user_id);
+				remaining.pop_front();
+				continue;
+			}
+
+			if wait_interval {
+				self.sleeper.sleep(options.interval).await;
+			}
+
+			wait_interval = true;
+
+			match self.set_member_role(group_id, user_id, role.id).await {
+				Ok(()) => {
+					pauses = 0;
+					report.report.succeeded.push(user_id);
+				},
+				Err(err) if err.kind() == &RoboltErrorKind::DryRunSkipped => {
+					wait_interval = false;
+					report.report.dry_run = true;
+					report.report.succeeded.push(user_id);
+				},
+				Err(err) if err.kind() == &RoboltErrorKind::RateLimited => {
+					report.rate_limited += 1;
+
+					if pauses == options.max_rate_limit_pauses {
+						return report.stopped(group_id, remaining, err);
+					}
+
+					pauses += 1;
+					wait_interval = false;
+					self.sleeper
+						.sleep(err.retry_after().unwrap_or(options.rate_limit_pause))
+						.await;
+					continue;
+				},
+				Err(err) => {
+					if matches!(
+						err.kind(),
+						RoboltErrorKind::PermissionDenied | RoboltErrorKind::GroupLocked
+					) {
+						return report.stopped(group_id, remaining, err);
+					}
+
+					report.report.failed.push((user_id, err));
+				},
+			}
+
+			remaining.pop_front();
+		}
+
+		report.progress = RankProgress::new(group_id, Vec::new());
+		report
+	}
+
+	/// Looks up the current role of each member in `changes` through Open
+	/// Cloud, members missing from the result not being in the group
+	async fn current_role_ids(
+		&self,
+		group_id: u64,
+		changes: &VecDeque<PendingRank>,
+	) -> Result<HashMap<u64, u64>, RoboltError> {
+		let user_ids = changes
+			.iter()
+			.map(|change| change.user_id)
+			.collect::<BTreeSet<u64>>()
+			.into_iter()
+			.collect::<Vec<u64>>();
+		let mut role_ids = HashMap::new();
+
+		for user_ids in user_ids.chunks(RANK_LOOKUP_BATCH_SIZE) {
+			let mut page_token = None;
+
+			loop {
+				let memberships = self
+					.cloud_group_memberships(
+						group_id,
+						Some(MembershipFilter::users(user_ids)),
+						100,
+						page_token.as_deref(),
+					)
+					.await?;
+
+				role_ids.extend(
+					memberships
+						.items
+						.into_iter()
+						.map(|membership| (membership.user_id, membership.role_id)),
+				);
+
+				page_token = match memberships.next_page_token {
+					Some(next) => Some(next),
+					None => break,
+				};
+			}
+		}
+
+		Ok(role_ids)
+	}
+
+	/// Pages through a group's pending join requests, starting at `cursor`,
+	/// and accepts or declines each one based on `decision`
+	///
+	/// Accepts and declines are sent through the batch endpoints in groups of
+	/// up to `batch_size`. The sweep stops at the first permission error, in
+	/// which case [SweepReport::cursor] can be passed back in to resume once
+	/// the issue has been resolved.
+	///
+	/// In a [dry run](crate::dry_run), the requests that would have been
+	/// accepted or declined are counted as such, with the report marked as a
+	/// dry run.
+	pub async fn sweep_join_requests<F>(
+		&self,
+		group_id: u64,
+		cursor: Option<&str>,
+		batch_size: usize,
+		decision: F,
+	) -> SweepReport
+	where
+		F: Fn(&JoinRequest) -> Decision,
+	{
+		let batch_size = batch_size.max(1);
+		let mut report = SweepReport {
+			cursor: cursor.map(String::from),
+			..Default::default()
+		};
+
+		loop {
+			let page = match self.join_requests(group_id, Limit::Max, report.cursor.as_deref()).await {
+				Ok(page) => page,
+				Err(err) => {
+					report.error = Some(err);
+					return report;
+				},
+			};
+
+			let mut accepts = Vec::new();
+			let mut declines = Vec::new();
+
+			for request in page.data.iter() {
+				match decision(request) {
+					Decision::Accept => accepts.push(request.requester.id),
+					Decision::Decline => declines.push(request.requester.id),
+					Decision::Skip => report.skipped += 1,
+				}
+			}
+
+			for (user_ids, accept) in [(accepts, true), (declines, false)] {
+				for batch in user_ids.chunks(batch_size) {
+					let res = match accept {
+						true => self.accept_join_requests(group_id, batch.to_vec()).await,
+						false => self.decline_join_requests(group_id, batch.to_vec()).await,
+					};
+
+					let res = match res {
+						Err(err) if err.kind() == &RoboltErrorKind::DryRunSkipped => {
+							report.dry_run = true;
+							Ok(())
+						},
+						res => res,
+					};
+
+					match res {
+						Ok(()) if accept => report.accepted += batch.len() as u64,
+						Ok(()) => report.declined += batch.len() as u64,
+						Err(err) if err.kind() == &RoboltErrorKind::PermissionDenied => {
+							report.error = Some(err);
+							return report;
+						},
+						Err(err) => report
+							.failed
+							.extend(batch.iter().map(|user_id| (*user_id, err.message.clone()))),
+					}
+				}
+			}
+
+			report.cursor = page.next_page_cursor;
+
+			if report.cursor.is_none() {
+				return report;
+			}
+		}
+	}
+}
+
+impl Robolt<Authenticated> {
+	/// Every post on a group's wall, failing if the wall has more pages than
+	/// [Robolt::max_pages_per_crawl]
+	async fn group_wall(&self, group_id: u64) -> Result<Vec<WallPost>, RoboltError> {
+		let collected = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+			self.wall_posts(group_id, Limit::Max, cursor.as_deref()).await
+		})
+		.max_pages(self.max_pages_per_crawl)
+		.collect_all()
+		.await?;
+
+		match collected.truncated {
+			true => Err(format!(
+				"The wall of group {group_id} has more than {} pages",
+				self.max_pages_per_crawl
+			)
+			.into()),
+			false => Ok(collected.items),
+		}
+	}
+
+	/// Looks up a post on a group's wall, paging from the newest post and
+	/// stopping as soon as it is found, or once the posts are older than it
+	async fn find_wall_post(&self, group_id: u64, post_id: u64) -> Result<WallPost, RoboltError> {
+		let mut pages = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+			self.wall_posts(group_id, Limit::Max, cursor.as_deref()).await
+		})
+		.max_pages(self.max_pages_per_crawl);
+
+		while let Some(page) = pages.next_page().await {
+			let page = page?;
+
+			if let Some(post) = page.iter().find(|post| post.id == post_id) {
+				return Ok(post.clone());
+			}
+
+			// Post IDs increase over time, so older pages cannot hold the post
+			if page.last().is_some_and(|post| post.id < post_id) {
+				break;
+			}
+		}
+
+		Err(
+			RoboltError::from(format!("Wall post {post_id} was not found in group {group_id}")).with_kind(
+				RoboltErrorKind::NotFound {
+					resource: ResourceKind::WallPost,
+					id: post_id,
+					reason: NotFoundReason::Unknown,
+				},
+			),
+		)
+	}
+
+	/// A recorder for the [ModerationLog](crate::moderation::ModerationLog),
+	/// acting as the authenticated user, `None` if no log is set
+	async fn moderation_recorder(&self, group_id: u64) -> Result<Option<Recorder<'_>>, RoboltError> {
+		let Some(log) = self.moderation_log.as_deref() else {
+			return Ok(None);
+		};
+
+		Ok(Some(Recorder {
+			log,
+			clock: self.clock.as_ref(),
+			group_id,
+			actor: self.me().await?,
+		}))
+	}
+
+	async fn send_wall_post_deletion(&self, group_id: u64, post_id: u64) -> Result<(), RoboltError> {
+		self.request(RobloxApi::Groups, format!("/v1/groups/{group_id}/wall/posts/{post_id}"))
+			.method(Method::DELETE)
+			.idempotency(Idempotency::Idempotent)
+			.send::<EmptyResponse>()
+			.await?;
+
+		Ok(())
+	}
+
+	async fn send_user_wall_posts_deletion(&self, group_id: u64, user_id: u64) -> Result<(), RoboltError> {
+		self.request(
+			RobloxApi::Groups,
+			format!("/v1/groups/{group_id}/wall/users/{user_id}/posts"),
+		)
+		.method(Method::DELETE)
+		.idempotency(Idempotency::Idempotent)
+		.send::<EmptyResponse>()
+		.await?;
+
+		Ok(())
+	}
+}
+
+fn outcome(res: &Result<(), RoboltError>) -> ModerationOutcome {
+	match res {
+		Ok(()) => ModerationOutcome::Succeeded,
+		Err(err) if err.kind() == &RoboltErrorKind::DryRunSkipped => ModerationOutcome::DryRunSkipped,
+		Err(err) => ModerationOutcome::Failed(err.message.clone()),
+	}
+}
+
+/// The result of a moderation action whose outcome was `recorded`, a failure
+/// to record a successful action being reported as
+/// [RoboltErrorKind::NotRecorded]
+fn recorded_result(res: Result<(), RoboltError>, recorded: Result<(), RoboltError>) -> Result<(), RoboltError> {
+	match (res, recorded) {
+		(Ok(()), Err(err)) => Err(err.with_kind(RoboltErrorKind::NotRecorded)),
+		(res, _) => res,
+	}
+}
+
+impl RoleCountDelta {
+	/// Computes the per-role changes between two role count snapshots, sorted
+	/// by role ID
+	///
+	/// Roles are matched by ID so renames are not reported. A role missing from
+	/// `previous` was added and a role missing from `current` was deleted.
+	pub fn between(previous: &HashMap<u64, u64>, current: &HashMap<u64, u64>) -> Vec<RoleCountDelta> {
+		previous
+			.keys()
+			.chain(current.keys())
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.map(|role_id| RoleCountDelta {
+				role_id: *role_id,
+				previous: previous.get(role_id).copied(),
+				current: current.get(role_id).copied(),
+			})
+			.filter(|delta| delta.previous != delta.current)
+			.collect()
+	}
+
+	/// The change in member count, treating added and deleted roles as having
+	/// had (or having) no members
+	pub fn delta(&self) -> i64 {
+		self.current.unwrap_or(0) as i64 - self.previous.unwrap_or(0) as i64
+	}
+
+	pub fn is_added(&self) -> bool {
+		self.previous.is_none()
+	}
+
+	pub fn is_deleted(&self) -> bool {
+		self.current.is_none()
+	}
+}
+
+impl Announcement {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the group shout, truncated to 255 characters
+	pub fn shout(mut self, text: &str) -> Self {
+		self.shout = Some(text.to_string());
+		self
+	}
+
+	/// Posts to the group wall, truncated to 500 characters
+	pub fn wall_post(mut self, text: &str) -> Self {
+		self.wall_post = Some(text.to_string());
+		self
+	}
+
+	/// Skips the remaining channels once one fails
+	pub fn abort_on_failure(mut self, abort_on_failure: bool) -> Self {
+		self.abort_on_failure = abort_on_failure;
+		self
+	}
+
+	/// Posts the announcement to each channel through `post`, which returns
+	/// the text as posted by Roblox
+	///
+	/// Channels are posted to in order (shout, then wall) with the text fitted
+	/// to each channel's length limit. A failed channel does not stop the
+	/// following ones unless [Announcement::abort_on_failure] is set.
+	pub async fn send<F, Fut>(self, post: F) -> AnnounceReport
+	where
+		F: Fn(AnnounceChannel, String) -> Fut,
+		Fut: Future<Output = Result<String, RoboltError>>,
+	{
+		let mut report = AnnounceReport::default();
+		let mut aborted = false;
+
+		let channels = [
+			(AnnounceChannel::Shout, self.shout, SHOUT_MAX_LENGTH),
+			(AnnounceChannel::Wall, self.wall_post, WALL_POST_MAX_LENGTH),
+		];
+
+		for (channel, text, max_length) in channels {
+			let Some(text) = text else {
+				continue;
+			};
+
+			let outcome = if aborted {
+				ChannelOutcome::Skipped
+			} else {
+				let (text, truncated) = fit_text(&text, max_length);
+
+				match post(channel.clone(), text.clone()).await {
+					Ok(posted) => ChannelOutcome::Sent {
+						filtered: posted != text,
+						truncated,
+						text: posted,
+					},
+					Err(err) if err.kind() == &RoboltErrorKind::DryRunSkipped => ChannelOutcome::Planned {
+						text,
+						truncated,
+					},
+					Err(err) => {
+						aborted = self.abort_on_failure;
+						ChannelOutcome::Failed(err)
+					},
+				}
+			};
+
+			match channel {
+				AnnounceChannel::Shout => report.shout = Some(outcome),
+				AnnounceChannel::Wall => report.wall = Some(outcome),
+			}
+		}
+
+		report
+	}
+}
+
+impl AnnounceReport {
+	pub fn is_success(&self) -> bool {
+		[&self.shout, &self.wall]
+			.into_iter()
+			.flatten()
+			.all(|outcome| matches!(outcome, ChannelOutcome::Sent { .. } | ChannelOutcome::Planned { .. }))
+	}
+}
+
+/// Truncates `text` to at most `max_length` characters, ending it with an
+/// ellipsis if anything was cut off
+fn fit_text(text: &str, max_length: usize) -> (String, bool) {
+	let text = text.trim();
+
+	if text.chars().count() <= max_length {
+		return (text.to_string(), false);
+	}
+
+	let mut fitted = text.chars().take(max_length.saturating_sub(1)).collect::<String>();
+	fitted.truncate(fitted.trim_end().len());
+	fitted.push('…');
+
+	(fitted, true)
+}
+
+impl AuditLogEntry {
+	/// The rank change described by this entry, if it is a `ChangeRank` entry
+	pub fn rank_change(&self) -> Option<RankChange> {
+		serde_json::from_value(self.description.clone()).ok()
+	}
+
+	/// The shout posted in this entry, if it is a `PostStatus` entry
+	pub fn shout(&self) -> Option<ShoutRecord> {
+		// The action type is spelled with spaces in entries, e.g. `Post Status`
+		if self.action_type.replace(' ', "") != "PostStatus" {
+			return None;
+		}
+
+		let description = serde_json::from_value::<ShoutDescription>(self.description.clone()).ok()?;
+
+		Some(ShoutRecord {
+			body: description.text,
+			poster: self.actor.user.clone(),
+			created: self.created.clone(),
+		})
+	}
+}
+
+impl ComplianceReport {
+	/// Joins the members of a group with the rank changes from its audit log
+	///
+	/// `rank_changes` must be sorted newest first, as returned by
+	/// [Robolt::group_audit_log]. A member is only attributed if their most
+	/// recent rank change put them in the role they currently hold, as the
+	/// audit log does not cover rank changes older than its retention period.
+	pub fn from_sources(
+		group_id: u64,
+		members: Vec<GroupMember>,
+		rank_changes: &[AuditLogEntry],
+		join_requests: Vec<JoinRequest>,
+	) -> Self {
+		let mut latest_changes = HashMap::new();
+
+		for entry in rank_changes {
+			if let Some(change) = entry.rank_change() {
+				latest_changes.entry(change.target_id).or_insert((entry, change));
+			}
+		}
+
+		let members = members
+			.into_iter()
+			.map(|member| {
+				let ranked_by = latest_changes
+					.get(&member.user.id)
+					.filter(|(_, change)| change.new_role_set_id == member.role.id)
+					.map(|(entry, _)| RankAttribution {
+						actor_id: entry.actor.user.id,
+						actor_name: entry.actor.user.username.clone(),
+						changed_at: entry.created.clone(),
+					});
+
+				ComplianceMember {
+					user_id: member.user.id,
+					username: member.user.username,
+					role_id: member.role.id,
+					role_name: member.role.name,
+					rank: member.role.rank,
+					ranked_by,
+				}
+			})
+			.collect();
+
+		let pending_requests = join_requests
+			.into_iter()
+			.map(|request| PendingJoinRequest {
+				user_id: request.requester.id,
+				username: request.requester.username,
+				requested_at: request.created,
+			})
+			.collect();
+
+		Self {
+			group_id,
+			members,
+			pending_requests,
+			truncated: false,
+		}
+	}
+
+	pub fn unattributed(&self) -> impl Iterator<Item = &ComplianceMember> {
+		self.members.iter().filter(|member| member.ranked_by.is_none())
+	}
+
+	/// Writes the report as JSON, or as CSV with one row per member followed by
+	/// one row per pending join request
+	pub fn write_to<W: Write>(&self, mut writer: W, format: ReportFormat) -> io::Result<()> {
+		match format {
+			ReportFormat::Json => serde_json::to_writer_pretty(writer, self).map_err(io::Error::from),
+			ReportFormat::Csv => {
+				writeln!(
+					writer,
+					"type,user_id,username,role_id,role_name,rank,attributed,ranked_by_id,ranked_by_name,changed_at"
+				)?;
+
+				for member in self.members.iter() {
+					let (attributed, actor_id, actor_name, changed_at) = match &member.ranked_by {
+						Some(ranked_by) => (
+							true,
+							ranked_by.actor_id.to_string(),
+							escape_field(&ranked_by.actor_name),
+							escape_field(&ranked_by.changed_at),
+						),
+						None => (false, String::new(), String::new(), String::new()),
+					};
+
+					writeln!(
+						writer,
+						"member,{},{},{},{},{},{attributed},{actor_id},{actor_name},{changed_at}",
+						member.user_id,
+						escape_field(&member.username),
+						member.role_id,
+						escape_field(&member.role_name),
+						member.rank,
+					)?;
+				}
+
+				for request in self.pending_requests.iter() {
+					writeln!(
+						writer,
+						"join_request,{},{},,,,false,,,{}",
+						request.user_id,
+						escape_field(&request.username),
+						escape_field(&request.requested_at),
+					)?;
+				}
+
+				Ok(())
+			},
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Group {
+	pub id: u64,
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	/// `None` if the group has been abandoned by its owner
+	pub owner: Option<PartialUser>,
+	pub shout: Option<GroupShout>,
+	#[serde(default)]
+	pub member_count: u64,
+	#[serde(default)]
+	pub is_builders_club_only: bool,
+	#[serde(default)]
+	pub public_entry_allowed: bool,
+	/// Only present when the group has been locked by moderation
+	pub is_locked: Option<bool>,
+	#[serde(default)]
+	pub has_verified_badge: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A group as returned by [Robolt::groups_v2]
+pub struct GroupV2 {
+	pub id: u64,
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	pub owner: Option<GroupOwnerV2>,
+	pub created: String,
+	#[serde(default)]
+	pub has_verified_badge: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupOwnerV2 {
+	pub id: u64,
+	#[serde(rename = "type")]
+	pub owner_type: String,
+}
+
+#[derive(Default, Debug)]
+/// A group merged from the v1 and v2 endpoints by [Robolt::group_full]
+///
+/// The name, description, owner ID and verified badge are provided by both
+/// endpoints, the v1 values being preferred. `created` only comes from v2,
+/// while the owner's username, member count, shout and locked state only come
+/// from v1.
+pub struct GroupFull {
+	pub id: u64,
+	pub name: String,
+	pub description: String,
+	/// From v2
+	pub created: Option<String>,
+	pub has_verified_badge: bool,
+	pub owner_id: Option<u64>,
+	/// From v1
+	pub owner: Option<PartialUser>,
+	/// From v1
+	pub member_count: Option<u64>,
+	/// From v1
+	pub shout: Option<GroupShout>,
+	/// From v1
+	pub is_locked: Option<bool>,
+	/// Set if one of the two calls failed
+	pub partial: Option<PartialGroup>,
+}
+
+#[derive(Debug)]
+/// The call that failed when fetching a [GroupFull], and why
+pub struct PartialGroup {
+	pub missing: GroupSource,
+	pub error: RoboltError,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupSource {
+	/// `GET /v1/groups/{groupId}`
+	V1,
+	/// `GET /v2/groups?groupIds={groupId}`
+	V2,
+}
+
+impl GroupFull {
+	fn merge(v1: Option<Group>, v2: Option<GroupV2>, partial: Option<PartialGroup>) -> Self {
+		let mut group = Self {
+			partial,
+			..Default::default()
+		};
+
+		if let Some(v2) = v2 {
+			group.id = v2.id;
+			group.name = v2.name;
+			group.description = v2.description;
+			group.created = Some(v2.created);
+			group.has_verified_badge = v2.has_verified_badge;
+			group.owner_id = v2.owner.map(|owner| owner.id);
+		}
+
+		if let Some(v1) = v1 {
+			group.id = v1.id;
+			group.name = v1.name;
+			group.description = v1.description;
+			group.has_verified_badge = v1.has_verified_badge;
+			group.owner_id = v1.owner.as_ref().map(|owner| owner.id);
+			group.owner = v1.owner;
+			group.member_count = Some(v1.member_count);
+			group.shout = v1.shout;
+			group.is_locked = Some(v1.is_locked.unwrap_or(false));
+		}
+
+		group
+	}
+
+	pub fn is_partial(&self) -> bool {
+		self.partial.is_some()
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// The channels to post an announcement to with [Robolt::announce]
+pub struct Announcement {
+	shout: Option<String>,
+	wall_post: Option<String>,
+	abort_on_failure: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnounceChannel {
+	Shout,
+	Wall,
+}
+
+#[derive(Default, Debug)]
+/// The outcome of each channel of an [Announcement], `None` for channels
+/// that were not part of the announcement
+pub struct AnnounceReport {
+	pub shout: Option<ChannelOutcome>,
+	pub wall: Option<ChannelOutcome>,
+}
+
+#[derive(Debug)]
+pub enum ChannelOutcome {
+	Sent {
+		/// The text as posted by Roblox
+		text: String,
+		/// Whether the text had to be shortened to fit the channel
+		truncated: bool,
+		/// Whether Roblox filtered part of the text
+		filtered: bool,
+	},
+	/// Not sent because of a [dry run](crate::dry_run), `text` being the
+	/// text that would have been posted
+	Planned {
+		text: String,
+		truncated: bool,
+	},
+	Failed(RoboltError),
+	/// Not attempted because an earlier channel failed
+	Skipped,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupSearchResult {
+	pub id: u64,
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	#[serde(default)]
+	pub member_count: u64,
+	pub previous_name: Option<String>,
+	#[serde(default)]
+	pub public_entry_allowed: bool,
+	pub created: String,
+	pub updated: String,
+	#[serde(default)]
+	pub has_verified_badge: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupShout {
+	/// Empty if the shout has been cleared
+	#[serde(default, deserialize_with = "null_as_empty")]
+	pub body: String,
+	pub poster: Option<PartialUser>,
+	pub created: String,
+	pub updated: String,
+}
+
+/// Reads a string that may be `null` as an empty string
+fn null_as_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+	Option::<String>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WallPost {
+	pub id: u64,
+	pub poster: Option<GroupMember>,
+	pub body: String,
+	pub created: String,
+	pub updated: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// A [WallPost] along with the current role of its poster, see
+/// [Robolt::wall_posts_enriched]
+pub struct EnrichedWallPost {
+	/// The post, with the role of the poster at the time of posting
+	pub post: WallPost,
+	/// `None` if the poster has left the group or the post has no poster
+	pub current_role: Option<GroupRole>,
+}
+
+impl GroupShout {
+	/// When the shout was first posted
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn created_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.created)
+	}
+
+	/// When the shout was last changed
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn updated_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.updated)
+	}
+}
+
+impl WallPost {
+	/// When the post was posted
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn created_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.created)
+	}
+
+	/// When the post was last edited
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn updated_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.updated)
+	}
+}
+
+impl EnrichedWallPost {
+	/// Whether the poster's role has changed since they posted, `true` for
+	/// posters who have left the group
+	pub fn role_changed(&self) -> bool {
+		match (&self.post.poster, &self.current_role) {
+			(Some(poster), Some(current_role)) => poster.role.id != current_role.id,
+			(Some(_), None) => true,
+			(None, _) => false,
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+/// A group a user is a member of, see [Robolt::user_groups]
+pub struct UserGroup {
+	pub group: Group,
+	/// The role of the user in the group
+	pub role: GroupRole,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRole {
+	pub id: u64,
+	pub name: String,
+	pub description: Option<String>,
+	pub rank: u8,
+	#[serde(default)]
+	pub member_count: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RoleCountDelta {
+	pub role_id: u64,
+	/// The member count in the previous snapshot, or `None` if the role was
+	/// added
+	pub previous: Option<u64>,
+	/// The current member count, or `None` if the role was deleted
+	pub current: Option<u64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinRequest {
+	pub requester: PartialUser,
+	pub created: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decision {
+	Accept,
+	Decline,
+	/// Leave the join request pending
+	Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// The role a member is moved to by [Robolt::rank_many]
+pub enum RoleTarget {
+	/// A role ID
+	Id(u64),
+	/// A rank between 0 and 255
+	Rank(u8),
+	/// A role name, compared case-insensitively
+	Name(String),
+}
+
+impl RoleTarget {
+	fn find<'a>(&self, roles: &'a [GroupRole]) -> Option<&'a GroupRole> {
+		roles.iter().find(|role| match self {
+			RoleTarget::Id(id) => role.id == *id,
+			RoleTarget::Rank(rank) => role.rank == *rank,
+			RoleTarget::Name(name) => role.name.eq_ignore_ascii_case(name),
+		})
+	}
+}
+
+impl fmt::Display for RoleTarget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			RoleTarget::Id(id) => write!(f, "with ID {id}"),
+			RoleTarget::Rank(rank) => write!(f, "with rank {rank}"),
+			RoleTarget::Name(name) => write!(f, "named {name}"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankOptions {
+	/// The minimum delay between two role changes, 4 seconds (15 per minute)
+	/// by default, well under the throttle of the groups API
+	pub interval: Duration,
+	/// How long to wait after a rate limited change when Roblox does not say
+	pub rate_limit_pause: Duration,
+	/// The number of pauses in a row after which the run stops
+	pub max_rate_limit_pauses: u32,
+}
+
+impl Default for RankOptions {
+	fn default() -> Self {
+		Self {
+			interval: Duration::from_secs(4),
+			rate_limit_pause: Duration::from_secs(60),
+			max_rate_limit_pauses: 5,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The role changes a [Robolt::rank_many] run has yet to send, which can be
+/// saved to resume the run later with [Robolt::resume_rank_many]
+pub struct RankProgress {
+	pub group_id: u64,
+	/// In the order they are sent
+	pub remaining: Vec<PendingRank>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRank {
+	pub user_id: u64,
+	pub role: RoleTarget,
+}
+
+impl RankProgress {
+	pub fn new(group_id: u64, changes: Vec<(u64, RoleTarget)>) -> Self {
+		Self {
+			group_id,
+			remaining: changes
+				.into_iter()
+				.map(|(user_id, role)| PendingRank {
+					user_id,
+					role,
+				})
+				.collect(),
+		}
+	}
+
+	pub fn is_done(&self) -> bool {
+		self.remaining.is_empty()
+	}
+}
+
+#[derive(Debug)]
+/// The outcome of a [Robolt::rank_many] run, per member
+pub struct RankReport {
+	/// Members whose role was changed, already at their target role, or whose
+	/// change failed
+	pub report: BatchReport<u64>,
+	/// The changes that were not sent, empty once the run has completed
+	pub progress: RankProgress,
+	/// The number of changes that were rate limited, including those sent
+	/// again successfully
+	pub rate_limited: usize,
+	/// The error that stopped the run early, if any
+	pub error: Option<RoboltError>,
+}
+
+impl Default for RankReport {
+	fn default() -> Self {
+		Self {
+			report: BatchReport::default(),
+			progress: RankProgress::new(0, Vec::new()),
+			rate_limited: 0,
+			error: None,
+		}
+	}
+}
+
+impl RankReport {
+	fn stopped(mut self, group_id: u64, remaining: VecDeque<PendingRank>, err: RoboltError) -> Self {
+		self.progress = RankProgress {
+			group_id,
+			remaining: remaining.into(),
+		};
+		self.error = Some(err);
+		self
+	}
+}
+
+#[derive(Default, Debug)]
+pub struct SweepReport {
+	pub accepted: u64,
+	pub declined: u64,
+	pub skipped: u64,
+	/// Users whose requests could not be handled, along with the error message
+	pub failed: Vec<(u64, String)>,
+	/// The cursor of the first page that was not fully processed, `None` once
+	/// every page has been swept
+	pub cursor: Option<String>,
+	/// The error that stopped the sweep early, if any
+	pub error: Option<RoboltError>,
+	/// Whether the accepts and declines were only planned, see
+	/// [dry_run](crate::dry_run)
+	pub dry_run: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMember {
+	pub user: PartialUser,
+	pub role: GroupRole,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+	pub actor: GroupMember,
+	pub action_type: String,
+	/// The details of the action, which vary per action type
+	#[serde(default)]
+	pub description: Value,
+	pub created: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RankChange {
+	pub target_id: u64,
+	#[serde(default)]
+	pub target_name: String,
+	pub old_role_set_id: u64,
+	pub new_role_set_id: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ShoutDescription {
+	text: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// The shouts of a group found in its audit log, see
+/// [Robolt::shout_history]
+pub struct ShoutHistory {
+	/// Newest first, an empty body meaning the shout was cleared
+	pub records: Vec<ShoutRecord>,
+	/// Whether the whole audit log was read, in which case shouts older than
+	/// its retention period are missing, rather than stopping at
+	/// `max_entries`
+	pub truncated_by_retention: bool,
+	/// Whether the audit log has more pages than [Robolt::max_pages_per_crawl]
+	/// and fewer than `max_entries` shouts were found before the limit
+	pub truncated: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ShoutRecord {
+	pub body: String,
+	pub poster: PartialUser,
+	pub created: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplianceOptions {
+	/// The maximum number of audit log pages (of 100 entries) to search for
+	/// rank changes
+	pub audit_log_pages: usize,
+	pub include_join_requests: bool,
+}
+
+impl Default for ComplianceOptions {
+	fn default() -> Self {
+		Self {
+			audit_log_pages: 10,
+			include_join_requests: true,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+	Json,
+	Csv,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Every member of a group along with who ranked them, and the group's
+/// pending join requests
+pub struct ComplianceReport {
+	pub group_id: u64,
+	pub members: Vec<ComplianceMember>,
+	pub pending_requests: Vec<PendingJoinRequest>,
+	/// Whether the members or join requests have more pages than
+	/// [Robolt::max_pages_per_crawl], in which case the report is incomplete
+	pub truncated: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceMember {
+	pub user_id: u64,
+	pub username: String,
+	pub role_id: u64,
+	pub role_name: String,
+	pub rank: u8,
+	/// `None` if the rank change could not be found in the audit log
+	pub ranked_by: Option<RankAttribution>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankAttribution {
+	pub actor_id: u64,
+	pub actor_name: String,
+	pub changed_at: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingJoinRequest {
+	pub user_id: u64,
+	pub username: String,
+	pub requested_at: String,
+}
+
+#[derive(Deserialize)]
+struct GroupRolesResponse {
+	roles: Vec<GroupRole>,
+}
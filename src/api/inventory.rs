@@ -0,0 +1,91 @@
+use serde::Deserialize;
+
+use crate::api::routes::RobloxApi;
+use crate::api::{Limit, Page, Robux, SortOrder};
+use crate::errors::{ResourceKind, RoboltError};
+use crate::Robolt;
+
+impl<State> Robolt<State> {
+	/// Whether a user owns an asset
+	///
+	/// Fails with [RoboltErrorKind::NotFound] and [NotFoundReason::Hidden] if
+	/// the user's inventory is private, as do the other ownership checks.
+	///
+	/// [RoboltErrorKind::NotFound]: crate::errors::RoboltErrorKind::NotFound
+	/// [NotFoundReason::Hidden]: crate::errors::NotFoundReason::Hidden
+	pub async fn owns_asset(&self, user_id: u64, asset_id: u64) -> Result<bool, RoboltError> {
+		self.is_owned(user_id, "Asset", asset_id).await
+	}
+
+	/// Whether a user has been awarded a badge
+	pub async fn owns_badge(&self, user_id: u64, badge_id: u64) -> Result<bool, RoboltError> {
+		self.is_owned(user_id, "Badge", badge_id).await
+	}
+
+	/// Whether a user owns a game pass
+	pub async fn owns_gamepass(&self, user_id: u64, gamepass_id: u64) -> Result<bool, RoboltError> {
+		self.is_owned(user_id, "GamePass", gamepass_id).await
+	}
+
+	/// Whether a user owns a bundle
+	pub async fn owns_bundle(&self, user_id: u64, bundle_id: u64) -> Result<bool, RoboltError> {
+		self.is_owned(user_id, "Bundle", bundle_id).await
+	}
+
+	/// A page of the limited items owned by a user
+	///
+	/// Fails with [RoboltErrorKind::NotFound] and [NotFoundReason::Hidden] if
+	/// the user's inventory is private.
+	///
+	/// [RoboltErrorKind::NotFound]: crate::errors::RoboltErrorKind::NotFound
+	/// [NotFoundReason::Hidden]: crate::errors::NotFoundReason::Hidden
+	pub async fn collectibles(
+		&self,
+		user_id: u64,
+		limit: Limit,
+		sort_order: SortOrder,
+		cursor: Option<&str>,
+	) -> Result<Page<Collectible>, RoboltError> {
+		let mut path = format!(
+			"/v1/users/{user_id}/assets/collectibles?limit={}&sortOrder={sort_order}",
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Inventory, path)
+			.resource(ResourceKind::User, user_id)
+			.send()
+			.await
+	}
+
+	/// Sends an ownership check, which responds with a bare `true` or `false`
+	async fn is_owned(&self, user_id: u64, item_type: &str, item_id: u64) -> Result<bool, RoboltError> {
+		self.request(
+			RobloxApi::Inventory,
+			format!("/v1/users/{user_id}/items/{item_type}/{item_id}/is-owned"),
+		)
+		.resource(ResourceKind::User, user_id)
+		.send()
+		.await
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A limited item owned by a user
+pub struct Collectible {
+	/// The ID of this copy of the item
+	pub user_asset_id: u64,
+	pub asset_id: u64,
+	pub name: String,
+	/// `None` for non-unique limiteds
+	pub serial_number: Option<u64>,
+	#[serde(default)]
+	pub recent_average_price: Robux,
+	pub original_price: Option<Robux>,
+	/// The number of copies in circulation, `None` for non-unique limiteds
+	pub asset_stock: Option<u64>,
+}
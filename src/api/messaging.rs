@@ -0,0 +1,197 @@
+//! Publishing to MessagingService topics through Open Cloud
+//!
+//! Messages are limited to [MESSAGE_MAX_BYTES] bytes of UTF-8, counted on the
+//! message string itself rather than on the JSON request body. Payloads
+//! published with [Robolt::publish_json] are serialized to JSON first, and
+//! if compression is allowed, payloads over the limit are sent compressed
+//! instead.
+//!
+//! ### Compressed messages
+//!
+//! A compressed message is the prefix [COMPRESSED_PREFIX] (`zlib:`) followed
+//! by the standard base64 encoding, with padding, of the zlib stream of the
+//! JSON payload. JSON never starts with `z`, so subscribers tell them apart
+//! by the prefix:
+//!
+//! ```lua
+//! MessagingService:SubscribeAsync(topic, function(message)
+//!     local data = message.Data
+//!     if string.sub(data, 1, 5) == "zlib:" then
+//!         data = zlibDecompress(base64Decode(string.sub(data, 6)))
+//!     end
+//!     local payload = HttpService:JSONDecode(data)
+//! end)
+//! ```
+
+use std::io::{Read, Write};
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::api::routes::RobloxApi;
+use crate::errors::{ResourceKind, RoboltError};
+use crate::utils::response::EmptyResponse;
+use crate::utils::retry::Idempotency;
+use crate::Robolt;
+
+/// The maximum size of a message in bytes
+pub const MESSAGE_MAX_BYTES: usize = 1024;
+/// The maximum length of a topic name in characters
+pub const TOPIC_MAX_LENGTH: usize = 80;
+/// The prefix of compressed messages, see the [module](self) documentation
+pub const COMPRESSED_PREFIX: &str = "zlib:";
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl<State> Robolt<State> {
+	/// Publishes a message to the subscribers of a topic in every server of a
+	/// universe, requires an API key to be set with [Robolt::set_api_key]
+	pub async fn publish_message(&self, universe_id: u64, topic: &str, message: &str) -> Result<(), RoboltError> {
+		validate_topic(topic)?;
+
+		if message.len() > MESSAGE_MAX_BYTES {
+			return Err(RoboltError::message_too_large(message.len(), None, MESSAGE_MAX_BYTES));
+		}
+
+		self.request(
+			RobloxApi::Cloud,
+			format!("/cloud/v2/universes/{universe_id}:publishMessage"),
+		)
+		.method(Method::POST)
+		.idempotency(Idempotency::NonIdempotent)
+		.resource(ResourceKind::Universe, universe_id)
+		.send_body::<_, EmptyResponse>(json!({ "topic": topic, "message": message }))
+		.await?;
+
+		Ok(())
+	}
+
+	/// Publishes `payload` serialized to JSON, see [encode_message]
+	pub async fn publish_json<T: Serialize>(
+		&self,
+		universe_id: u64,
+		topic: &str,
+		payload: &T,
+		allow_compression: bool,
+	) -> Result<(), RoboltError> {
+		validate_topic(topic)?;
+
+		let message = encode_message(payload, allow_compression)?;
+		self.publish_message(universe_id, topic, &message).await
+	}
+}
+
+/// Checks that a topic name is not empty and at most [TOPIC_MAX_LENGTH]
+/// characters long
+pub fn validate_topic(topic: &str) -> Result<(), RoboltError> {
+	let length = topic.chars().count();
+
+	match length {
+		0 => Err("Topic name is empty".to_string().into()),
+		length if length > TOPIC_MAX_LENGTH => {
+			Err(format!("Topic name is {length} characters long, at most {TOPIC_MAX_LENGTH} are allowed").into())
+		},
+		_ => Ok(()),
+	}
+}
+
+/// Serializes `payload` to the message sent by [Robolt::publish_json]
+///
+/// Payloads over [MESSAGE_MAX_BYTES] fail with
+/// [RoboltErrorKind::MessageTooLarge], unless `allow_compression` is set and
+/// the compressed message fits, in which case the compressed message is
+/// returned. Payloads within the limit are never compressed.
+///
+/// [RoboltErrorKind::MessageTooLarge]: crate::errors::RoboltErrorKind::MessageTooLarge
+pub fn encode_message<T: Serialize>(payload: &T, allow_compression: bool) -> Result<String, RoboltError> {
+	let json = serde_json::to_string(payload)?;
+
+	if json.len() <= MESSAGE_MAX_BYTES {
+		return Ok(json);
+	}
+
+	if !allow_compression {
+		return Err(RoboltError::message_too_large(json.len(), None, MESSAGE_MAX_BYTES));
+	}
+
+	let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+	encoder.write_all(json.as_bytes()).map_err(|err| err.to_string())?;
+	let compressed = encoder.finish().map_err(|err| err.to_string())?;
+	let message = format!("{COMPRESSED_PREFIX}{}", base64_encode(&compressed));
+
+	match message.len() {
+		size if size <= MESSAGE_MAX_BYTES => Ok(message),
+		size => Err(RoboltError::message_too_large(
+			json.len(),
+			Some(size),
+			MESSAGE_MAX_BYTES,
+		)),
+	}
+}
+
+/// Reverses [encode_message], returning the JSON of the payload
+pub fn decode_message(message: &str) -> Result<String, RoboltError> {
+	let Some(encoded) = message.strip_prefix(COMPRESSED_PREFIX) else {
+		return Ok(message.to_string());
+	};
+
+	let compressed = base64_decode(encoded).ok_or_else(|| "Compressed message is not valid base64".to_string())?;
+	let mut json = String::new();
+
+	ZlibDecoder::new(compressed.as_slice())
+		.read_to_string(&mut json)
+		.map_err(|err| format!("Compressed message is not a valid zlib stream: {err}"))?;
+
+	Ok(json)
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+	let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+	for chunk in bytes.chunks(3) {
+		let group = chunk
+			.iter()
+			.enumerate()
+			.fold(0u32, |group, (i, byte)| group | (*byte as u32) << (16 - i * 8));
+
+		for i in 0..4 {
+			match i <= chunk.len() {
+				true => encoded.push(BASE64_ALPHABET[(group >> (18 - i * 6)) as usize & 0x3F] as char),
+				false => encoded.push('='),
+			}
+		}
+	}
+
+	encoded
+}
+
+fn base64_decode(encoded: &str) -> Option<Vec<u8>> {
+	if !encoded.len().is_multiple_of(4) {
+		return None;
+	}
+
+	let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+
+	for chunk in encoded.as_bytes().chunks(4) {
+		let padding = chunk.iter().rev().take_while(|byte| **byte == b'=').count();
+
+		if padding > 2 {
+			return None;
+		}
+
+		let mut group = 0u32;
+
+		for (i, byte) in chunk[..4 - padding].iter().enumerate() {
+			let value = BASE64_ALPHABET.iter().position(|c| c == byte)? as u32;
+			group |= value << (18 - i * 6);
+		}
+
+		bytes.extend_from_slice(&group.to_be_bytes()[1..4 - padding]);
+	}
+
+	Some(bytes)
+}
@@ -1,20 +1,85 @@
+//! Wrappers around the Roblox web APIs
+//!
+//! Roblox routinely omits fields from its responses, so models follow a single
+//! policy when a field is not guaranteed to be present:
+//!
+//! * Fields whose absence carries meaning (e.g. no description, no shout) are
+//!   `Option<T>`
+//! * Flags, counts and lists that may be omitted use `#[serde(default)]` and
+//!   fall back to `false`, `0` or an empty list
+//! * Identifiers and names are required
+//!
+//! Unknown fields are always ignored, so new fields added by Roblox never
+//! cause deserialization to fail.
+
 pub mod account_information;
 pub mod account_settings;
+pub mod auth;
 pub mod avatar;
 pub mod badges;
 pub mod catalog;
+pub mod cloud;
+pub mod develop;
+pub mod economy;
 pub mod friends;
+pub mod games;
+pub mod groups;
+pub mod inventory;
+pub mod messaging;
 pub mod presence;
+pub mod private_messages;
 pub mod routes;
+pub mod screening;
+pub mod thumbnails;
+pub mod trades;
 pub mod users;
 
 // Nothing to export
-mod auth;
-mod economy;
 mod points;
 mod premium_features;
 
-#[derive(Default, Debug, Clone, PartialEq)]
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A single page of a cursor-paginated endpoint
+pub struct Page<T> {
+	pub previous_page_cursor: Option<String>,
+	pub next_page_cursor: Option<String>,
+	pub data: Vec<T>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A page of search results, along with any other metadata returned by the
+/// search endpoint
+pub struct SearchResults<T> {
+	#[serde(rename = "data")]
+	pub items: Vec<T>,
+	pub previous_page_cursor: Option<String>,
+	pub next_page_cursor: Option<String>,
+	/// Every other top-level field of the response
+	#[serde(flatten)]
+	pub extras: Map<String, Value>,
+}
+
+impl<T> SearchResults<T> {
+	/// The keyword the results were searched for, as echoed back by Roblox
+	pub fn keyword(&self) -> Option<&str> {
+		self.extras.get("keyword")?.as_str()
+	}
+
+	/// Whether exact matches were moved to the top of the results, if the
+	/// endpoint reports it
+	pub fn prioritizes_exact_match(&self) -> Option<bool> {
+		self.extras.get("prioritizeExactMatch")?.as_bool()
+	}
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 /// Limits the number of items returned by the API
 ///
@@ -35,3 +100,213 @@ pub enum Limit {
 	/// Return up to 100 items
 	Max = 100,
 }
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+/// The order of the items of a paginated endpoint
+pub enum SortOrder {
+	#[default]
+	/// Oldest items first
+	Asc,
+	/// Newest items first
+	Desc,
+}
+
+impl fmt::Display for SortOrder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SortOrder::Asc => write!(f, "Asc"),
+			SortOrder::Desc => write!(f, "Desc"),
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An amount of Robux
+///
+/// Deserializes from both JSON numbers and numeric strings, and always
+/// serializes to a number.
+pub struct Robux(pub u64);
+
+impl Robux {
+	/// The share of a sale that Roblox keeps
+	const MARKETPLACE_FEE_PERCENT: u64 = 30;
+
+	pub fn checked_add(self, other: Robux) -> Option<Robux> {
+		self.0.checked_add(other.0).map(Robux)
+	}
+
+	pub fn checked_sub(self, other: Robux) -> Option<Robux> {
+		self.0.checked_sub(other.0).map(Robux)
+	}
+
+	pub fn saturating_add(self, other: Robux) -> Robux {
+		Robux(self.0.saturating_add(other.0))
+	}
+
+	pub fn saturating_sub(self, other: Robux) -> Robux {
+		Robux(self.0.saturating_sub(other.0))
+	}
+
+	/// What the seller earns from a sale at this price, once Roblox has taken
+	/// its 30% cut
+	///
+	/// Roblox rounds earnings down, so an item sold for 7 earns 4.
+	pub fn after_marketplace_fee(self) -> Robux {
+		let share = 100 - Self::MARKETPLACE_FEE_PERCENT;
+		Robux(self.0 / 100 * share + self.0 % 100 * share / 100)
+	}
+
+	/// The part of a sale at this price that Roblox keeps
+	pub fn marketplace_fee(self) -> Robux {
+		self.saturating_sub(self.after_marketplace_fee())
+	}
+}
+
+impl From<u64> for Robux {
+	fn from(amount: u64) -> Self {
+		Robux(amount)
+	}
+}
+
+impl fmt::Display for Robux {
+	/// Formats the amount with thousands separators, e.g. `1,234,567`
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let digits = self.0.to_string();
+		let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+
+		for (i, digit) in digits.chars().enumerate() {
+			if i > 0 && (digits.len() - i).is_multiple_of(3) {
+				formatted.push(',');
+			}
+
+			formatted.push(digit);
+		}
+
+		f.pad(&formatted)
+	}
+}
+
+impl Serialize for Robux {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_u64(self.0)
+	}
+}
+
+impl<'de> Deserialize<'de> for Robux {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Amount {
+			Number(u64),
+			String(String),
+		}
+
+		match Amount::deserialize(deserializer)? {
+			Amount::Number(amount) => Ok(Robux(amount)),
+			Amount::String(amount) => amount
+				.trim()
+				.parse()
+				.map(Robux)
+				.map_err(|_| serde::de::Error::custom(format!("invalid Robux amount `{amount}`"))),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum CreatorType {
+	Group,
+	User,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The owner of an asset, bundle or game
+pub enum Creator {
+	User { id: u64, name: String },
+	Group { id: u64, name: String },
+}
+
+impl Default for Creator {
+	fn default() -> Self {
+		Creator::User {
+			id: 0,
+			name: String::new(),
+		}
+	}
+}
+
+impl Creator {
+	pub fn id(&self) -> u64 {
+		match self {
+			Creator::User {
+				id, ..
+			} |
+			Creator::Group {
+				id, ..
+			} => *id,
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		match self {
+			Creator::User {
+				name, ..
+			} |
+			Creator::Group {
+				name, ..
+			} => name,
+		}
+	}
+
+	fn new(creator_type: CreatorType, id: u64, name: String) -> Self {
+		match creator_type {
+			CreatorType::User => Creator::User {
+				id,
+				name,
+			},
+			CreatorType::Group => Creator::Group {
+				id,
+				name,
+			},
+		}
+	}
+}
+
+/// Deserializes a [Creator] through one of the per-endpoint representations
+/// below, as each API names the creator fields differently
+pub(crate) fn deserialize_creator<'de, D, T>(deserializer: D) -> Result<Creator, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de> + Into<Creator>,
+{
+	T::deserialize(deserializer).map(Into::into)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Creator fields inlined into the parent object (catalog item details)
+pub(crate) struct FlatCreator {
+	creator_type: CreatorType,
+	creator_target_id: u64,
+	creator_name: String,
+}
+
+impl From<FlatCreator> for Creator {
+	fn from(creator: FlatCreator) -> Self {
+		Creator::new(creator.creator_type, creator.creator_target_id, creator.creator_name)
+	}
+}
+
+#[derive(Deserialize)]
+/// A nested creator object with a `type` field (bundles)
+pub(crate) struct TypedCreator {
+	id: u64,
+	name: String,
+	#[serde(rename = "type")]
+	creator_type: CreatorType,
+}
+
+impl From<TypedCreator> for Creator {
+	fn from(creator: TypedCreator) -> Self {
+		Creator::new(creator.creator_type, creator.id, creator.name)
+	}
+}
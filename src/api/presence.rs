@@ -1,37 +1,269 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
 use reqwest::Method;
-use serde::Deserialize;
-use serde_repr::Deserialize_repr;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::api::routes::RobloxApi;
 use crate::errors::RoboltError;
+use crate::utils::retry::Idempotency;
+#[cfg(feature = "chrono")]
+use crate::utils::timestamp;
 use crate::Robolt;
 
 impl<State> Robolt<State> {
+	/// The presence of each user in `user_ids`
+	///
+	/// Where a user is in game or in Studio is only returned to clients
+	/// allowed to see it by the user's privacy settings, which unauthenticated
+	/// clients never are, see [UserPresence::is_location_hidden].
 	pub async fn presences(&self, user_ids: Vec<u64>) -> Result<Vec<UserPresence>, RoboltError> {
 		let body = HashMap::from([("userIds", user_ids)]);
 
 		self.request(RobloxApi::Presence, "/v1/presence/users")
 			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.read_only()
 			.send_body::<_, UserPresences>(body)
 			.await
 			.map(|res| res.user_presences)
 	}
 
+	/// When each user in `user_ids` was last online
 	pub async fn partial_presences(&self, user_ids: Vec<u64>) -> Result<Vec<PartialUserPresence>, RoboltError> {
 		let body = HashMap::from([("userIds", user_ids)]);
 
 		self.request(RobloxApi::Presence, "/v1/presence/last-online")
 			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.read_only()
 			.send_body::<_, LastOnlineTimestamps>(body)
 			.await
 			.map(|res| res.last_online_timestamps)
 	}
 }
 
+const DEFAULT_PRESENCE_HISTORY_DEPTH: usize = 10;
+
+/// Polls the presence of a set of users and keeps a bounded history of the
+/// changes in their presence, e.g. to show when a user was last seen in a game
+///
+/// Only changes are recorded. A new state must be observed for at least the
+/// [grace window](PresenceTracker::grace) before it is recorded, so that a
+/// user flapping between two states does not flood the history.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use robolt::api::presence::PresenceTracker;
+/// # async fn run() -> Result<(), robolt::errors::RoboltError> {
+/// let client = robolt::Robolt::new();
+/// let mut tracker = PresenceTracker::new(vec![1, 2], Duration::from_secs(60));
+/// let mut interval = tokio::time::interval(tracker.interval());
+///
+/// loop {
+///     interval.tick().await;
+///     tracker.poll(&client).await?;
+///     let events = tracker.drain_events();
+///     // persist the events...
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PresenceTracker {
+	user_ids: Vec<u64>,
+	interval: Duration,
+	depth: usize,
+	grace: Duration,
+	users: HashMap<u64, TrackedUser>,
+	events: VecDeque<PresenceTransition>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrackedUser {
+	current: Option<PresenceState>,
+	/// A state that differs from the current one, and when it was first seen
+	pending: Option<(PresenceState, u64)>,
+	history: VecDeque<PresenceTransition>,
+}
+
+impl PresenceTracker {
+	pub fn new(user_ids: Vec<u64>, interval: Duration) -> Self {
+		Self {
+			user_ids,
+			interval,
+			depth: DEFAULT_PRESENCE_HISTORY_DEPTH,
+			grace: Duration::ZERO,
+			users: HashMap::new(),
+			events: VecDeque::new(),
+		}
+	}
+
+	/// The number of transitions kept per user, 10 by default
+	///
+	/// Events that have not been drained are capped at `depth` per tracked
+	/// user, the oldest being dropped first.
+	pub fn depth(mut self, depth: usize) -> Self {
+		self.depth = depth.max(1);
+		self
+	}
+
+	/// How long a new state must last before it is recorded, none by default
+	pub fn grace(mut self, grace: Duration) -> Self {
+		self.grace = grace;
+		self
+	}
+
+	pub fn interval(&self) -> Duration {
+		self.interval
+	}
+
+	/// Fetches the presence of every tracked user and records any change
+	pub async fn poll<State>(&mut self, client: &Robolt<State>) -> Result<(), RoboltError> {
+		let presences = client.presences(self.user_ids.clone()).await?;
+		let now = client.clock.unix_time().as_millis() as u64;
+
+		self.observe(&presences, now);
+		Ok(())
+	}
+
+	/// Records the changes in a set of presences observed at `observed_at`,
+	/// a Unix timestamp in milliseconds
+	///
+	/// Presences of users that are not tracked are ignored.
+	pub fn observe(&mut self, presences: &[UserPresence], observed_at: u64) {
+		for presence in presences {
+			if !self.user_ids.contains(&presence.user_id) {
+				continue;
+			}
+
+			let state = PresenceState::from(presence);
+			let user = self.users.entry(presence.user_id).or_default();
+
+			let since = match &user.current {
+				Some(current) if current.is_same(&state) => {
+					user.pending = None;
+					continue;
+				},
+				None => observed_at,
+				Some(_) => match &user.pending {
+					Some((pending, since)) if pending.is_same(&state) => *since,
+					_ => {
+						user.pending = Some((state.clone(), observed_at));
+						observed_at
+					},
+				},
+			};
+
+			if user.current.is_some() && observed_at.saturating_sub(since) < self.grace.as_millis() as u64 {
+				continue;
+			}
+
+			let transition = PresenceTransition {
+				user_id: presence.user_id,
+				from: user.current.replace(state.clone()),
+				to: state,
+				observed_at: since,
+			};
+
+			user.pending = None;
+			user.history.push_back(transition.clone());
+
+			if user.history.len() > self.depth {
+				user.history.pop_front();
+			}
+
+			self.events.push_back(transition);
+
+			if self.events.len() > self.depth * self.user_ids.len() {
+				self.events.pop_front();
+			}
+		}
+	}
+
+	/// The last time the user was seen online, in a game or in Studio, as
+	/// the transition out of that state
+	pub fn last_seen(&self, user_id: u64) -> Option<&PresenceTransition> {
+		self.history(user_id).rev().find(|transition| {
+			transition
+				.from
+				.as_ref()
+				.is_some_and(|from| from.presence_type != UserPresenceType::Offline)
+		})
+	}
+
+	/// The current recorded state of the user, if they have been observed
+	pub fn current(&self, user_id: u64) -> Option<&PresenceState> {
+		self.users.get(&user_id)?.current.as_ref()
+	}
+
+	/// The recorded transitions of the user, oldest first
+	pub fn history(&self, user_id: u64) -> impl DoubleEndedIterator<Item = &PresenceTransition> {
+		self.users
+			.get(&user_id)
+			.into_iter()
+			.flat_map(|user| user.history.iter())
+	}
+
+	/// Takes every transition recorded since the last call, oldest first, so
+	/// that they can be persisted
+	pub fn drain_events(&mut self) -> Vec<PresenceTransition> {
+		self.events.drain(..).collect()
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The parts of a presence that are tracked by [PresenceTracker]
+pub struct PresenceState {
+	pub presence_type: UserPresenceType,
+	pub universe_id: Option<u64>,
+	pub place_id: Option<u64>,
+	/// The name of the game or page the user was in, e.g. `Jailbreak`
+	pub last_location: String,
+}
+
+impl PresenceState {
+	/// Whether two states are the same for tracking purposes, moving between
+	/// places of the same universe is not a change
+	fn is_same(&self, other: &Self) -> bool {
+		self.presence_type == other.presence_type && self.universe_id == other.universe_id
+	}
+}
+
+impl From<&UserPresence> for PresenceState {
+	fn from(presence: &UserPresence) -> Self {
+		Self {
+			presence_type: presence.user_presence_type.clone(),
+			universe_id: presence.universe_id,
+			place_id: presence.place_id,
+			last_location: presence.last_location.clone(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A change in the presence of a user recorded by [PresenceTracker]
+pub struct PresenceTransition {
+	pub user_id: u64,
+	/// `None` for the first observed state of the user
+	pub from: Option<PresenceState>,
+	pub to: PresenceState,
+	/// The Unix timestamp in milliseconds at which the new state was first
+	/// observed
+	pub observed_at: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
+/// The presence of a user
+///
+/// The location fields are `None` (and `last_location` empty) whenever the
+/// user is not in game or in Studio, or the client is not allowed to see
+/// where they are.
 pub struct UserPresence {
 	#[serde(alias = "UserPresenceType")]
 	pub user_presence_type: UserPresenceType,
@@ -39,14 +271,47 @@ pub struct UserPresence {
 	pub user_location_type: Option<UserLocationType>,
 	#[serde(default)]
 	pub user_id: u64,
-	pub last_online: String,
+	/// No longer returned by most responses, see [Robolt::partial_presences]
+	#[serde(default)]
+	pub last_online: Option<String>,
+	/// The name of the game or page the user is in, e.g. `Jailbreak`
+	#[serde(default)]
 	pub last_location: String,
-	pub game_id: Option<u64>,
+	/// The ID of the server the user is in
+	pub game_id: Option<String>,
 	pub place_id: Option<u64>,
 	pub universe_id: Option<u64>,
 	pub root_place_id: Option<u64>,
 }
 
+impl UserPresence {
+	/// Whether the user is in game or in Studio but the client is not allowed
+	/// to see where, e.g. because it is unauthenticated or the user's privacy
+	/// settings hide it
+	pub fn is_location_hidden(&self) -> bool {
+		matches!(
+			self.user_presence_type,
+			UserPresenceType::InGame | UserPresenceType::InStudio
+		) && self.place_id.is_none()
+	}
+
+	/// When the user was last online, `None` if not returned
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn last_online_at(&self) -> Result<Option<DateTime<Utc>>, RoboltError> {
+		self.last_online.as_deref().map(timestamp::parse).transpose()
+	}
+}
+
+impl PartialUserPresence {
+	/// When the user was last online
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn last_online_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.last_online)
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PartialUserPresence {
@@ -54,7 +319,7 @@ pub struct PartialUserPresence {
 	pub last_online: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize_repr)]
+#[derive(Debug, Clone, PartialEq, Deserialize_repr, Serialize_repr)]
 #[repr(u8)]
 pub enum UserPresenceType {
 	Offline = 0,
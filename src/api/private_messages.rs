@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::api::routes::RobloxApi;
+use crate::api::users::PartialUser;
+use crate::errors::RoboltError;
+use crate::utils::client::Authenticated;
+use crate::utils::paging::{NumberPaging, NumberedPage, PageIterator};
+use crate::utils::response::CountResponse;
+use crate::utils::retry::Idempotency;
+#[cfg(feature = "chrono")]
+use crate::utils::timestamp;
+use crate::Robolt;
+
+impl Robolt<Authenticated> {
+	/// A page of the private messages of the authenticated user
+	///
+	/// Unlike most endpoints, messages are paginated by page number, starting
+	/// at page 0. See [Robolt::messages_pages] to iterate over every page.
+	pub async fn messages(&self, tab: MessageTab, page: u32, page_size: u32) -> Result<MessagesPage, RoboltError> {
+		self.request(
+			RobloxApi::PrivateMessages,
+			format!("/v1/messages?messageTab={tab}&pageNumber={page}&pageSize={page_size}"),
+		)
+		.send()
+		.await
+	}
+
+	/// Pages through the private messages of the authenticated user
+	///
+	/// The cursors of the iterator are page numbers starting at 1, one more
+	/// than the page number passed to [Robolt::messages].
+	pub fn messages_pages(&self, tab: MessageTab, page_size: u32) -> PageIterator<'_, Message> {
+		let paging = NumberPaging::new(u64::from(page_size));
+
+		PageIterator::new(paging.clone(), move |cursor| {
+			let page = paging.page_number(cursor.as_deref());
+
+			async move {
+				let res = self.messages(tab, page? as u32 - 1, page_size).await?;
+
+				Ok(NumberedPage {
+					items: res.collection,
+					total: Some(res.total_collection_size),
+					final_page: Some(res.page_number + 1 >= res.total_pages),
+				})
+			}
+		})
+		.max_pages(self.max_pages_per_crawl)
+	}
+
+	pub async fn unread_messages_count(&self) -> Result<u64, RoboltError> {
+		self.request(RobloxApi::PrivateMessages, "/v1/messages/unread/count")
+			.send::<CountResponse<u64>>()
+			.await
+			.map(|res| res.count)
+	}
+
+	/// Sends a private message from the authenticated user
+	///
+	/// Fails with [RoboltErrorKind::MessageNotSent] if Roblox refuses the
+	/// message, e.g. because the recipient does not accept messages from the
+	/// user, the error message being the reason given by Roblox.
+	///
+	/// [RoboltErrorKind::MessageNotSent]: crate::errors::RoboltErrorKind::MessageNotSent
+	pub async fn send_message(&self, recipient_id: u64, subject: &str, body: &str) -> Result<(), RoboltError> {
+		let user_id = self.authenticated_user_id().await?;
+		let body = json!({
+			"userId": user_id,
+			"recipientId": recipient_id,
+			"subject": subject,
+			"body": body,
+		});
+
+		let res = self
+			.request(RobloxApi::PrivateMessages, "/v1/messages/send")
+			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body::<_, SendMessageResponse>(body)
+			.await?;
+
+		match res.success {
+			true => Ok(()),
+			false => Err(RoboltError::message_not_sent(res.message)),
+		}
+	}
+
+	/// Marks messages of the authenticated user as read, returning the
+	/// messages that could not be marked
+	pub async fn mark_messages_read(&self, message_ids: Vec<u64>) -> Result<Vec<FailedMessage>, RoboltError> {
+		self.update_messages("/v1/messages/mark-read", message_ids).await
+	}
+
+	/// Archives messages of the authenticated user, returning the messages
+	/// that could not be archived
+	pub async fn archive_messages(&self, message_ids: Vec<u64>) -> Result<Vec<FailedMessage>, RoboltError> {
+		self.update_messages("/v1/messages/archive", message_ids).await
+	}
+
+	async fn update_messages(&self, path: &str, message_ids: Vec<u64>) -> Result<Vec<FailedMessage>, RoboltError> {
+		let body = HashMap::from([("messageIds", Value::from(message_ids))]);
+
+		self.request(RobloxApi::PrivateMessages, path)
+			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.send_body::<_, UpdateMessagesResponse>(body)
+			.await
+			.map(|res| res.failed_messages)
+	}
+}
+
+impl Message {
+	/// When the message was sent
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn created_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.created)
+	}
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+/// The folder of private messages listed by [Robolt::messages]
+pub enum MessageTab {
+	#[default]
+	Inbox,
+	Sent,
+	Archive,
+}
+
+impl fmt::Display for MessageTab {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			MessageTab::Inbox => write!(f, "Inbox"),
+			MessageTab::Sent => write!(f, "Sent"),
+			MessageTab::Archive => write!(f, "Archive"),
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A page of private messages, see [Robolt::messages]
+pub struct MessagesPage {
+	pub collection: Vec<Message>,
+	/// The total number of messages across every page
+	#[serde(default)]
+	pub total_collection_size: u64,
+	#[serde(default)]
+	pub total_pages: u32,
+	/// The page number, starting at 0
+	#[serde(default)]
+	pub page_number: u32,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+	pub id: u64,
+	pub sender: PartialUser,
+	pub recipient: PartialUser,
+	pub subject: String,
+	pub body: String,
+	pub created: String,
+	pub updated: String,
+	#[serde(default)]
+	pub is_read: bool,
+	/// Whether the message was sent by Roblox rather than a user
+	#[serde(default)]
+	pub is_system_message: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A message that could not be marked as read or archived
+pub struct FailedMessage {
+	pub message_id: u64,
+	pub error_message: String,
+}
+
+#[derive(Deserialize)]
+struct SendMessageResponse {
+	success: bool,
+	#[serde(default)]
+	message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateMessagesResponse {
+	#[serde(default)]
+	failed_messages: Vec<FailedMessage>,
+}
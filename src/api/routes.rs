@@ -1,7 +1,10 @@
-use std::ops::Deref;
+use std::collections::HashMap;
+use std::fmt::Display;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RobloxApi {
 	Presence,
+	PrivateMessages,
 	Users,
 	AccountInformation,
 	AccountSettings,
@@ -13,13 +16,26 @@ pub enum RobloxApi {
 	PremiumFeatures,
 	Auth,
 	Catalog,
+	Groups,
+	Develop,
+	Cloud,
+	Thumbnails,
+	Games,
+	Trades,
+	Inventory,
 	Custom(&'static str),
 }
 
 impl RobloxApi {
+	/// Builds the full URL of an endpoint on this API
+	pub(crate) fn endpoint<S: Display>(&self, path: S) -> String {
+		format!("https://{}{path}", self.url())
+	}
+
 	pub(crate) fn url(&self) -> &str {
 		match self {
 			RobloxApi::Presence => "presence.roblox.com",
+			RobloxApi::PrivateMessages => "privatemessages.roblox.com",
 			RobloxApi::Users => "users.roblox.com",
 			RobloxApi::AccountInformation => "accountinformation.roblox.com",
 			RobloxApi::AccountSettings => "accountsettings.roblox.com",
@@ -31,7 +47,82 @@ impl RobloxApi {
 			RobloxApi::PremiumFeatures => "premiumfeatures.roblox.com",
 			RobloxApi::Auth => "auth.roblox.com",
 			RobloxApi::Catalog => "catalog.roblox.com",
-			RobloxApi::Custom(s) => s.deref(),
+			RobloxApi::Groups => "groups.roblox.com",
+			RobloxApi::Develop => "develop.roblox.com",
+			RobloxApi::Cloud => "apis.roblox.com",
+			RobloxApi::Thumbnails => "thumbnails.roblox.com",
+			RobloxApi::Games => "games.roblox.com",
+			RobloxApi::Trades => "trades.roblox.com",
+			RobloxApi::Inventory => "inventory.roblox.com",
+			RobloxApi::Custom(s) => s,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The base URLs that requests are sent to, the Roblox hosts over HTTPS by
+/// default, see [Robolt::endpoints](crate::Robolt::endpoints)
+///
+/// Overriding them points the client at a local mock server or at a proxy
+/// that rewrites hosts. A base URL set for a single API takes precedence over
+/// the base URL of every API, under which each API is reached by its host.
+///
+/// ```
+/// # use robolt::api::routes::{Endpoints, RobloxApi};
+/// let endpoints = Endpoints::new()
+///     .base("http://127.0.0.1:8080")
+///     .api(RobloxApi::Users, "http://127.0.0.1:9090/");
+///
+/// assert_eq!(
+///     endpoints.url(RobloxApi::Users, "/v1/users/1"),
+///     "http://127.0.0.1:9090/v1/users/1"
+/// );
+/// assert_eq!(
+///     endpoints.url(RobloxApi::Groups, "/v1/groups/1"),
+///     "http://127.0.0.1:8080/groups.roblox.com/v1/groups/1"
+/// );
+/// ```
+pub struct Endpoints {
+	base: Option<String>,
+	apis: HashMap<RobloxApi, String>,
+}
+
+impl Endpoints {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sends requests to every API to `base`, e.g. `http://127.0.0.1:8080`,
+	/// the host of the API being prepended to the path of the endpoint
+	pub fn base(mut self, base: &str) -> Self {
+		self.base = Some(base.trim_end_matches('/').to_string());
+		self
+	}
+
+	/// Sends requests to `api` to `base`, e.g. `http://127.0.0.1:8080`, in
+	/// place of its host
+	pub fn api(mut self, api: RobloxApi, base: &str) -> Self {
+		self.apis.insert(api, base.trim_end_matches('/').to_string());
+		self
+	}
+
+	/// The full URL of an endpoint of `api`
+	pub fn url<S: Display>(&self, api: RobloxApi, path: S) -> String {
+		match (self.apis.get(&api), &self.base) {
+			(Some(base), _) => format!("{base}{path}"),
+			(None, Some(base)) => format!("{base}/{}{path}", api.url()),
+			(None, None) => api.endpoint(path),
 		}
 	}
 }
+
+/// Percent-encodes a value for use in a query string
+pub(crate) fn encode_query_value(value: &str) -> String {
+	value
+		.bytes()
+		.map(|byte| match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+			_ => format!("%{byte:02X}"),
+		})
+		.collect()
+}
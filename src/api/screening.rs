@@ -0,0 +1,383 @@
+//! Heuristics for screening users, e.g. before accepting a group join request
+//! or a wall post
+//!
+//! A [ScreeningProfile] holds the configuration of every rule and evaluates a
+//! user against it, with [Robolt::screen] fetching only the data that the
+//! enabled rules need.
+
+use std::collections::HashMap;
+
+use crate::api::avatar::Avatar;
+use crate::api::users::User;
+use crate::errors::RoboltError;
+use crate::utils::time::{Clock, SystemClock};
+use crate::Robolt;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+impl<State> Robolt<State> {
+	/// Fetches a user along with the extra data used by `profile` and
+	/// evaluates them with [ScreeningProfile::evaluate]
+	///
+	/// The avatar, friend count and username history are only fetched when a
+	/// rule that needs them is enabled.
+	pub async fn screen(&self, user_id: u64, profile: &ScreeningProfile) -> Result<ScreeningVerdict, RoboltError>
+	where
+		State: Sync,
+	{
+		let (user, avatar, friend_count, previous_usernames) = futures::try_join!(
+			self.user(user_id),
+			async {
+				match profile.is_enabled(ScreeningRule::DefaultAvatar) {
+					true => self.avatar(user_id).await.map(Some),
+					false => Ok(None),
+				}
+			},
+			async {
+				match profile.is_enabled(ScreeningRule::FewFriends) {
+					true => self.friend_count(user_id).await.map(Some),
+					false => Ok(None),
+				}
+			},
+			async {
+				match profile.is_enabled(ScreeningRule::UsernameChurn) {
+					true => self.username_history(user_id).await.map(Some),
+					false => Ok(None),
+				}
+			},
+		)?;
+
+		let extras = ScreeningExtras {
+			avatar,
+			friend_count,
+			previous_usernames,
+		};
+
+		Ok(profile.evaluate_at(&user, &extras, self.clock.unix_time().as_secs()))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScreeningRule {
+	/// The account is younger than [ScreeningProfile::min_account_age]
+	AccountAge,
+	/// The user wears no assets and every body part has the same color, as
+	/// with the avatar of a new account
+	DefaultAvatar,
+	/// The user has fewer friends than [ScreeningProfile::min_friends]
+	FewFriends,
+	/// The username matches one of [ScreeningProfile::username_patterns]
+	UsernamePattern,
+	/// The user has changed their username more than
+	/// [ScreeningProfile::max_previous_usernames] times
+	UsernameChurn,
+}
+
+impl ScreeningRule {
+	const ALL: [ScreeningRule; 5] = [
+		ScreeningRule::AccountAge,
+		ScreeningRule::DefaultAvatar,
+		ScreeningRule::FewFriends,
+		ScreeningRule::UsernamePattern,
+		ScreeningRule::UsernameChurn,
+	];
+
+	fn default_score(&self) -> u32 {
+		match self {
+			ScreeningRule::AccountAge => 40,
+			ScreeningRule::DefaultAvatar => 30,
+			ScreeningRule::FewFriends => 10,
+			ScreeningRule::UsernamePattern => 50,
+			ScreeningRule::UsernameChurn => 20,
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// Data used by some rules that is not part of [User], rules whose data is
+/// missing are skipped
+pub struct ScreeningExtras {
+	pub avatar: Option<Avatar>,
+	pub friend_count: Option<u64>,
+	pub previous_usernames: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+/// The rules used to screen users and their configuration
+///
+/// Every rule is enabled by default except [ScreeningRule::UsernamePattern],
+/// which is enabled by setting [ScreeningProfile::username_patterns].
+pub struct ScreeningProfile {
+	min_account_age: u64,
+	min_friends: u64,
+	max_previous_usernames: usize,
+	username_patterns: Vec<UsernamePattern>,
+	scores: HashMap<ScreeningRule, u32>,
+	disabled: Vec<ScreeningRule>,
+	threshold: u32,
+}
+
+impl Default for ScreeningProfile {
+	fn default() -> Self {
+		Self {
+			min_account_age: 7,
+			min_friends: 1,
+			max_previous_usernames: 3,
+			username_patterns: Vec::new(),
+			scores: ScreeningRule::ALL
+				.iter()
+				.map(|rule| (*rule, rule.default_score()))
+				.collect(),
+			disabled: Vec::new(),
+			threshold: 50,
+		}
+	}
+}
+
+impl ScreeningProfile {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The minimum age of an account in days, 7 by default
+	pub fn min_account_age(mut self, days: u64) -> Self {
+		self.min_account_age = days;
+		self
+	}
+
+	/// The minimum number of friends, 1 by default
+	pub fn min_friends(mut self, min_friends: u64) -> Self {
+		self.min_friends = min_friends;
+		self
+	}
+
+	/// The maximum number of previous usernames, 3 by default
+	pub fn max_previous_usernames(mut self, max_previous_usernames: usize) -> Self {
+		self.max_previous_usernames = max_previous_usernames;
+		self
+	}
+
+	/// Patterns matched against the whole username, ignoring case
+	///
+	/// `*` matches any number of characters, `?` any single character and `#`
+	/// any digit, e.g. `*free*robux*` or `*_###`. The patterns are compiled
+	/// once, when they are set.
+	pub fn username_patterns(mut self, patterns: &[&str]) -> Self {
+		self.username_patterns = patterns.iter().map(|pattern| UsernamePattern::new(pattern)).collect();
+		self
+	}
+
+	/// The score added when `rule` fires
+	pub fn score(mut self, rule: ScreeningRule, score: u32) -> Self {
+		self.scores.insert(rule, score);
+		self
+	}
+
+	/// The total score from which a user is flagged, 50 by default
+	pub fn threshold(mut self, threshold: u32) -> Self {
+		self.threshold = threshold;
+		self
+	}
+
+	pub fn disable(mut self, rule: ScreeningRule) -> Self {
+		self.disabled.push(rule);
+		self
+	}
+
+	/// Whether a rule is evaluated, rules that are enabled may still be skipped
+	/// if their data is missing
+	pub fn is_enabled(&self, rule: ScreeningRule) -> bool {
+		match rule {
+			ScreeningRule::UsernamePattern if self.username_patterns.is_empty() => false,
+			rule => !self.disabled.contains(&rule),
+		}
+	}
+
+	pub fn evaluate(&self, user: &User, extras: &ScreeningExtras) -> ScreeningVerdict {
+		self.evaluate_at(user, extras, SystemClock.unix_time().as_secs())
+	}
+
+	/// Evaluates a user as of `now`, a Unix timestamp in seconds
+	pub fn evaluate_at(&self, user: &User, extras: &ScreeningExtras, now: u64) -> ScreeningVerdict {
+		let mut verdict = ScreeningVerdict::default();
+
+		for rule in ScreeningRule::ALL {
+			if !self.is_enabled(rule) {
+				continue;
+			}
+
+			let reason = match self.check(rule, user, extras, now) {
+				Some(reason) => reason,
+				None => {
+					verdict.skipped.push(rule);
+					continue;
+				},
+			};
+
+			if let Some(reason) = reason {
+				let score = self.scores.get(&rule).copied().unwrap_or_default();
+
+				verdict.score += score;
+				verdict.fired.push(RuleHit {
+					rule,
+					score,
+					reason,
+				});
+			}
+		}
+
+		verdict.flagged = verdict.score >= self.threshold;
+		verdict
+	}
+
+	/// Checks a single rule, returning `None` if the data it needs is missing
+	/// and the reason it fired otherwise, if it did
+	fn check(
+		&self,
+		rule: ScreeningRule,
+		user: &User,
+		extras: &ScreeningExtras,
+		now: u64,
+	) -> Option<Option<String>> {
+		match rule {
+			ScreeningRule::AccountAge => {
+				let age = now.saturating_sub(parse_date(&user.created)?) / SECONDS_PER_DAY;
+				Some((age < self.min_account_age).then(|| format!("Account is {age} days old")))
+			},
+			ScreeningRule::DefaultAvatar => {
+				let avatar = extras.avatar.as_ref()?;
+				let colors = &avatar.body_colors;
+				let uniform = [
+					&colors.torso_color_id,
+					&colors.right_arm_color_id,
+					&colors.left_arm_color_id,
+					&colors.right_leg_color_id,
+					&colors.left_leg_color_id,
+				]
+				.iter()
+				.all(|color| **color == colors.head_color_id);
+
+				Some((avatar.assets.is_empty() && uniform).then(|| "Avatar has never been customized".to_string()))
+			},
+			ScreeningRule::FewFriends => {
+				let friend_count = extras.friend_count?;
+				Some((friend_count < self.min_friends).then(|| format!("User has {friend_count} friends")))
+			},
+			ScreeningRule::UsernamePattern => Some(
+				self.username_patterns
+					.iter()
+					.find(|pattern| pattern.matches(&user.username))
+					.map(|pattern| format!("Username matches `{}`", pattern.source)),
+			),
+			ScreeningRule::UsernameChurn => {
+				let count = extras.previous_usernames.as_ref()?.len();
+				Some((count > self.max_previous_usernames).then(|| format!("User has {count} previous usernames")))
+			},
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// The outcome of [ScreeningProfile::evaluate]
+pub struct ScreeningVerdict {
+	/// The rules that fired, in the order they were evaluated
+	pub fired: Vec<RuleHit>,
+	/// Enabled rules that were skipped because their data was missing
+	pub skipped: Vec<ScreeningRule>,
+	pub score: u32,
+	/// Whether the score reached [ScreeningProfile::threshold]
+	pub flagged: bool,
+}
+
+impl ScreeningVerdict {
+	pub fn has_fired(&self, rule: ScreeningRule) -> bool {
+		self.fired.iter().any(|hit| hit.rule == rule)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleHit {
+	pub rule: ScreeningRule,
+	pub score: u32,
+	pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A compiled [ScreeningProfile::username_patterns] pattern
+struct UsernamePattern {
+	source: String,
+	tokens: Vec<PatternToken>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PatternToken {
+	Char(char),
+	AnyChar,
+	Digit,
+	Any,
+}
+
+impl UsernamePattern {
+	fn new(source: &str) -> Self {
+		let tokens = source
+			.to_lowercase()
+			.chars()
+			.map(|c| match c {
+				'*' => PatternToken::Any,
+				'?' => PatternToken::AnyChar,
+				'#' => PatternToken::Digit,
+				c => PatternToken::Char(c),
+			})
+			.collect();
+
+		Self {
+			source: source.to_string(),
+			tokens,
+		}
+	}
+
+	fn matches(&self, username: &str) -> bool {
+		let username = username.to_lowercase().chars().collect::<Vec<char>>();
+
+		// `matched[j]` is whether the tokens seen so far match the first `j`
+		// characters of the username
+		let mut matched = vec![false; username.len() + 1];
+		matched[0] = true;
+
+		for token in &self.tokens {
+			let previous = matched.clone();
+
+			matched[0] = previous[0] && *token == PatternToken::Any;
+
+			for j in 1..=username.len() {
+				let c = username[j - 1];
+
+				matched[j] = match token {
+					PatternToken::Any => previous[j] || matched[j - 1],
+					PatternToken::AnyChar => previous[j - 1],
+					PatternToken::Digit => previous[j - 1] && c.is_ascii_digit(),
+					PatternToken::Char(expected) => previous[j - 1] && c == *expected,
+				};
+			}
+		}
+
+		matched[username.len()]
+	}
+}
+
+/// Parses the date of an ISO 8601 timestamp such as `2015-03-14T09:26:53.58Z`
+/// into a Unix timestamp in seconds, ignoring the time of day
+fn parse_date(timestamp: &str) -> Option<u64> {
+	let mut parts = timestamp.get(..10)?.split('-').map(|part| part.parse::<i64>().ok());
+	let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+
+	// Days from civil, see https://howardhinnant.github.io/date_algorithms.html
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	let days = era * 146_097 + day_of_era - 719_468;
+
+	u64::try_from(days).ok().map(|days| days * SECONDS_PER_DAY)
+}
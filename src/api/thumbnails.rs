@@ -0,0 +1,600 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::api::avatar::{Avatar, BodyColors, BodyScale, BodyType};
+use crate::api::routes::RobloxApi;
+use crate::errors::RoboltError;
+use crate::utils::client::Authenticated;
+use crate::utils::response::DataResponse;
+use crate::utils::retry::Idempotency;
+use crate::utils::time::{Sleeper, SystemClock};
+use crate::Robolt;
+
+const THUMBNAILS_BATCH_SIZE: usize = 100;
+const TRY_ON_POLL_ATTEMPTS: u32 = 10;
+const TRY_ON_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+const ASSET_SIZES: &[ThumbnailSize] = &[
+	ThumbnailSize(30, 30),
+	ThumbnailSize(42, 42),
+	ThumbnailSize(50, 50),
+	ThumbnailSize(60, 62),
+	ThumbnailSize(75, 75),
+	ThumbnailSize(110, 110),
+	ThumbnailSize(140, 140),
+	ThumbnailSize(150, 150),
+	ThumbnailSize(160, 100),
+	ThumbnailSize(160, 600),
+	ThumbnailSize(250, 250),
+	ThumbnailSize(256, 144),
+	ThumbnailSize(300, 250),
+	ThumbnailSize(304, 166),
+	ThumbnailSize(384, 216),
+	ThumbnailSize(396, 216),
+	ThumbnailSize(420, 420),
+	ThumbnailSize(480, 270),
+	ThumbnailSize(512, 512),
+	ThumbnailSize(576, 324),
+	ThumbnailSize(700, 700),
+	ThumbnailSize(728, 90),
+	ThumbnailSize(768, 432),
+	ThumbnailSize(1200, 80),
+];
+const AVATAR_SIZES: &[ThumbnailSize] = &[
+	ThumbnailSize(30, 30),
+	ThumbnailSize(48, 48),
+	ThumbnailSize(60, 60),
+	ThumbnailSize(75, 75),
+	ThumbnailSize(100, 100),
+	ThumbnailSize(110, 110),
+	ThumbnailSize(140, 140),
+	ThumbnailSize(150, 150),
+	ThumbnailSize(150, 200),
+	ThumbnailSize(180, 180),
+	ThumbnailSize(250, 250),
+	ThumbnailSize(352, 352),
+	ThumbnailSize(420, 420),
+	ThumbnailSize(720, 720),
+];
+const AVATAR_HEADSHOT_SIZES: &[ThumbnailSize] = &[
+	ThumbnailSize(48, 48),
+	ThumbnailSize(50, 50),
+	ThumbnailSize(60, 60),
+	ThumbnailSize(75, 75),
+	ThumbnailSize(100, 100),
+	ThumbnailSize(110, 110),
+	ThumbnailSize(150, 150),
+	ThumbnailSize(180, 180),
+	ThumbnailSize(352, 352),
+	ThumbnailSize(420, 420),
+	ThumbnailSize(720, 720),
+];
+const BUNDLE_SIZES: &[ThumbnailSize] = &[ThumbnailSize(150, 150), ThumbnailSize(420, 420)];
+const OUTFIT_SIZES: &[ThumbnailSize] = &[ThumbnailSize(150, 150), ThumbnailSize(420, 420)];
+const BADGE_SIZES: &[ThumbnailSize] = &[ThumbnailSize(150, 150)];
+const TRY_ON_SIZES: &[ThumbnailSize] = &[ThumbnailSize(150, 150), ThumbnailSize(420, 420)];
+
+impl<State> Robolt<State> {
+	pub async fn asset_thumbnails(
+		&self,
+		asset_ids: &[u64],
+		size: ThumbnailSize,
+		format: ThumbnailFormat,
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		self.thumbnails("/v1/assets", "assetIds", asset_ids, size, format, ASSET_SIZES)
+			.await
+	}
+
+	/// Fetches the full-body avatar thumbnails of users
+	///
+	/// Thumbnails that are still rendering are returned as
+	/// [ThumbnailState::Pending], see [Robolt::resolve_thumbnails] to wait for
+	/// them.
+	pub async fn avatar_thumbnails(
+		&self,
+		user_ids: &[u64],
+		size: ThumbnailSize,
+		format: ThumbnailFormat,
+		circular: bool,
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		self.thumbnails(
+			&format!("/v1/users/avatar?isCircular={circular}"),
+			"userIds",
+			user_ids,
+			size,
+			format,
+			AVATAR_SIZES,
+		)
+		.await
+	}
+
+	/// Fetches the avatar headshots of users
+	///
+	/// Thumbnails that are still rendering are returned as
+	/// [ThumbnailState::Pending], see [Robolt::resolve_thumbnails] to wait for
+	/// them.
+	pub async fn avatar_headshots(
+		&self,
+		user_ids: &[u64],
+		size: ThumbnailSize,
+		format: ThumbnailFormat,
+		circular: bool,
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		self.thumbnails(
+			&format!("/v1/users/avatar-headshot?isCircular={circular}"),
+			"userIds",
+			user_ids,
+			size,
+			format,
+			AVATAR_HEADSHOT_SIZES,
+		)
+		.await
+	}
+
+	pub async fn bundle_thumbnails(
+		&self,
+		bundle_ids: &[u64],
+		size: ThumbnailSize,
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		self.thumbnails(
+			"/v1/bundles/thumbnails",
+			"bundleIds",
+			bundle_ids,
+			size,
+			ThumbnailFormat::Png,
+			BUNDLE_SIZES,
+		)
+		.await
+	}
+
+	pub async fn outfit_thumbnails(
+		&self,
+		outfit_ids: &[u64],
+		size: ThumbnailSize,
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		self.thumbnails(
+			"/v1/users/outfits",
+			"userOutfitIds",
+			outfit_ids,
+			size,
+			ThumbnailFormat::Png,
+			OUTFIT_SIZES,
+		)
+		.await
+	}
+
+	pub async fn badge_icons(
+		&self,
+		badge_ids: &[u64],
+		size: ThumbnailSize,
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		self.thumbnails(
+			"/v1/badges/icons",
+			"badgeIds",
+			badge_ids,
+			size,
+			ThumbnailFormat::Png,
+			BADGE_SIZES,
+		)
+		.await
+	}
+
+	/// Validates the size against the sizes supported by the endpoint, then
+	/// requests the thumbnails in batches of 100
+	///
+	/// `path` may carry query parameters of its own, which are kept.
+	async fn thumbnails(
+		&self,
+		path: &str,
+		ids_param: &str,
+		ids: &[u64],
+		size: ThumbnailSize,
+		format: ThumbnailFormat,
+		valid_sizes: &[ThumbnailSize],
+	) -> Result<Vec<Thumbnail>, RoboltError> {
+		let (endpoint, query) = match path.split_once('?') {
+			Some((endpoint, query)) => (endpoint, format!("{query}&")),
+			None => (path, String::new()),
+		};
+
+		validate_size(endpoint, size, valid_sizes)?;
+
+		let mut thumbnails = Vec::new();
+
+		for chunk in ids.chunks(THUMBNAILS_BATCH_SIZE) {
+			let chunk = chunk.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(",");
+
+			let res = self
+				.request(
+					RobloxApi::Thumbnails,
+					format!("{endpoint}?{query}{ids_param}={chunk}&size={size}&format={format:?}"),
+				)
+				.send::<DataResponse<Thumbnail>>()
+				.await?;
+
+			thumbnails.extend(res.data);
+		}
+
+		Ok(thumbnails)
+	}
+}
+
+impl<State> Robolt<State> {
+	/// Requests thumbnails of any type through the batch endpoint, then
+	/// re-requests the ones that are still pending every `poll_interval`
+	/// until none are left or `max_wait` has elapsed
+	///
+	/// Only pending thumbnails are requested again. Thumbnails that are still
+	/// pending at the deadline are returned as they are, and blocked or failed
+	/// thumbnails are returned without being retried.
+	pub async fn resolve_thumbnails(
+		&self,
+		requests: &[ThumbnailRequest],
+		poll_interval: Duration,
+		max_wait: Duration,
+	) -> Result<HashMap<ThumbnailRequest, Thumbnail>, RoboltError> {
+		let started = self.clock.now();
+		let mut thumbnails = self.batch_thumbnails(requests).await?;
+
+		loop {
+			let pending = requests
+				.iter()
+				.filter(|request| thumbnails[*request].state == ThumbnailState::Pending)
+				.cloned()
+				.collect::<Vec<ThumbnailRequest>>();
+
+			let elapsed = self.clock.now().saturating_duration_since(started);
+
+			if pending.is_empty() || elapsed + poll_interval > max_wait {
+				return Ok(thumbnails);
+			}
+
+			self.sleeper.sleep(poll_interval).await;
+			thumbnails.extend(self.batch_thumbnails(&pending).await?);
+		}
+	}
+
+	/// Requests thumbnails through the batch endpoint in batches of 100,
+	/// treating thumbnails missing from the response as pending
+	async fn batch_thumbnails(
+		&self,
+		requests: &[ThumbnailRequest],
+	) -> Result<HashMap<ThumbnailRequest, Thumbnail>, RoboltError> {
+		let mut thumbnails = requests
+			.iter()
+			.map(|request| {
+				let thumbnail = Thumbnail {
+					target_id: request.target_id,
+					..Thumbnail::default()
+				};
+
+				(request.clone(), thumbnail)
+			})
+			.collect::<HashMap<ThumbnailRequest, Thumbnail>>();
+
+		for chunk in requests.chunks(THUMBNAILS_BATCH_SIZE) {
+			let body = chunk.iter().map(BatchThumbnailRequest::from).collect::<Vec<_>>();
+			let res = self
+				.request(RobloxApi::Thumbnails, "/v1/batch")
+				.method(Method::POST)
+				.idempotency(Idempotency::Idempotent)
+				.read_only()
+				.send_body::<_, DataResponse<BatchThumbnail>>(body)
+				.await?;
+
+			for thumbnail in res.data {
+				let request = chunk.iter().find(|request| request.id() == thumbnail.request_id);
+
+				if let Some(request) = request {
+					thumbnails.insert(request.clone(), thumbnail.into());
+				}
+			}
+		}
+
+		Ok(thumbnails)
+	}
+}
+
+impl Robolt<Authenticated> {
+	/// Renders a user's avatar wearing the given assets on top of the assets
+	/// they are currently wearing, waiting for the render to complete
+	///
+	/// Renders are always PNGs. If the render is still pending after 10 polls
+	/// (2 seconds apart), the pending thumbnail is returned.
+	pub async fn try_on(
+		&self,
+		user_id: u64,
+		asset_ids: &[u64],
+		size: ThumbnailSize,
+	) -> Result<Thumbnail, RoboltError> {
+		validate_size("/v1/avatar/render", size, TRY_ON_SIZES)?;
+
+		let avatar = self.avatar(user_id).await?;
+		let body = TryOnRequest::new(user_id, &avatar, asset_ids, size);
+
+		poll_thumbnail_with(TRY_ON_POLL_ATTEMPTS, TRY_ON_POLL_INTERVAL, self.sleeper.as_ref(), || async {
+			self.request(RobloxApi::Avatar, "/v1/avatar/render")
+				.method(Method::POST)
+				.idempotency(Idempotency::Idempotent)
+				.read_only()
+				.send_body::<_, RenderResponse>(&body)
+				.await
+				.map(|res| Thumbnail {
+					target_id: user_id,
+					state: res.state,
+					image_url: res.image_url,
+					version: String::new(),
+				})
+		})
+		.await
+	}
+}
+
+/// Calls `f` until the returned thumbnail is no longer pending, up to
+/// `attempts` times with `interval` between calls
+///
+/// The last thumbnail is returned even if it is still pending.
+pub async fn poll_thumbnail<F, Fut>(attempts: u32, interval: Duration, f: F) -> Result<Thumbnail, RoboltError>
+where
+	F: Fn() -> Fut,
+	Fut: Future<Output = Result<Thumbnail, RoboltError>>,
+{
+	poll_thumbnail_with(attempts, interval, &SystemClock, f).await
+}
+
+/// [poll_thumbnail] with a custom [Sleeper]
+pub async fn poll_thumbnail_with<S, F, Fut>(
+	attempts: u32,
+	interval: Duration,
+	sleeper: &S,
+	f: F,
+) -> Result<Thumbnail, RoboltError>
+where
+	S: Sleeper + ?Sized,
+	F: Fn() -> Fut,
+	Fut: Future<Output = Result<Thumbnail, RoboltError>>,
+{
+	let mut thumbnail = f().await?;
+
+	for _ in 1..attempts {
+		if thumbnail.state != ThumbnailState::Pending {
+			break;
+		}
+
+		sleeper.sleep(interval).await;
+		thumbnail = f().await?;
+	}
+
+	Ok(thumbnail)
+}
+
+fn validate_size(path: &str, size: ThumbnailSize, valid_sizes: &[ThumbnailSize]) -> Result<(), RoboltError> {
+	if valid_sizes.contains(&size) {
+		return Ok(());
+	}
+
+	let valid_sizes = valid_sizes
+		.iter()
+		.map(ThumbnailSize::to_string)
+		.collect::<Vec<String>>()
+		.join(", ");
+
+	Err(RoboltError::from(format!(
+		"Invalid thumbnail size {size} for {path}, expected one of: {valid_sizes}"
+	)))
+}
+
+impl TryOnRequest {
+	pub fn new(user_id: u64, avatar: &Avatar, asset_ids: &[u64], size: ThumbnailSize) -> Self {
+		let mut assets = avatar
+			.assets
+			.iter()
+			.map(|asset| TryOnAsset {
+				id: asset.id,
+			})
+			.collect::<Vec<TryOnAsset>>();
+
+		for asset_id in asset_ids {
+			if !assets.iter().any(|asset| asset.id == *asset_id) {
+				assets.push(TryOnAsset {
+					id: *asset_id,
+				});
+			}
+		}
+
+		Self {
+			thumbnail_config: TryOnThumbnailConfig {
+				thumbnail_id: user_id,
+				thumbnail_type: "2d",
+				size: size.to_string(),
+			},
+			avatar_definition: TryOnAvatarDefinition {
+				assets,
+				body_colors: avatar.body_colors.clone(),
+				scales: avatar.scales.clone(),
+				player_avatar_type: TryOnAvatarType {
+					player_avatar_type: avatar.player_avatar_type.clone(),
+				},
+			},
+		}
+	}
+}
+
+impl ThumbnailRequest {
+	pub fn new(kind: ThumbnailType, target_id: u64, size: ThumbnailSize) -> Self {
+		Self {
+			kind,
+			target_id,
+			size,
+			format: ThumbnailFormat::default(),
+		}
+	}
+
+	pub fn format(mut self, format: ThumbnailFormat) -> Self {
+		self.format = format;
+		self
+	}
+
+	/// The ID identifying the request in a batch, unique per target, type,
+	/// size and format
+	fn id(&self) -> String {
+		format!("{}:{:?}:{}:{:?}", self.target_id, self.kind, self.size, self.format)
+	}
+}
+
+impl From<&ThumbnailRequest> for BatchThumbnailRequest {
+	fn from(request: &ThumbnailRequest) -> Self {
+		Self {
+			request_id: request.id(),
+			target_id: request.target_id,
+			kind: request.kind,
+			size: request.size.to_string(),
+			format: format!("{:?}", request.format),
+		}
+	}
+}
+
+impl From<BatchThumbnail> for Thumbnail {
+	fn from(thumbnail: BatchThumbnail) -> Self {
+		Self {
+			target_id: thumbnail.target_id,
+			state: thumbnail.state,
+			image_url: thumbnail.image_url,
+			version: thumbnail.version,
+		}
+	}
+}
+
+impl fmt::Display for ThumbnailSize {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}x{}", self.0, self.1)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The width and height of a thumbnail, in pixels
+pub struct ThumbnailSize(pub u16, pub u16);
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThumbnailFormat {
+	#[default]
+	Png,
+	Jpeg,
+	Webp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A thumbnail requested with [Robolt::resolve_thumbnails]
+pub struct ThumbnailRequest {
+	pub kind: ThumbnailType,
+	pub target_id: u64,
+	pub size: ThumbnailSize,
+	pub format: ThumbnailFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum ThumbnailType {
+	Avatar,
+	AvatarHeadShot,
+	AvatarBust,
+	Asset,
+	BundleThumbnail,
+	Outfit,
+	BadgeIcon,
+	GameIcon,
+	GroupIcon,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchThumbnailRequest {
+	request_id: String,
+	target_id: u64,
+	#[serde(rename = "type")]
+	kind: ThumbnailType,
+	size: String,
+	format: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchThumbnail {
+	request_id: String,
+	target_id: u64,
+	state: ThumbnailState,
+	image_url: Option<String>,
+	#[serde(default)]
+	version: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Thumbnail {
+	pub target_id: u64,
+	pub state: ThumbnailState,
+	/// Only present once the thumbnail has been rendered
+	pub image_url: Option<String>,
+	#[serde(default)]
+	pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The body sent to render an avatar with [Robolt::try_on]
+pub struct TryOnRequest {
+	thumbnail_config: TryOnThumbnailConfig,
+	avatar_definition: TryOnAvatarDefinition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TryOnThumbnailConfig {
+	thumbnail_id: u64,
+	thumbnail_type: &'static str,
+	size: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TryOnAvatarDefinition {
+	assets: Vec<TryOnAsset>,
+	body_colors: BodyColors,
+	scales: BodyScale,
+	player_avatar_type: TryOnAvatarType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TryOnAsset {
+	id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TryOnAvatarType {
+	player_avatar_type: BodyType,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenderResponse {
+	state: ThumbnailState,
+	image_url: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub enum ThumbnailState {
+	Completed,
+	/// The thumbnail is still being rendered, request it again later
+	#[default]
+	Pending,
+	Blocked,
+	InReview,
+	Error,
+	#[serde(other)]
+	Other,
+}
@@ -0,0 +1,433 @@
+use std::fmt;
+
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::routes::RobloxApi;
+use crate::api::users::PartialUser;
+use crate::api::{Limit, Page, Robux};
+use crate::errors::RoboltError;
+use crate::utils::client::Authenticated;
+use crate::utils::response::EmptyResponse;
+use crate::utils::retry::Idempotency;
+use crate::Robolt;
+
+impl Robolt<Authenticated> {
+	/// Checks whether the authenticated user can send a trade to a user,
+	/// without sending anything
+	pub async fn can_trade_with(&self, user_id: u64) -> Result<TradeEligibility, RoboltError> {
+		self.request(RobloxApi::Trades, format!("/v1/users/{user_id}/can-trade-with"))
+			.send()
+			.await
+	}
+
+	/// Fetches the limits every trade must respect, see
+	/// [TradeMetadata::validate]
+	pub async fn trade_metadata(&self) -> Result<TradeMetadata, RoboltError> {
+		self.request(RobloxApi::Trades, "/v1/trades/metadata").send().await
+	}
+
+	/// A page of the trades of the authenticated user with the given status,
+	/// newest first
+	pub async fn trades(
+		&self,
+		status: TradeStatus,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<Page<Trade>, RoboltError> {
+		let mut path = format!("/v1/trades/{status}?limit={}&sortOrder=Desc", limit as u8);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Trades, path).send().await
+	}
+
+	/// Fetches a trade of the authenticated user along with the items and
+	/// Robux offered by both users
+	pub async fn trade(&self, trade_id: u64) -> Result<TradeDetails, RoboltError> {
+		self.request(RobloxApi::Trades, format!("/v1/trades/{trade_id}"))
+			.send()
+			.await
+	}
+
+	/// Accepts an inbound trade
+	///
+	/// Fails with [RoboltErrorKind::ChallengeRequired] if Roblox asks for a
+	/// two-step verification before accepting.
+	///
+	/// [RoboltErrorKind::ChallengeRequired]: crate::errors::RoboltErrorKind::ChallengeRequired
+	pub async fn accept_trade(&self, trade_id: u64) -> Result<(), RoboltError> {
+		self.request(RobloxApi::Trades, format!("/v1/trades/{trade_id}/accept"))
+			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
+			.send::<EmptyResponse>()
+			.await?;
+
+		Ok(())
+	}
+
+	/// Declines an inbound trade, or cancels an outbound one
+	pub async fn decline_trade(&self, trade_id: u64) -> Result<(), RoboltError> {
+		self.request(RobloxApi::Trades, format!("/v1/trades/{trade_id}/decline"))
+			.method(Method::POST)
+			.idempotency(Idempotency::Idempotent)
+			.send::<EmptyResponse>()
+			.await?;
+
+		Ok(())
+	}
+
+	/// Sends a trade from the authenticated user, returning its ID
+	///
+	/// The offer can be checked against the trade limits beforehand with
+	/// [TradeMetadata::validate]. Fails with
+	/// [RoboltErrorKind::ChallengeRequired] if Roblox asks for a two-step
+	/// verification before sending.
+	///
+	/// [RoboltErrorKind::ChallengeRequired]: crate::errors::RoboltErrorKind::ChallengeRequired
+	pub async fn send_trade(&self, trade: &TradeOffer) -> Result<u64, RoboltError> {
+		let user_id = self.authenticated_user_id().await?;
+		let body = json!({
+			"offers": [
+				trade.offer.body(user_id),
+				trade.request.body(trade.user_id),
+			],
+		});
+
+		self.request(RobloxApi::Trades, "/v1/trades/send")
+			.method(Method::POST)
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body::<_, SentTrade>(body)
+			.await
+			.map(|res| res.id)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The trades listed by [Robolt::trades]
+pub enum TradeStatus {
+	/// Trades received by the authenticated user and awaiting an answer
+	Inbound,
+	/// Trades sent by the authenticated user and awaiting an answer
+	Outbound,
+	Completed,
+	/// Trades that were declined, cancelled or expired
+	Inactive,
+}
+
+impl fmt::Display for TradeStatus {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TradeStatus::Inbound => write!(f, "Inbound"),
+			TradeStatus::Outbound => write!(f, "Outbound"),
+			TradeStatus::Completed => write!(f, "Completed"),
+			TradeStatus::Inactive => write!(f, "Inactive"),
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A trade listed by [Robolt::trades]
+pub struct Trade {
+	pub id: u64,
+	/// The other user of the trade
+	pub user: PartialUser,
+	pub created: String,
+	pub expiration: Option<String>,
+	#[serde(default)]
+	pub is_active: bool,
+	/// e.g. `Open`, `Completed`, `Declined` or `Expired`
+	pub status: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A trade along with its offers, see [Robolt::trade]
+pub struct TradeDetails {
+	#[serde(flatten)]
+	pub trade: Trade,
+	/// The side of the authenticated user first, then the side of the other
+	/// user
+	#[serde(default)]
+	pub offers: Vec<TradeDetailsOffer>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The items and Robux one user puts into a trade
+pub struct TradeDetailsOffer {
+	pub user: PartialUser,
+	#[serde(default)]
+	pub user_assets: Vec<TradeAsset>,
+	#[serde(default)]
+	pub robux: Robux,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A copy of a limited item in a trade
+pub struct TradeAsset {
+	#[serde(rename = "id")]
+	pub user_asset_id: u64,
+	/// Only set for limited unique items
+	pub serial_number: Option<u64>,
+	pub asset_id: u64,
+	pub name: String,
+	#[serde(default)]
+	pub recent_average_price: Robux,
+	pub original_price: Option<Robux>,
+	/// The number of copies of a limited unique item
+	pub asset_stock: Option<u64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// A trade to send with [Robolt::send_trade]
+pub struct TradeOffer {
+	/// The user the trade is sent to
+	pub user_id: u64,
+	/// The items and Robux of the authenticated user
+	pub offer: TradeOfferSide,
+	/// The items and Robux requested from the other user
+	pub request: TradeOfferSide,
+}
+
+impl From<&TradeDetailsOffer> for TradeOfferSide {
+	/// Values every item at its recent average price, e.g. to check a
+	/// counter offer with [TradeMetadata::validate]
+	fn from(offer: &TradeDetailsOffer) -> Self {
+		Self {
+			items: offer
+				.user_assets
+				.iter()
+				.map(|asset| TradeOfferItem {
+					user_asset_id: asset.user_asset_id,
+					value: asset.recent_average_price,
+				})
+				.collect(),
+			robux: offer.robux,
+		}
+	}
+}
+
+#[derive(Deserialize)]
+struct SentTrade {
+	id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "CanTradeResponse")]
+/// Whether a trade can be sent to a user, and why not
+pub enum TradeEligibility {
+	Eligible,
+	CannotTradeWithSelf,
+	/// The authenticated user cannot trade, e.g. because they do not have
+	/// Premium
+	SenderCannotTrade,
+	/// The other user cannot trade
+	CannotTrade,
+	/// The privacy settings of either user do not allow the trade
+	Privacy,
+	Unknown {
+		status: String,
+	},
+}
+
+impl TradeEligibility {
+	pub fn is_eligible(&self) -> bool {
+		*self == Self::Eligible
+	}
+}
+
+impl From<CanTradeResponse> for TradeEligibility {
+	fn from(res: CanTradeResponse) -> Self {
+		if res.can_trade {
+			return Self::Eligible;
+		}
+
+		match res.status.as_str() {
+			"CannotTradeWithSelf" => Self::CannotTradeWithSelf,
+			"SenderCannotTrade" => Self::SenderCannotTrade,
+			"ReceiverCannotTrade" | "UserCannotTrade" => Self::CannotTrade,
+			"SenderPrivacySettingsTooStrict" | "ReceiverPrivacySettingsTooStrict" => Self::Privacy,
+			_ => Self::Unknown {
+				status: res.status,
+			},
+		}
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CanTradeResponse {
+	can_trade: bool,
+	#[serde(default)]
+	status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeMetadata {
+	pub max_items_per_side: usize,
+	/// The minimum value of the offered side relative to the requested side
+	pub min_value_ratio: f64,
+	/// The maximum Robux that can be added to a side, relative to the value of
+	/// its items
+	pub trade_system_max_robux_percent: Option<f64>,
+	/// The share of traded Robux that Roblox keeps
+	#[serde(default)]
+	pub trade_system_robux_fee: f64,
+}
+
+impl TradeMetadata {
+	/// Checks an offer against the trade limits before it is sent, returning
+	/// every limit it breaks
+	///
+	/// The value of each side is the value of its items plus the Robux added to
+	/// it once the trade fee has been taken.
+	pub fn validate(&self, offer: &TradeOfferSide, request: &TradeOfferSide) -> Vec<TradeOfferIssue> {
+		let mut issues = Vec::new();
+
+		for (side, contents) in [(TradeSide::Offer, offer), (TradeSide::Request, request)] {
+			if contents.items.is_empty() {
+				issues.push(TradeOfferIssue::NoItems {
+					side,
+				});
+			}
+
+			if contents.items.len() > self.max_items_per_side {
+				issues.push(TradeOfferIssue::TooManyItems {
+					side,
+					count: contents.items.len(),
+					max: self.max_items_per_side,
+				});
+			}
+
+			if let Some(max_robux_percent) = self.trade_system_max_robux_percent {
+				let max_robux = Robux((contents.item_value().0 as f64 * max_robux_percent) as u64);
+
+				if contents.robux > max_robux {
+					issues.push(TradeOfferIssue::TooMuchRobux {
+						side,
+						robux: contents.robux,
+						max: max_robux,
+					});
+				}
+			}
+		}
+
+		let requested_value = request.value(self.trade_system_robux_fee);
+
+		if requested_value > 0.0 {
+			let ratio = offer.value(self.trade_system_robux_fee) / requested_value;
+
+			if ratio < self.min_value_ratio {
+				issues.push(TradeOfferIssue::ValueRatioTooLow {
+					ratio,
+					min: self.min_value_ratio,
+				});
+			}
+		}
+
+		issues
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// The items and Robux one user puts into a trade
+pub struct TradeOfferSide {
+	pub items: Vec<TradeOfferItem>,
+	pub robux: Robux,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TradeOfferItem {
+	pub user_asset_id: u64,
+	/// The value of the item, usually its recent average price
+	pub value: Robux,
+}
+
+impl TradeOfferSide {
+	/// The total value of the items, excluding Robux
+	pub fn item_value(&self) -> Robux {
+		self.items
+			.iter()
+			.fold(Robux(0), |total, item| total.saturating_add(item.value))
+	}
+
+	fn value(&self, robux_fee: f64) -> f64 {
+		self.item_value().0 as f64 + self.robux.0 as f64 * (1.0 - robux_fee)
+	}
+
+	/// The side as sent to the trade endpoint, `user_id` owning its items
+	fn body(&self, user_id: u64) -> serde_json::Value {
+		json!({
+			"userId": user_id,
+			"userAssetIds": self.items.iter().map(|item| item.user_asset_id).collect::<Vec<u64>>(),
+			"robux": self.robux,
+		})
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+	/// The side of the authenticated user
+	Offer,
+	/// The side of the other user
+	Request,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A trade limit broken by an offer, see [TradeMetadata::validate]
+pub enum TradeOfferIssue {
+	NoItems {
+		side: TradeSide,
+	},
+	TooManyItems {
+		side: TradeSide,
+		count: usize,
+		max: usize,
+	},
+	TooMuchRobux {
+		side: TradeSide,
+		robux: Robux,
+		max: Robux,
+	},
+	/// The offered side is worth too little compared to the requested side
+	ValueRatioTooLow {
+		ratio: f64,
+		min: f64,
+	},
+}
+
+impl fmt::Display for TradeOfferIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NoItems {
+				side,
+			} => write!(f, "The {side:?} side has no items"),
+			Self::TooManyItems {
+				side,
+				count,
+				max,
+			} => write!(f, "The {side:?} side has {count} items, at most {max} are allowed"),
+			Self::TooMuchRobux {
+				side,
+				robux,
+				max,
+			} => write!(f, "The {side:?} side adds {robux} Robux, at most {max} are allowed"),
+			Self::ValueRatioTooLow {
+				ratio,
+				min,
+			} => write!(
+				f,
+				"The offer is worth {:.0}% of the request, at least {:.0}% is required",
+				ratio * 100.0,
+				min * 100.0
+			),
+		}
+	}
+}
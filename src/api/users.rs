@@ -1,61 +1,220 @@
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::PoisonError;
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use reqwest::Method;
 use serde::Deserialize;
 use serde_json::Value;
 
-use crate::api::routes::RobloxApi;
-use crate::api::Limit;
+use crate::api::routes::{encode_query_value, RobloxApi};
+use crate::api::{Limit, Page, SearchResults, SortOrder};
 use crate::utils::client::Authenticated;
-use crate::utils::errors::RoboltError;
+use crate::utils::errors::{ResourceKind, RoboltError, RoboltErrorKind};
+use crate::utils::paging::{CursorPaging, PageIterator};
 use crate::utils::response::{DataResponse, EmptyResponse};
+use crate::utils::retry::Idempotency;
+#[cfg(feature = "chrono")]
+use crate::utils::timestamp;
 use crate::Robolt;
 
+/// The maximum number of users per batch lookup
+const USERS_BATCH_SIZE: usize = 100;
+/// The error code returned when the display name was changed in the last 7
+/// days
+const DISPLAY_NAME_COOLDOWN_CODE: i32 = 5;
+
 impl<State> Robolt<State> {
 	pub async fn user(&self, user_id: u64) -> Result<User, RoboltError> {
 		self.request(RobloxApi::Users, format!("/v1/users/{user_id}"))
+			.resource(ResourceKind::User, user_id)
 			.send()
 			.await
 	}
 
 	pub async fn partial_user(&self, user_id: u64) -> Result<PartialUser, RoboltError> {
 		self.request(RobloxApi::Users, format!("/v1/users/{user_id}"))
+			.resource(ResourceKind::User, user_id)
 			.send()
 			.await
 	}
 
-	pub async fn search_users(&self, keyword: &str, limit: Limit) -> Result<Vec<PartialUser>, RoboltError> {
-		self.request(
-			RobloxApi::Users,
-			format!("/v1/users/search?keyword={keyword}&limit={}", limit as u8),
-		)
-		.send::<DataResponse<PartialUser>>()
-		.await
-		.map(|res| res.data)
+	/// Fetches the full record of every user in `user_ids`, in the same order
+	///
+	/// There is no batch endpoint for full records, so every user is fetched
+	/// individually with [Robolt::user] with at most `concurrency` requests
+	/// in flight. A user that cannot be fetched only fails its own result, and
+	/// each request is retried on its own, so a rate limited request does not
+	/// hold up the others.
+	pub async fn users(&self, user_ids: &[u64], concurrency: usize) -> Vec<Result<User, RoboltError>> {
+		self.users_with_progress(user_ids, concurrency, |_, _| {}).await
 	}
 
+	/// [Robolt::users], calling `progress` with the number of users fetched so
+	/// far and the total number of users after each user
+	pub async fn users_with_progress<F>(
+		&self,
+		user_ids: &[u64],
+		concurrency: usize,
+		progress: F,
+	) -> Vec<Result<User, RoboltError>>
+	where
+		F: Fn(usize, usize),
+	{
+		let total = user_ids.len();
+		let done = AtomicUsize::new(0);
+
+		stream::iter(user_ids)
+			.map(|user_id| {
+				let (done, progress) = (&done, &progress);
+
+				async move {
+					let res = self.user(*user_id).await;
+					progress(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+					res
+				}
+			})
+			.buffered(concurrency.max(1))
+			.collect()
+			.await
+	}
+
+	pub async fn search_users(
+		&self,
+		keyword: &str,
+		limit: Limit,
+		cursor: Option<&str>,
+	) -> Result<SearchResults<PartialUser>, RoboltError> {
+		let mut path = format!(
+			"/v1/users/search?keyword={}&limit={}",
+			encode_query_value(keyword),
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Users, path).send().await
+	}
+
+	/// Fetches the users with the given IDs, in any order
+	///
+	/// IDs are requested in batches of 100, so any number of IDs can be
+	/// passed. Users that do not exist are missing from the result, see
+	/// [Robolt::lookup_users] to tell which.
 	pub async fn users_from_ids(
 		&self,
 		user_ids: Vec<u64>,
 		exclude_banned: bool,
 	) -> Result<Vec<PartialUser>, RoboltError> {
-		let body = HashMap::from([
-			("excludeBannedUsers", Value::from(exclude_banned)),
-			("userIds", Value::from(user_ids)),
-		]);
-
-		self.request(RobloxApi::Users, "/v1/users")
-			.method(Method::POST)
-			.send_body::<_, DataResponse<PartialUser>>(body)
+		let mut users = Vec::new();
+
+		for chunk in user_ids.chunks(USERS_BATCH_SIZE) {
+			let body = HashMap::from([
+				("excludeBannedUsers", Value::from(exclude_banned)),
+				("userIds", Value::from(chunk)),
+			]);
+
+			let res = self
+				.request(RobloxApi::Users, "/v1/users")
+				.method(Method::POST)
+				.idempotency(Idempotency::Idempotent)
+				.read_only()
+				.send_body::<_, DataResponse<PartialUser>>(body)
+				.await?;
+
+			users.extend(res.data);
+		}
+
+		Ok(users)
+	}
+
+	/// Fetches the users with the given IDs keyed by ID, along with the IDs
+	/// that were not found
+	///
+	/// An ID is not found if the account does not exist or has been
+	/// terminated, or if it is banned and `exclude_banned` is set.
+	pub async fn lookup_users(
+		&self,
+		user_ids: Vec<u64>,
+		exclude_banned: bool,
+	) -> Result<UserLookup<u64>, RoboltError> {
+		let found = self
+			.users_from_ids(user_ids.clone(), exclude_banned)
+			.await?
+			.into_iter()
+			.map(|user| (user.id, user))
+			.collect::<HashMap<u64, PartialUser>>();
+
+		let mut not_found = Vec::new();
+
+		for user_id in user_ids {
+			if !found.contains_key(&user_id) && !not_found.contains(&user_id) {
+				not_found.push(user_id);
+			}
+		}
+
+		Ok(UserLookup {
+			found,
+			not_found,
+		})
+	}
+
+	/// Fetches every previous username of a user, oldest first
+	///
+	/// Stops after [Robolt::max_pages_per_crawl] pages, see
+	/// [Robolt::username_history_pages] to page through the history instead.
+	pub async fn username_history(&self, user_id: u64) -> Result<Vec<String>, RoboltError>
+	where
+		State: Sync,
+	{
+		self.username_history_pages(user_id, Limit::Max, SortOrder::Asc)
+			.collect_all()
 			.await
-			.map(|res| res.data)
+			.map(|collected| collected.items)
 	}
 
-	pub async fn username_history(&self, user_id: u64) -> Result<Vec<String>, RoboltError> {
-		self.request(RobloxApi::Users, format!("/v1/users/{user_id}/username-history"))
-			.send::<DataResponse<String>>()
+	pub async fn username_history_page(
+		&self,
+		user_id: u64,
+		limit: Limit,
+		sort_order: SortOrder,
+		cursor: Option<&str>,
+	) -> Result<Page<String>, RoboltError> {
+		let mut path = format!(
+			"/v1/users/{user_id}/username-history?limit={}&sortOrder={sort_order}",
+			limit as u8
+		);
+
+		if let Some(cursor) = cursor {
+			path.push_str(&format!("&cursor={cursor}"));
+		}
+
+		self.request(RobloxApi::Users, path)
+			.resource(ResourceKind::User, user_id)
+			.send()
 			.await
-			.map(|res| res.data)
+	}
+
+	/// Pages through the previous usernames of a user
+	pub fn username_history_pages(
+		&self,
+		user_id: u64,
+		limit: Limit,
+		sort_order: SortOrder,
+	) -> PageIterator<'_, String>
+	where
+		State: Sync,
+	{
+		PageIterator::new(CursorPaging::new(), move |cursor| async move {
+			self.username_history_page(user_id, limit, sort_order, cursor.as_deref())
+				.await
+		})
+		.max_pages(self.max_pages_per_crawl)
 	}
 
 	pub async fn validate_display_name(&self, display_name: &str, date_of_birth: &str) -> Result<(), RoboltError> {
@@ -68,6 +227,81 @@ impl<State> Robolt<State> {
 
 		Ok(())
 	}
+
+	/// Fetches the users with the given usernames, in any order
+	///
+	/// Usernames are requested in batches of 100, so any number of usernames
+	/// can be passed. Users that do not exist are missing from the result, see
+	/// [Robolt::lookup_usernames] to tell which.
+	pub async fn users_from_usernames(
+		&self,
+		usernames: Vec<&str>,
+		exclude_banned: bool,
+	) -> Result<Vec<PartialUser>, RoboltError> {
+		self.requested_usernames(usernames, exclude_banned)
+			.await
+			.map(|users| users.into_iter().map(|user| user.user).collect())
+	}
+
+	/// Fetches the users with the given usernames keyed by the username as
+	/// passed, along with the usernames that were not found
+	///
+	/// Usernames are matched case-insensitively, the casing of the account
+	/// being [PartialUser::username]. A username is not found if no account
+	/// has it, or if its account is banned and `exclude_banned` is set.
+	pub async fn lookup_usernames(
+		&self,
+		usernames: Vec<&str>,
+		exclude_banned: bool,
+	) -> Result<UserLookup<String>, RoboltError> {
+		let users = self.requested_usernames(usernames.clone(), exclude_banned).await?;
+		let mut lookup = UserLookup::default();
+
+		for username in usernames {
+			let user = users
+				.iter()
+				.find(|user| user.requested_username.eq_ignore_ascii_case(username));
+
+			match user {
+				Some(user) => {
+					lookup.found.insert(username.to_string(), user.user.clone());
+				},
+				None if !lookup.not_found.iter().any(|missing| missing == username) => {
+					lookup.not_found.push(username.to_string());
+				},
+				None => {},
+			}
+		}
+
+		Ok(lookup)
+	}
+
+	async fn requested_usernames(
+		&self,
+		usernames: Vec<&str>,
+		exclude_banned: bool,
+	) -> Result<Vec<RequestedUser>, RoboltError> {
+		let mut users = Vec::new();
+
+		for chunk in usernames.chunks(USERS_BATCH_SIZE) {
+			let body = HashMap::from([
+				("excludeBannedUsers", Value::from(exclude_banned)),
+				("usernames", Value::from(chunk)),
+			]);
+
+			let res = self
+				.request(RobloxApi::Users, "/v1/usernames/users")
+				.method(Method::POST)
+				.idempotency(Idempotency::Idempotent)
+				.read_only()
+				.send_body::<_, DataResponse<RequestedUser>>(body)
+				.await?;
+
+			users.extend(res.data);
+		}
+
+		Ok(users)
+	}
 }
 
 impl Robolt<Authenticated> {
@@ -75,24 +309,94 @@ impl Robolt<Authenticated> {
 		self.request(RobloxApi::Users, "/v1/users/authenticated").send().await
 	}
 
-	pub async fn users_from_usernames(
-		&self,
-		usernames: Vec<&str>,
-		exclude_banned: bool,
-	) -> Result<Vec<PartialUser>, RoboltError> {
-		let body = HashMap::from([
-			("excludeBannedUsers", Value::from(exclude_banned)),
-			("usernames", Value::from(usernames)),
-		]);
-
-		self.request(RobloxApi::Users, "/v1/usernames/users")
-			.method(Method::POST)
-			.send_body::<_, DataResponse<PartialUser>>(body)
+	/// The authenticated user, fetched with [Robolt::me] on the first call
+	/// and reused afterwards
+	///
+	/// Clients returned by [Robolt::login] have already fetched it.
+	pub async fn authenticated_user(&self) -> Result<PartialUser, RoboltError> {
+		let cached = self.user.lock().unwrap_or_else(PoisonError::into_inner).clone();
+
+		if let Some(user) = cached {
+			return Ok(user);
+		}
+
+		let user = self.me().await?;
+		*self.user.lock().unwrap_or_else(PoisonError::into_inner) = Some(user.clone());
+
+		Ok(user)
+	}
+
+	/// The ID of the authenticated user, see [Robolt::authenticated_user]
+	pub async fn authenticated_user_id(&self) -> Result<u64, RoboltError> {
+		self.authenticated_user().await.map(|user| user.id)
+	}
+
+	/// Changes the display name of the authenticated user
+	///
+	/// The display name can only be changed once every 7 days, fails with
+	/// [RoboltErrorKind::DisplayNameCooldown] until then.
+	pub async fn set_display_name(&self, display_name: &str) -> Result<(), RoboltError> {
+		let user_id = self.authenticated_user_id().await?;
+		let body = HashMap::from([("newDisplayName", display_name)]);
+
+		self.request(RobloxApi::Users, format!("/v1/users/{user_id}/display-names"))
+			.method(Method::PATCH)
+			.invalidates(RobloxApi::Users, format!("/v1/users/{user_id}"))
+			.idempotency(Idempotency::NonIdempotent)
+			.send_body::<_, EmptyResponse>(body)
 			.await
-			.map(|res| res.data)
+			.map_err(|err| match err.code {
+				DISPLAY_NAME_COOLDOWN_CODE => err.with_kind(RoboltErrorKind::DisplayNameCooldown),
+				_ => err,
+			})?;
+
+		Ok(())
+	}
+}
+
+impl User {
+	/// Classifies the account based on its ban flag and whether its name has
+	/// been replaced with a moderation placeholder
+	///
+	/// Terminated accounts are not covered, as fetching them fails with a 404.
+	pub fn moderation_state(&self) -> ModerationState {
+		if is_placeholder_name(&self.username) || is_placeholder_name(&self.display_name) {
+			ModerationState::ContentDeleted
+		} else if self.is_banned {
+			ModerationState::Banned
+		} else {
+			ModerationState::Active
+		}
+	}
+
+	/// When the account was created, e.g. to compute its age
+	#[cfg(feature = "chrono")]
+	#[doc(cfg(feature = "chrono"))]
+	pub fn created_at(&self) -> Result<DateTime<Utc>, RoboltError> {
+		timestamp::parse(&self.created)
 	}
 }
 
+/// Checks whether a name or description is a moderation placeholder such as
+/// `[ Content Deleted ]`
+pub fn is_placeholder_name(name: &str) -> bool {
+	let name = name.trim();
+
+	match name.strip_prefix('[').and_then(|name| name.strip_suffix(']')) {
+		Some(inner) => inner.trim().to_lowercase().starts_with("content deleted"),
+		None => false,
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModerationState {
+	Active,
+	/// The account is banned but its details are still visible
+	Banned,
+	/// The account's name has been replaced with a placeholder by moderation
+	ContentDeleted,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
@@ -101,18 +405,55 @@ pub struct User {
 	pub external_app_display_name: Option<String>,
 	pub description: Option<String>,
 	pub created: String,
+	#[serde(default)]
 	pub is_banned: bool,
+	#[serde(default)]
 	pub has_verified_badge: bool,
 	pub id: u64,
 	pub display_name: String,
 }
 
+#[derive(Debug, Clone)]
+/// The users found by a batch lookup keyed by what was requested, see
+/// [Robolt::lookup_users] and [Robolt::lookup_usernames]
+pub struct UserLookup<K> {
+	pub found: HashMap<K, PartialUser>,
+	/// The requested IDs or usernames that no user was returned for
+	pub not_found: Vec<K>,
+}
+
+impl<K: Eq + Hash> PartialEq for UserLookup<K> {
+	fn eq(&self, other: &Self) -> bool {
+		self.found == other.found && self.not_found == other.not_found
+	}
+}
+
+impl<K> Default for UserLookup<K> {
+	fn default() -> Self {
+		Self {
+			found: HashMap::new(),
+			not_found: Vec::new(),
+		}
+	}
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PartialUser {
 	#[serde(alias = "userId")]
 	pub id: u64,
-	#[serde(rename = "name")]
+	#[serde(rename = "name", alias = "username")]
 	pub username: String,
+	#[serde(default)]
 	pub display_name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A user returned by a username lookup, along with the username it was
+/// requested with
+struct RequestedUser {
+	requested_username: String,
+	#[serde(flatten)]
+	user: PartialUser,
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use std::process::ExitCode;
+
+use robolt::cli::{self, Cli, CliError};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+	let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+	let command = match cli::parse(&args) {
+		Ok(command) => command,
+		Err(err) => {
+			eprintln!("{err}\n\n{}", cli::USAGE);
+			return ExitCode::from(CliError::from(err).exit_code());
+		},
+	};
+
+	match Cli::from_env().run(&command, &mut std::io::stdout().lock()).await {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(err) => {
+			eprintln!("{err}");
+			ExitCode::from(err.exit_code())
+		},
+	}
+}
@@ -1,7 +1,28 @@
-#![feature(doc_cfg, core_intrinsics)]
+#![feature(doc_cfg)]
 
-pub use utils::client::Robolt;
-pub use utils::errors;
+#[cfg(feature = "cli")]
+pub use utils::cli;
+pub use utils::client::{AuthenticatedClient, Client, HttpConfig, Robolt};
+#[cfg(feature = "test-util")]
+pub use utils::test_util;
+#[cfg(feature = "chrono")]
+pub use utils::timestamp;
+pub use utils::{
+	analysis,
+	batch,
+	cache,
+	csv,
+	dry_run,
+	errors,
+	middleware,
+	moderation,
+	paging,
+	raw,
+	retry,
+	schema,
+	snapshots,
+	time,
+};
 
 pub mod api;
 mod utils;
@@ -0,0 +1,79 @@
+//! Trend analysis of limited sales charts
+//!
+//! Every helper expects the points oldest first and evenly spaced, as returned
+//! by [ResaleData::price_points](crate::api::economy::ResaleData::price_points).
+
+use crate::api::economy::PricePoint;
+
+/// The average price of every `window` consecutive points
+///
+/// The `n`th value averages `points[n..n + window]`, so there are
+/// `points.len() - window + 1` values. Returns nothing if `window` is `0` or
+/// larger than the number of points.
+pub fn moving_average(points: &[PricePoint], window: usize) -> Vec<f64> {
+	if window == 0 {
+		return Vec::new();
+	}
+
+	points
+		.windows(window)
+		.map(|window| window.iter().map(price).sum::<f64>() / window.len() as f64)
+		.collect()
+}
+
+/// The standard deviation of the relative price change between consecutive
+/// points, e.g. `0.1` for prices that swing by about 10% a day
+///
+/// Changes from a price of `0` are skipped. Returns `None` if there are no
+/// price changes to measure.
+pub fn volatility(points: &[PricePoint]) -> Option<f64> {
+	let changes = points
+		.windows(2)
+		.filter(|pair| pair[0].price.0 > 0)
+		.map(|pair| (price(&pair[1]) - price(&pair[0])) / price(&pair[0]))
+		.collect::<Vec<f64>>();
+
+	if changes.is_empty() {
+		return None;
+	}
+
+	let count = changes.len() as f64;
+	let mean = changes.iter().sum::<f64>() / count;
+	let variance = changes.iter().map(|change| (change - mean).powi(2)).sum::<f64>() / count;
+
+	Some(variance.sqrt())
+}
+
+/// The price one point after the last, extrapolated from a least squares
+/// linear fit of the whole chart
+///
+/// The projection never drops below `0`. Returns `None` for an empty chart.
+pub fn projected_value(points: &[PricePoint]) -> Option<f64> {
+	if points.is_empty() {
+		return None;
+	}
+
+	let count = points.len() as f64;
+	let mean_x = (count - 1.0) / 2.0;
+	let mean_y = points.iter().map(price).sum::<f64>() / count;
+
+	let (covariance, variance) = points
+		.iter()
+		.enumerate()
+		.map(|(x, point)| (x as f64 - mean_x, price(point) - mean_y))
+		.fold((0.0, 0.0), |(covariance, variance), (dx, dy)| {
+			(covariance + dx * dy, variance + dx * dx)
+		});
+
+	// A single point has no slope
+	let slope = match points.len() {
+		1 => 0.0,
+		_ => covariance / variance,
+	};
+
+	Some((mean_y + slope * (count - mean_x)).max(0.0))
+}
+
+fn price(point: &PricePoint) -> f64 {
+	point.price.0 as f64
+}
@@ -0,0 +1,217 @@
+use std::future::Future;
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+
+use crate::utils::errors::{RoboltError, RoboltErrorKind};
+use crate::utils::time::{Clock, Sleeper, SystemClock};
+
+#[derive(Debug)]
+/// The outcome of a bulk operation, per target
+pub struct BatchReport<K> {
+	pub succeeded: Vec<K>,
+	pub failed: Vec<(K, RoboltError)>,
+	/// Targets that did not need any action
+	pub skipped: Vec<K>,
+	/// Whether this report describes a dry run, in which case `succeeded`
+	/// lists the targets that would have been acted on
+	///
+	/// Also set when a call failed with [RoboltErrorKind::DryRunSkipped], see
+	/// [dry_run](crate::dry_run).
+	pub dry_run: bool,
+}
+
+impl<K> Default for BatchReport<K> {
+	fn default() -> Self {
+		Self {
+			succeeded: Vec::new(),
+			failed: Vec::new(),
+			skipped: Vec::new(),
+			dry_run: false,
+		}
+	}
+}
+
+impl<K> BatchReport<K> {
+	pub fn is_success(&self) -> bool {
+		self.failed.is_empty()
+	}
+
+	pub fn len(&self) -> usize {
+		self.succeeded.len() + self.failed.len() + self.skipped.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+/// Runs `f` for every target with at most `concurrency` calls in flight,
+/// collecting the results into a [BatchReport] in input order
+///
+/// Calls skipped by a [dry run](crate::dry_run) are reported as succeeded.
+pub async fn run<K, I, F, Fut>(targets: I, concurrency: usize, f: F) -> BatchReport<K>
+where
+	K: Clone,
+	I: IntoIterator<Item = K>,
+	F: Fn(K) -> Fut,
+	Fut: Future<Output = Result<(), RoboltError>>,
+{
+	let results = stream::iter(targets)
+		.map(|target| {
+			let fut = f(target.clone());
+			async move { (target, fut.await) }
+		})
+		.buffered(concurrency.max(1))
+		.collect::<Vec<_>>()
+		.await;
+
+	let mut report = BatchReport::default();
+
+	for (target, res) in results {
+		match res {
+			Ok(()) => report.succeeded.push(target),
+			Err(err) if err.kind() == &RoboltErrorKind::DryRunSkipped => {
+				report.dry_run = true;
+				report.succeeded.push(target);
+			},
+			Err(err) => report.failed.push((target, err)),
+		}
+	}
+
+	report
+}
+
+/// Keeps track of the delay between calls made by [drip]
+///
+/// The delay doubles whenever a call is rate limited (up to 16 times the base
+/// delay) and shrinks back towards the base delay by a quarter on every
+/// successful call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pacer {
+	base: Duration,
+	delay: Duration,
+}
+
+impl Pacer {
+	const MAX_SLOWDOWN: u32 = 16;
+
+	/// Spreads `count` calls evenly over `window`
+	pub fn new(count: usize, window: Duration) -> Self {
+		let base = window / (count.max(1) as u32);
+
+		Self {
+			base,
+			delay: base,
+		}
+	}
+
+	pub fn delay(&self) -> Duration {
+		self.delay
+	}
+
+	pub fn on_success(&mut self) {
+		self.delay = (self.delay * 3 / 4).max(self.base);
+	}
+
+	pub fn on_rate_limited(&mut self) {
+		self.delay = (self.delay * 2).min(self.base * Self::MAX_SLOWDOWN);
+	}
+}
+
+#[derive(Debug)]
+/// The outcome of [drip]
+pub struct DripReport<K, T = ()> {
+	pub report: BatchReport<K>,
+	/// The value returned for each target that succeeded, in input order,
+	/// without the targets skipped by a [dry run](crate::dry_run)
+	pub values: Vec<(K, T)>,
+	/// The number of calls that failed with [RoboltErrorKind::RateLimited]
+	pub rate_limited: usize,
+	pub elapsed: Duration,
+}
+
+impl<K, T> DripReport<K, T> {
+	/// The achieved rate, in calls per second
+	pub fn rate(&self) -> f64 {
+		match self.elapsed.as_secs_f64() {
+			secs if secs > 0.0 => self.report.len() as f64 / secs,
+			_ => 0.0,
+		}
+	}
+}
+
+/// Runs `f` for every target one at a time, spreading the calls evenly over
+/// `window` and backing off whenever a call is rate limited
+///
+/// Each call starts [Pacer::delay] after the previous one started, so slow
+/// calls do not stretch the run past `window`. The values returned by
+/// successful calls are kept in [DripReport::values].
+///
+/// Rate limited targets are reported as failed rather than retried, and
+/// calls skipped by a [dry run](crate::dry_run) are reported as succeeded.
+pub async fn drip<K, T, F, Fut>(targets: Vec<K>, window: Duration, f: F) -> DripReport<K, T>
+where
+	K: Clone,
+	F: Fn(K) -> Fut,
+	Fut: Future<Output = Result<T, RoboltError>>,
+{
+	drip_with(targets, window, &SystemClock, f).await
+}
+
+/// [drip] on a custom clock, which is used both to wait between calls and to
+/// measure the elapsed time
+pub async fn drip_with<K, T, C, F, Fut>(targets: Vec<K>, window: Duration, clock: &C, f: F) -> DripReport<K, T>
+where
+	K: Clone,
+	C: Clock + Sleeper + ?Sized,
+	F: Fn(K) -> Fut,
+	Fut: Future<Output = Result<T, RoboltError>>,
+{
+	let mut pacer = Pacer::new(targets.len(), window);
+	let mut report = BatchReport::default();
+	let mut values = Vec::new();
+	let mut rate_limited = 0;
+	let started = clock.now();
+	let mut last_call = started;
+
+	for (i, target) in targets.into_iter().enumerate() {
+		if i > 0 {
+			// The time spent on the previous call counts towards the delay
+			let wait = (last_call + pacer.delay()).saturating_duration_since(clock.now());
+
+			if !wait.is_zero() {
+				clock.sleep(wait).await;
+			}
+		}
+
+		last_call = clock.now();
+
+		match f(target.clone()).await {
+			Ok(value) => {
+				pacer.on_success();
+				report.succeeded.push(target.clone());
+				values.push((target, value));
+			},
+			Err(err) if err.kind() == &RoboltErrorKind::DryRunSkipped => {
+				report.dry_run = true;
+				report.succeeded.push(target);
+			},
+			Err(err) => {
+				if err.kind() == &RoboltErrorKind::RateLimited {
+					rate_limited += 1;
+					pacer.on_rate_limited();
+				}
+
+				report.failed.push((target, err));
+			},
+		}
+	}
+
+	DripReport {
+		report,
+		values,
+		rate_limited,
+		elapsed: clock.now().saturating_duration_since(started),
+	}
+}
@@ -0,0 +1,232 @@
+//! Caching the responses of read endpoints
+//!
+//! With a [Cache] set through [Robolt::cache](crate::Robolt::cache), the body
+//! of every successful `GET` response is stored under its URL and reused
+//! until its TTL expires, so fetching the same user or group repeatedly only
+//! reaches Roblox once per TTL. Other methods and failed responses are never
+//! cached, and clients without a cache send every request as usual.
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use robolt::cache::MemoryCache;
+//! # async fn run() -> Result<(), robolt::errors::RoboltError> {
+//! let client = robolt::Robolt::new().cache(MemoryCache::new(1000), Duration::from_secs(60));
+//!
+//! client.user(1).await?;
+//! client.partial_user(1).await?;
+//!
+//! assert_eq!(client.cache_stats().hits, 1);
+//!
+//! // Fetch the latest state, caching it for the next calls
+//! client.bypass_cache().user(1).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Cached responses skip the [middleware](crate::middleware) and retries, as
+//! no request is sent. Clones of a client share its cache, including the
+//! authenticated client returned by
+//! [Robolt::set_cookie](crate::Robolt::set_cookie). As the cache is keyed by
+//! URL alone, responses to requests sending the `.ROBLOSECURITY` cookie or an
+//! API key are never cached, since they depend on the session.
+//!
+//! Successful writes remove the cached responses they make stale, e.g.
+//! changing the display name of the authenticated user removes their cached
+//! profile.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::utils::time::{Clock, SystemClock};
+
+/// Stores response bodies by key, the URL of the request
+pub trait Cache: Debug + Send + Sync {
+	/// The value stored under `key`, `None` if there is none or it has expired
+	fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+	/// Stores `value` under `key` for `ttl`, replacing any previous value
+	fn put(&self, key: &str, value: Vec<u8>, ttl: Duration);
+
+	fn remove(&self, key: &str);
+
+	/// The number of values currently stored
+	fn len(&self) -> usize;
+
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How a client's cache has been used, see
+/// [Robolt::cache_stats](crate::Robolt::cache_stats)
+pub struct CacheStats {
+	/// The requests answered from the cache
+	pub hits: u64,
+	/// The cacheable requests that were sent because nothing fresh was cached
+	pub misses: u64,
+	pub entries: usize,
+}
+
+#[derive(Debug)]
+/// An in-memory [Cache] holding at most `capacity` values, evicting the least
+/// recently used one when full
+pub struct MemoryCache {
+	capacity: usize,
+	clock: Arc<dyn Clock>,
+	entries: Mutex<MemoryEntries>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryEntries {
+	values: HashMap<String, MemoryEntry>,
+	/// Incremented on every access, to order entries by last use
+	tick: u64,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+	value: Vec<u8>,
+	expires_at: Instant,
+	last_used: u64,
+}
+
+impl MemoryCache {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity: capacity.max(1),
+			clock: Arc::new(SystemClock),
+			entries: Mutex::default(),
+		}
+	}
+
+	/// Reads the time from `clock` instead of the system clock to expire
+	/// values
+	pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+		self.clock = Arc::new(clock);
+		self
+	}
+}
+
+impl Cache for MemoryCache {
+	fn get(&self, key: &str) -> Option<Vec<u8>> {
+		let now = self.clock.now();
+		let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+		entries.tick += 1;
+		let tick = entries.tick;
+
+		if entries.values.get(key)?.expires_at <= now {
+			entries.values.remove(key);
+			return None;
+		}
+
+		let entry = entries.values.get_mut(key)?;
+		entry.last_used = tick;
+		Some(entry.value.clone())
+	}
+
+	fn put(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+		let now = self.clock.now();
+		let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+		entries.tick += 1;
+		let tick = entries.tick;
+
+		if !entries.values.contains_key(key) && entries.values.len() >= self.capacity {
+			entries.values.retain(|_, entry| entry.expires_at > now);
+		}
+
+		if !entries.values.contains_key(key) && entries.values.len() >= self.capacity {
+			let oldest = entries
+				.values
+				.iter()
+				.min_by_key(|(_, entry)| entry.last_used)
+				.map(|(key, _)| key.clone());
+
+			if let Some(oldest) = oldest {
+				entries.values.remove(&oldest);
+			}
+		}
+
+		entries.values.insert(key.to_string(), MemoryEntry {
+			value,
+			expires_at: now + ttl,
+			last_used: tick,
+		});
+	}
+
+	fn remove(&self, key: &str) {
+		self.entries
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.values
+			.remove(key);
+	}
+
+	fn len(&self) -> usize {
+		self.entries.lock().unwrap_or_else(PoisonError::into_inner).values.len()
+	}
+}
+
+#[derive(Debug, Clone)]
+/// The cache of a client, counting its hits and misses
+pub(crate) struct ResponseCache {
+	cache: Arc<dyn Cache>,
+	ttl: Duration,
+	/// Whether cached values are ignored, fresh responses still being stored
+	bypass: bool,
+	hits: Arc<AtomicU64>,
+	misses: Arc<AtomicU64>,
+}
+
+impl ResponseCache {
+	pub(crate) fn new(cache: Arc<dyn Cache>, ttl: Duration) -> Self {
+		Self {
+			cache,
+			ttl,
+			bypass: false,
+			hits: Arc::default(),
+			misses: Arc::default(),
+		}
+	}
+
+	pub(crate) fn bypassed(&self) -> Self {
+		Self {
+			bypass: true,
+			..self.clone()
+		}
+	}
+
+	pub(crate) fn get(&self, key: &str) -> Option<Vec<u8>> {
+		if self.bypass {
+			return None;
+		}
+
+		let value = self.cache.get(key);
+
+		match value {
+			Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+			None => self.misses.fetch_add(1, Ordering::Relaxed),
+		};
+
+		value
+	}
+
+	pub(crate) fn put(&self, key: &str, value: Vec<u8>) {
+		self.cache.put(key, value, self.ttl);
+	}
+
+	pub(crate) fn remove(&self, key: &str) {
+		self.cache.remove(key);
+	}
+
+	pub(crate) fn stats(&self) -> CacheStats {
+		CacheStats {
+			hits: self.hits.load(Ordering::Relaxed),
+			misses: self.misses.load(Ordering::Relaxed),
+			entries: self.cache.len(),
+		}
+	}
+}
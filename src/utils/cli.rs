@@ -0,0 +1,503 @@
+//! The `robolt-cli` binary, a small group management tool built on the library
+//!
+//! Commands are parsed with [parse] and run by [Cli::run], which writes its
+//! output to any writer so both can be driven without a terminal. Failures
+//! are reported as a [CliError], whose [CliError::exit_code] depends on the
+//! class of the error:
+//!
+//! | Code | Error                                            |
+//! |------|--------------------------------------------------|
+//! | 1    | Any other error                                  |
+//! | 2    | Invalid arguments                                |
+//! | 3    | Missing or rejected credentials                  |
+//! | 4    | The user, group or role does not exist           |
+//! | 5    | Not allowed, e.g. missing permissions or locked  |
+//! | 6    | Rate limited, or the display name is on cooldown |
+//!
+//! The `.ROBLOSECURITY` cookie and the Open Cloud API key are read from the
+//! `ROBLOSECURITY` and `ROBLOX_API_KEY` environment variables.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::api::auth::RobloxCookie;
+use crate::api::groups::{AuditLogEntry, GroupMember, GroupRole};
+use crate::api::thumbnails::{ThumbnailRequest, ThumbnailSize, ThumbnailState, ThumbnailType};
+use crate::api::users::PartialUser;
+use crate::api::Limit;
+use crate::errors::{RoboltError, RoboltErrorKind};
+use crate::paging::{CursorPaging, PageIterator};
+use crate::utils::client::Authenticated;
+use crate::Robolt;
+
+pub const COOKIE_VAR: &str = "ROBLOSECURITY";
+pub const API_KEY_VAR: &str = "ROBLOX_API_KEY";
+
+const THUMBNAIL_SIZE: ThumbnailSize = ThumbnailSize(420, 420);
+const THUMBNAIL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const THUMBNAIL_MAX_WAIT: Duration = Duration::from_secs(30);
+
+pub const USAGE: &str = "Usage: robolt-cli <command>
+
+Commands:
+  user lookup <username>               Looks up a user by username
+  group rank <group> <user> <role>     Changes the role of a member, the user being an ID
+                                       or username and the role an ID, rank or name
+  group audit <group> [--since <date>] Lists the audit log of a group, newest first
+  export members <group> [--csv]       Lists the members of a group
+  thumbnail <user> --out <file>        Saves the avatar thumbnail of a user
+  help                                 Shows this message
+
+Environment:
+  ROBLOSECURITY    The .ROBLOSECURITY cookie, required by group rank and group audit
+  ROBLOX_API_KEY   An Open Cloud API key";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+	UserLookup {
+		username: String,
+	},
+	GroupRank {
+		group_id: u64,
+		user: Target,
+		role: Target,
+	},
+	GroupAudit {
+		group_id: u64,
+		/// Only entries created at or after this date, as `YYYY-MM-DD` or an
+		/// RFC 3339 timestamp
+		since: Option<String>,
+	},
+	ExportMembers {
+		group_id: u64,
+		csv: bool,
+	},
+	Thumbnail {
+		user_id: u64,
+		out: PathBuf,
+	},
+	Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A user or role given either by ID or by name
+pub enum Target {
+	Id(u64),
+	Name(String),
+}
+
+impl Target {
+	fn parse(value: &str) -> Self {
+		match value.parse() {
+			Ok(id) => Self::Id(id),
+			Err(_) => Self::Name(value.to_string()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl Error for UsageError {}
+
+/// Parses the arguments of the binary, without the program name
+pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Command, UsageError> {
+	let args = args.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+	match args.as_slice() {
+		[] | ["help" | "--help" | "-h"] => Ok(Command::Help),
+		["user", "lookup", username] => Ok(Command::UserLookup {
+			username: username.to_string(),
+		}),
+		["group", "rank", group, user, role] => Ok(Command::GroupRank {
+			group_id: parse_id("group", group)?,
+			user: Target::parse(user),
+			role: Target::parse(role),
+		}),
+		["group", "audit", group, options @ ..] => {
+			let mut since = None;
+
+			for (flag, value) in flags(options)? {
+				match (flag, value) {
+					("--since", Some(value)) => since = Some(parse_date(value)?),
+					_ => return Err(unknown_flag("group audit", flag)),
+				}
+			}
+
+			Ok(Command::GroupAudit {
+				group_id: parse_id("group", group)?,
+				since,
+			})
+		},
+		["export", "members", group, options @ ..] => {
+			let mut csv = false;
+
+			for (flag, value) in flags(options)? {
+				match (flag, value) {
+					("--csv", None) => csv = true,
+					_ => return Err(unknown_flag("export members", flag)),
+				}
+			}
+
+			Ok(Command::ExportMembers {
+				group_id: parse_id("group", group)?,
+				csv,
+			})
+		},
+		["thumbnail", user, options @ ..] => {
+			let mut out = None;
+
+			for (flag, value) in flags(options)? {
+				match (flag, value) {
+					("--out", Some(value)) => out = Some(PathBuf::from(value)),
+					_ => return Err(unknown_flag("thumbnail", flag)),
+				}
+			}
+
+			Ok(Command::Thumbnail {
+				user_id: parse_id("user", user)?,
+				out: out.ok_or_else(|| UsageError("thumbnail requires --out <file>".to_string()))?,
+			})
+		},
+		_ => Err(UsageError(format!("Unknown command: {}", args.join(" ")))),
+	}
+}
+
+/// Splits options into flags and their values, flags taking a value being
+/// followed by it
+fn flags<'a>(options: &[&'a str]) -> Result<Vec<(&'a str, Option<&'a str>)>, UsageError> {
+	let mut flags = Vec::new();
+	let mut options = options.iter();
+
+	while let Some(flag) = options.next() {
+		if !flag.starts_with("--") {
+			return Err(UsageError(format!("Unexpected argument: {flag}")));
+		}
+
+		let value = match *flag {
+			"--since" | "--out" => Some(
+				*options
+					.next()
+					.ok_or_else(|| UsageError(format!("{flag} requires a value")))?,
+			),
+			_ => None,
+		};
+
+		flags.push((*flag, value));
+	}
+
+	Ok(flags)
+}
+
+fn parse_id(name: &str, value: &str) -> Result<u64, UsageError> {
+	value
+		.parse()
+		.map_err(|_| UsageError(format!("Invalid {name} ID: {value}")))
+}
+
+/// Checks that a date starts with `YYYY-MM-DD`, which is enough to compare it
+/// with the timestamps returned by Roblox
+fn parse_date(value: &str) -> Result<String, UsageError> {
+	let bytes = value.as_bytes();
+	let is_date = bytes.len() >= 10 &&
+		bytes[..10].iter().enumerate().all(|(i, byte)| match i {
+			4 | 7 => *byte == b'-',
+			_ => byte.is_ascii_digit(),
+		});
+
+	if !is_date {
+		return Err(UsageError(format!("Invalid date, expected YYYY-MM-DD: {value}")));
+	}
+
+	Ok(value.to_string())
+}
+
+fn unknown_flag(command: &str, flag: &str) -> UsageError {
+	UsageError(format!("Unknown option for {command}: {flag}"))
+}
+
+#[derive(Debug)]
+pub enum CliError {
+	Usage(UsageError),
+	/// An environment variable required by the command is not set
+	MissingCredentials(&'static str),
+	/// The cookie was rejected
+	Auth(String),
+	/// A user, group or role was not found by name
+	NotFound(String),
+	Api(RoboltError),
+	Io(io::Error),
+}
+
+impl CliError {
+	pub fn exit_code(&self) -> u8 {
+		match self {
+			Self::Usage(_) => 2,
+			Self::MissingCredentials(_) | Self::Auth(_) => 3,
+			Self::NotFound(_) => 4,
+			Self::Api(err) => match err.kind() {
+				RoboltErrorKind::NotFound {
+					..
+				} => 4,
+				RoboltErrorKind::PermissionDenied |
+				RoboltErrorKind::GroupLocked |
+				RoboltErrorKind::AgeRestricted |
+				RoboltErrorKind::MessageNotSent |
+				RoboltErrorKind::ChallengeRequired {
+					..
+				} |
+				RoboltErrorKind::HostNotAllowed => 5,
+				RoboltErrorKind::RateLimited | RoboltErrorKind::DisplayNameCooldown => 6,
+				RoboltErrorKind::InvalidCookie => 3,
+				_ => 1,
+			},
+			Self::Io(_) => 1,
+		}
+	}
+}
+
+impl fmt::Display for CliError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Usage(err) => write!(f, "{err}"),
+			Self::MissingCredentials(var) => write!(f, "The {var} environment variable is not set"),
+			Self::Auth(err) => write!(f, "Failed to authenticate: {err}"),
+			Self::NotFound(err) => write!(f, "{err}"),
+			Self::Api(err) => write!(f, "{err}"),
+			Self::Io(err) => write!(f, "{err}"),
+		}
+	}
+}
+
+impl Error for CliError {}
+
+impl From<UsageError> for CliError {
+	fn from(err: UsageError) -> Self {
+		Self::Usage(err)
+	}
+}
+
+impl From<RoboltError> for CliError {
+	fn from(err: RoboltError) -> Self {
+		Self::Api(err)
+	}
+}
+
+impl From<io::Error> for CliError {
+	fn from(err: io::Error) -> Self {
+		Self::Io(err)
+	}
+}
+
+#[derive(Debug, Clone)]
+/// Runs commands with a client, authenticating it with the cookie for the
+/// commands that need it
+pub struct Cli {
+	client: Robolt,
+	cookie: Option<String>,
+}
+
+impl Cli {
+	pub fn new(client: Robolt) -> Self {
+		Self {
+			client,
+			cookie: None,
+		}
+	}
+
+	/// Builds the client from the environment, see the [module](self)
+	/// documentation
+	pub fn from_env() -> Self {
+		let mut client = Robolt::new().guard_locked_groups(true);
+
+		if let Ok(api_key) = std::env::var(API_KEY_VAR) {
+			client = client.set_api_key(api_key);
+		}
+
+		Self {
+			client,
+			cookie: std::env::var(COOKIE_VAR).ok(),
+		}
+	}
+
+	pub fn cookie(mut self, cookie: &str) -> Self {
+		self.cookie = Some(cookie.to_string());
+		self
+	}
+
+	pub async fn run<W: Write>(&self, command: &Command, out: &mut W) -> Result<(), CliError> {
+		match command {
+			Command::Help => writeln!(out, "{USAGE}")?,
+			Command::UserLookup {
+				username,
+			} => {
+				let user = self.find_user(username).await?;
+				writeln!(out, "{}", format_user(&user))?;
+			},
+			Command::GroupRank {
+				group_id,
+				user,
+				role,
+			} => {
+				let client = self.authenticated().await?;
+				let user_id = match user {
+					Target::Id(id) => *id,
+					Target::Name(name) => self.find_user(name).await?.id,
+				};
+				let roles = client.group_roles(*group_id).await?;
+				let role = find_role(&roles, role)
+					.ok_or_else(|| CliError::NotFound(format!("Group {group_id} has no role {}", target(role))))?;
+
+				client.set_member_role(*group_id, user_id, role.id).await?;
+				writeln!(out, "Changed the role of {user_id} to {} ({})", role.name, role.id)?;
+			},
+			Command::GroupAudit {
+				group_id,
+				since,
+			} => {
+				let client = self.authenticated().await?;
+				let (client, group_id) = (&client, *group_id);
+				let mut pages = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+					client
+						.group_audit_log(group_id, None, Limit::Max, cursor.as_deref())
+						.await
+				})
+				.max_pages(self.client.max_pages_per_crawl);
+
+				// Entries are listed newest first, so the first entry older than
+				// `since` ends the listing
+				'pages: while let Some(page) = pages.next_page().await {
+					for entry in page? {
+						if since.as_ref().is_some_and(|since| entry.created < *since) {
+							break 'pages;
+						}
+
+						writeln!(out, "{}", format_audit_entry(&entry))?;
+					}
+				}
+			},
+			Command::ExportMembers {
+				group_id,
+				csv,
+			} => {
+				let (client, group_id) = (&self.client, *group_id);
+				let mut pages = PageIterator::new(CursorPaging::new(), move |cursor| async move {
+					client.group_members(group_id, Limit::Max, cursor.as_deref()).await
+				})
+				.max_pages(client.max_pages_per_crawl);
+
+				if *csv {
+					return Ok(pages.write_csv(&mut *out).await?);
+				}
+
+				while let Some(page) = pages.next_page().await {
+					for member in page? {
+						writeln!(out, "{}", format_member(&member))?;
+					}
+				}
+			},
+			Command::Thumbnail {
+				user_id,
+				out: path,
+			} => {
+				let request = ThumbnailRequest::new(ThumbnailType::Avatar, *user_id, THUMBNAIL_SIZE);
+				let thumbnails = self
+					.client
+					.resolve_thumbnails(
+						std::slice::from_ref(&request),
+						THUMBNAIL_POLL_INTERVAL,
+						THUMBNAIL_MAX_WAIT,
+					)
+					.await?;
+
+				let url = thumbnails
+					.get(&request)
+					.filter(|thumbnail| thumbnail.state == ThumbnailState::Completed)
+					.and_then(|thumbnail| thumbnail.image_url.clone())
+					.ok_or_else(|| RoboltError::from(format!("The thumbnail of {user_id} is not available")))?;
+
+				let image = self
+					.client
+					.http
+					.get(&url)
+					.send()
+					.await
+					.and_then(|res| res.error_for_status())
+					.map_err(RoboltError::from)?
+					.bytes()
+					.await
+					.map_err(RoboltError::from)?;
+
+				std::fs::write(path, &image)?;
+				writeln!(out, "Saved {} bytes to {}", image.len(), path.display())?;
+			},
+		}
+
+		Ok(())
+	}
+
+	async fn authenticated(&self) -> Result<Robolt<Authenticated>, CliError> {
+		let cookie = self.cookie.as_deref().ok_or(CliError::MissingCredentials(COOKIE_VAR))?;
+		let cookie = RobloxCookie::parse(cookie).map_err(|err| CliError::Auth(err.to_string()))?;
+
+		self.client
+			.clone()
+			.set_cookie(cookie)
+			.await
+			.map_err(|err| CliError::Auth(err.to_string()))
+	}
+
+	async fn find_user(&self, username: &str) -> Result<PartialUser, CliError> {
+		self.client
+			.users_from_usernames(vec![username], false)
+			.await?
+			.into_iter()
+			.next()
+			.ok_or_else(|| CliError::NotFound(format!("No user is named {username}")))
+	}
+}
+
+fn find_role<'a>(roles: &'a [GroupRole], role: &Target) -> Option<&'a GroupRole> {
+	roles.iter().find(|candidate| match role {
+		Target::Id(id) => candidate.id == *id || u64::from(candidate.rank) == *id,
+		Target::Name(name) => candidate.name.eq_ignore_ascii_case(name),
+	})
+}
+
+fn target(target: &Target) -> String {
+	match target {
+		Target::Id(id) => id.to_string(),
+		Target::Name(name) => name.clone(),
+	}
+}
+
+pub fn format_user(user: &PartialUser) -> String {
+	if user.display_name.is_empty() || user.display_name == user.username {
+		return format!("{} ({})", user.username, user.id);
+	}
+
+	format!("{} (@{}, {})", user.display_name, user.username, user.id)
+}
+
+pub fn format_member(member: &GroupMember) -> String {
+	format!(
+		"{}\t{}\t{} ({})",
+		member.user.id, member.user.username, member.role.name, member.role.rank
+	)
+}
+
+pub fn format_audit_entry(entry: &AuditLogEntry) -> String {
+	format!(
+		"{}\t{}\t{}\t{}",
+		entry.created, entry.actor.user.username, entry.action_type, entry.description
+	)
+}
@@ -1,14 +1,42 @@
-use std::error::Error;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::Duration;
 
-use reqwest::header::{HeaderMap, ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, USER_AGENT};
-use reqwest::{Client, Method};
+use reqwest::header::{
+	HeaderMap,
+	HeaderValue,
+	ACCEPT,
+	CONTENT_LENGTH,
+	CONTENT_TYPE,
+	COOKIE,
+	RETRY_AFTER,
+	USER_AGENT,
+};
+use reqwest::{Method, Proxy, Response, StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use crate::api::routes::RobloxApi;
-use crate::utils::errors::{RobloxAPIErrors, RoboltError};
+use crate::api::auth::RobloxCookie;
+use crate::api::routes::{Endpoints, RobloxApi};
+use crate::api::users::PartialUser;
+use crate::utils::cache::{Cache, CacheStats, ResponseCache};
+use crate::utils::dry_run::{PlannedRequest, PlannedRequests};
+use crate::utils::errors::{CloudError, ResourceKind, RobloxAPIErrors, RoboltError};
+use crate::utils::middleware::{Middleware, Middlewares, RequestParts, ResponseMeta};
+use crate::utils::moderation::ModerationLog;
+use crate::utils::retry::{Idempotency, RetryPolicy};
+use crate::utils::schema::SchemaWatch;
+#[cfg(feature = "test-util")]
+use crate::utils::test_util::MockTransport;
+use crate::utils::time::{Clock, Sleeper, SystemClock};
+
+/// The default limit of [Robolt::max_response_size], 8 MiB
+const DEFAULT_MAX_RESPONSE_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_MAX_PAGES_PER_CRAWL: usize = 1000;
+/// The default [HttpConfig::timeout]
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 impl Default for Robolt {
 	fn default() -> Self {
@@ -18,80 +46,566 @@ impl Default for Robolt {
 
 impl Robolt {
 	pub fn new() -> Self {
-		let client = Client::builder()
-			.default_headers(default_client_headers())
-			.build()
-			.expect("Failed to build client");
+		Self::with_http_config(HttpConfig::default()).expect("Failed to build client")
+	}
 
-		Self {
+	/// Creates a client sending its requests with the given HTTP settings,
+	/// which are kept once it is authenticated
+	///
+	/// Fails if the proxy URL or the user agent is invalid.
+	pub fn with_http_config(config: HttpConfig) -> Result<Self, RoboltError> {
+		let client = config.build()?;
+
+		Ok(Self {
 			state: PhantomData::<Unauthenticated>,
 			http: client,
 			cookie: None,
-			xcsrf: None,
-		}
+			xcsrf: Arc::default(),
+			api_key: None,
+			middleware: Middlewares::default(),
+			retry: RetryPolicy::default(),
+			locked_groups: LockedGroups::default(),
+			max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+			max_pages_per_crawl: DEFAULT_MAX_PAGES_PER_CRAWL,
+			schema_watch: None,
+			moderation_log: None,
+			dry_run: None,
+			capture_error_bodies: false,
+			cache: None,
+			endpoints: Endpoints::default(),
+			user: Arc::default(),
+			raw_hosts: Vec::new(),
+			clock: Arc::new(SystemClock),
+			sleeper: Arc::new(SystemClock),
+			#[cfg(feature = "test-util")]
+			mock_transport: None,
+		})
 	}
 
-	pub async fn from(roblox_cookie: String) -> Result<Robolt<Authenticated>, Box<dyn Error>> {
-		Self::new().set_cookie(roblox_cookie).await
+	pub async fn from(roblox_cookie: String) -> Result<Robolt<Authenticated>, RoboltError> {
+		Self::new().set_cookie(RobloxCookie::parse(&roblox_cookie)?).await
 	}
 }
 
 impl<State> Robolt<State> {
+	/// Registers a [Middleware] to run around every request, after any
+	/// previously registered middlewares
+	pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+		self.middleware.0.push(Arc::new(middleware));
+		self
+	}
+
+	/// Sets the Open Cloud API key used for requests to `apis.roblox.com`
+	///
+	/// The key is never sent to any other host.
+	pub fn set_api_key(mut self, api_key: String) -> Self {
+		self.api_key = Some(api_key);
+		self
+	}
+
+	pub fn remove_api_key(mut self) -> Self {
+		self.api_key = None;
+		self
+	}
+
+	/// Sends requests to the given base URLs instead of the Roblox hosts, e.g.
+	/// to a local mock server, see [Endpoints]
+	///
+	/// The cookie, the CSRF token and the Open Cloud API key are sent to
+	/// these URLs as they would be to Roblox. Raw requests are sent to the URL
+	/// they are given.
+	pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+		self.endpoints = endpoints;
+		self
+	}
+
+	/// Makes group write helpers check whether the group is locked before
+	/// sending anything, failing with [RoboltErrorKind::GroupLocked] if it is
+	///
+	/// The locked state of each group is fetched once and cached, and the
+	/// cache is refreshed whenever a group is fetched with [Robolt::group].
+	///
+	/// [RoboltErrorKind::GroupLocked]: crate::errors::RoboltErrorKind::GroupLocked
+	pub fn guard_locked_groups(mut self, guard: bool) -> Self {
+		self.locked_groups.guard = guard;
+		self
+	}
+
+	/// Sets the maximum size of a response body in bytes, 8 MiB by default
+	///
+	/// Larger responses fail with [RoboltErrorKind::ResponseTooLarge] without
+	/// being read into memory.
+	///
+	/// [RoboltErrorKind::ResponseTooLarge]: crate::errors::RoboltErrorKind::ResponseTooLarge
+	pub fn max_response_size(mut self, max_response_size: u64) -> Self {
+		self.max_response_size = max_response_size;
+		self
+	}
+
+	/// Sets the maximum number of pages fetched by the page iterators of the
+	/// client, 1,000 by default
+	///
+	/// Iterators stop once the limit is reached instead of failing, see
+	/// [ExhaustionReason::CrawlTruncated]. The limit can be overridden per
+	/// iterator with [PageIterator::max_pages].
+	///
+	/// [ExhaustionReason::CrawlTruncated]: crate::paging::ExhaustionReason::CrawlTruncated
+	/// [PageIterator::max_pages]: crate::paging::PageIterator::max_pages
+	pub fn max_pages_per_crawl(mut self, max_pages: usize) -> Self {
+		self.max_pages_per_crawl = max_pages;
+		self
+	}
+
+	/// Reports the fields of successful responses that their models did not
+	/// consume, see [SchemaWatch]
+	///
+	/// Meant for debugging, responses are deserialized as usual when no watch
+	/// is set.
+	pub fn schema_watch(mut self, watch: SchemaWatch) -> Self {
+		self.schema_watch = Some(watch);
+		self
+	}
+
+	/// Records the content removed by destructive group actions before it is
+	/// removed, see [ModerationLog]
+	pub fn moderation_log<L: ModerationLog + 'static>(mut self, log: L) -> Self {
+		self.moderation_log = Some(Arc::new(log));
+		self
+	}
+
+	/// Records every request that could change something into `planned`
+	/// instead of sending it, see [dry_run](crate::dry_run)
+	pub fn dry_run(mut self, planned: PlannedRequests) -> Self {
+		self.dry_run = Some(planned);
+		self
+	}
+
+	/// Keeps the start of the body of failed responses, and of responses that
+	/// do not match their model, in the returned errors, see
+	/// [RoboltError::body]
+	///
+	/// Off by default, as bodies can hold personal information that should
+	/// not end up in logs.
+	pub fn capture_error_bodies(mut self, capture: bool) -> Self {
+		self.capture_error_bodies = capture;
+		self
+	}
+
+	/// Serves successful `GET` responses from `cache` for `ttl` after they
+	/// were received, see [cache](crate::cache)
+	///
+	/// Requests sending the `.ROBLOSECURITY` cookie or an API key are never
+	/// cached, so only unauthenticated clients are served from the cache.
+	pub fn cache<C: Cache + 'static>(mut self, cache: C, ttl: Duration) -> Self {
+		self.cache = Some(ResponseCache::new(Arc::new(cache), ttl));
+		self
+	}
+
+	/// A clone of the client whose requests are sent even if a fresh
+	/// response is cached, the responses still being cached for other clients
+	pub fn bypass_cache(&self) -> Self
+	where
+		State: Clone,
+	{
+		let mut client = self.clone();
+		client.cache = self.cache.as_ref().map(ResponseCache::bypassed);
+		client
+	}
+
+	/// Removes the cached response of a URL, e.g.
+	/// `https://users.roblox.com/v1/users/1` after changing the user
+	pub fn invalidate_cached(&self, url: &str) {
+		if let Some(cache) = &self.cache {
+			cache.remove(url);
+		}
+	}
+
+	/// The hits, misses and entries of the cache, all zero without one
+	pub fn cache_stats(&self) -> CacheStats {
+		self.cache.as_ref().map(ResponseCache::stats).unwrap_or_default()
+	}
+
+	/// Reads the time from `clock` instead of the system clock
+	pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+		self.clock = Arc::new(clock);
+		self
+	}
+
+	/// Waits on `sleeper` instead of the tokio timer, e.g. between retries and
+	/// polls
+	pub fn sleeper<S: Sleeper + 'static>(mut self, sleeper: S) -> Self {
+		self.sleeper = Arc::new(sleeper);
+		self
+	}
+
+	/// Answers every request with `transport` instead of sending it
+	#[cfg(feature = "test-util")]
+	#[doc(cfg(feature = "test-util"))]
+	pub fn mock_transport(mut self, transport: MockTransport) -> Self {
+		self.mock_transport = Some(transport);
+		self
+	}
+
+	/// Sets how failed requests are retried, see [RetryPolicy]
+	pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+		self.retry = retry;
+		self
+	}
+
 	pub(crate) fn request<S: ToString + Display>(&self, domain: RobloxApi, path: S) -> RequestBuilder<'_, State> {
-		RequestBuilder::new(domain.url(), path, self)
+		RequestBuilder::new(domain, path, self)
 	}
 
 	async fn inner_request<U, T>(
 		&self,
-		method: Method,
-		endpoint: String,
+		request: RequestBuilder<'_, State>,
 		body: Option<U>,
 	) -> Result<T, RoboltError>
 	where
 		T: DeserializeOwned,
 		U: Serialize,
 	{
-		let builder = {
-			let mut builder = self.http.request(method, format!("https://{endpoint}"));
-			let mut has_body = false;
+		#[cfg(feature = "tracing")]
+		let span = crate::utils::trace::request_span(&request.method, &request.endpoint);
+		let res = self.send_request(request, body);
+		#[cfg(feature = "tracing")]
+		let res = tracing::Instrument::instrument(res, span);
+
+		res.await
+	}
+
+	async fn send_request<U, T>(
+		&self,
+		request: RequestBuilder<'_, State>,
+		body: Option<U>,
+	) -> Result<T, RoboltError>
+	where
+		T: DeserializeOwned,
+		U: Serialize,
+	{
+		let mut headers = HeaderMap::new();
+
+		let xcsrf = self.xcsrf.read().unwrap_or_else(PoisonError::into_inner).clone();
+
+		if let (Some(cookie), Some(xcsrf)) = (&self.cookie, &xcsrf) {
+			headers.insert(COOKIE, sensitive("cookie", cookie)?);
+			headers.insert("x-csrf-token", sensitive("CSRF token", xcsrf)?);
+
+			if body.is_none() {
+				headers.insert(CONTENT_LENGTH, HeaderValue::from_static("0"));
+			}
+		}
+
+		if let (Some(api_key), true) = (&self.api_key, request.cloud) {
+			headers.insert("x-api-key", sensitive("API key", api_key)?);
+		}
+
+		let body = body.map(serde_json::to_value).transpose()?;
+
+		if let (Some(planned), false) = (&self.dry_run, request.read_only) {
+			planned.push(PlannedRequest {
+				method: request.method.clone(),
+				url: request.endpoint.clone(),
+				body,
+			});
+
+			return Err(RoboltError::dry_run_skipped(&request.method, &request.endpoint));
+		}
 
-			if let Some(body) = &body {
-				builder = builder.json(body);
-				has_body = true;
+		// The cache is shared with the clients returned by `set_cookie` and
+		// `login`, so responses depending on the session are never stored
+		let cacheable =
+			request.method == Method::GET && !headers.contains_key(COOKIE) && !headers.contains_key("x-api-key");
+		let cache = self.cache.as_ref().filter(|_| cacheable);
+
+		if let Some(body) = cache.and_then(|cache| cache.get(&request.endpoint)) {
+			return self.deserialize_response(&request.method, &request.endpoint, StatusCode::OK, &body);
+		}
+
+		let max_response_size = request.max_response_size.unwrap_or(self.max_response_size);
+		let mut csrf_retried = false;
+		let mut attempt = 0;
+
+		loop {
+			let mut parts = RequestParts {
+				method: request.method.clone(),
+				url: request.endpoint.clone(),
+				headers: headers.clone(),
+				body: body.clone(),
+				is_retry: attempt > 0 || csrf_retried,
+				idempotency: request.idempotency,
+			};
+
+			for middleware in self.middleware.0.iter() {
+				middleware.before_send(&mut parts)?;
 			}
 
-			if let (Some(cookie), Some(xcsrf)) = (&self.cookie, &self.xcsrf) {
-				let mut headers = HeaderMap::new();
-				headers.insert(COOKIE, cookie.parse().unwrap());
-				headers.insert("x-csrf-token", xcsrf.parse().unwrap());
+			let idempotency = parts.idempotency;
+			let builder = {
+				let mut builder = self
+					.http
+					.request(parts.method.clone(), &parts.url)
+					.headers(parts.headers.clone());
+
+				if let Some(body) = &parts.body {
+					builder = builder.json(body);
+				}
+
+				builder
+			};
+
+			let started = self.clock.now();
+
+			#[cfg(feature = "test-util")]
+			let res = match &self.mock_transport {
+				Some(transport) => transport.respond(&parts)?,
+				None => builder.send().await?,
+			};
+			#[cfg(not(feature = "test-util"))]
+			let res = builder.send().await?;
+
+			let meta = ResponseMeta {
+				method: parts.method,
+				url: parts.url,
+				status: res.status(),
+				elapsed: self.clock.now().saturating_duration_since(started),
+				is_retry: parts.is_retry,
+			};
+
+			for middleware in self.middleware.0.iter().rev() {
+				middleware.after_receive(&meta);
+			}
+
+			#[cfg(feature = "tracing")]
+			crate::utils::trace::response(&meta);
+
+			let status = res.status();
+
+			if status.is_success() {
+				let body = read_body(res, max_response_size).await?;
+				let json = self.deserialize_response(&meta.method, &meta.url, status, &body)?;
+
+				if let Some(cache) = cache {
+					cache.put(&request.endpoint, body);
+				}
 
-				if !has_body {
-					headers.insert(CONTENT_LENGTH, "0".parse().unwrap());
+				if let (Some(cache), false) = (&self.cache, request.method == Method::GET) {
+					cache.remove(&request.endpoint);
+					request.invalidates.iter().for_each(|url| cache.remove(url));
 				}
 
-				builder = builder.headers(headers);
+				return Ok(json);
 			}
 
-			builder
+			let csrf_token = match status {
+				StatusCode::FORBIDDEN if xcsrf.is_some() => res.headers().get("x-csrf-token").cloned(),
+				_ => None,
+			};
+			let retry_after = res
+				.headers()
+				.get(RETRY_AFTER)
+				.and_then(|value| value.to_str().ok()?.parse().ok())
+				.map(Duration::from_secs);
+			let challenge = challenge_required(status, res.headers());
+
+			let text = read_body(res, max_response_size).await?;
+			let mut err = error_from_body(status, &String::from_utf8_lossy(&text))
+				.with_retry_after(retry_after)
+				.with_url(&meta.url)
+				.with_body(self.capture_error_bodies.then_some(text.as_slice()));
+
+			if let Some(challenge) = challenge {
+				err = err.with_kind(challenge);
+			}
+
+			if let (Some(mut csrf_token), false) = (csrf_token, csrf_retried) {
+				csrf_retried = true;
+				csrf_token.set_sensitive(true);
+
+				if let Ok(token) = csrf_token.to_str() {
+					*self.xcsrf.write().unwrap_or_else(PoisonError::into_inner) = Some(token.to_string());
+				}
+
+				self.retry.notify(1, &err, Duration::ZERO);
+				headers.insert("x-csrf-token", csrf_token);
+				continue;
+			}
+
+			if self.retry.should_retry(status, idempotency, attempt + 1) {
+				attempt += 1;
+				let delay = self.retry.retry_delay(attempt, retry_after);
+
+				self.retry.notify(attempt, &err, delay);
+				self.sleeper.sleep(delay).await;
+				continue;
+			}
+
+			return Err(match (request.api, request.resource) {
+				(Some(api), Some((resource, id))) => err.not_found(api, resource, id),
+				_ => err,
+			});
+		}
+	}
+
+	/// Deserializes the body of a successful response
+	fn deserialize_response<T: DeserializeOwned>(
+		&self,
+		method: &Method,
+		url: &str,
+		status: StatusCode,
+		body: &[u8],
+	) -> Result<T, RoboltError> {
+		let json = match &self.schema_watch {
+			Some(watch) => watch.deserialize::<T>(method, url, body),
+			None => serde_json::from_slice::<T>(body),
 		};
 
-		let res = builder.send().await?;
-		let status = res.status();
+		json.map_err(|err| {
+			RoboltError::invalid_response(err, method, url)
+				.with_status(status)
+				.with_body(self.capture_error_bodies.then_some(body))
+		})
+	}
+}
+
+/// A header value that is hidden from the [Debug] output of headers, e.g.
+/// cookies and tokens, failing if `value` contains characters not allowed in
+/// headers such as newlines
+fn sensitive(name: &str, value: &str) -> Result<HeaderValue, RoboltError> {
+	let mut value = HeaderValue::from_str(value).map_err(|_| format!("The {name} is not a valid header value"))?;
+	value.set_sensitive(true);
+	Ok(value)
+}
+
+/// Reads the body of a response, failing as soon as it is known to be larger
+/// than `limit` bytes
+async fn read_body(mut res: Response, limit: u64) -> Result<Vec<u8>, RoboltError> {
+	if let Some(length) = res.content_length().filter(|length| *length > limit) {
+		return Err(RoboltError::response_too_large(limit, Some(length)));
+	}
+
+	let mut body = Vec::new();
+
+	while let Some(chunk) = res.chunk().await? {
+		if (body.len() + chunk.len()) as u64 > limit {
+			return Err(RoboltError::response_too_large(limit, None));
+		}
+
+		body.extend_from_slice(&chunk);
+	}
+
+	Ok(body)
+}
+
+/// The challenge Roblox asks for before accepting the request, from the
+/// `rblx-challenge-*` headers of a `403 Forbidden` response
+fn challenge_required(status: StatusCode, headers: &HeaderMap) -> Option<crate::utils::errors::RoboltErrorKind> {
+	let header = |name: &str| Some(headers.get(name)?.to_str().ok()?.to_string());
+
+	match status {
+		StatusCode::FORBIDDEN => Some(crate::utils::errors::RoboltErrorKind::ChallengeRequired {
+			id: header("rblx-challenge-id")?,
+			challenge_type: header("rblx-challenge-type"),
+		}),
+		_ => None,
+	}
+}
+
+/// Builds the error returned for a failed response from its body
+fn error_from_body(status: StatusCode, body: &str) -> RoboltError {
+	let api_error = serde_json::from_str::<RobloxAPIErrors>(body)
+		.ok()
+		.and_then(|res| res.errors.into_iter().next());
+
+	if let Some(err) = api_error {
+		return err.with_status(status);
+	}
+
+	if let Ok(err) = serde_json::from_str::<CloudError>(body) {
+		return RoboltError::from(err).with_status(status);
+	}
+
+	// Still an error returned by the API, e.g. an HTML error page
+	RoboltError::from(status.to_string())
+		.with_kind(crate::utils::errors::RoboltErrorKind::Api)
+		.with_status(status)
+}
+
+impl Default for HttpConfig {
+	fn default() -> Self {
+		Self {
+			timeout: Some(DEFAULT_TIMEOUT),
+			connect_timeout: None,
+			proxy: None,
+			user_agent: None,
+		}
+	}
+}
+
+impl HttpConfig {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets how long a request may take from connecting to reading the whole
+	/// response, 30 seconds by default
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
 
-		if !status.is_success() {
-			let err_res = res.json::<RobloxAPIErrors>().await?;
+	/// Lets requests take as long as they need
+	pub fn no_timeout(mut self) -> Self {
+		self.timeout = None;
+		self
+	}
+
+	/// Sets how long connecting to Roblox may take, only limited by
+	/// [HttpConfig::timeout] by default
+	pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+		self.connect_timeout = Some(connect_timeout);
+		self
+	}
+
+	/// Sends every request through the proxy at `url`, e.g.
+	/// `http://127.0.0.1:8080` or `socks5://127.0.0.1:1080`
+	pub fn proxy(mut self, url: &str) -> Self {
+		self.proxy = Some(url.to_string());
+		self
+	}
+
+	/// Replaces the default `robolt/<version>` user agent
+	pub fn user_agent(mut self, user_agent: &str) -> Self {
+		self.user_agent = Some(user_agent.to_string());
+		self
+	}
 
-			let err = err_res
-				.errors
-				.into_iter()
-				.next()
-				.ok_or(RoboltError::from(status.to_string()))?;
+	fn build(&self) -> Result<reqwest::Client, RoboltError> {
+		let mut headers = default_client_headers();
 
-			return Err(err);
+		if let Some(user_agent) = &self.user_agent {
+			let user_agent = user_agent
+				.parse()
+				.map_err(|_| format!("Invalid user agent {user_agent:?}"))?;
+
+			headers.insert(USER_AGENT, user_agent);
+		}
+
+		let mut builder = reqwest::Client::builder().default_headers(headers);
+
+		if let Some(timeout) = self.timeout {
+			builder = builder.timeout(timeout);
+		}
+
+		if let Some(connect_timeout) = self.connect_timeout {
+			builder = builder.connect_timeout(connect_timeout);
 		}
 
-		let json = res.json::<T>().await?;
-		Ok(json)
+		if let Some(proxy) = &self.proxy {
+			builder = builder.proxy(Proxy::all(proxy)?);
+		}
+
+		Ok(builder.build()?)
 	}
 }
 
@@ -109,52 +623,172 @@ pub(crate) fn default_client_headers() -> HeaderMap {
 }
 
 impl<'a, State> RequestBuilder<'a, State> {
-	fn new<S: ToString + Display>(domain: &str, path: S, robolt: &'a Robolt<State>) -> Self {
+	fn new<S: ToString + Display>(domain: RobloxApi, path: S, robolt: &'a Robolt<State>) -> Self {
+		Self {
+			method: Method::GET,
+			endpoint: robolt.endpoints.url(domain, path),
+			cloud: matches!(domain, RobloxApi::Cloud),
+			api: Some(domain),
+			resource: None,
+			max_response_size: None,
+			idempotency: Idempotency::Idempotent,
+			read_only: true,
+			invalidates: Vec::new(),
+			robolt,
+		}
+	}
+
+	/// Builds a request to a full URL, see [Robolt::raw]
+	pub(crate) fn from_url(url: Url, robolt: &'a Robolt<State>) -> Self {
 		Self {
 			method: Method::GET,
-			endpoint: format!("{domain}{path}"),
+			cloud: url.host_str() == Some(RobloxApi::Cloud.url()),
+			endpoint: url.to_string(),
+			api: None,
+			resource: None,
+			max_response_size: None,
+			idempotency: Idempotency::Idempotent,
+			read_only: true,
+			invalidates: Vec::new(),
 			robolt,
 		}
 	}
 
+	/// Overrides [Robolt::max_response_size] for this request, for endpoints
+	/// that legitimately return large bodies
+	pub(crate) fn max_response_size(mut self, max_response_size: u64) -> Self {
+		self.max_response_size = Some(max_response_size);
+		self
+	}
+
+	/// Sets the method of the request, resetting its [Idempotency] to
+	/// [Idempotency::Unknown] for any method other than `GET`
+	///
+	/// Requests with any method other than `GET` are skipped in a
+	/// [dry run](crate::dry_run) unless marked [RequestBuilder::read_only].
 	pub(crate) fn method(mut self, method: Method) -> Self {
+		self.idempotency = match method {
+			Method::GET => Idempotency::Idempotent,
+			_ => Idempotency::Unknown,
+		};
+		self.read_only = method == Method::GET;
 		self.method = method;
 		self
 	}
 
+	/// Marks a request that only looks data up, e.g. a batch lookup sent as
+	/// `POST`, so it is still sent in a [dry run](crate::dry_run)
+	pub(crate) fn read_only(mut self) -> Self {
+		self.read_only = true;
+		self
+	}
+
+	/// Marks the request as fetching a single resource, so errors meaning it is
+	/// missing become [RoboltErrorKind::NotFound]
+	///
+	/// [RoboltErrorKind::NotFound]: crate::errors::RoboltErrorKind::NotFound
+	pub(crate) fn resource(mut self, resource: ResourceKind, id: u64) -> Self {
+		self.resource = Some((resource, id));
+		self
+	}
+
+	/// Removes the cached response of another endpoint once the request
+	/// succeeds, for writes that change what it returns
+	///
+	/// The cached response of the request's own URL is always removed after a
+	/// successful request with any method other than `GET`.
+	pub(crate) fn invalidates<S: ToString + Display>(mut self, domain: RobloxApi, path: S) -> Self {
+		self.invalidates.push(self.robolt.endpoints.url(domain, path));
+		self
+	}
+
+	pub(crate) fn idempotency(mut self, idempotency: Idempotency) -> Self {
+		self.idempotency = idempotency;
+		self
+	}
+
 	pub(crate) async fn send_body<T, U>(self, body: T) -> Result<U, RoboltError>
 	where
 		T: Serialize,
 		U: DeserializeOwned,
 	{
-		self.robolt.inner_request(self.method, self.endpoint, Some(body)).await
+		self.robolt.inner_request(self, Some(body)).await
 	}
 
 	pub(crate) async fn send<T>(self) -> Result<T, RoboltError>
 	where
 		T: DeserializeOwned,
 	{
-		self.robolt
-			.inner_request::<(), T>(self.method, self.endpoint, None)
-			.await
+		self.robolt.inner_request::<(), T>(self, None).await
 	}
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// The settings of the HTTP client of [Robolt], see
+/// [Robolt::with_http_config]
+pub struct HttpConfig {
+	timeout: Option<Duration>,
+	connect_timeout: Option<Duration>,
+	proxy: Option<String>,
+	user_agent: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Unauthenticated;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Authenticated;
 
+/// A client without a `.ROBLOSECURITY` cookie, which can only call the
+/// public endpoints
+///
+/// See [Robolt::login] to obtain an [AuthenticatedClient], so that calling an
+/// endpoint that requires the cookie without one is a compile error rather
+/// than a `401 Unauthorized`.
+pub type Client = Robolt<Unauthenticated>;
+
+/// A client holding a `.ROBLOSECURITY` cookie and its CSRF token, which can
+/// call every endpoint of [Client] as well as those requiring the cookie
+pub type AuthenticatedClient = Robolt<Authenticated>;
+
 #[derive(Debug, Clone)]
 pub struct Robolt<State = Unauthenticated> {
 	#[cfg(feature = "http")]
-	pub http: Client,
+	pub http: reqwest::Client,
 	#[cfg(not(feature = "http"))]
-	pub(crate) http: Client,
+	pub(crate) http: reqwest::Client,
 	pub(crate) state: PhantomData<State>,
 	pub(crate) cookie: Option<String>,
-	pub(crate) xcsrf: Option<String>,
+	/// The CSRF token, replaced whenever Roblox rotates it and shared between
+	/// clones
+	pub(crate) xcsrf: Arc<RwLock<Option<String>>>,
+	pub(crate) api_key: Option<String>,
+	pub(crate) middleware: Middlewares,
+	pub(crate) retry: RetryPolicy,
+	pub(crate) locked_groups: LockedGroups,
+	pub(crate) max_response_size: u64,
+	pub(crate) max_pages_per_crawl: usize,
+	pub(crate) schema_watch: Option<SchemaWatch>,
+	pub(crate) moderation_log: Option<Arc<dyn ModerationLog>>,
+	pub(crate) dry_run: Option<PlannedRequests>,
+	pub(crate) capture_error_bodies: bool,
+	pub(crate) cache: Option<ResponseCache>,
+	pub(crate) endpoints: Endpoints,
+	/// The authenticated user once fetched, shared between clones
+	pub(crate) user: Arc<Mutex<Option<PartialUser>>>,
+	/// Hosts that raw requests are allowed to reach on top of `roblox.com`
+	pub(crate) raw_hosts: Vec<String>,
+	pub(crate) clock: Arc<dyn Clock>,
+	pub(crate) sleeper: Arc<dyn Sleeper>,
+	#[cfg(feature = "test-util")]
+	pub(crate) mock_transport: Option<MockTransport>,
+}
+
+#[derive(Debug, Clone, Default)]
+/// The cached locked state of groups, shared between clones of a client
+pub(crate) struct LockedGroups {
+	pub(crate) guard: bool,
+	pub(crate) cache: Arc<Mutex<HashMap<u64, bool>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -162,4 +796,14 @@ pub(crate) struct RequestBuilder<'a, State> {
 	robolt: &'a Robolt<State>,
 	method: Method,
 	endpoint: String,
+	cloud: bool,
+	/// `None` for raw requests
+	api: Option<RobloxApi>,
+	resource: Option<(ResourceKind, u64)>,
+	max_response_size: Option<u64>,
+	idempotency: Idempotency,
+	/// Whether the request is sent in a dry run
+	read_only: bool,
+	/// The URLs whose cached responses are removed once the request succeeds
+	invalidates: Vec<String>,
 }
\ No newline at end of file
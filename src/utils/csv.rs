@@ -0,0 +1,300 @@
+//! Flattening of models into CSV rows for data export
+//!
+//! Nested fields are flattened into dotted column names such as `owner.id`,
+//! and missing optional values are written as empty fields.
+//!
+//! Rows are written by hand rather than with the `csv` crate, as the
+//! only CSV feature needed is quoting fields, which [RFC 4180] defines in a
+//! few lines. Fields containing commas, quotes or line breaks are quoted,
+//! with quotes doubled.
+//!
+//! [RFC 4180]: https://www.rfc-editor.org/rfc/rfc4180
+
+#[cfg(feature = "csv")]
+use std::io::{self, Write};
+
+use crate::api::cloud::{InventoryItem, InventoryItemDetails};
+use crate::api::economy::{PricePoint, Transaction};
+use crate::api::games::GameDetails;
+use crate::api::groups::{Group, GroupMember};
+use crate::api::users::{PartialUser, User};
+use crate::api::Creator;
+
+/// A model that can be written as a single CSV row
+pub trait ToRow {
+	/// The column names, in the same order as [ToRow::values]
+	fn headers() -> Vec<&'static str>;
+
+	fn values(&self) -> Vec<String>;
+}
+
+/// Writes a header row followed by one row per item, quoting values that
+/// contain commas, quotes or line breaks
+#[cfg(feature = "csv")]
+#[doc(cfg(feature = "csv"))]
+pub fn write_rows<W, T, I>(mut writer: W, rows: I) -> io::Result<()>
+where
+	W: Write,
+	T: ToRow,
+	I: IntoIterator<Item = T>,
+{
+	write_row(&mut writer, T::headers().into_iter().map(String::from).collect())?;
+
+	for row in rows {
+		write_row(&mut writer, row.values())?;
+	}
+
+	writer.flush()
+}
+
+#[cfg(feature = "csv")]
+pub(crate) fn write_row<W: Write>(writer: &mut W, values: Vec<String>) -> io::Result<()> {
+	let row = values
+		.iter()
+		.map(|value| escape_field(value))
+		.collect::<Vec<String>>()
+		.join(",");
+
+	writeln!(writer, "{row}")
+}
+
+/// Quotes a CSV field if it contains a comma, quote or line break
+pub(crate) fn escape_field(value: &str) -> String {
+	match value.contains([',', '"', '\n', '\r']) {
+		true => format!("\"{}\"", value.replace('"', "\"\"")),
+		false => value.to_string(),
+	}
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+	value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+impl ToRow for User {
+	fn headers() -> Vec<&'static str> {
+		vec![
+			"id",
+			"username",
+			"display_name",
+			"description",
+			"created",
+			"is_banned",
+			"has_verified_badge",
+		]
+	}
+
+	fn values(&self) -> Vec<String> {
+		vec![
+			self.id.to_string(),
+			self.username.clone(),
+			self.display_name.clone(),
+			optional(&self.description),
+			self.created.clone(),
+			self.is_banned.to_string(),
+			self.has_verified_badge.to_string(),
+		]
+	}
+}
+
+impl ToRow for PartialUser {
+	fn headers() -> Vec<&'static str> {
+		vec!["id", "username", "display_name"]
+	}
+
+	fn values(&self) -> Vec<String> {
+		vec![self.id.to_string(), self.username.clone(), self.display_name.clone()]
+	}
+}
+
+impl ToRow for Group {
+	fn headers() -> Vec<&'static str> {
+		vec![
+			"id",
+			"name",
+			"description",
+			"owner.id",
+			"owner.username",
+			"shout.body",
+			"member_count",
+			"public_entry_allowed",
+			"is_locked",
+			"has_verified_badge",
+		]
+	}
+
+	fn values(&self) -> Vec<String> {
+		vec![
+			self.id.to_string(),
+			self.name.clone(),
+			self.description.clone(),
+			optional(&self.owner.as_ref().map(|owner| owner.id)),
+			optional(&self.owner.as_ref().map(|owner| &owner.username)),
+			optional(&self.shout.as_ref().map(|shout| &shout.body)),
+			self.member_count.to_string(),
+			self.public_entry_allowed.to_string(),
+			self.is_locked.unwrap_or(false).to_string(),
+			self.has_verified_badge.to_string(),
+		]
+	}
+}
+
+impl ToRow for GroupMember {
+	fn headers() -> Vec<&'static str> {
+		vec![
+			"user.id",
+			"user.username",
+			"user.display_name",
+			"role.id",
+			"role.name",
+			"role.rank",
+		]
+	}
+
+	fn values(&self) -> Vec<String> {
+		vec![
+			self.user.id.to_string(),
+			self.user.username.clone(),
+			self.user.display_name.clone(),
+			self.role.id.to_string(),
+			self.role.name.clone(),
+			self.role.rank.to_string(),
+		]
+	}
+}
+
+impl ToRow for GameDetails {
+	fn headers() -> Vec<&'static str> {
+		vec![
+			"id",
+			"root_place_id",
+			"name",
+			"description",
+			"creator.id",
+			"creator.type",
+			"creator.name",
+			"price",
+			"playing",
+			"visits",
+			"max_players",
+			"favorited_count",
+			"genre",
+			"created",
+			"updated",
+		]
+	}
+
+	fn values(&self) -> Vec<String> {
+		let creator_type = match self.creator {
+			Creator::User {
+				..
+			} => "User",
+			Creator::Group {
+				..
+			} => "Group",
+		};
+
+		vec![
+			self.id.to_string(),
+			self.root_place_id.to_string(),
+			self.name.clone(),
+			optional(&self.description),
+			self.creator.id().to_string(),
+			creator_type.to_string(),
+			self.creator.name().to_string(),
+			optional(&self.price.map(|price| price.0)),
+			self.playing.to_string(),
+			self.visits.to_string(),
+			self.max_players.to_string(),
+			self.favorited_count.to_string(),
+			optional(&self.genre),
+			self.created.clone(),
+			self.updated.clone(),
+		]
+	}
+}
+
+impl ToRow for Transaction {
+	fn headers() -> Vec<&'static str> {
+		vec![
+			"id",
+			"created",
+			"is_pending",
+			"agent.id",
+			"agent.type",
+			"agent.name",
+			"currency.amount",
+			"currency.type",
+			"purchase_token",
+		]
+	}
+
+	fn values(&self) -> Vec<String> {
+		vec![
+			self.id.to_string(),
+			self.created.clone(),
+			self.is_pending.to_string(),
+			self.agent.id.to_string(),
+			self.agent.agent_type.clone(),
+			self.agent.name.clone(),
+			self.currency.amount.to_string(),
+			self.currency.currency_type.clone(),
+			optional(&self.purchase_token),
+		]
+	}
+}
+
+impl ToRow for PricePoint {
+	fn headers() -> Vec<&'static str> {
+		vec!["date", "price", "volume"]
+	}
+
+	fn values(&self) -> Vec<String> {
+		vec![self.date.clone(), self.price.0.to_string(), self.volume.to_string()]
+	}
+}
+
+impl ToRow for InventoryItem {
+	fn headers() -> Vec<&'static str> {
+		vec![
+			"id",
+			"details.type",
+			"details.id",
+			"details.asset_type",
+			"details.instance_id",
+			"details.serial_number",
+		]
+	}
+
+	fn values(&self) -> Vec<String> {
+		let (item_type, id) = match &self.details {
+			InventoryItemDetails::AssetDetails(asset) => ("asset", asset.asset_id),
+			InventoryItemDetails::BadgeDetails {
+				badge_id,
+			} => ("badge", *badge_id),
+			InventoryItemDetails::GamePassDetails {
+				game_pass_id,
+			} => ("game_pass", *game_pass_id),
+			InventoryItemDetails::PrivateServerDetails {
+				private_server_id,
+			} => ("private_server", *private_server_id),
+		};
+
+		let asset = match &self.details {
+			InventoryItemDetails::AssetDetails(asset) => Some(asset),
+			_ => None,
+		};
+
+		vec![
+			self.id.clone(),
+			item_type.to_string(),
+			id.to_string(),
+			optional(&asset.map(|asset| &asset.inventory_item_asset_type)),
+			optional(&asset.and_then(|asset| asset.instance_id.as_ref())),
+			optional(
+				&asset
+					.and_then(|asset| asset.collectible_details.as_ref())
+					.and_then(|collectible| collectible.serial_number),
+			),
+		]
+	}
+}
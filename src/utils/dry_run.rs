@@ -0,0 +1,100 @@
+//! Planning the requests of an operation without sending them
+//!
+//! With [Robolt::dry_run](crate::Robolt::dry_run) set, every request that could
+//! change something is recorded into [PlannedRequests] instead of being sent,
+//! and fails with [RoboltErrorKind::DryRunSkipped]. `GET` requests and the
+//! `POST` endpoints that only look data up are still sent, so the plan is
+//! built from the current state:
+//!
+//! ```no_run
+//! # use robolt::dry_run::PlannedRequests;
+//! # use robolt::api::groups::{RankOptions, RoleTarget};
+//! # async fn run(cookie: String) -> Result<(), robolt::errors::RoboltError> {
+//! let planned = PlannedRequests::new();
+//! let client = robolt::Robolt::new()
+//!     .dry_run(planned.clone())
+//!     .set_cookie(cookie.parse()?)
+//!     .await?;
+//! let report = client
+//!     .rank_many(1, vec![(2, RoleTarget::Rank(10))], &RankOptions::default())
+//!     .await;
+//!
+//! assert!(report.report.dry_run);
+//!
+//! for request in planned.requests() {
+//!     println!("{request}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Orchestration helpers such as [Robolt::rank_many](crate::Robolt::rank_many)
+//! and [Robolt::sweep_join_requests](crate::Robolt::sweep_join_requests) treat
+//! skipped requests as successful and mark their reports as dry runs, while
+//! single-request wrappers return the error.
+//!
+//! [RoboltErrorKind::DryRunSkipped]: crate::errors::RoboltErrorKind::DryRunSkipped
+
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::utils::middleware::redact;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A request recorded instead of being sent
+pub struct PlannedRequest {
+	pub method: Method,
+	pub url: String,
+	pub body: Option<Value>,
+}
+
+impl fmt::Display for PlannedRequest {
+	/// Formats the request as `METHOD URL`, followed by its body with
+	/// sensitive fields redacted
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} {}", self.method, self.url)?;
+
+		match &self.body {
+			Some(body) => write!(f, " {}", redact(body)),
+			None => Ok(()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+/// The requests planned by a client in dry run mode, in the order they would
+/// have been sent
+///
+/// Clones share the same log, so a clone kept before handing one to
+/// [Robolt::dry_run](crate::Robolt::dry_run) can be inspected afterwards.
+pub struct PlannedRequests(Arc<Mutex<Vec<PlannedRequest>>>);
+
+impl PlannedRequests {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn requests(&self) -> Vec<PlannedRequest> {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+
+	pub fn len(&self) -> usize {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Empties the log, e.g. before planning another operation
+	pub fn clear(&self) {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+
+	pub(crate) fn push(&self, request: PlannedRequest) {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).push(request);
+	}
+}
@@ -1,18 +1,77 @@
 use std::fmt;
+use std::time::Duration;
 
+use reqwest::{Method, StatusCode};
 use serde::Deserialize;
 
+use crate::api::routes::RobloxApi;
+
+/// The maximum number of bytes of a response body kept by an error, see
+/// [RoboltError::body]
+const MAX_ERROR_BODY_LEN: usize = 1024;
+
+/// The error codes each API uses for a resource that does not exist, on top
+/// of which any `404 Not Found` is [NotFoundReason::Unknown] and any
+/// `410 Gone` is [NotFoundReason::Deleted]
+const NOT_FOUND_CODES: &[(RobloxApi, ResourceKind, i32, NotFoundReason)] = &[
+	// The user id is invalid.
+	(RobloxApi::Users, ResourceKind::User, 3, NotFoundReason::Invalid),
+	// The target user is invalid or does not exist.
+	(RobloxApi::Friends, ResourceKind::User, 1, NotFoundReason::Invalid),
+	// The specified user does not exist!
+	(RobloxApi::Avatar, ResourceKind::User, 1, NotFoundReason::Invalid),
+	// The specified userOutfit does not exist!
+	(RobloxApi::Avatar, ResourceKind::Outfit, 1, NotFoundReason::Invalid),
+	// Group is invalid or does not exist.
+	(RobloxApi::Groups, ResourceKind::Group, 1, NotFoundReason::Invalid),
+	// Badge is invalid or does not exist.
+	(RobloxApi::Badges, ResourceKind::Badge, 1, NotFoundReason::Invalid),
+	// The universe is invalid.
+	(RobloxApi::Badges, ResourceKind::Universe, 1, NotFoundReason::Invalid),
+	// The user is invalid or does not exist.
+	(RobloxApi::Badges, ResourceKind::User, 4, NotFoundReason::Invalid),
+	// Invalid bundle
+	(RobloxApi::Catalog, ResourceKind::Bundle, 1, NotFoundReason::Invalid),
+	// The asset id is invalid.
+	(RobloxApi::Economy, ResourceKind::Asset, 1, NotFoundReason::Invalid),
+	// The universe does not exist.
+	(RobloxApi::Develop, ResourceKind::Universe, 1, NotFoundReason::Invalid),
+	// The place is invalid.
+	(RobloxApi::Games, ResourceKind::Place, 1, NotFoundReason::Invalid),
+	// The target user's inventory is private.
+	(RobloxApi::Catalog, ResourceKind::User, 11, NotFoundReason::Hidden),
+	// The specified user does not exist!
+	(RobloxApi::Inventory, ResourceKind::User, 1, NotFoundReason::Invalid),
+	// You don't have permissions to view the specified user's inventory.
+	(RobloxApi::Inventory, ResourceKind::User, 4, NotFoundReason::Hidden),
+];
+
 #[derive(Deserialize, Debug)]
 /// An error associated with either the Roblox API or a HTTP request.
 pub struct RoboltError {
 	pub message: String,
 	#[serde(default = "default_error_code")]
-	pub code: i8,
+	pub code: i32,
 	#[serde(skip)]
 	kind: RoboltErrorKind,
+	#[serde(skip)]
+	status: Option<u16>,
+	#[serde(skip)]
+	retry_after: Option<Duration>,
+	#[serde(skip)]
+	context: Option<Box<ErrorContext>>,
 }
 
-fn default_error_code() -> i8 {
+#[derive(Debug, Default)]
+/// The URL and body of the response that caused an error, boxed as they are
+/// rarely set and would otherwise make every `Result` carrying a
+/// [RoboltError] larger
+struct ErrorContext {
+	url: Option<String>,
+	body: Option<String>,
+}
+
+fn default_error_code() -> i32 {
 	-1
 }
 
@@ -21,11 +80,325 @@ pub(crate) struct RobloxAPIErrors {
 	pub(crate) errors: Vec<RoboltError>,
 }
 
-#[derive(Debug, Default)]
-enum RoboltErrorKind {
+#[derive(Deserialize)]
+/// The error envelope returned by Open Cloud endpoints
+pub(crate) struct CloudError {
+	code: String,
+	message: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum RoboltErrorKind {
 	#[default]
 	Api,
+	/// The authenticated user lacks the permissions required by the endpoint
+	PermissionDenied,
+	/// The endpoint responded with 429 Too Many Requests
+	RateLimited,
+	/// The group is locked by moderation and rejects every change
+	GroupLocked,
+	/// The response body is larger than the configured limit, `actual` being
+	/// the size announced by the response if any
+	ResponseTooLarge {
+		limit: u64,
+		actual: Option<u64>,
+	},
+	/// A MessagingService message is larger than the limit once serialized,
+	/// `compressed` being its size after compression if it was allowed
+	MessageTooLarge {
+		size: usize,
+		compressed: Option<usize>,
+		limit: usize,
+	},
+	/// The account must be 13 or older and verified, e.g. to add Discord
+	/// social links
+	AgeRestricted,
+	/// Roblox asks for a challenge such as a two-step verification to be
+	/// completed before the request is accepted, e.g. when accepting or
+	/// sending a trade
+	ChallengeRequired {
+		/// The `rblx-challenge-id` header of the response
+		id: String,
+		/// The `rblx-challenge-type` header of the response, e.g.
+		/// `twostepverification`
+		challenge_type: Option<String>,
+	},
+	/// The display name was changed in the last 7 days and cannot be changed
+	/// again yet, see
+	/// [Robolt::set_display_name](crate::Robolt::set_display_name)
+	DisplayNameCooldown,
+	/// Roblox refused to send a private message, e.g. because the recipient
+	/// does not accept messages from the user, see
+	/// [Robolt::send_message](crate::Robolt::send_message)
+	MessageNotSent,
+	/// A raw request was made to a host that is not allowed, see
+	/// [Robolt::raw](crate::Robolt::raw)
+	HostNotAllowed,
+	/// The request would have changed something and was recorded instead of
+	/// being sent, see [Robolt::dry_run](crate::Robolt::dry_run)
+	DryRunSkipped,
+	/// The moderation action succeeded but its outcome could not be written
+	/// to the [ModerationLog](crate::moderation::ModerationLog), so it must
+	/// not be retried
+	NotRecorded,
+	/// The resource fetched by a wrapper does not exist or is not visible
+	NotFound {
+		resource: ResourceKind,
+		id: u64,
+		reason: NotFoundReason,
+	},
+	/// The request could not be sent or its response could not be read, e.g.
+	/// a connection failure or a timeout
+	Http,
+	/// The response body is not valid JSON or does not match the model
+	Deserialize,
+	/// The `.ROBLOSECURITY` cookie could not be parsed or was rejected by
+	/// Roblox
+	InvalidCookie,
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The kind of resource fetched by a wrapper, see [RoboltErrorKind::NotFound]
+pub enum ResourceKind {
+	User,
+	Group,
+	Badge,
+	Asset,
+	Bundle,
+	Outfit,
+	Universe,
+	Place,
+	DeveloperProduct,
+	WallPost,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+/// Why a resource was not found, as far as the error returned by its API
+/// tells
+pub enum NotFoundReason {
+	/// The API does not say why, e.g. a bare 404
+	#[default]
 	Unknown,
+	/// The resource existed but has been deleted
+	Deleted,
+	/// The resource exists but is hidden from the client, e.g. by privacy
+	/// settings
+	Hidden,
+	/// The ID has never belonged to a resource
+	Invalid,
+}
+
+impl NotFoundReason {
+	/// Whether fetching the resource again will fail the same way, so a miss
+	/// can be cached for good
+	pub fn is_permanent(&self) -> bool {
+		matches!(self, Self::Deleted | Self::Invalid)
+	}
+}
+
+impl RoboltError {
+	pub fn kind(&self) -> &RoboltErrorKind {
+		&self.kind
+	}
+
+	/// The HTTP status code of the response that caused the error, if any
+	pub fn status(&self) -> Option<u16> {
+		self.status
+	}
+
+	/// How long Roblox asked to wait before sending the request again, from
+	/// the `Retry-After` header of a rate limited response
+	pub fn retry_after(&self) -> Option<Duration> {
+		self.retry_after
+	}
+
+	/// The URL of the request that caused the error, if it was sent
+	pub fn url(&self) -> Option<&str> {
+		self.context.as_ref()?.url.as_deref()
+	}
+
+	/// The start of the body of the response that caused the error, at most
+	/// 1 KiB, if [Robolt::capture_error_bodies](crate::Robolt::capture_error_bodies)
+	/// is enabled
+	pub fn body(&self) -> Option<&str> {
+		self.context.as_ref()?.body.as_deref()
+	}
+
+	/// A successful response whose body does not match the model
+	pub(crate) fn invalid_response(error: serde_json::Error, method: &Method, url: &str) -> Self {
+		Self {
+			kind: RoboltErrorKind::Deserialize,
+			code: default_error_code(),
+			message: format!("Invalid response from {method} {url}: {error}"),
+			status: None,
+			retry_after: None,
+			context: Some(Box::new(ErrorContext {
+				url: Some(url.to_string()),
+				body: None,
+			})),
+		}
+	}
+
+	pub(crate) fn group_locked(group_id: u64) -> Self {
+		Self {
+			kind: RoboltErrorKind::GroupLocked,
+			code: default_error_code(),
+			message: format!("Group {group_id} is locked"),
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	pub(crate) fn response_too_large(limit: u64, actual: Option<u64>) -> Self {
+		let message = match actual {
+			Some(actual) => format!("Response body of {actual} bytes exceeds the limit of {limit} bytes"),
+			None => format!("Response body exceeds the limit of {limit} bytes"),
+		};
+
+		Self {
+			kind: RoboltErrorKind::ResponseTooLarge {
+				limit,
+				actual,
+			},
+			code: default_error_code(),
+			message,
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	pub(crate) fn message_too_large(size: usize, compressed: Option<usize>, limit: usize) -> Self {
+		let message = match compressed {
+			Some(compressed) => format!(
+				"Message of {size} bytes ({compressed} bytes compressed) exceeds the limit of {limit} bytes"
+			),
+			None => format!("Message of {size} bytes exceeds the limit of {limit} bytes"),
+		};
+
+		Self {
+			kind: RoboltErrorKind::MessageTooLarge {
+				size,
+				compressed,
+				limit,
+			},
+			code: default_error_code(),
+			message,
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	pub(crate) fn host_not_allowed(url: &str) -> Self {
+		Self {
+			kind: RoboltErrorKind::HostNotAllowed,
+			code: default_error_code(),
+			message: format!("Refusing to send a request to {url}, only HTTPS requests to roblox.com or allowed hosts can be sent"),
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	pub(crate) fn dry_run_skipped(method: &Method, url: &str) -> Self {
+		Self {
+			kind: RoboltErrorKind::DryRunSkipped,
+			code: default_error_code(),
+			message: format!("Request skipped by dry run: {method} {url}"),
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	pub(crate) fn message_not_sent(message: String) -> Self {
+		Self {
+			kind: RoboltErrorKind::MessageNotSent,
+			code: default_error_code(),
+			message,
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	pub(crate) fn invalid_cookie(message: String) -> Self {
+		Self {
+			kind: RoboltErrorKind::InvalidCookie,
+			code: default_error_code(),
+			message,
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+
+	/// Turns the error of a wrapper fetching a single resource into
+	/// [RoboltErrorKind::NotFound] if it means the resource is missing
+	pub(crate) fn not_found(self, api: RobloxApi, resource: ResourceKind, id: u64) -> Self {
+		let reason = match self.status {
+			Some(400 | 403 | 404) => NOT_FOUND_CODES
+				.iter()
+				.find(|(code_api, code_resource, code, _)| {
+					*code_api == api && *code_resource == resource && *code == self.code
+				})
+				.map(|(.., reason)| *reason)
+				.or((self.status == Some(404)).then_some(NotFoundReason::Unknown)),
+			Some(410) => Some(NotFoundReason::Deleted),
+			_ => None,
+		};
+
+		match reason {
+			Some(reason) => self.with_kind(RoboltErrorKind::NotFound {
+				resource,
+				id,
+				reason,
+			}),
+			None => self,
+		}
+	}
+
+	pub(crate) fn with_kind(mut self, kind: RoboltErrorKind) -> Self {
+		self.kind = kind;
+		self
+	}
+
+	pub(crate) fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+		self.retry_after = retry_after;
+		self
+	}
+
+	pub(crate) fn with_url(mut self, url: &str) -> Self {
+		self.context.get_or_insert_with(Box::default).url = Some(url.to_string());
+		self
+	}
+
+	/// Keeps the start of a response body, lossily decoded as UTF-8
+	pub(crate) fn with_body(mut self, body: Option<&[u8]>) -> Self {
+		if let Some(body) = body {
+			let body = String::from_utf8_lossy(&body[..body.len().min(MAX_ERROR_BODY_LEN)]);
+			let body = body.trim_end_matches(char::REPLACEMENT_CHARACTER).to_string();
+			self.context.get_or_insert_with(Box::default).body = Some(body);
+		}
+
+		self
+	}
+
+	pub(crate) fn with_status(mut self, status: StatusCode) -> Self {
+		if status == StatusCode::FORBIDDEN && self.kind == RoboltErrorKind::Api {
+			self.kind = RoboltErrorKind::PermissionDenied;
+		}
+
+		if status == StatusCode::TOO_MANY_REQUESTS {
+			self.kind = RoboltErrorKind::RateLimited;
+		}
+
+		self.status = Some(status.as_u16());
+		self
+	}
 }
 
 #[doc(hidden)]
@@ -33,6 +406,37 @@ impl fmt::Display for RoboltError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let kind = match self.kind {
 			RoboltErrorKind::Api => format!("API Error (code: {})", self.code),
+			RoboltErrorKind::PermissionDenied => format!("Permission Denied (code: {})", self.code),
+			RoboltErrorKind::RateLimited => format!("Rate Limited (code: {})", self.code),
+			RoboltErrorKind::GroupLocked => "Group Locked".to_string(),
+			RoboltErrorKind::ResponseTooLarge {
+				..
+			} => "Response Too Large".to_string(),
+			RoboltErrorKind::MessageTooLarge {
+				..
+			} => "Message Too Large".to_string(),
+			RoboltErrorKind::AgeRestricted => format!("Age Restricted (code: {})", self.code),
+			RoboltErrorKind::ChallengeRequired {
+				ref challenge_type, ..
+			} => match challenge_type {
+				Some(challenge_type) => format!("Challenge Required ({challenge_type})"),
+				None => "Challenge Required".to_string(),
+			},
+			RoboltErrorKind::DisplayNameCooldown => format!("Display Name Cooldown (code: {})", self.code),
+			RoboltErrorKind::MessageNotSent => "Message Not Sent".to_string(),
+			RoboltErrorKind::HostNotAllowed => "Host Not Allowed".to_string(),
+			RoboltErrorKind::DryRunSkipped => "Dry Run Skipped".to_string(),
+			RoboltErrorKind::NotRecorded => "Not Recorded".to_string(),
+			RoboltErrorKind::NotFound {
+				resource,
+				id,
+				reason,
+			} => {
+				format!("{resource:?} {id} Not Found ({reason:?})")
+			},
+			RoboltErrorKind::Http => "HTTP Error".to_string(),
+			RoboltErrorKind::Deserialize => "Deserialize Error".to_string(),
+			RoboltErrorKind::InvalidCookie => "Invalid Cookie".to_string(),
 			RoboltErrorKind::Unknown => "Unknown".to_string(),
 		};
 
@@ -40,6 +444,8 @@ impl fmt::Display for RoboltError {
 	}
 }
 
+impl std::error::Error for RoboltError {}
+
 #[doc(hidden)]
 impl From<String> for RoboltError {
 	fn from(error: String) -> Self {
@@ -47,6 +453,9 @@ impl From<String> for RoboltError {
 			kind: RoboltErrorKind::Unknown,
 			code: default_error_code(),
 			message: error,
+			status: None,
+			retry_after: None,
+			context: None,
 		}
 	}
 }
@@ -55,9 +464,54 @@ impl From<String> for RoboltError {
 impl From<reqwest::Error> for RoboltError {
 	fn from(error: reqwest::Error) -> Self {
 		Self {
-			kind: RoboltErrorKind::Unknown,
+			kind: RoboltErrorKind::Http,
 			code: default_error_code(),
 			message: error.to_string(),
+			status: error.status().map(|status| status.as_u16()),
+			retry_after: None,
+			context: None,
 		}
 	}
 }
+
+#[doc(hidden)]
+impl From<serde_json::Error> for RoboltError {
+	fn from(error: serde_json::Error) -> Self {
+		Self {
+			kind: RoboltErrorKind::Deserialize,
+			code: default_error_code(),
+			message: error.to_string(),
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+}
+
+#[doc(hidden)]
+impl From<CloudError> for RoboltError {
+	fn from(error: CloudError) -> Self {
+		let message = match missing_scope(&error.message) {
+			Some(scope) => format!("Missing API key scope `{scope}`: {}", error.message),
+			None => format!("{}: {}", error.code, error.message),
+		};
+
+		Self {
+			kind: RoboltErrorKind::Api,
+			code: default_error_code(),
+			message,
+			status: None,
+			retry_after: None,
+			context: None,
+		}
+	}
+}
+
+/// Extracts the scope name from Open Cloud messages such as
+/// `Insufficient scope. Required scope: group:read`
+fn missing_scope(message: &str) -> Option<&str> {
+	let (_, rest) = message.split_once("scope:")?;
+	rest.split_whitespace()
+		.next()
+		.map(|scope| scope.trim_end_matches(['.', ',']))
+}
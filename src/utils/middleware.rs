@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode, Url};
+use serde_json::Value;
+
+use crate::api::routes::RobloxApi;
+use crate::utils::errors::RoboltError;
+use crate::utils::retry::Idempotency;
+
+/// A hook that runs around every request sent by the client
+///
+/// Middlewares run in the order they were registered in for
+/// [Middleware::before_send] and in reverse order for
+/// [Middleware::after_receive]. Returning an error from
+/// [Middleware::before_send] aborts the request, the remaining middlewares
+/// are skipped and the error is returned to the caller.
+pub trait Middleware: Send + Sync {
+	fn before_send(&self, _parts: &mut RequestParts) -> Result<(), RoboltError> {
+		Ok(())
+	}
+
+	fn after_receive(&self, _meta: &ResponseMeta) {}
+}
+
+/// Body fields that are replaced in the [Debug] output of [RequestParts]
+const REDACTED_FIELDS: [&str; 1] = ["password"];
+/// Headers that are replaced in the [Debug] output of [RequestParts]
+const REDACTED_HEADERS: [&str; 3] = ["cookie", "x-csrf-token", "x-api-key"];
+
+#[derive(Clone)]
+/// The parts of a request that middlewares are allowed to modify
+///
+/// The [Debug] output redacts sensitive body fields such as passwords, along
+/// with the `Cookie`, `X-CSRF-TOKEN` and `X-API-Key` headers.
+pub struct RequestParts {
+	pub method: Method,
+	pub url: String,
+	pub headers: HeaderMap,
+	pub body: Option<Value>,
+	/// Whether the request is being sent again after a failed attempt
+	pub is_retry: bool,
+	pub idempotency: Idempotency,
+}
+
+impl fmt::Debug for RequestParts {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RequestParts")
+			.field("method", &self.method)
+			.field("url", &self.url)
+			.field("headers", &redact_headers(&self.headers))
+			.field("body", &self.body.as_ref().map(redact))
+			.field("is_retry", &self.is_retry)
+			.field("idempotency", &self.idempotency)
+			.finish()
+	}
+}
+
+fn redact_headers(headers: &HeaderMap) -> HeaderMap {
+	let mut headers = headers.clone();
+
+	for name in REDACTED_HEADERS {
+		if headers.contains_key(name) {
+			headers.insert(name, HeaderValue::from_static("[redacted]"));
+		}
+	}
+
+	headers
+}
+
+pub(crate) fn redact(value: &Value) -> Value {
+	match value {
+		Value::Object(map) => Value::Object(
+			map.iter()
+				.map(|(key, value)| match REDACTED_FIELDS.contains(&key.as_str()) {
+					true => (key.clone(), Value::from("[redacted]")),
+					false => (key.clone(), redact(value)),
+				})
+				.collect(),
+		),
+		Value::Array(values) => Value::Array(values.iter().map(redact).collect()),
+		value => value.clone(),
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+	pub method: Method,
+	pub url: String,
+	pub status: StatusCode,
+	pub elapsed: Duration,
+	pub is_retry: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+/// Adds a fixed set of headers to every request, replacing existing values
+pub struct HeaderInjector {
+	headers: HeaderMap,
+}
+
+impl HeaderInjector {
+	pub fn new(headers: HeaderMap) -> Self {
+		Self {
+			headers,
+		}
+	}
+}
+
+impl Middleware for HeaderInjector {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		for (name, value) in self.headers.iter() {
+			parts.headers.insert(name, value.clone());
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+/// Logs the method, URL, status and duration of every request to stderr
+///
+/// Headers and bodies are never logged.
+pub struct RequestLogger;
+
+impl Middleware for RequestLogger {
+	fn after_receive(&self, meta: &ResponseMeta) {
+		eprintln!(
+			"[Robolt] {} {} -> {} ({}ms){}",
+			meta.method,
+			meta.url,
+			meta.status,
+			meta.elapsed.as_millis(),
+			if meta.is_retry { " (retry)" } else { "" }
+		);
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The requests sent to a single API, see [RequestStats]
+pub struct ApiStats {
+	/// Every request sent, retries included
+	pub requests: u64,
+	/// Requests sent again after a failed attempt
+	pub retries: u64,
+	/// Responses with a status other than 2xx
+	pub failures: u64,
+	/// The total time spent waiting for responses
+	pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+/// Counts the requests sent to each API, keyed by host (e.g.
+/// `groups.roblox.com`)
+///
+/// Clones share the same counts, so a clone kept before registering the
+/// middleware can be read while the client is in use. Requests skipped by a
+/// [dry run](crate::dry_run) are never sent and are not counted.
+pub struct RequestStats(Arc<Mutex<BTreeMap<String, ApiStats>>>);
+
+impl RequestStats {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The requests sent to `api`, all zero if none were
+	pub fn api(&self, api: RobloxApi) -> ApiStats {
+		self.host(api.url())
+	}
+
+	/// The requests sent to `host`, all zero if none were
+	pub fn host(&self, host: &str) -> ApiStats {
+		self.0
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(host)
+			.cloned()
+			.unwrap_or_default()
+	}
+
+	/// The requests sent to every host so far
+	pub fn snapshot(&self) -> BTreeMap<String, ApiStats> {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+
+	pub fn reset(&self) {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+}
+
+impl Middleware for RequestStats {
+	fn after_receive(&self, meta: &ResponseMeta) {
+		let Some(host) = Url::parse(&meta.url)
+			.ok()
+			.and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+		else {
+			return;
+		};
+
+		let mut hosts = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+		let stats = hosts.entry(host).or_default();
+
+		stats.requests += 1;
+		stats.retries += meta.is_retry as u64;
+		stats.failures += !meta.status.is_success() as u64;
+		stats.elapsed += meta.elapsed;
+	}
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Middlewares(pub(crate) Vec<Arc<dyn Middleware>>);
+
+impl fmt::Debug for Middlewares {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Middlewares({})", self.0.len())
+	}
+}
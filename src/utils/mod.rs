@@ -1,3 +1,27 @@
+pub mod analysis;
+pub mod batch;
+pub mod cache;
+#[cfg(feature = "cli")]
+#[doc(cfg(feature = "cli"))]
+pub mod cli;
 pub mod client;
+pub mod csv;
+pub mod dry_run;
 pub mod errors;
+pub mod middleware;
+pub mod moderation;
+pub mod paging;
+pub mod raw;
 pub(crate) mod response;
+pub mod retry;
+pub mod schema;
+pub mod snapshots;
+#[cfg(feature = "test-util")]
+#[doc(cfg(feature = "test-util"))]
+pub mod test_util;
+pub mod time;
+#[cfg(feature = "chrono")]
+#[doc(cfg(feature = "chrono"))]
+pub mod timestamp;
+#[cfg(feature = "tracing")]
+pub(crate) mod trace;
@@ -0,0 +1,167 @@
+//! Audit trail of destructive group actions
+//!
+//! Content removed through the API disappears immediately, so a
+//! [ModerationLog] set with
+//! [Robolt::moderation_log](crate::Robolt::moderation_log) is handed the full
+//! content before it is removed. Every action is recorded twice: as
+//! [ModerationOutcome::Pending] before the destructive request is sent, and
+//! with its outcome once it has completed.
+//!
+//! If the pending record cannot be written, the action is not performed.
+
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use serde_json::{json, Value};
+
+use crate::api::groups::WallPost;
+use crate::api::users::PartialUser;
+use crate::errors::RoboltError;
+use crate::utils::time::Clock;
+
+/// Records the moderation actions performed by a client
+pub trait ModerationLog: Debug + Send + Sync {
+	/// Records `action`, failing the action if this fails while it is still
+	/// [ModerationOutcome::Pending]
+	fn record(&self, action: ModerationAction) -> Result<(), RoboltError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A moderation action along with the content it affects
+pub struct ModerationAction {
+	pub group_id: u64,
+	/// The authenticated user performing the action
+	pub actor: PartialUser,
+	/// When the action was recorded, in seconds since the Unix epoch
+	pub timestamp: u64,
+	pub target: ModerationTarget,
+	pub outcome: ModerationOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The content affected by a [ModerationAction]
+pub enum ModerationTarget {
+	/// A wall post being deleted, as it was before the deletion
+	DeletedWallPost(WallPost),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModerationOutcome {
+	/// The destructive request has not been sent yet
+	Pending,
+	Succeeded,
+	/// The request failed with the given error message
+	Failed(String),
+	/// The request was recorded instead of being sent, see
+	/// [dry_run](crate::dry_run)
+	DryRunSkipped,
+}
+
+#[derive(Debug)]
+/// A [ModerationLog] appending each action to a file as a line of JSON
+///
+/// ```json
+/// {"action":"deleteWallPost","actor":{"id":1,"username":"Moderator"},"groupId":1,"outcome":"pending","post":{...},"timestamp":1700000000}
+/// ```
+pub struct JsonlModerationLog {
+	path: PathBuf,
+	file: Mutex<File>,
+}
+
+impl JsonlModerationLog {
+	/// Appends to the file at `path`, creating it if needed
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, RoboltError> {
+		let path = path.as_ref().to_path_buf();
+		let file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&path)
+			.map_err(|err| format!("Failed to open moderation log {}: {err}", path.display()))?;
+
+		Ok(Self {
+			path,
+			file: Mutex::new(file),
+		})
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+}
+
+impl ModerationLog for JsonlModerationLog {
+	fn record(&self, action: ModerationAction) -> Result<(), RoboltError> {
+		let mut line = serde_json::to_vec(&action_json(&action))?;
+		line.push(b'\n');
+
+		let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+		file.write_all(&line)
+			.and_then(|_| file.flush())
+			.map_err(|err| format!("Failed to write to moderation log {}: {err}", self.path.display()).into())
+	}
+}
+
+/// Records the actions of a single operation as the same actor
+pub(crate) struct Recorder<'a> {
+	pub(crate) log: &'a dyn ModerationLog,
+	pub(crate) clock: &'a dyn Clock,
+	pub(crate) group_id: u64,
+	pub(crate) actor: PartialUser,
+}
+
+impl Recorder<'_> {
+	pub(crate) fn record(&self, target: ModerationTarget, outcome: ModerationOutcome) -> Result<(), RoboltError> {
+		self.log.record(ModerationAction {
+			group_id: self.group_id,
+			actor: self.actor.clone(),
+			timestamp: self.clock.unix_time().as_secs(),
+			target,
+			outcome,
+		})
+	}
+}
+
+fn action_json(action: &ModerationAction) -> Value {
+	let mut record = json!({
+		"timestamp": action.timestamp,
+		"groupId": action.group_id,
+		"actor": {
+			"id": action.actor.id,
+			"username": action.actor.username,
+		},
+	});
+
+	match &action.target {
+		ModerationTarget::DeletedWallPost(post) => {
+			record["action"] = json!("deleteWallPost");
+			record["post"] = json!({
+				"id": post.id,
+				"body": post.body,
+				"created": post.created,
+				"updated": post.updated,
+				"poster": post.poster.as_ref().map(|poster| json!({
+					"id": poster.user.id,
+					"username": poster.user.username,
+					"displayName": poster.user.display_name,
+					"roleId": poster.role.id,
+					"roleName": poster.role.name,
+				})),
+			});
+		},
+	}
+
+	match &action.outcome {
+		ModerationOutcome::Pending => record["outcome"] = json!("pending"),
+		ModerationOutcome::Succeeded => record["outcome"] = json!("succeeded"),
+		ModerationOutcome::DryRunSkipped => record["outcome"] = json!("dryRunSkipped"),
+		ModerationOutcome::Failed(message) => {
+			record["outcome"] = json!("failed");
+			record["error"] = json!(message);
+		},
+	}
+
+	record
+}
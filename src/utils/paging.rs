@@ -0,0 +1,351 @@
+//! Pagination over the paging styles used by Roblox
+//!
+//! Endpoints are paginated with either cursors ([CursorPaging]), Open Cloud
+//! page tokens ([TokenPaging]) or page numbers ([NumberPaging]). Wrappers pick
+//! the style of their endpoint and return a [PageIterator], which behaves the
+//! same way regardless of it. Every style is resumed through an opaque string
+//! cursor, see [PageIterator::cursor].
+//!
+//! Iterators created by the client stop after
+//! [Robolt::max_pages_per_crawl](crate::Robolt::max_pages_per_crawl) pages,
+//! so an endpoint that never stops handing out cursors cannot page forever.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+use crate::api::cloud::CloudPage;
+use crate::api::Page;
+use crate::utils::errors::RoboltError;
+
+/// How the items and the next cursor are read from a page
+pub trait Paging {
+	type Page;
+	type Item;
+
+	/// Splits a page fetched at `cursor` into its items and the cursor of the
+	/// next page, `None` once the last page has been reached
+	fn split(&self, cursor: Option<&str>, page: Self::Page) -> (Vec<Self::Item>, Option<String>);
+}
+
+/// Pages continued by [Page::next_page_cursor]
+pub struct CursorPaging<T>(PhantomData<fn() -> T>);
+
+impl<T> CursorPaging<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> Default for CursorPaging<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Paging for CursorPaging<T> {
+	type Item = T;
+	type Page = Page<T>;
+
+	fn split(&self, _: Option<&str>, page: Page<T>) -> (Vec<T>, Option<String>) {
+		(page.data, page.next_page_cursor)
+	}
+}
+
+/// Open Cloud pages continued by [CloudPage::next_page_token]
+pub struct TokenPaging<T>(PhantomData<fn() -> T>);
+
+impl<T> TokenPaging<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> Default for TokenPaging<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Paging for TokenPaging<T> {
+	type Item = T;
+	type Page = CloudPage<T>;
+
+	fn split(&self, _: Option<&str>, page: CloudPage<T>) -> (Vec<T>, Option<String>) {
+		(page.items, page.next_page_token)
+	}
+}
+
+/// Pages requested by page number and size, the page number being used as the
+/// cursor
+pub struct NumberPaging<T> {
+	page_size: u64,
+	first_page: u64,
+	item: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for NumberPaging<T> {
+	fn clone(&self) -> Self {
+		Self {
+			page_size: self.page_size,
+			first_page: self.first_page,
+			item: PhantomData,
+		}
+	}
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// A page of a page-numbered endpoint
+///
+/// The last page is detected from whichever of `total` and `final_page` the
+/// endpoint returns, or from a page with fewer items than the page size.
+pub struct NumberedPage<T> {
+	pub items: Vec<T>,
+	/// The total number of items across every page
+	pub total: Option<u64>,
+	pub final_page: Option<bool>,
+}
+
+impl<T> NumberPaging<T> {
+	/// Pages of `page_size` items, starting at page 1
+	pub fn new(page_size: u64) -> Self {
+		Self {
+			page_size: page_size.max(1),
+			first_page: 1,
+			item: PhantomData,
+		}
+	}
+
+	/// The page fetched when no cursor is given
+	pub fn first_page(mut self, page: u64) -> Self {
+		self.first_page = page.max(1);
+		self
+	}
+
+	/// The page number of `cursor`, the first page if there is none
+	pub fn page_number(&self, cursor: Option<&str>) -> Result<u64, RoboltError> {
+		match cursor {
+			Some(cursor) => cursor
+				.parse::<u64>()
+				.ok()
+				.filter(|page| *page > 0)
+				.ok_or_else(|| format!("Invalid page number cursor: {cursor}").into()),
+			None => Ok(self.first_page),
+		}
+	}
+}
+
+impl<T> Paging for NumberPaging<T> {
+	type Item = T;
+	type Page = NumberedPage<T>;
+
+	fn split(&self, cursor: Option<&str>, page: NumberedPage<T>) -> (Vec<T>, Option<String>) {
+		let number = self.page_number(cursor).unwrap_or(self.first_page);
+		let fetched = number.saturating_mul(self.page_size);
+
+		let is_last = page.final_page.unwrap_or(false) ||
+			page.total.is_some_and(|total| fetched >= total) ||
+			(page.items.len() as u64) < self.page_size;
+
+		(page.items, (!is_last).then(|| (number + 1).to_string()))
+	}
+}
+
+type FetchPage<'a, T> =
+	Box<dyn FnMut(Option<String>) -> BoxFuture<'a, Result<(Vec<T>, Option<String>), RoboltError>> + Send + 'a>;
+
+/// Fetches the pages of an endpoint one at a time
+///
+/// A failed page can be retried by calling [PageIterator::next_page] again,
+/// or resumed later by passing [PageIterator::cursor] to
+/// [PageIterator::resume_from]. The iteration stops if the API hands out a
+/// cursor that was already visited, or once [PageIterator::max_pages] pages
+/// have been fetched, see [PageIterator::exhaustion_reason].
+pub struct PageIterator<'a, T> {
+	fetch: FetchPage<'a, T>,
+	cursor: Option<String>,
+	visited: HashSet<String>,
+	exhausted: Option<ExhaustionReason>,
+	pages_fetched: usize,
+	max_pages: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Why a [PageIterator] stopped fetching pages
+pub enum ExhaustionReason {
+	/// The last page was fetched
+	Completed,
+	/// The API handed out a cursor that was already visited
+	CursorLoop,
+	/// The page limit was reached with pages left, which can still be fetched
+	/// by resuming from [PageIterator::cursor]
+	CrawlTruncated,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+/// The items fetched by [PageIterator::collect_all]
+pub struct Collected<T> {
+	pub items: Vec<T>,
+	/// Whether the page limit was reached with pages left
+	pub truncated: bool,
+}
+
+impl<'a, T: Send + 'a> PageIterator<'a, T> {
+	/// Pages through the endpoint fetched by `fetch`, which is given the
+	/// cursor of the page to fetch
+	pub fn new<P, F, Fut>(paging: P, mut fetch: F) -> Self
+	where
+		P: Paging<Item = T> + Send + Sync + 'a,
+		P::Page: Send,
+		F: FnMut(Option<String>) -> Fut + Send + 'a,
+		Fut: Future<Output = Result<P::Page, RoboltError>> + Send + 'a,
+	{
+		let paging = Arc::new(paging);
+
+		Self {
+			fetch: Box::new(move |cursor| {
+				let paging = paging.clone();
+				let page = fetch(cursor.clone());
+
+				Box::pin(async move { Ok(paging.split(cursor.as_deref(), page.await?)) })
+			}),
+			cursor: None,
+			visited: HashSet::new(),
+			exhausted: None,
+			pages_fetched: 0,
+			max_pages: None,
+		}
+	}
+
+	/// Stops after `max_pages` pages, overriding
+	/// [Robolt::max_pages_per_crawl](crate::Robolt::max_pages_per_crawl)
+	pub fn max_pages(mut self, max_pages: usize) -> Self {
+		self.max_pages = Some(max_pages);
+		self
+	}
+
+	/// Starts at a cursor returned by [PageIterator::cursor]
+	pub fn resume_from(mut self, cursor: &str) -> Self {
+		self.cursor = Some(cursor.to_string());
+		self
+	}
+
+	/// The cursor of the next page, `None` before the first page and once the
+	/// last page has been fetched
+	///
+	/// The cursor is kept when the page limit is reached, so a truncated crawl
+	/// can be continued with [PageIterator::resume_from].
+	pub fn cursor(&self) -> Option<&str> {
+		self.cursor.as_deref()
+	}
+
+	pub fn is_done(&self) -> bool {
+		self.exhausted.is_some()
+	}
+
+	/// Why the iteration stopped, `None` until it has
+	pub fn exhaustion_reason(&self) -> Option<ExhaustionReason> {
+		self.exhausted
+	}
+
+	pub fn pages_fetched(&self) -> usize {
+		self.pages_fetched
+	}
+
+	/// Fetches the items of the next page, `None` once every page has been
+	/// fetched
+	pub async fn next_page(&mut self) -> Option<Result<Vec<T>, RoboltError>> {
+		if self.exhausted.is_some() {
+			return None;
+		}
+
+		if self.limit_reached() {
+			self.exhausted = Some(ExhaustionReason::CrawlTruncated);
+			return None;
+		}
+
+		let (items, next) = match (self.fetch)(self.cursor.clone()).await {
+			Ok(page) => page,
+			Err(err) => return Some(Err(err)),
+		};
+
+		self.pages_fetched += 1;
+
+		if let Some(cursor) = self.cursor.take() {
+			self.visited.insert(cursor);
+		}
+
+		match next {
+			Some(next) if self.visited.contains(&next) => self.exhausted = Some(ExhaustionReason::CursorLoop),
+			Some(next) => {
+				self.cursor = Some(next);
+
+				if self.limit_reached() {
+					self.exhausted = Some(ExhaustionReason::CrawlTruncated);
+				}
+			},
+			None => self.exhausted = Some(ExhaustionReason::Completed),
+		}
+
+		Some(Ok(items))
+	}
+
+	fn limit_reached(&self) -> bool {
+		self.max_pages.is_some_and(|max_pages| self.pages_fetched >= max_pages)
+	}
+
+	/// Fetches pages until at least `count` items have been fetched, returning
+	/// the first `count`
+	pub async fn take(mut self, count: usize) -> Result<Vec<T>, RoboltError> {
+		let mut items = Vec::new();
+
+		while items.len() < count {
+			match self.next_page().await {
+				Some(page) => items.extend(page?),
+				None => break,
+			}
+		}
+
+		items.truncate(count);
+		Ok(items)
+	}
+
+	/// Fetches every remaining page, up to the page limit
+	pub async fn collect_all(mut self) -> Result<Collected<T>, RoboltError> {
+		let mut items = Vec::new();
+
+		while let Some(page) = self.next_page().await {
+			items.extend(page?);
+		}
+
+		Ok(Collected {
+			items,
+			truncated: self.exhausted == Some(ExhaustionReason::CrawlTruncated),
+		})
+	}
+
+	/// Writes every remaining item to `writer` as CSV, one page at a time, see
+	/// [write_rows](crate::csv::write_rows)
+	#[cfg(feature = "csv")]
+	#[doc(cfg(feature = "csv"))]
+	pub async fn write_csv<W: std::io::Write>(mut self, mut writer: W) -> Result<(), RoboltError>
+	where
+		T: crate::csv::ToRow,
+	{
+		let write = |writer: &mut W, values| crate::utils::csv::write_row(writer, values);
+		let headers = T::headers().into_iter().map(String::from).collect();
+
+		write(&mut writer, headers).map_err(|err| err.to_string())?;
+
+		while let Some(page) = self.next_page().await {
+			for item in page? {
+				write(&mut writer, item.values()).map_err(|err| err.to_string())?;
+			}
+		}
+
+		writer.flush().map_err(|err| err.to_string().into())
+	}
+}
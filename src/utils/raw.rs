@@ -0,0 +1,196 @@
+use reqwest::{Method, Url};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api::routes::encode_query_value;
+use crate::utils::client::RequestBuilder;
+use crate::utils::errors::RoboltError;
+use crate::utils::retry::Idempotency;
+use crate::Robolt;
+
+/// The host that raw requests are always allowed to reach, along with its
+/// subdomains
+const ROBLOX_HOST: &str = "roblox.com";
+
+impl<State> Robolt<State> {
+	/// Builds requests to endpoints that are not wrapped by the client, see
+	/// [RawClient]
+	pub fn raw(&self) -> RawClient<'_, State> {
+		RawClient {
+			robolt: self,
+		}
+	}
+
+	/// Allows raw requests to `host` and its subdomains, on top of
+	/// `roblox.com`, see [Robolt::raw]
+	pub fn allow_raw_host(mut self, host: &str) -> Self {
+		self.raw_hosts.push(host.to_ascii_lowercase());
+		self
+	}
+}
+
+/// Sends requests to arbitrary endpoints with the client's authentication,
+/// middlewares, retry policy and error handling
+///
+/// Requests can only be sent over HTTPS to `roblox.com` and its subdomains
+/// unless other hosts are allowed with [Robolt::allow_raw_host], so that the
+/// cookie cannot be sent to another domain by accident.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), robolt::errors::RoboltError> {
+/// let client = robolt::Robolt::new();
+/// let url = "https://groups.roblox.com/v1/groups/1/audit-log";
+/// let request = client.raw().get(url).query("limit", "10");
+/// let log: serde_json::Value = request.send_json().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawClient<'a, State> {
+	robolt: &'a Robolt<State>,
+}
+
+impl<'a, State> RawClient<'a, State> {
+	pub fn get(&self, url: &str) -> RawRequest<'a, State> {
+		self.request(Method::GET, url)
+	}
+
+	pub fn post(&self, url: &str) -> RawRequest<'a, State> {
+		self.request(Method::POST, url)
+	}
+
+	pub fn patch(&self, url: &str) -> RawRequest<'a, State> {
+		self.request(Method::PATCH, url)
+	}
+
+	pub fn delete(&self, url: &str) -> RawRequest<'a, State> {
+		self.request(Method::DELETE, url)
+	}
+
+	pub fn request(&self, method: Method, url: &str) -> RawRequest<'a, State> {
+		RawRequest {
+			robolt: self.robolt,
+			method,
+			url: url.to_string(),
+			query: Vec::new(),
+			body: None,
+			body_error: None,
+			idempotency: Idempotency::Unknown,
+			max_response_size: None,
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+/// A request built with [RawClient]
+pub struct RawRequest<'a, State> {
+	robolt: &'a Robolt<State>,
+	method: Method,
+	url: String,
+	query: Vec<(String, String)>,
+	body: Option<Value>,
+	/// Why the body given to [RawRequest::json] could not be serialized,
+	/// returned when the request is sent
+	body_error: Option<String>,
+	idempotency: Idempotency,
+	max_response_size: Option<u64>,
+}
+
+impl<'a, State> RawRequest<'a, State> {
+	/// Appends a query parameter to the URL, encoding the key and value
+	pub fn query(mut self, key: &str, value: &str) -> Self {
+		self.query.push((key.to_string(), value.to_string()));
+		self
+	}
+
+	/// Sets the JSON body of the request
+	///
+	/// If the body cannot be serialized to JSON, sending the request fails
+	/// without reaching the network.
+	pub fn json<T: Serialize>(mut self, body: &T) -> Self {
+		match serde_json::to_value(body) {
+			Ok(body) => {
+				self.body = Some(body);
+				self.body_error = None;
+			},
+			Err(err) => self.body_error = Some(format!("Failed to serialize request body: {err}")),
+		}
+
+		self
+	}
+
+	/// Marks whether the request can be retried after a transient server
+	/// error, raw requests are [Idempotency::Unknown] by default and never
+	/// retried after one
+	pub fn idempotency(mut self, idempotency: Idempotency) -> Self {
+		self.idempotency = idempotency;
+		self
+	}
+
+	/// Overrides [Robolt::max_response_size] for this request, for endpoints
+	/// that legitimately return large bodies
+	pub fn max_response_size(mut self, max_response_size: u64) -> Self {
+		self.max_response_size = Some(max_response_size);
+		self
+	}
+
+	/// Sends the request and deserializes the JSON response
+	pub async fn send_json<T: DeserializeOwned>(self) -> Result<T, RoboltError> {
+		if let Some(err) = self.body_error {
+			return Err(err.into());
+		}
+
+		let robolt = self.robolt;
+		let url = self.url()?;
+		let mut request = RequestBuilder::from_url(url, robolt)
+			.method(self.method)
+			.idempotency(self.idempotency);
+
+		if let Some(max_response_size) = self.max_response_size {
+			request = request.max_response_size(max_response_size);
+		}
+
+		match self.body {
+			Some(body) => request.send_body(body).await,
+			None => request.send().await,
+		}
+	}
+
+	/// Sends the request and discards the response body
+	pub async fn send(self) -> Result<(), RoboltError> {
+		self.send_json::<IgnoredAny>().await.map(|_| ())
+	}
+
+	/// Builds the final URL, checking that its host is allowed
+	fn url(&self) -> Result<Url, RoboltError> {
+		let mut url = Url::parse(&self.url).map_err(|err| format!("Invalid URL {}: {err}", self.url))?;
+		let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+
+		let allowed = std::iter::once(ROBLOX_HOST)
+			.chain(self.robolt.raw_hosts.iter().map(String::as_str))
+			.any(|allowed| host == allowed || host.ends_with(&format!(".{allowed}")));
+
+		if url.scheme() != "https" || !allowed {
+			return Err(RoboltError::host_not_allowed(&self.url));
+		}
+
+		if !self.query.is_empty() {
+			let query = self
+				.query
+				.iter()
+				.map(|(key, value)| format!("{}={}", encode_query_value(key), encode_query_value(value)))
+				.collect::<Vec<String>>()
+				.join("&");
+
+			let query = match url.query() {
+				Some(existing) if !existing.is_empty() => format!("{existing}&{query}"),
+				_ => query,
+			};
+
+			url.set_query(Some(&query));
+		}
+
+		Ok(url)
+	}
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::utils::errors::RoboltError;
+
+type RetryHook = dyn Fn(u32, &RoboltError, Duration) + Send + Sync;
+
+/// Controls how failed requests are retried
+///
+/// Requests rejected because of an expired CSRF token are always retried once
+/// with the token sent back by Roblox. Rate limited requests, and
+/// [Idempotency::Idempotent] requests that failed with a transient server
+/// error, are retried up to [RetryPolicy::max_retries] times, waiting for the
+/// `Retry-After` header if present and backing off exponentially from
+/// [RetryPolicy::base_delay] otherwise, never longer than
+/// [RetryPolicy::max_delay].
+#[derive(Clone)]
+pub struct RetryPolicy {
+	max_retries: u32,
+	base_delay: Duration,
+	max_delay: Duration,
+	on_retry: Option<Arc<RetryHook>>,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_retries: 0,
+			base_delay: Duration::from_secs(1),
+			max_delay: Duration::from_secs(60),
+			on_retry: None,
+		}
+	}
+}
+
+impl RetryPolicy {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The number of times a request is retried, none by default
+	pub fn max_retries(mut self, max_retries: u32) -> Self {
+		self.max_retries = max_retries;
+		self
+	}
+
+	pub fn base_delay(mut self, base_delay: Duration) -> Self {
+		self.base_delay = base_delay;
+		self
+	}
+
+	/// The longest delay before a retry, also capping `Retry-After` headers
+	pub fn max_delay(mut self, max_delay: Duration) -> Self {
+		self.max_delay = max_delay;
+		self
+	}
+
+	/// Registers a callback that is called before every retry with the attempt
+	/// number (starting at 1), the error that caused the retry and the delay
+	/// before the request is sent again
+	///
+	/// A panic in the callback is caught and ignored, it never aborts the
+	/// request.
+	pub fn on_retry<F>(mut self, on_retry: F) -> Self
+	where
+		F: Fn(u32, &RoboltError, Duration) + Send + Sync + 'static,
+	{
+		self.on_retry = Some(Arc::new(on_retry));
+		self
+	}
+
+	/// The delay before the given retry (starting at 1), when the response did
+	/// not include a `Retry-After` header
+	pub fn delay(&self, attempt: u32) -> Duration {
+		let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+		self.base_delay.saturating_mul(factor).min(self.max_delay)
+	}
+
+	/// The delay before the given retry (starting at 1), waiting for the
+	/// `Retry-After` header of the response if any
+	pub(crate) fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+		match retry_after {
+			Some(retry_after) => retry_after.min(self.max_delay),
+			None => self.delay(attempt),
+		}
+	}
+
+	/// Whether a request that failed with `status` should be sent again for
+	/// the given attempt (starting at 1)
+	///
+	/// Rate limited requests were never processed and are retried whatever
+	/// their idempotency, while transient server errors (500, 502, 503 and
+	/// 504) are only retried for [Idempotency::Idempotent] requests.
+	pub fn should_retry(&self, status: StatusCode, idempotency: Idempotency, attempt: u32) -> bool {
+		if attempt > self.max_retries {
+			return false;
+		}
+
+		match status {
+			StatusCode::TOO_MANY_REQUESTS => true,
+			StatusCode::INTERNAL_SERVER_ERROR |
+			StatusCode::BAD_GATEWAY |
+			StatusCode::SERVICE_UNAVAILABLE |
+			StatusCode::GATEWAY_TIMEOUT => idempotency == Idempotency::Idempotent,
+			_ => false,
+		}
+	}
+
+	pub(crate) fn notify(&self, attempt: u32, err: &RoboltError, delay: Duration) {
+		#[cfg(feature = "tracing")]
+		crate::utils::trace::retry(attempt, err, delay);
+
+		if let Some(on_retry) = &self.on_retry {
+			let _ = panic::catch_unwind(AssertUnwindSafe(|| on_retry(attempt, err, delay)));
+		}
+	}
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Whether sending a request more than once has the same effect as sending it
+/// once, which decides whether it is retried after a transient server error
+///
+/// `GET` requests are idempotent, and every other request made by the client
+/// is marked explicitly, e.g. batch reads sent as `POST` are idempotent while
+/// mutations are not.
+pub enum Idempotency {
+	Idempotent,
+	NonIdempotent,
+	/// Never retried after a server error
+	#[default]
+	Unknown,
+}
+
+impl fmt::Debug for RetryPolicy {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RetryPolicy")
+			.field("max_retries", &self.max_retries)
+			.field("base_delay", &self.base_delay)
+			.field("max_delay", &self.max_delay)
+			.field("on_retry", &self.on_retry.is_some())
+			.finish()
+	}
+}
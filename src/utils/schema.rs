@@ -0,0 +1,161 @@
+//! Detection of response fields that the models do not know about
+//!
+//! Roblox adds fields to its responses without notice, and models silently
+//! ignore them (see the [api](crate::api) module). When a [SchemaWatch] is set
+//! with [Robolt::schema_watch](crate::Robolt::schema_watch), every successful
+//! response is deserialized while recording the fields that the model skipped,
+//! and each endpoint reports its unknown fields once per client.
+//!
+//! Fields collected by `#[serde(flatten)]` maps count as consumed, and fields
+//! under models buffered by serde (untagged enums, `#[serde(from)]`) are not
+//! tracked.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde_ignored::Path;
+
+type DriftHook = dyn Fn(&SchemaDrift) + Send + Sync;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The fields of a response that its model did not consume
+pub struct SchemaDrift {
+	/// The method and URL of the endpoint, numeric path segments being
+	/// replaced with `{id}`, e.g. `GET https://users.roblox.com/v1/users/{id}`
+	pub endpoint: String,
+	/// The paths of the unknown fields, e.g. `data[].hasVerifiedBadge`
+	pub fields: Vec<String>,
+}
+
+impl fmt::Display for SchemaDrift {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Unknown fields in {}: {}", self.endpoint, self.fields.join(", "))
+	}
+}
+
+#[derive(Clone)]
+/// Reports the fields of responses that their models did not consume, once
+/// per endpoint
+///
+/// Clones of a client share the endpoints that were already reported.
+pub struct SchemaWatch {
+	on_drift: Arc<DriftHook>,
+	reported: Arc<Mutex<HashSet<String>>>,
+}
+
+impl SchemaWatch {
+	/// Calls `on_drift` for the first response of each endpoint that has
+	/// unknown fields
+	///
+	/// A panic in the callback is caught and ignored, it never fails the
+	/// request.
+	pub fn new<F>(on_drift: F) -> Self
+	where
+		F: Fn(&SchemaDrift) + Send + Sync + 'static,
+	{
+		Self {
+			on_drift: Arc::new(on_drift),
+			reported: Arc::default(),
+		}
+	}
+
+	/// Deserializes a response body, reporting the fields that `T` skipped
+	pub(crate) fn deserialize<T: DeserializeOwned>(
+		&self,
+		method: &Method,
+		url: &str,
+		body: &[u8],
+	) -> Result<T, serde_json::Error> {
+		let mut deserializer = serde_json::Deserializer::from_slice(body);
+		let mut fields = Vec::new();
+		let value = serde_ignored::deserialize(&mut deserializer, |path| {
+			let field = field_path(&path);
+
+			if !fields.contains(&field) {
+				fields.push(field);
+			}
+		})?;
+
+		deserializer.end()?;
+
+		if fields.is_empty() {
+			return Ok(value);
+		}
+
+		let endpoint = format!("{method} {}", endpoint(url));
+
+		if self
+			.reported
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.insert(endpoint.clone())
+		{
+			let drift = SchemaDrift {
+				endpoint,
+				fields,
+			};
+
+			let _ = panic::catch_unwind(AssertUnwindSafe(|| (self.on_drift)(&drift)));
+		}
+
+		Ok(value)
+	}
+}
+
+impl fmt::Debug for SchemaWatch {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("SchemaWatch")
+			.field(
+				"reported",
+				&self.reported.lock().unwrap_or_else(PoisonError::into_inner).len(),
+			)
+			.finish()
+	}
+}
+
+/// The URL without its query, numeric path segments being replaced with
+/// `{id}` so requests for different resources share an endpoint
+fn endpoint(url: &str) -> String {
+	let url = url.split(['?', '#']).next().unwrap_or(url);
+	let is_id = |segment: &str| !segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit());
+
+	url.split('/')
+		.enumerate()
+		.map(|(i, segment)| match i > 2 && is_id(segment) {
+			true => "{id}",
+			false => segment,
+		})
+		.collect::<Vec<&str>>()
+		.join("/")
+}
+
+/// Formats a path with `[]` for array elements, so an unknown field of every
+/// element of an array is reported once
+fn field_path(path: &Path) -> String {
+	match path {
+		Path::Root => String::new(),
+		Path::Seq {
+			parent, ..
+		} => format!("{}[]", field_path(parent)),
+		Path::Map {
+			parent,
+			key,
+		} => match field_path(parent) {
+			parent if parent.is_empty() => key.clone(),
+			parent => format!("{parent}.{key}"),
+		},
+		Path::Some {
+			parent,
+		} |
+		Path::NewtypeStruct {
+			parent,
+		} |
+		Path::NewtypeVariant {
+			parent,
+		} => field_path(parent),
+	}
+}
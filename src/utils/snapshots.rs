@@ -0,0 +1,233 @@
+//! Versioned models for storing fetched data long term
+//!
+//! The models of the [api](crate::api) module follow the Roblox responses and
+//! change shape between releases. Snapshots are stable copies of them meant
+//! to be serialized and stored, each carrying its `schemaVersion` and the time
+//! it was captured at.
+//!
+//! ### Stability
+//!
+//! Once released, a versioned snapshot type (e.g. [UserSnapshotV1]) never
+//! changes. New fields are only added in a new version, which can be upgraded
+//! to from every older one, and [UserSnapshot] and [GroupSnapshot] always name
+//! the latest version. Snapshots stored by any release are read back with
+//! `load_any`, which upgrades them to the latest version:
+//!
+//! ```no_run
+//! # use robolt::snapshots::UserSnapshot;
+//! # fn load(stored: &[u8]) -> Result<(), robolt::errors::RoboltError> {
+//! let snapshot = UserSnapshot::load_any(stored)?;
+//! let stored = snapshot.to_bytes()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::api::groups::Group;
+use crate::api::users::User;
+use crate::errors::RoboltError;
+
+/// The latest version of user snapshots
+pub type UserSnapshot = UserSnapshotV2;
+/// The latest version of group snapshots
+pub type GroupSnapshot = GroupSnapshotV1;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A user, as stored before verified badges and external display names were
+/// captured
+pub struct UserSnapshotV1 {
+	/// Always `1`
+	pub schema_version: u32,
+	/// When the user was fetched, in seconds since the Unix epoch
+	pub captured_at: u64,
+	pub id: u64,
+	pub username: String,
+	pub display_name: String,
+	pub description: Option<String>,
+	pub created: String,
+	pub is_banned: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A user
+pub struct UserSnapshotV2 {
+	/// Always `2`
+	pub schema_version: u32,
+	/// When the user was fetched, in seconds since the Unix epoch
+	pub captured_at: u64,
+	pub id: u64,
+	pub username: String,
+	pub display_name: String,
+	pub description: Option<String>,
+	pub created: String,
+	pub is_banned: bool,
+	/// `false` for snapshots upgraded from version 1
+	pub has_verified_badge: bool,
+	/// `None` for snapshots upgraded from version 1
+	pub external_app_display_name: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// A group
+pub struct GroupSnapshotV1 {
+	/// Always `1`
+	pub schema_version: u32,
+	/// When the group was fetched, in seconds since the Unix epoch
+	pub captured_at: u64,
+	pub id: u64,
+	pub name: String,
+	pub description: String,
+	/// `None` if the group had been abandoned by its owner
+	pub owner_id: Option<u64>,
+	pub owner_username: Option<String>,
+	/// The body of the shout, if any
+	pub shout: Option<String>,
+	pub member_count: u64,
+	pub public_entry_allowed: bool,
+	pub is_locked: bool,
+	pub has_verified_badge: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaVersion {
+	schema_version: u32,
+}
+
+impl UserSnapshotV1 {
+	pub const SCHEMA_VERSION: u32 = 1;
+}
+
+impl UserSnapshotV2 {
+	pub const SCHEMA_VERSION: u32 = 2;
+
+	/// Captures `user` as fetched at `captured_at`
+	pub fn captured(user: User, captured_at: SystemTime) -> Self {
+		Self {
+			schema_version: Self::SCHEMA_VERSION,
+			captured_at: unix_seconds(captured_at),
+			id: user.id,
+			username: user.username,
+			display_name: user.display_name,
+			description: user.description,
+			created: user.created,
+			is_banned: user.is_banned,
+			has_verified_badge: user.has_verified_badge,
+			external_app_display_name: user.external_app_display_name,
+		}
+	}
+
+	/// Reads a user snapshot of any version, upgrading it to the latest
+	///
+	/// Fails if the snapshot has no `schemaVersion`, or was stored by a newer
+	/// release with a version this one does not know.
+	pub fn load_any(bytes: &[u8]) -> Result<Self, RoboltError> {
+		match schema_version(bytes)? {
+			UserSnapshotV1::SCHEMA_VERSION => load::<UserSnapshotV1>(bytes).map(Self::from),
+			Self::SCHEMA_VERSION => load(bytes),
+			version => Err(unsupported_version("user", version, Self::SCHEMA_VERSION)),
+		}
+	}
+
+	pub fn to_bytes(&self) -> Result<Vec<u8>, RoboltError> {
+		Ok(serde_json::to_vec(self)?)
+	}
+}
+
+impl From<User> for UserSnapshotV2 {
+	/// Captures `user` at the current time
+	fn from(user: User) -> Self {
+		Self::captured(user, SystemTime::now())
+	}
+}
+
+impl From<UserSnapshotV1> for UserSnapshotV2 {
+	fn from(snapshot: UserSnapshotV1) -> Self {
+		Self {
+			schema_version: Self::SCHEMA_VERSION,
+			captured_at: snapshot.captured_at,
+			id: snapshot.id,
+			username: snapshot.username,
+			display_name: snapshot.display_name,
+			description: snapshot.description,
+			created: snapshot.created,
+			is_banned: snapshot.is_banned,
+			has_verified_badge: false,
+			external_app_display_name: None,
+		}
+	}
+}
+
+impl GroupSnapshotV1 {
+	pub const SCHEMA_VERSION: u32 = 1;
+
+	/// Captures `group` as fetched at `captured_at`
+	pub fn captured(group: Group, captured_at: SystemTime) -> Self {
+		let (owner_id, owner_username) = match group.owner {
+			Some(owner) => (Some(owner.id), Some(owner.username)),
+			None => (None, None),
+		};
+
+		Self {
+			schema_version: Self::SCHEMA_VERSION,
+			captured_at: unix_seconds(captured_at),
+			id: group.id,
+			name: group.name,
+			description: group.description,
+			owner_id,
+			owner_username,
+			shout: group.shout.map(|shout| shout.body),
+			member_count: group.member_count,
+			public_entry_allowed: group.public_entry_allowed,
+			is_locked: group.is_locked.unwrap_or(false),
+			has_verified_badge: group.has_verified_badge,
+		}
+	}
+
+	/// Reads a group snapshot of any version, upgrading it to the latest
+	///
+	/// Fails if the snapshot has no `schemaVersion`, or was stored by a newer
+	/// release with a version this one does not know.
+	pub fn load_any(bytes: &[u8]) -> Result<Self, RoboltError> {
+		match schema_version(bytes)? {
+			Self::SCHEMA_VERSION => load(bytes),
+			version => Err(unsupported_version("group", version, Self::SCHEMA_VERSION)),
+		}
+	}
+
+	pub fn to_bytes(&self) -> Result<Vec<u8>, RoboltError> {
+		Ok(serde_json::to_vec(self)?)
+	}
+}
+
+impl From<Group> for GroupSnapshotV1 {
+	/// Captures `group` at the current time
+	fn from(group: Group) -> Self {
+		Self::captured(group, SystemTime::now())
+	}
+}
+
+fn schema_version(bytes: &[u8]) -> Result<u32, RoboltError> {
+	serde_json::from_slice::<SchemaVersion>(bytes)
+		.map(|version| version.schema_version)
+		.map_err(|err| format!("Snapshot has no valid schemaVersion: {err}").into())
+}
+
+fn load<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, RoboltError> {
+	Ok(serde_json::from_slice(bytes)?)
+}
+
+fn unsupported_version(kind: &str, version: u32, latest: u32) -> RoboltError {
+	format!("Unsupported {kind} snapshot schema version {version}, at most {latest} is supported").into()
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
@@ -0,0 +1,381 @@
+//! Utilities for testing code built on the client without network access
+//!
+//! A [MockTransport] answers requests with canned [MockResponse]s instead of
+//! sending them, and a [FixtureSet] loads recorded responses from a directory
+//! so that crawlers can be snapshot-tested:
+//!
+//! ```no_run
+//! let client = robolt::test_util::client_with_fixtures("tests/fixtures/group_123");
+//! ```
+//!
+//! A [VirtualClock] stands in for the system clock, so that retries, pollers
+//! and schedulers can be tested without waiting.
+
+use std::collections::VecDeque;
+use std::future::ready;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use std::{fs, io};
+
+use futures::future::BoxFuture;
+use futures::stream;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Body, Method, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::utils::errors::RoboltError;
+use crate::utils::middleware::RequestParts;
+use crate::utils::time::{Clock, Sleeper};
+use crate::Robolt;
+
+/// Builds a client whose requests are answered by the fixtures in `dir`, see
+/// [FixtureSet::load]
+///
+/// # Panics
+///
+/// Panics if the fixtures cannot be loaded.
+pub fn client_with_fixtures<P: AsRef<Path>>(dir: P) -> Robolt {
+	let dir = dir.as_ref();
+	let fixtures = FixtureSet::load(dir)
+		.unwrap_or_else(|err| panic!("Failed to load fixtures from {}: {err}", dir.display()));
+
+	Robolt::new().mock_transport(fixtures.transport())
+}
+
+#[derive(Debug, Clone, Default)]
+/// Answers requests with canned responses, recording every request it
+/// receives
+///
+/// Clones share their routes and recorded requests, so a clone can be kept
+/// to inspect the requests sent by a client.
+pub struct MockTransport {
+	routes: Arc<Mutex<Vec<MockRoute>>>,
+	requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+#[derive(Debug)]
+struct MockRoute {
+	method: Method,
+	url: String,
+	responses: VecDeque<MockResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+	pub method: Method,
+	pub url: String,
+	pub headers: HeaderMap,
+	pub body: Option<Value>,
+}
+
+impl MockTransport {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Answers requests to `url` (including its query string) with `response`
+	///
+	/// Responses added to the same route are returned in order, the last one
+	/// being repeated once the others have been used.
+	pub fn route(self, method: Method, url: &str, response: MockResponse) -> Self {
+		{
+			let mut routes = self.routes.lock().unwrap();
+
+			match routes
+				.iter_mut()
+				.find(|route| route.method == method && route.url == url)
+			{
+				Some(route) => route.responses.push_back(response),
+				None => routes.push(MockRoute {
+					method,
+					url: url.to_string(),
+					responses: VecDeque::from([response]),
+				}),
+			}
+		}
+
+		self
+	}
+
+	/// The requests received so far, in the order they were sent
+	pub fn requests(&self) -> Vec<RecordedRequest> {
+		self.requests.lock().unwrap().clone()
+	}
+
+	pub(crate) fn respond(&self, parts: &RequestParts) -> Result<Response, RoboltError> {
+		let (method, url) = (&parts.method, &parts.url);
+
+		self.requests.lock().unwrap().push(RecordedRequest {
+			method: method.clone(),
+			url: url.clone(),
+			headers: parts.headers.clone(),
+			body: parts.body.clone(),
+		});
+
+		let mut routes = self.routes.lock().unwrap();
+		let route = routes
+			.iter_mut()
+			.find(|route| route.method == *method && route.url == *url)
+			.ok_or_else(|| format!("No mock response for {method} {url}"))?;
+
+		let response = match route.responses.len() {
+			1 => route.responses[0].clone(),
+			_ => route.responses.pop_front().unwrap(),
+		};
+
+		Ok(response.into_response())
+	}
+}
+
+#[derive(Debug, Clone)]
+/// A canned response returned by a [MockTransport]
+pub struct MockResponse {
+	status: StatusCode,
+	headers: HeaderMap,
+	body: Vec<u8>,
+	streamed: bool,
+}
+
+impl MockResponse {
+	/// A `200 OK` response with a JSON body
+	pub fn json<T: Serialize>(body: &T) -> Self {
+		let mut headers = HeaderMap::new();
+		headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+		Self {
+			status: StatusCode::OK,
+			headers,
+			body: serde_json::to_vec(body).expect("Failed to serialize mock response"),
+			streamed: false,
+		}
+	}
+
+	/// A `200 OK` response with an empty JSON object, as returned by most
+	/// endpoints without a response body
+	pub fn empty() -> Self {
+		Self::json(&json!({}))
+	}
+
+	/// A response in the error format of the Roblox API
+	pub fn error(status: StatusCode, code: i32, message: &str) -> Self {
+		Self::json(&json!({
+			"errors": [{ "code": code, "message": message }],
+		}))
+		.status(status)
+	}
+
+	pub fn status(mut self, status: StatusCode) -> Self {
+		self.status = status;
+		self
+	}
+
+	/// # Panics
+	///
+	/// Panics if the name or value is not a valid header.
+	pub fn header(mut self, name: &str, value: &str) -> Self {
+		self.headers.insert(
+			HeaderName::from_bytes(name.as_bytes()).expect("Invalid mock header name"),
+			HeaderValue::from_str(value).expect("Invalid mock header value"),
+		);
+		self
+	}
+
+	/// Sends the body as a stream of chunks without a known length, like a
+	/// chunked response without a `Content-Length` header
+	pub fn streamed(mut self) -> Self {
+		self.streamed = true;
+		self
+	}
+
+	fn into_response(self) -> Response {
+		let body = match self.streamed {
+			true => {
+				let chunks: Vec<Result<Vec<u8>, io::Error>> =
+					self.body.chunks(1024).map(|chunk| Ok(chunk.to_vec())).collect();
+				Body::wrap_stream(stream::iter(chunks))
+			},
+			false => Body::from(self.body),
+		};
+
+		let mut response = http::Response::new(body);
+		*response.status_mut() = self.status;
+		*response.headers_mut() = self.headers;
+
+		Response::from(response)
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+/// Recorded responses loaded from a directory of fixtures
+pub struct FixtureSet {
+	pub fixtures: Vec<Fixture>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// A recorded response and the request it answers
+///
+/// ```json
+/// {
+///     "method": "GET",
+///     "url": "https://groups.roblox.com/v1/groups/1",
+///     "status": 200,
+///     "body": { "id": 1, "name": "Example Group" }
+/// }
+/// ```
+///
+/// The method defaults to `GET` and the status to `200`.
+pub struct Fixture {
+	#[serde(default = "default_fixture_method")]
+	pub method: String,
+	pub url: String,
+	#[serde(default = "default_fixture_status")]
+	pub status: u16,
+	#[serde(default)]
+	pub headers: Vec<(String, String)>,
+	pub body: Value,
+}
+
+fn default_fixture_method() -> String {
+	"GET".to_string()
+}
+
+fn default_fixture_status() -> u16 {
+	200
+}
+
+impl FixtureSet {
+	/// Loads every `.json` fixture in `dir`, in file name order
+	///
+	/// Fixtures recorded for the same request are returned in that order, so
+	/// prefixing file names with a number (`01_page.json`, `02_page.json`)
+	/// keeps repeated requests deterministic.
+	pub fn load<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+		let mut paths = fs::read_dir(dir)?
+			.map(|entry| entry.map(|entry| entry.path()))
+			.collect::<io::Result<Vec<_>>>()?;
+
+		paths.retain(|path| path.extension().is_some_and(|extension| extension == "json"));
+		paths.sort();
+
+		let fixtures = paths
+			.iter()
+			.map(|path| {
+				let fixture = fs::read_to_string(path)?;
+				serde_json::from_str(&fixture).map_err(|err| {
+					io::Error::new(io::ErrorKind::InvalidData, format!("{}: {err}", path.display()))
+				})
+			})
+			.collect::<io::Result<Vec<Fixture>>>()?;
+
+		Ok(Self {
+			fixtures,
+		})
+	}
+
+	/// Builds a [MockTransport] serving every fixture
+	///
+	/// # Panics
+	///
+	/// Panics if a fixture has an invalid method, status or header.
+	pub fn transport(&self) -> MockTransport {
+		self.fixtures.iter().fold(MockTransport::new(), |transport, fixture| {
+			let method = Method::from_bytes(fixture.method.as_bytes()).expect("Invalid fixture method");
+			let status = StatusCode::from_u16(fixture.status).expect("Invalid fixture status");
+			let response = fixture.headers.iter().fold(
+				MockResponse::json(&fixture.body).status(status),
+				|response, (name, value)| response.header(name, value),
+			);
+
+			transport.route(method, &fixture.url, response)
+		})
+	}
+}
+
+#[derive(Debug, Clone)]
+/// A clock that only moves when slept on or advanced, recording every sleep
+///
+/// Sleeping completes immediately and moves the clock forward by the slept
+/// duration. Clones share their time and recorded sleeps, so a clone can be
+/// handed to a client and another kept to inspect it:
+///
+/// ```
+/// # use robolt::test_util::VirtualClock;
+/// let clock = VirtualClock::new();
+/// let client = robolt::Robolt::new().clock(clock.clone()).sleeper(clock.clone());
+/// ```
+pub struct VirtualClock {
+	state: Arc<Mutex<VirtualTime>>,
+}
+
+#[derive(Debug)]
+struct VirtualTime {
+	started: Instant,
+	system_started: SystemTime,
+	elapsed: Duration,
+	sleeps: Vec<Duration>,
+}
+
+impl Default for VirtualClock {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl VirtualClock {
+	/// Starts a clock at the current system time
+	pub fn new() -> Self {
+		Self::starting_at(SystemTime::now())
+	}
+
+	/// Starts a clock at the given system time
+	pub fn starting_at(system_time: SystemTime) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(VirtualTime {
+				started: Instant::now(),
+				system_started: system_time,
+				elapsed: Duration::ZERO,
+				sleeps: Vec::new(),
+			})),
+		}
+	}
+
+	/// Moves the clock forward without recording a sleep
+	pub fn advance(&self, duration: Duration) {
+		self.state.lock().unwrap().elapsed += duration;
+	}
+
+	/// The time the clock has moved forward since it was started
+	pub fn elapsed(&self) -> Duration {
+		self.state.lock().unwrap().elapsed
+	}
+
+	/// Every sleep requested so far, in order
+	pub fn sleeps(&self) -> Vec<Duration> {
+		self.state.lock().unwrap().sleeps.clone()
+	}
+}
+
+impl Clock for VirtualClock {
+	fn now(&self) -> Instant {
+		let state = self.state.lock().unwrap();
+		state.started + state.elapsed
+	}
+
+	fn system_time(&self) -> SystemTime {
+		let state = self.state.lock().unwrap();
+		state.system_started + state.elapsed
+	}
+}
+
+impl Sleeper for VirtualClock {
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		let mut state = self.state.lock().unwrap();
+		state.elapsed += duration;
+		state.sleeps.push(duration);
+
+		Box::pin(ready(()))
+	}
+}
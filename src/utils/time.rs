@@ -0,0 +1,50 @@
+//! The time sources of the client
+//!
+//! Everything that waits or reads the time (retries, pollers, the drip
+//! scheduler, presence tracking) goes through a [Clock] and a [Sleeper], both
+//! [SystemClock] by default. Swapping them for a virtual clock lets tests run
+//! these components without waiting, see `test_util::VirtualClock`.
+
+use std::fmt::Debug;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures::future::BoxFuture;
+
+/// Reads the current time
+pub trait Clock: Debug + Send + Sync {
+	/// A monotonic instant, used to measure elapsed time
+	fn now(&self) -> Instant;
+
+	/// The wall-clock time, used for timestamps
+	fn system_time(&self) -> SystemTime;
+
+	/// The time elapsed since the Unix epoch
+	fn unix_time(&self) -> Duration {
+		self.system_time().duration_since(UNIX_EPOCH).unwrap_or_default()
+	}
+}
+
+/// Waits for a duration to elapse
+pub trait Sleeper: Debug + Send + Sync {
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// The system clock, sleeping on the tokio timer
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> Instant {
+		Instant::now()
+	}
+
+	fn system_time(&self) -> SystemTime {
+		SystemTime::now()
+	}
+}
+
+impl Sleeper for SystemClock {
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		Box::pin(tokio::time::sleep(duration))
+	}
+}
@@ -0,0 +1,53 @@
+//! Parsing the timestamps returned by Roblox into [chrono] types
+//!
+//! Models keep their timestamps as the strings returned by Roblox, and the
+//! most used ones can be read as a [DateTime] through accessors such as
+//! [User::created_at](crate::api::users::User::created_at). Other timestamps
+//! are read with [parse], and models of your own can deserialize them
+//! directly:
+//!
+//! ```
+//! # use chrono::{DateTime, Utc};
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Post {
+//!     #[serde(deserialize_with = "robolt::timestamp::deserialize")]
+//!     created: DateTime<Utc>,
+//!     #[serde(default, deserialize_with = "robolt::timestamp::deserialize_option")]
+//!     deleted: Option<DateTime<Utc>>,
+//! }
+//! ```
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+use crate::errors::RoboltError;
+
+/// Parses an ISO 8601 timestamp, with or without fractional seconds (e.g.
+/// `2015-03-14T09:26:53.58Z` or `2015-03-14T09:26:53Z`)
+///
+/// Timestamps without an offset, which some endpoints return, are read as
+/// UTC.
+pub fn parse(timestamp: &str) -> Result<DateTime<Utc>, RoboltError> {
+	DateTime::parse_from_rfc3339(timestamp)
+		.map(|time| time.with_timezone(&Utc))
+		.or_else(|_| {
+			NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f")
+				.map(|time| Utc.from_utc_datetime(&time))
+		})
+		.map_err(|err| format!("Invalid timestamp {timestamp:?}: {err}").into())
+}
+
+/// Deserializes a timestamp with [parse]
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+	let timestamp = String::deserialize(deserializer)?;
+	parse(&timestamp).map_err(|err| D::Error::custom(err.message))
+}
+
+/// Deserializes a timestamp that may be `null` with [parse]
+pub fn deserialize_option<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+	Option::<String>::deserialize(deserializer)?
+		.map(|timestamp| parse(&timestamp).map_err(|err| D::Error::custom(err.message)))
+		.transpose()
+}
@@ -0,0 +1,58 @@
+//! Instrumentation of requests with [tracing], behind the `tracing` feature
+//!
+//! Every request is wrapped in a `robolt.request` span at debug level holding
+//! its method, host and path, on which the status and elapsed time of the last
+//! attempt are recorded. Each response is logged at debug level, or at warn
+//! level if it failed, and so is each retry.
+//!
+//! Only the method, host, path, status and timings are recorded. Headers,
+//! bodies and query strings never are, so the `Cookie`, `X-CSRF-TOKEN` and
+//! `X-API-Key` headers cannot reach a subscriber.
+
+use std::time::Duration;
+
+use reqwest::{Method, Url};
+use tracing::{field, Span};
+
+use crate::utils::errors::RoboltError;
+use crate::utils::middleware::ResponseMeta;
+
+/// The span wrapping every attempt of a request to `url`
+pub(crate) fn request_span(method: &Method, url: &str) -> Span {
+	let url = Url::parse(url).ok();
+	let host = url.as_ref().and_then(Url::host_str).unwrap_or_default();
+	let path = url.as_ref().map(Url::path).unwrap_or_default();
+
+	tracing::debug_span!(
+		"robolt.request",
+		%method,
+		host,
+		path,
+		status = field::Empty,
+		elapsed_ms = field::Empty,
+	)
+}
+
+/// Records a response on the current request span
+pub(crate) fn response(meta: &ResponseMeta) {
+	let status = meta.status.as_u16();
+	let elapsed_ms = meta.elapsed.as_millis() as u64;
+	let span = Span::current();
+
+	span.record("status", status);
+	span.record("elapsed_ms", elapsed_ms);
+
+	match meta.status.is_success() {
+		true => tracing::debug!(status, elapsed_ms, retry = meta.is_retry, "response received"),
+		false => tracing::warn!(status, elapsed_ms, retry = meta.is_retry, "request failed"),
+	}
+}
+
+pub(crate) fn retry(attempt: u32, err: &RoboltError, delay: Duration) {
+	tracing::warn!(
+		attempt,
+		delay_ms = delay.as_millis() as u64,
+		error = %err,
+		"retrying request"
+	);
+}
@@ -13,3 +13,55 @@ async fn user_socials() {
 	let client = Robolt::new();
 	assert_ok!(client.user_socials(1).await);
 }
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn profile_updates() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::account_information::Gender;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		)
+		.route(
+			Method::POST,
+			"https://accountinformation.roblox.com/v1/description",
+			MockResponse::json(&json!({ "description": "####" })),
+		)
+		.route(
+			Method::POST,
+			"https://accountinformation.roblox.com/v1/gender",
+			MockResponse::json(&json!({})),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	assert_eq!(client.set_description("hello").await.unwrap(), "####");
+	client.set_gender(Gender::Female).await.unwrap();
+
+	let bodies = transport
+		.requests()
+		.into_iter()
+		.filter(|req| req.url.starts_with("https://accountinformation.roblox.com"))
+		.map(|req| req.body)
+		.collect::<Vec<_>>();
+	assert_eq!(bodies, vec![
+		Some(json!({ "description": "hello" })),
+		Some(json!({ "gender": "3" })),
+	]);
+}
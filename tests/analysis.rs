@@ -0,0 +1,74 @@
+use robolt::analysis::{moving_average, projected_value, volatility};
+use robolt::api::economy::PricePoint;
+use robolt::api::Robux;
+
+fn chart(prices: &[u64]) -> Vec<PricePoint> {
+	prices
+		.iter()
+		.enumerate()
+		.map(|(day, price)| PricePoint {
+			date: format!("2023-05-{:02}T05:00:00Z", day + 1),
+			price: Robux(*price),
+			volume: 1,
+		})
+		.collect()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+	assert!((actual - expected).abs() < 1e-9, "expected {expected}, got {actual}");
+}
+
+#[test]
+fn moving_average_windows() {
+	let points = chart(&[1000, 1100, 1200, 1300]);
+
+	assert_eq!(moving_average(&points, 1), vec![1000.0, 1100.0, 1200.0, 1300.0]);
+	assert_eq!(moving_average(&points, 2), vec![1050.0, 1150.0, 1250.0]);
+	assert_eq!(moving_average(&points, 3), vec![1100.0, 1200.0]);
+	assert_eq!(moving_average(&points, 4), vec![1150.0]);
+}
+
+#[test]
+fn moving_average_window_larger_than_chart() {
+	assert!(moving_average(&chart(&[1000, 1100]), 3).is_empty());
+	assert!(moving_average(&chart(&[1000, 1100]), 0).is_empty());
+	assert!(moving_average(&[], 1).is_empty());
+}
+
+#[test]
+fn volatility_of_swinging_prices() {
+	// Changes of +10% and -10%
+	assert_close(volatility(&chart(&[100, 110, 99])).unwrap(), 0.1);
+	assert_close(volatility(&chart(&[500, 500, 500])).unwrap(), 0.0);
+}
+
+#[test]
+fn volatility_skips_zero_prices() {
+	// Only the change from 50 to 100 is measured
+	assert_close(volatility(&chart(&[0, 50, 100])).unwrap(), 0.0);
+	assert_eq!(volatility(&chart(&[0, 50])), None);
+}
+
+#[test]
+fn volatility_of_short_charts() {
+	assert_eq!(volatility(&chart(&[100])), None);
+	assert_eq!(volatility(&[]), None);
+}
+
+#[test]
+fn projected_value_follows_trend() {
+	assert_close(projected_value(&chart(&[1000, 1100, 1200, 1300])).unwrap(), 1400.0);
+	// Slope of 0.5 through the mean of 2
+	assert_close(projected_value(&chart(&[1, 3, 2])).unwrap(), 3.0);
+}
+
+#[test]
+fn projected_value_never_negative() {
+	assert_close(projected_value(&chart(&[300, 100])).unwrap(), 0.0);
+}
+
+#[test]
+fn projected_value_of_short_charts() {
+	assert_close(projected_value(&chart(&[500])).unwrap(), 500.0);
+	assert_eq!(projected_value(&[]), None);
+}
@@ -1,3 +1,8 @@
+use std::sync::{Arc, Mutex};
+
+use robolt::api::auth::{CookieParseError, PasswordValidation, RobloxCookie, UsernameValidation};
+use robolt::errors::RoboltError;
+use robolt::middleware::{Middleware, RequestParts};
 use robolt::Robolt;
 
 #[test]
@@ -5,3 +10,399 @@ fn is_authenticated() {
 	let client = Robolt::new();
 	assert!(!client.is_authenticated());
 }
+
+const TOKEN: &str = "ABCDEF0123456789";
+const WARNING: &str = "_|WARNING:-DO-NOT-SHARE-THIS.--Sharing-this-will-allow-someone-to-log-in-as-you-and-to-steal-your-ROBUX-and-items.|_";
+
+#[test]
+fn roblox_cookie_formats() {
+	let warned = format!("{WARNING}{TOKEN}");
+	let encoded = warned.replace('|', "%7C").replace(':', "%3A");
+
+	let cases = [
+		("raw token", TOKEN.to_string(), TOKEN.to_string()),
+		("raw token with warning", warned.clone(), warned.clone()),
+		("surrounding whitespace", format!("  {warned}\n"), warned.clone()),
+		("key=value pair", format!(".ROBLOSECURITY={warned}"), warned.clone()),
+		(
+			"cookie header",
+			format!("Cookie: RBXEventTrackerV2=x; .ROBLOSECURITY={warned}; other=1"),
+			warned.clone(),
+		),
+		("double quoted", format!("\"{warned}\""), warned.clone()),
+		("single quoted", format!("'{warned}'"), warned.clone()),
+		(
+			"quoted value in pair",
+			format!(".ROBLOSECURITY=\"{warned}\""),
+			warned.clone(),
+		),
+		(
+			"json escaped",
+			format!("\"{}\"", warned.replace('|', "\\u007c")),
+			warned.clone(),
+		),
+		(
+			"json object",
+			format!(r#"{{"domain": ".roblox.com", "name": ".ROBLOSECURITY", "value": "{warned}"}}"#),
+			warned.clone(),
+		),
+		("url encoded", encoded.clone(), warned.clone()),
+		(
+			"url encoded pair",
+			format!(".ROBLOSECURITY%3D{encoded}"),
+			warned.clone(),
+		),
+	];
+
+	for (format, input, expected) in cases {
+		let cookie = RobloxCookie::parse(&input).unwrap_or_else(|err| panic!("{format}: {err}"));
+
+		assert_eq!(cookie.value(), expected, "{format}");
+		assert_eq!(cookie.token(), TOKEN, "{format}");
+		assert_eq!(cookie.header_value(), format!(".ROBLOSECURITY={expected}"), "{format}");
+	}
+}
+
+#[test]
+fn roblox_cookie_errors() {
+	let cases = [
+		("", CookieParseError::Empty, "empty cookie"),
+		(WARNING, CookieParseError::Empty, "empty cookie"),
+		(
+			"other=1; RBXEventTrackerV2=x",
+			CookieParseError::MissingCookie(vec!["other".to_string(), "RBXEventTrackerV2".to_string()]),
+			"`.ROBLOSECURITY=<token>` pair",
+		),
+		("'ABCDEF", CookieParseError::UnterminatedQuote, "quoted cookie"),
+		("\"ABCDEF", CookieParseError::InvalidJson, "JSON"),
+		("ABC%ZZ", CookieParseError::InvalidUrlEncoding, "URL-encoded"),
+		(
+			"_|WARNING:-DO-NOT-SHARE-THIS.ABCDEF",
+			CookieParseError::UnterminatedWarning,
+			"`|_`",
+		),
+		("ABC DEF", CookieParseError::InvalidCharacter(' '), "invalid character"),
+		(
+			"ABC\nDEF",
+			CookieParseError::InvalidCharacter('\n'),
+			"invalid character",
+		),
+	];
+
+	for (input, expected, message) in cases {
+		let err = RobloxCookie::parse(input).unwrap_err();
+
+		assert_eq!(err, expected, "{input:?}");
+		assert!(err.to_string().contains(message), "{input:?}: {err}");
+	}
+}
+
+#[test]
+fn roblox_cookie_debug_is_redacted() {
+	let cookie = RobloxCookie::parse(TOKEN).unwrap();
+	assert_eq!(format!("{cookie:?}"), "RobloxCookie(..)");
+}
+
+#[test]
+fn password_validation_codes() {
+	let cases = [
+		(
+			include_str!("fixtures/auth/password_valid.json"),
+			PasswordValidation::Valid,
+		),
+		(
+			include_str!("fixtures/auth/password_too_short.json"),
+			PasswordValidation::TooShort,
+		),
+		(
+			include_str!("fixtures/auth/password_forbidden.json"),
+			PasswordValidation::Forbidden,
+		),
+		(
+			include_str!("fixtures/auth/password_same_as_username.json"),
+			PasswordValidation::SameAsUsername,
+		),
+		(
+			include_str!("fixtures/auth/password_unknown.json"),
+			PasswordValidation::Unknown {
+				code: 9,
+				message: "Password contains a forbidden sequence".to_string(),
+			},
+		),
+	];
+
+	for (fixture, expected) in cases {
+		assert_eq!(serde_json::from_str::<PasswordValidation>(fixture).unwrap(), expected);
+	}
+
+	assert!(PasswordValidation::Valid.is_valid());
+}
+
+#[test]
+fn username_validation_codes() {
+	let validation =
+		serde_json::from_str::<UsernameValidation>(r#"{"code":1,"message":"Username is already in use"}"#);
+	assert_eq!(validation.unwrap(), UsernameValidation::AlreadyInUse);
+}
+
+/// Records the debug output of requests and aborts them before they reach
+/// the network
+struct DebugRecorder(Arc<Mutex<Vec<String>>>);
+
+impl Middleware for DebugRecorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		self.0.lock().unwrap().push(format!("{parts:?}"));
+		Err("aborted".to_string().into())
+	}
+}
+
+#[tokio::test]
+async fn validate_password_is_redacted() {
+	let requests = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new().middleware(DebugRecorder(requests.clone()));
+
+	let err = client
+		.validate_password("ExampleUser", "hunter2hunter2")
+		.await
+		.unwrap_err();
+	let requests = requests.lock().unwrap();
+
+	assert!(!format!("{err:?}").contains("hunter2"));
+	assert!(!requests[0].contains("hunter2"));
+	assert!(requests[0].contains("[redacted]"));
+	assert!(requests[0].contains("ExampleUser"));
+	assert!(!requests[0].contains("/v2/passwords/validate?"));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn auth_headers_are_redacted() {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://auth.roblox.com/v2/logout",
+		MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+			.header("x-csrf-token", "csrf-secret"),
+	);
+	let requests = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.set_cookie(RobloxCookie::parse(TOKEN).unwrap())
+		.await
+		.unwrap()
+		.middleware(DebugRecorder(requests.clone()));
+
+	assert!(client.me().await.is_err());
+	let requests = requests.lock().unwrap();
+
+	assert!(requests[0].contains("cookie"));
+	assert!(!requests[0].contains(TOKEN));
+	assert!(!requests[0].contains("csrf-secret"));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn login_verifies_the_cookie() {
+	use reqwest::{Method, StatusCode};
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::{AuthenticatedClient, Client};
+	use serde_json::json;
+
+	let logout_url = "https://auth.roblox.com/v2/logout";
+	let me_url = "https://users.roblox.com/v1/users/authenticated";
+	let csrf = || {
+		MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed").header("x-csrf-token", "token")
+	};
+
+	let transport = MockTransport::new()
+		.route(Method::POST, logout_url, csrf())
+		.route(Method::POST, logout_url, MockResponse::json(&json!({})))
+		.route(
+			Method::GET,
+			me_url,
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		);
+	let sent = |url: &str| transport.requests().iter().filter(|req| req.url == url).count();
+	let client: AuthenticatedClient = Client::new()
+		.mock_transport(transport.clone())
+		.login(RobloxCookie::parse(TOKEN).unwrap())
+		.await
+		.unwrap();
+
+	assert_eq!(client.authenticated_user().await.unwrap().username, "builder");
+	assert_eq!(client.authenticated_user_id().await.unwrap(), 7);
+	assert_eq!(sent(me_url), 1);
+
+	let client: Client = client.logout().await.unwrap();
+	assert!(!client.is_authenticated());
+	assert_eq!(sent(logout_url), 2);
+
+	let transport = MockTransport::new().route(Method::POST, logout_url, csrf()).route(
+		Method::GET,
+		me_url,
+		MockResponse::error(
+			StatusCode::UNAUTHORIZED,
+			0,
+			"Authorization has been denied for this request.",
+		),
+	);
+	let err = Client::new()
+		.mock_transport(transport)
+		.login(RobloxCookie::parse(TOKEN).unwrap())
+		.await
+		.unwrap_err();
+
+	assert_eq!(err.kind(), &RoboltErrorKind::InvalidCookie);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn rotated_csrf_tokens_are_kept() {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let url = "https://friends.roblox.com/v1/users/1/unfriend";
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "first"),
+		)
+		.route(
+			Method::POST,
+			url,
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "second"),
+		)
+		.route(Method::POST, url, MockResponse::json(&json!({})));
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse(TOKEN).unwrap())
+		.await
+		.unwrap();
+
+	client.unfriend(1).await.unwrap();
+	client.clone().unfriend(1).await.unwrap();
+
+	let tokens = transport
+		.requests()
+		.into_iter()
+		.filter(|req| req.url == url)
+		.map(|req| req.headers["x-csrf-token"].to_str().unwrap().to_string())
+		.collect::<Vec<String>>();
+	assert_eq!(tokens, vec!["first", "second", "second"]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn auth_watchdog_reports_transitions_once() {
+	use std::time::Duration;
+
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::AuthHealth;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let url = "https://users.roblox.com/v1/users/authenticated";
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			url,
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		)
+		.route(
+			Method::GET,
+			url,
+			MockResponse::error(
+				StatusCode::UNAUTHORIZED,
+				0,
+				"Authorization has been denied for this request.",
+			),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse(TOKEN).unwrap())
+		.await
+		.unwrap();
+	let transitions = Arc::new(Mutex::new(Vec::new()));
+
+	let watchdog = client.auth_watchdog(Duration::from_millis(10), {
+		let transitions = transitions.clone();
+		move |previous, current| transitions.lock().unwrap().push((previous, current))
+	});
+	tokio::time::sleep(Duration::from_millis(200)).await;
+	drop(watchdog);
+
+	assert!(transport.requests().iter().filter(|req| req.url == url).count() > 2);
+	assert_eq!(*transitions.lock().unwrap(), vec![(
+		AuthHealth::Healthy,
+		AuthHealth::CookieInvalid
+	)]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn auth_watchdog_survives_callback_panics() {
+	use std::time::Duration;
+
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::AuthHealth;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let url = "https://users.roblox.com/v1/users/authenticated";
+	let healthy = MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" }));
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(Method::GET, url, healthy.clone())
+		.route(
+			Method::GET,
+			url,
+			MockResponse::error(
+				StatusCode::UNAUTHORIZED,
+				0,
+				"Authorization has been denied for this request.",
+			),
+		)
+		.route(Method::GET, url, healthy);
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.set_cookie(RobloxCookie::parse(TOKEN).unwrap())
+		.await
+		.unwrap();
+	let transitions = Arc::new(Mutex::new(Vec::new()));
+
+	let watchdog = client.auth_watchdog(Duration::from_millis(10), {
+		let transitions = transitions.clone();
+		move |previous, current| {
+			if current == AuthHealth::CookieInvalid {
+				panic!("callback failed");
+			}
+
+			transitions.lock().unwrap().push((previous, current));
+		}
+	});
+	tokio::time::sleep(Duration::from_millis(200)).await;
+	drop(watchdog);
+
+	assert_eq!(*transitions.lock().unwrap(), vec![(
+		AuthHealth::CookieInvalid,
+		AuthHealth::Healthy
+	)]);
+}
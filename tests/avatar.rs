@@ -46,3 +46,52 @@ async fn outfit() {
 	let client = Robolt::new();
 	assert_ok!(client.outfit(11675594890).await);
 }
+
+#[test]
+fn avatar_types() {
+	use robolt::api::avatar::BodyType;
+
+	assert_eq!(serde_json::from_str::<BodyType>("\"R15\"").unwrap(), BodyType::R15);
+	assert_eq!(serde_json::from_str::<BodyType>("1").unwrap(), BodyType::R6);
+	assert_eq!(serde_json::from_str::<BodyType>("3").unwrap(), BodyType::R15);
+	assert!(serde_json::from_str::<BodyType>("2").is_err());
+	assert!(serde_json::from_str::<BodyType>("\"Rthro\"").is_err());
+	assert_eq!(serde_json::to_string(&BodyType::R15).unwrap(), "\"R15\"");
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn set_avatar_type() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::avatar::BodyType;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::POST,
+			"https://avatar.roblox.com/v1/avatar/set-player-avatar-type",
+			MockResponse::json(&json!({ "success": true })),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.set_avatar_type(BodyType::R15).await.unwrap();
+
+	let request = transport
+		.requests()
+		.into_iter()
+		.find(|request| request.url.ends_with("/set-player-avatar-type"))
+		.unwrap();
+	assert_eq!(request.body, Some(json!({ "playerAvatarType": "R15" })));
+}
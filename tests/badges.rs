@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use tokio_test::assert_ok;
 
+use robolt::api::badges::{BadgeStats, BadgeStatsSnapshot, BadgeStatsTracker};
 use robolt::api::Limit;
 use robolt::Robolt;
 
@@ -21,6 +24,12 @@ async fn user_badges() {
 	assert_ok!(client.user_badges(1, Limit::Min).await);
 }
 
+#[tokio::test]
+async fn user_badges_page() {
+	let client = Robolt::new();
+	assert_ok!(client.user_badges_page(1, Limit::Min, None).await);
+}
+
 #[tokio::test]
 async fn awarded_badge_timestamps() {
 	let client = Robolt::new();
@@ -28,3 +37,98 @@ async fn awarded_badge_timestamps() {
 
 	assert_ok!(client.awarded_badge_timestamps(2, badges).await);
 }
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn awarded_badge_timestamps_chunked() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let badge_ids = (1..=250).collect::<Vec<u64>>();
+	let awarded_date = |badge_id: u64| format!("2023-05-01T12:00:{:02}Z", badge_id % 60);
+	// Every third badge is awarded, except for badge 99 which is missing from
+	// every response
+	let is_awarded = |badge_id: &u64| badge_id.is_multiple_of(3) && *badge_id != 99;
+
+	let transport = badge_ids.chunks(100).fold(MockTransport::new(), |transport, chunk| {
+		let ids = chunk.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(",");
+		let data = chunk
+			.iter()
+			.filter(|badge_id| is_awarded(badge_id))
+			.map(|badge_id| json!({ "badgeId": badge_id, "awardedDate": awarded_date(*badge_id) }))
+			.collect::<Vec<_>>();
+
+		transport.route(
+			Method::GET,
+			&format!("https://badges.roblox.com/v1/users/2/badges/awarded-dates?badgeIds={ids}"),
+			MockResponse::json(&json!({ "data": data })),
+		)
+	});
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let res = client.awarded_badge_timestamps(2, badge_ids.clone()).await.unwrap();
+
+	let chunk_sizes = transport
+		.requests()
+		.iter()
+		.map(|request| request.url.rsplit('=').next().unwrap().split(',').count())
+		.collect::<Vec<usize>>();
+	assert_eq!(chunk_sizes, vec![100, 100, 50]);
+
+	let awarded = res
+		.awarded
+		.iter()
+		.map(|timestamp| (timestamp.badge_id, timestamp.awarded_date.clone()))
+		.collect::<HashMap<u64, String>>();
+	let expected = badge_ids
+		.iter()
+		.filter(|badge_id| is_awarded(badge_id))
+		.map(|badge_id| (*badge_id, awarded_date(*badge_id)))
+		.collect::<HashMap<u64, String>>();
+	assert_eq!(awarded, expected);
+
+	assert!(res.not_awarded.contains(&99));
+	assert_eq!(res.awarded.len() + res.not_awarded.len(), 250);
+}
+
+fn stats(awarded_count: u64) -> BadgeStats {
+	BadgeStats {
+		awarded_count,
+		..Default::default()
+	}
+}
+
+#[test]
+fn badge_stats_deltas() {
+	let previous = BadgeStatsSnapshot::new(&HashMap::from([(1, stats(100)), (2, stats(50)), (3, stats(10))]));
+	let current = HashMap::from([(1, stats(105)), (2, stats(5050)), (4, stats(7))]);
+
+	let deltas = BadgeStatsTracker::new(1000).compare(&previous, &current);
+
+	assert_eq!(
+		deltas
+			.iter()
+			.map(|delta| (delta.badge_id, delta.previous, delta.delta, delta.anomalous))
+			.collect::<Vec<_>>(),
+		vec![(1, Some(100), 5, false), (2, Some(50), 5000, true), (4, None, 0, false),]
+	);
+}
+
+#[test]
+fn badge_stats_snapshot_round_trip() {
+	let snapshot = BadgeStatsSnapshot::new(&HashMap::from([(1, stats(100))]));
+	let json = serde_json::to_string(&snapshot).unwrap();
+
+	assert_eq!(json, r#"{"version":1,"awardedCounts":{"1":100}}"#);
+	assert_eq!(serde_json::from_str::<BadgeStatsSnapshot>(&json).unwrap(), snapshot);
+
+	let unversioned = serde_json::from_str::<BadgeStatsSnapshot>(r#"{"awardedCounts":{"1":100}}"#).unwrap();
+	assert_eq!(unversioned.version, 0);
+
+	let deltas =
+		BadgeStatsTracker::new(10).compare(&unversioned, &HashMap::from([(1, stats(120)), (2, stats(3))]));
+	assert_eq!(deltas[0].delta, 20);
+	assert!(deltas[0].anomalous);
+	assert_eq!(deltas[1].previous, None);
+}
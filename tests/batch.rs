@@ -0,0 +1,161 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use robolt::batch::{self, Pacer};
+#[cfg(feature = "test-util")]
+use robolt::test_util::VirtualClock;
+
+#[tokio::test]
+async fn batch_report_preserves_order_and_failures() {
+	let report = batch::run(1..=6u64, 2, |id| async move {
+		match id % 3 {
+			0 => Err(format!("failed {id}").into()),
+			_ => Ok(()),
+		}
+	})
+	.await;
+
+	assert_eq!(report.succeeded, vec![1, 2, 4, 5]);
+	assert_eq!(
+		report
+			.failed
+			.iter()
+			.map(|(id, err)| (*id, err.message.as_str()))
+			.collect::<Vec<_>>(),
+		vec![(3, "failed 3"), (6, "failed 6")]
+	);
+	assert_eq!(report.len(), 6);
+	assert!(!report.is_success());
+	assert!(!report.dry_run);
+}
+
+#[tokio::test]
+async fn batch_respects_concurrency() {
+	let in_flight = AtomicUsize::new(0);
+	let peak = AtomicUsize::new(0);
+
+	let report = batch::run(0..20u64, 3, |_| async {
+		let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+		peak.fetch_max(current, Ordering::SeqCst);
+		tokio::task::yield_now().await;
+		in_flight.fetch_sub(1, Ordering::SeqCst);
+		Ok(())
+	})
+	.await;
+
+	assert!(report.is_success());
+	assert_eq!(report.succeeded.len(), 20);
+	assert!(peak.load(Ordering::SeqCst) <= 3);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn drip_spreads_calls_over_window() {
+	let clock = VirtualClock::new();
+
+	let report = batch::drip_with((0..10u64).collect(), Duration::from_secs(60), &clock, |id| async move {
+		match id {
+			4 => Err("failed".to_string().into()),
+			_ => Ok(()),
+		}
+	})
+	.await;
+
+	assert_eq!(report.report.succeeded.len(), 9);
+	assert_eq!(report.report.failed.len(), 1);
+	assert_eq!(report.rate_limited, 0);
+	assert_eq!(clock.sleeps(), vec![Duration::from_secs(6); 9]);
+	assert_eq!(report.elapsed, Duration::from_secs(54));
+	assert!((report.rate() - 10.0 / 54.0).abs() < f64::EPSILON);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn drip_counts_call_time_towards_delay() {
+	let clock = VirtualClock::new();
+
+	let report = batch::drip_with((0..5u64).collect(), Duration::from_secs(50), &clock, |id| {
+		let clock = clock.clone();
+
+		async move {
+			// Calls take 4s, except for the fourth one which outlasts the delay
+			clock.advance(Duration::from_secs(if id == 3 { 15 } else { 4 }));
+			Ok(())
+		}
+	})
+	.await;
+
+	assert!(report.report.is_success());
+	assert_eq!(clock.sleeps(), vec![Duration::from_secs(6); 3]);
+	assert_eq!(report.elapsed, Duration::from_secs(49));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn drip_backs_off_when_rate_limited() {
+	use reqwest::{Method, StatusCode};
+	use robolt::retry::RetryPolicy;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::Robolt;
+
+	let user = serde_json::from_str::<serde_json::Value>(include_str!("fixtures/users/active.json")).unwrap();
+	let transport = (0..10u64).fold(MockTransport::new(), |transport, id| {
+		let response = match id {
+			2 | 3 => MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests"),
+			_ => MockResponse::json(&user),
+		};
+
+		transport.route(
+			Method::GET,
+			&format!("https://users.roblox.com/v1/users/{id}"),
+			response,
+		)
+	});
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.retry_policy(RetryPolicy::new().max_retries(0));
+	let clock = VirtualClock::new();
+
+	let report = batch::drip_with((0..10u64).collect(), Duration::from_secs(10), &clock, |id| {
+		client.user(id)
+	})
+	.await;
+
+	assert_eq!(report.rate_limited, 2);
+	assert_eq!(report.report.failed.len(), 2);
+	assert_eq!(report.values.iter().map(|(id, _)| *id).collect::<Vec<u64>>(), vec![
+		0, 1, 4, 5, 6, 7, 8, 9
+	]);
+
+	// The delay doubles after each 429 and shrinks back by a quarter after
+	// every success until it reaches the base delay again
+	let millis = [1000, 1000, 2000, 4000, 3000, 2250, 1687, 1265, 1000];
+	let sleeps = clock.sleeps();
+	assert_eq!(
+		sleeps.iter().map(Duration::as_millis).collect::<Vec<_>>(),
+		millis.iter().map(|millis| *millis as u128).collect::<Vec<_>>()
+	);
+}
+
+#[test]
+fn pacer_slows_down_and_recovers() {
+	let mut pacer = Pacer::new(100, Duration::from_secs(100));
+	assert_eq!(pacer.delay(), Duration::from_secs(1));
+
+	pacer.on_rate_limited();
+	pacer.on_rate_limited();
+	assert_eq!(pacer.delay(), Duration::from_secs(4));
+
+	for _ in 0..10 {
+		pacer.on_rate_limited();
+	}
+	assert_eq!(pacer.delay(), Duration::from_secs(16));
+
+	pacer.on_success();
+	assert_eq!(pacer.delay(), Duration::from_secs(12));
+
+	for _ in 0..20 {
+		pacer.on_success();
+	}
+	assert_eq!(pacer.delay(), Duration::from_secs(1));
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use robolt::cache::{Cache, CacheStats, MemoryCache};
+use robolt::Robolt;
+
+#[test]
+fn memory_cache_evicts_least_recently_used() {
+	let cache = MemoryCache::new(2);
+
+	cache.put("a", b"1".to_vec(), Duration::from_secs(60));
+	cache.put("b", b"2".to_vec(), Duration::from_secs(60));
+	assert_eq!(cache.get("a"), Some(b"1".to_vec()));
+
+	cache.put("c", b"3".to_vec(), Duration::from_secs(60));
+
+	assert_eq!(cache.len(), 2);
+	assert_eq!(cache.get("b"), None);
+	assert_eq!(cache.get("a"), Some(b"1".to_vec()));
+	assert_eq!(cache.get("c"), Some(b"3".to_vec()));
+
+	cache.remove("a");
+	assert_eq!(cache.get("a"), None);
+}
+
+#[test]
+fn no_cache_by_default() {
+	let client = Robolt::new();
+	client.invalidate_cached("https://users.roblox.com/v1/users/1");
+
+	assert_eq!(client.cache_stats(), CacheStats::default());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn memory_cache_expires_values() {
+	use robolt::test_util::VirtualClock;
+
+	let clock = VirtualClock::new();
+	let cache = MemoryCache::new(10).clock(clock.clone());
+
+	cache.put("a", b"1".to_vec(), Duration::from_secs(60));
+	clock.advance(Duration::from_secs(59));
+	assert_eq!(cache.get("a"), Some(b"1".to_vec()));
+
+	clock.advance(Duration::from_secs(1));
+	assert_eq!(cache.get("a"), None);
+	assert!(cache.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn successful_gets_are_cached() {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let user = json!({
+		"name": "Roblox",
+		"displayName": "Roblox",
+		"description": "",
+		"created": "2006-02-27T21:06:40.3Z",
+		"isBanned": false,
+		"hasVerifiedBadge": true,
+		"id": 1
+	});
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::json(&user),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/2",
+			MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+		)
+		.route(
+			Method::POST,
+			"https://users.roblox.com/v1/users",
+			MockResponse::json(&json!({ "data": [user] })),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.cache(MemoryCache::new(10), Duration::from_secs(60));
+	let sent = || transport.requests().len();
+
+	assert_eq!(client.user(1).await.unwrap().username, "Roblox");
+	assert_eq!(client.user(1).await.unwrap().username, "Roblox");
+	assert_eq!(client.partial_user(1).await.unwrap().display_name, "Roblox");
+	assert_eq!(sent(), 1);
+	assert_eq!(client.cache_stats(), CacheStats {
+		hits: 2,
+		misses: 1,
+		entries: 1,
+	});
+
+	// Errors and other methods are never cached
+	assert!(client.user(2).await.is_err());
+	assert!(client.user(2).await.is_err());
+	client.users_from_ids(vec![1], false).await.unwrap();
+	client.users_from_ids(vec![1], false).await.unwrap();
+	assert_eq!(sent(), 5);
+	assert_eq!(client.cache_stats().entries, 1);
+
+	client.bypass_cache().user(1).await.unwrap();
+	assert_eq!(sent(), 6);
+
+	client.invalidate_cached("https://users.roblox.com/v1/users/1");
+	client.user(1).await.unwrap();
+	assert_eq!(sent(), 7);
+	client.user(1).await.unwrap();
+	assert_eq!(sent(), 7);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn sessions_are_never_cached() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let user = |display_name: &str| {
+		MockResponse::json(&json!({
+			"name": "builder",
+			"displayName": display_name,
+			"description": "",
+			"created": "2006-02-27T21:06:40.3Z",
+			"isBanned": false,
+			"hasVerifiedBadge": false,
+			"id": 7
+		}))
+	};
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		)
+		.route(Method::GET, "https://users.roblox.com/v1/users/7", user("Builder"))
+		.route(Method::GET, "https://users.roblox.com/v1/users/7", user("Renamed"))
+		.route(
+			Method::PATCH,
+			"https://users.roblox.com/v1/users/7/display-names",
+			MockResponse::json(&json!({})),
+		);
+	let public = Robolt::new()
+		.mock_transport(transport.clone())
+		.cache(MemoryCache::new(10), Duration::from_secs(60));
+	let client = public
+		.clone()
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+	let sent = |url: &str| transport.requests().iter().filter(|req| req.url == url).count();
+
+	assert_eq!(public.user(7).await.unwrap().display_name, "Builder");
+	assert_eq!(public.user(7).await.unwrap().display_name, "Builder");
+	assert_eq!(sent("https://users.roblox.com/v1/users/7"), 1);
+
+	// Responses to requests sending the cookie depend on the session
+	client.me().await.unwrap();
+	client.me().await.unwrap();
+	client.user(7).await.unwrap();
+	assert_eq!(sent("https://users.roblox.com/v1/users/authenticated"), 2);
+	assert_eq!(sent("https://users.roblox.com/v1/users/7"), 2);
+	assert_eq!(public.cache_stats().entries, 1);
+
+	client.set_display_name("Renamed").await.unwrap();
+	assert_eq!(public.cache_stats().entries, 0);
+	assert_eq!(public.user(7).await.unwrap().display_name, "Renamed");
+}
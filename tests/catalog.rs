@@ -1,7 +1,7 @@
 use tokio_test::assert_ok;
 
-use robolt::api::catalog::BundleType;
-use robolt::api::Limit;
+use robolt::api::catalog::{AssetFavorite, Bundle, BundleFavorite, BundleType, CatalogSearchResponse};
+use robolt::api::{Creator, Limit};
 use robolt::Robolt;
 
 #[tokio::test]
@@ -52,3 +52,60 @@ async fn bundle_favorite_count() {
 	let client = Robolt::new();
 	assert_ok!(client.bundle_favorite_count(495).await);
 }
+
+#[test]
+fn catalog_item_creator() {
+	let item: CatalogSearchResponse =
+		serde_json::from_str(include_str!("fixtures/catalog/item_details.json")).unwrap();
+
+	assert_eq!(item.creator, Creator::Group {
+		id: 2000001,
+		name: "Example Group".to_string(),
+	});
+}
+
+#[test]
+fn bundle_creator() {
+	let bundle: Bundle = serde_json::from_str(include_str!("fixtures/catalog/bundle.json")).unwrap();
+
+	assert_eq!(bundle.creator, Creator::User {
+		id: 1,
+		name: "Roblox".to_string(),
+	});
+}
+
+#[test]
+fn favorite_models() {
+	let asset: Option<AssetFavorite> =
+		serde_json::from_str(include_str!("fixtures/catalog/asset_favorite.json")).unwrap();
+	let bundle: Option<BundleFavorite> =
+		serde_json::from_str(include_str!("fixtures/catalog/bundle_favorite.json")).unwrap();
+
+	assert_eq!(asset.unwrap().asset_id, 3333105280);
+	assert_eq!(bundle.unwrap().created, "2022-11-03T09:01:54.12Z");
+}
+
+#[test]
+fn null_favorite_models() {
+	assert_eq!(serde_json::from_str::<Option<AssetFavorite>>("null").unwrap(), None);
+	assert_eq!(serde_json::from_str::<Option<BundleFavorite>>("null").unwrap(), None);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn null_favorite_response() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let url = "https://catalog.roblox.com/v1/favorites/users/1/assets/3333105280/favorite";
+	let transport = MockTransport::new().route(Method::GET, url, MockResponse::json(&serde_json::Value::Null));
+	let client = Robolt::new().mock_transport(transport);
+
+	let favorite = client
+		.raw()
+		.get(url)
+		.send_json::<Option<AssetFavorite>>()
+		.await
+		.unwrap();
+	assert_eq!(favorite, None);
+}
@@ -0,0 +1,231 @@
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+
+use robolt::api::groups::{GroupMember, GroupRole};
+use robolt::api::users::PartialUser;
+use robolt::cli::{self, Cli, CliError, Command, Target, UsageError};
+use robolt::Robolt;
+
+fn parse(args: &str) -> Result<Command, UsageError> {
+	cli::parse(&args.split_whitespace().collect::<Vec<&str>>())
+}
+
+fn user(id: u64, username: &str, display_name: &str) -> PartialUser {
+	PartialUser {
+		id,
+		username: username.to_string(),
+		display_name: display_name.to_string(),
+	}
+}
+
+#[test]
+fn parse_commands() {
+	assert_eq!(parse("").unwrap(), Command::Help);
+	assert_eq!(parse("--help").unwrap(), Command::Help);
+	assert_eq!(parse("user lookup builderman").unwrap(), Command::UserLookup {
+		username: "builderman".to_string(),
+	});
+	assert_eq!(parse("group rank 7 builderman 42").unwrap(), Command::GroupRank {
+		group_id: 7,
+		user: Target::Name("builderman".to_string()),
+		role: Target::Id(42),
+	});
+	assert_eq!(parse("group audit 7").unwrap(), Command::GroupAudit {
+		group_id: 7,
+		since: None,
+	});
+	assert_eq!(
+		parse("group audit 7 --since 2023-05-01").unwrap(),
+		Command::GroupAudit {
+			group_id: 7,
+			since: Some("2023-05-01".to_string()),
+		}
+	);
+	assert_eq!(parse("export members 7 --csv").unwrap(), Command::ExportMembers {
+		group_id: 7,
+		csv: true,
+	});
+	assert_eq!(parse("thumbnail 156 --out avatar.png").unwrap(), Command::Thumbnail {
+		user_id: 156,
+		out: PathBuf::from("avatar.png"),
+	});
+}
+
+#[test]
+fn parse_errors() {
+	let error = |args: &str| parse(args).unwrap_err().0;
+
+	assert_eq!(error("group ban 7 1"), "Unknown command: group ban 7 1");
+	assert_eq!(error("group audit seven"), "Invalid group ID: seven");
+	assert_eq!(error("group audit 7 --since"), "--since requires a value");
+	assert_eq!(
+		error("group audit 7 --since yesterday"),
+		"Invalid date, expected YYYY-MM-DD: yesterday"
+	);
+	assert_eq!(
+		error("export members 7 --json"),
+		"Unknown option for export members: --json"
+	);
+	assert_eq!(error("export members 7 csv"), "Unexpected argument: csv");
+	assert_eq!(error("thumbnail 156"), "thumbnail requires --out <file>");
+}
+
+#[test]
+fn format_output() {
+	assert_eq!(cli::format_user(&user(1, "Roblox", "Roblox")), "Roblox (1)");
+	assert_eq!(
+		cli::format_user(&user(156, "builderman", "Builder Man")),
+		"Builder Man (@builderman, 156)"
+	);
+	assert_eq!(
+		cli::format_member(&GroupMember {
+			user: user(156, "builderman", ""),
+			role: GroupRole {
+				id: 42,
+				name: "Admin".to_string(),
+				rank: 254,
+				..Default::default()
+			},
+		}),
+		"156\tbuilderman\tAdmin (254)"
+	);
+}
+
+#[test]
+fn usage_exit_code() {
+	let err = CliError::from(parse("user lookup").unwrap_err());
+	assert_eq!(err.exit_code(), 2);
+}
+
+#[tokio::test]
+async fn authenticated_commands_require_a_cookie() {
+	let err = Cli::new(Robolt::new())
+		.run(&parse("group audit 7").unwrap(), &mut Vec::new())
+		.await
+		.unwrap_err();
+
+	assert!(matches!(err, CliError::MissingCredentials("ROBLOSECURITY")));
+	assert_eq!(err.exit_code(), 3);
+	assert_eq!(err.to_string(), "The ROBLOSECURITY environment variable is not set");
+}
+
+#[cfg(feature = "test-util")]
+mod mock {
+	use reqwest::{Method, StatusCode};
+	use serde_json::json;
+
+	use robolt::cli::{Cli, CliError};
+	use robolt::test_util::{client_with_fixtures, MockResponse, MockTransport};
+	use robolt::Robolt;
+
+	use super::parse;
+
+	const USERNAMES_URL: &str = "https://users.roblox.com/v1/usernames/users";
+
+	async fn run(client: Robolt, args: &str) -> Result<String, CliError> {
+		let mut out = Vec::new();
+		Cli::new(client).run(&parse(args).unwrap(), &mut out).await?;
+
+		Ok(String::from_utf8(out).unwrap())
+	}
+
+	#[tokio::test]
+	async fn user_lookup() {
+		let transport = MockTransport::new().route(
+			Method::POST,
+			USERNAMES_URL,
+			MockResponse::json(&json!({
+				"data": [{ "requestedUsername": "builderman", "id": 156, "name": "builderman", "displayName": "Builder Man" }],
+			})),
+		);
+		let client = Robolt::new().mock_transport(transport.clone());
+
+		let output = run(client, "user lookup builderman").await.unwrap();
+
+		assert_eq!(output, "Builder Man (@builderman, 156)\n");
+		assert_eq!(
+			transport.requests()[0].body,
+			Some(json!({ "usernames": ["builderman"], "excludeBannedUsers": false }))
+		);
+	}
+
+	#[tokio::test]
+	async fn unknown_user_exit_code() {
+		let transport =
+			MockTransport::new().route(Method::POST, USERNAMES_URL, MockResponse::json(&json!({ "data": [] })));
+		let err = run(Robolt::new().mock_transport(transport), "user lookup nobody")
+			.await
+			.unwrap_err();
+
+		assert_eq!(err.to_string(), "No user is named nobody");
+		assert_eq!(err.exit_code(), 4);
+	}
+
+	#[tokio::test]
+	async fn api_error_exit_codes() {
+		let cases = [
+			(StatusCode::TOO_MANY_REQUESTS, 6),
+			(StatusCode::FORBIDDEN, 5),
+			(StatusCode::BAD_REQUEST, 1),
+		];
+
+		for (status, exit_code) in cases {
+			let transport = MockTransport::new().route(
+				Method::POST,
+				USERNAMES_URL,
+				MockResponse::error(status, 0, "Request failed"),
+			);
+			let client = Robolt::new().mock_transport(transport);
+			let err = run(client, "user lookup builderman").await.unwrap_err();
+
+			assert_eq!(err.exit_code(), exit_code, "{status}");
+		}
+	}
+
+	#[tokio::test]
+	async fn missing_group_exit_code() {
+		let transport = MockTransport::new().route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/7/users?limit=100&sortOrder=Asc",
+			MockResponse::error(StatusCode::BAD_REQUEST, 1, "Group is invalid or does not exist."),
+		);
+		let err = run(Robolt::new().mock_transport(transport), "export members 7")
+			.await
+			.unwrap_err();
+
+		assert_eq!(err.exit_code(), 4);
+	}
+
+	#[tokio::test]
+	async fn export_members() {
+		let output = run(client_with_fixtures("tests/fixtures/group_123"), "export members 123")
+			.await
+			.unwrap();
+
+		assert_eq!(
+			output,
+			"100\tGroupOwner\tOwner (255)\n200\tModerator\tModerator (100)\n300\tMember\tMember (1)\n"
+		);
+	}
+
+	#[tokio::test]
+	async fn export_members_csv() {
+		let output = run(
+			client_with_fixtures("tests/fixtures/group_123"),
+			"export members 123 --csv",
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(
+			output,
+			concat!(
+				"user.id,user.username,user.display_name,role.id,role.name,role.rank\n",
+				"100,GroupOwner,GroupOwner,3,Owner,255\n",
+				"200,Moderator,Moderator,2,Moderator,100\n",
+				"300,Member,Member,1,Member,1\n",
+			)
+		);
+	}
+}
@@ -0,0 +1,151 @@
+use robolt::{HttpConfig, Robolt};
+
+#[test]
+fn max_response_size() {
+	let client = Robolt::new();
+	assert!(format!("{client:?}").contains("max_response_size: 8388608"));
+
+	let client = client.max_response_size(1024);
+	assert!(format!("{client:?}").contains("max_response_size: 1024"));
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn usable_from_spawned_tasks() {
+	let client = Robolt::new();
+
+	// Handlers of async frameworks (e.g. Discord bots) run on spawned tasks,
+	// which requires the client and its futures to be `Send`
+	assert_send(&client);
+	assert_send(&client.user(1));
+	assert_send(&client.username_history(1));
+	assert_send(&client.group_members(1, Default::default(), None));
+}
+
+#[test]
+fn invalid_http_config() {
+	assert!(Robolt::with_http_config(HttpConfig::new().proxy("not a proxy")).is_err());
+	assert!(Robolt::with_http_config(HttpConfig::new().user_agent("robolt\n")).is_err());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn http_config_survives_authentication() {
+	use std::time::Duration;
+
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://auth.roblox.com/v2/logout",
+		MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed").header("x-csrf-token", "token"),
+	);
+	let config = HttpConfig::new()
+		.timeout(Duration::from_secs(5))
+		.connect_timeout(Duration::from_secs(2))
+		.user_agent("my-bot/1.0");
+	let client = Robolt::with_http_config(config)
+		.unwrap()
+		.mock_transport(transport)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	assert!(format!("{client:?}").contains(r#""user-agent": "my-bot/1.0""#));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn endpoints_can_be_overridden() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::routes::{Endpoints, RobloxApi};
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"http://127.0.0.1:8080/auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"http://127.0.0.1:9090/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 1, "name": "Roblox", "displayName": "Roblox" })),
+		);
+	let endpoints = Endpoints::new()
+		.base("http://127.0.0.1:8080/")
+		.api(RobloxApi::Users, "http://127.0.0.1:9090");
+	let client = Robolt::new()
+		.endpoints(endpoints)
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	assert_eq!(client.me().await.unwrap().id, 1);
+	assert_eq!(transport.requests().len(), 2);
+	assert_eq!(
+		Endpoints::default().url(RobloxApi::Groups, "/v1/groups/1"),
+		"https://groups.roblox.com/v1/groups/1"
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn oversized_responses_are_rejected() {
+	use reqwest::Method;
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let body = json!({ "description": "a".repeat(4096) });
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/sized",
+			MockResponse::json(&body),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/streamed",
+			MockResponse::json(&body).streamed(),
+		);
+	let client = Robolt::new().mock_transport(transport).max_response_size(1024);
+
+	let err = client
+		.raw()
+		.get("https://users.roblox.com/v1/sized")
+		.send()
+		.await
+		.unwrap_err();
+	assert!(matches!(err.kind(), RoboltErrorKind::ResponseTooLarge {
+		limit: 1024,
+		actual: Some(actual),
+	} if *actual > 4096));
+
+	let err = client
+		.raw()
+		.get("https://users.roblox.com/v1/streamed")
+		.send()
+		.await
+		.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::ResponseTooLarge {
+		limit: 1024,
+		actual: None,
+	});
+
+	let res: serde_json::Value = client
+		.raw()
+		.get("https://users.roblox.com/v1/streamed")
+		.max_response_size(8192)
+		.send_json()
+		.await
+		.unwrap();
+	assert_eq!(res, body);
+}
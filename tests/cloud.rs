@@ -0,0 +1,172 @@
+use std::sync::{Arc, Mutex};
+
+use robolt::api::cloud::{
+	parse_resource_id,
+	CloudGroup,
+	CloudMembership,
+	CloudPage,
+	CloudUser,
+	InventoryFilter,
+	InventoryItem,
+	InventoryItemDetails,
+	MembershipFilter,
+};
+use robolt::errors::RoboltError;
+use robolt::middleware::{Middleware, RequestParts};
+use robolt::Robolt;
+
+struct ApiKeyRecorder(Arc<Mutex<Vec<bool>>>);
+
+impl Middleware for ApiKeyRecorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		self.0.lock().unwrap().push(parts.headers.contains_key("x-api-key"));
+		Err("recorded".to_string().into())
+	}
+}
+
+struct UrlRecorder(Arc<Mutex<Option<String>>>);
+
+impl Middleware for UrlRecorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		*self.0.lock().unwrap() = Some(parts.url.clone());
+		Err("recorded".to_string().into())
+	}
+}
+
+#[test]
+fn cloud_user() {
+	let user: CloudUser = serde_json::from_str(include_str!("fixtures/cloud/user.json")).unwrap();
+
+	assert_eq!(user.id, 100000001);
+	assert_eq!(user.premium, Some(true));
+	assert_eq!(
+		user.social_network_profiles.unwrap().twitter.as_deref(),
+		Some("example")
+	);
+}
+
+#[test]
+fn cloud_user_without_scopes() {
+	let user: CloudUser = serde_json::from_str(include_str!("fixtures/cloud/user_without_scopes.json")).unwrap();
+
+	assert_eq!(user.premium, None);
+	assert_eq!(user.id_verified, None);
+	assert_eq!(user.social_network_profiles, None);
+}
+
+#[test]
+fn cloud_group() {
+	let group: CloudGroup = serde_json::from_str(include_str!("fixtures/cloud/group.json")).unwrap();
+
+	assert_eq!(group.id, 2000001);
+	assert_eq!(group.owner, Some(100000001));
+	assert_eq!(group.member_count, 1523);
+}
+
+#[test]
+fn resource_ids() {
+	assert_eq!(parse_resource_id("users/123"), Some(123));
+	assert_eq!(parse_resource_id("groups/7/memberships/abc"), None);
+	assert_eq!(parse_resource_id("users/"), None);
+}
+
+#[tokio::test]
+async fn api_key_only_sent_to_open_cloud() {
+	let recorded = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new()
+		.set_api_key("key".to_string())
+		.middleware(ApiKeyRecorder(recorded.clone()));
+
+	let _ = client.cloud_user(1).await;
+	let _ = client.user(1).await;
+
+	assert_eq!(*recorded.lock().unwrap(), vec![true, false]);
+}
+
+#[tokio::test]
+async fn invalid_api_key_is_an_error() {
+	let client = Robolt::new().set_api_key("key\nwith newline".to_string());
+	let err = client.cloud_user(1).await.unwrap_err();
+
+	assert!(err.message.contains("API key"), "{err}");
+}
+
+#[test]
+fn cloud_memberships() {
+	let page: CloudPage<CloudMembership> =
+		serde_json::from_str(include_str!("fixtures/cloud/memberships.json")).unwrap();
+
+	assert_eq!(page.items.len(), 2);
+	assert_eq!(page.items[0].id, "NTA0MjAxMjE");
+	assert_eq!(page.items[0].user_id, 100000001);
+	assert_eq!(page.items[0].role_id, 31701802);
+	assert_eq!(page.next_page_token.as_deref(), Some("eyJsYXN0SWQiOjEwMDAwMDAwMn0"));
+
+	let last: CloudPage<CloudMembership> =
+		serde_json::from_str(r#"{ "groupMemberships": [], "nextPageToken": "" }"#).unwrap();
+	assert_eq!(last.next_page_token, None);
+}
+
+#[test]
+fn membership_filters() {
+	assert_eq!(MembershipFilter::user(123).to_string(), "user == 'users/123'");
+	assert_eq!(
+		MembershipFilter::users(&[1, 2]).to_string(),
+		"user in ['users/1', 'users/2']"
+	);
+	assert_eq!(MembershipFilter::role(7, 99).to_string(), "role == 'groups/7/roles/99'");
+}
+
+#[tokio::test]
+async fn cloud_memberships_query() {
+	let recorded = Arc::new(Mutex::new(None));
+	let client = Robolt::new().middleware(UrlRecorder(recorded.clone()));
+
+	let _ = client
+		.cloud_group_memberships(7, Some(MembershipFilter::user(123)), 50, Some("abc"))
+		.await;
+
+	assert_eq!(
+		recorded.lock().unwrap().take().unwrap(),
+		"https://apis.roblox.com/cloud/v2/groups/7/memberships?maxPageSize=50&filter=user%20%3D%3D%20%27users%2F123%27&pageToken=abc"
+	);
+}
+
+#[test]
+fn cloud_inventory_items() {
+	let page: CloudPage<InventoryItem> =
+		serde_json::from_str(include_str!("fixtures/cloud/inventory_items.json")).unwrap();
+
+	assert_eq!(page.items.len(), 4);
+	assert_eq!(page.next_page_token, None);
+	assert_eq!(page.items[0].id, "VVNFUl9BU1NFVF9JRD0xMjM0");
+
+	match &page.items[1].details {
+		InventoryItemDetails::AssetDetails(asset) => {
+			assert_eq!(asset.asset_id, 5678);
+			assert_eq!(asset.collectible_details.as_ref().unwrap().serial_number, Some(42));
+		},
+		details => panic!("expected asset details, got {details:?}"),
+	}
+
+	assert_eq!(page.items[2].details, InventoryItemDetails::BadgeDetails {
+		badge_id: 901
+	});
+	assert_eq!(page.items[3].details, InventoryItemDetails::GamePassDetails {
+		game_pass_id: 123
+	});
+}
+
+#[test]
+fn inventory_filters() {
+	assert_eq!(InventoryFilter::new().to_string(), "");
+	assert_eq!(
+		InventoryFilter::new()
+			.asset_ids(vec![1, 2])
+			.badge_ids(vec![3])
+			.inventory_item_asset_types(vec!["HAT".to_string(), "CLASSIC_SHIRT".to_string()])
+			.only_collectibles(true)
+			.to_string(),
+		"assetIds=1,2;badgeIds=3;inventoryItemAssetTypes=HAT,CLASSIC_SHIRT;onlyCollectibles=true"
+	);
+}
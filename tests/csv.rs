@@ -0,0 +1,66 @@
+#![cfg(feature = "csv")]
+
+use robolt::api::cloud::{CloudPage, InventoryItem};
+use robolt::api::economy::ResaleData;
+use robolt::api::games::GameDetails;
+use robolt::api::groups::{Group, GroupMember};
+use robolt::api::users::User;
+use robolt::api::Page;
+use robolt::csv::{write_rows, ToRow};
+
+fn to_csv<T: ToRow>(rows: Vec<T>) -> String {
+	let mut csv = Vec::new();
+	write_rows(&mut csv, rows).unwrap();
+	String::from_utf8(csv).unwrap()
+}
+
+#[test]
+fn users_csv() {
+	let users: Vec<User> = serde_json::from_str(include_str!("fixtures/csv/users.json")).unwrap();
+	assert_eq!(to_csv(users), include_str!("fixtures/csv/users.csv"));
+}
+
+#[test]
+fn group_members_csv() {
+	let members: Page<GroupMember> = serde_json::from_str(include_str!("fixtures/groups/members.json")).unwrap();
+	assert_eq!(to_csv(members.data), include_str!("fixtures/csv/members.csv"));
+}
+
+#[test]
+fn groups_csv() {
+	let group: Group = serde_json::from_str(include_str!("fixtures/groups/group.json")).unwrap();
+	assert_eq!(to_csv(vec![group]), include_str!("fixtures/csv/groups.csv"));
+}
+
+#[test]
+fn game_details_csv() {
+	let universes: serde_json::Value =
+		serde_json::from_str(include_str!("fixtures/games/universes.json")).unwrap();
+	let games: Vec<GameDetails> = serde_json::from_value(universes["data"].clone()).unwrap();
+	assert_eq!(to_csv(games), include_str!("fixtures/csv/games.csv"));
+}
+
+#[test]
+fn inventory_items_csv() {
+	let page: CloudPage<InventoryItem> =
+		serde_json::from_str(include_str!("fixtures/cloud/inventory_items.json")).unwrap();
+	assert_eq!(to_csv(page.items), include_str!("fixtures/csv/inventory_items.csv"));
+}
+
+#[test]
+fn price_points_csv() {
+	let resale_data: ResaleData = serde_json::from_str(include_str!("fixtures/economy/resale_data.json")).unwrap();
+	assert_eq!(
+		to_csv(resale_data.price_points()),
+		"date,price,volume\n\
+		 2023-05-01T05:00:00Z,1000,12\n\
+		 2023-05-02T05:00:00Z,1100,3\n\
+		 2023-05-03T05:00:00Z,1200,0\n\
+		 2023-05-04T05:00:00Z,1300,7\n"
+	);
+}
+
+#[test]
+fn empty_csv_has_headers() {
+	assert_eq!(to_csv(Vec::<User>::new()), format!("{}\n", User::headers().join(",")));
+}
@@ -0,0 +1,74 @@
+use robolt::api::develop::{PlayableDevice, UniverseAvatarType, UniverseConfiguration};
+
+#[test]
+fn universe_configuration_unknown_enum_values() {
+	let configuration: UniverseConfiguration = serde_json::from_str(
+		r#"{
+			"id": 2680623874,
+			"name": "Example Universe",
+			"universeAvatarType": "MorphToSomethingNew",
+			"playableDevices": ["Computer", "VR", "Fridge"],
+			"studioAccessToApisAllowed": true,
+			"permissions": {
+				"IsThirdPartyTeleportAllowed": false,
+				"IsThirdPartyAssetAllowed": true,
+				"IsThirdPartyPurchaseAllowed": false
+			}
+		}"#,
+	)
+	.unwrap();
+
+	assert_eq!(configuration.universe_avatar_type, UniverseAvatarType::Other);
+	assert_eq!(configuration.playable_devices, vec![
+		PlayableDevice::Computer,
+		PlayableDevice::Vr,
+		PlayableDevice::Other
+	]);
+	assert_eq!(configuration.studio_access_to_apis_allowed, Some(true));
+	assert!(configuration.permissions.unwrap().third_party_asset_allowed);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn universe_configuration_reads_and_writes_same_version() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::Robolt;
+	use serde_json::json;
+
+	let url = "https://develop.roblox.com/v2/universes/1/configuration";
+	let configuration = MockResponse::json(&json!({
+		"id": 1,
+		"name": "Example Universe",
+		"universeAvatarType": "MorphToR15",
+		"studioAccessToApisAllowed": false
+	}));
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(Method::GET, url, configuration.clone())
+		.route(Method::PATCH, url, configuration);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	assert!(client.assert_api_access_enabled(1).await.is_err());
+	client
+		.update_universe_configuration(1)
+		.studio_access_to_apis_allowed(true)
+		.update()
+		.await
+		.unwrap();
+
+	let requests = transport.requests();
+	assert_eq!(requests[1].url, url);
+	assert_eq!(requests[2].url, url);
+	assert_eq!(requests[2].body, Some(json!({ "studioAccessToApisAllowed": true })));
+}
@@ -0,0 +1,140 @@
+use robolt::api::economy::{
+	PricePoint,
+	ResaleData,
+	RevenueSummary,
+	Transaction,
+	TransactionItem,
+	TransactionPlace,
+};
+use robolt::api::{Page, Robux};
+
+#[test]
+fn revenue_summary_tolerates_bucket_changes() {
+	let summary: RevenueSummary = serde_json::from_str(
+		r#"{
+			"pendingRobux": 1200,
+			"itemSaleRobux": 3400,
+			"premiumPayouts": 56,
+			"immersiveAdPayouts": 78
+		}"#,
+	)
+	.unwrap();
+
+	assert_eq!(summary.pending_robux, 1200);
+	assert_eq!(summary.item_sale_robux, 3400);
+	assert_eq!(summary.premium_payouts, 56);
+	assert_eq!(summary.trade_system_robux, 0);
+	assert_eq!(summary.extra.get("immersiveAdPayouts"), Some(&78.into()));
+}
+
+#[test]
+fn robux_marketplace_fee_rounds_down() {
+	let earnings = [
+		(0, 0),
+		(1, 0),
+		(2, 1),
+		(5, 3),
+		(7, 4),
+		(10, 7),
+		(15, 10),
+		(99, 69),
+		(100, 70),
+		(1337, 935),
+	];
+
+	for (price, earned) in earnings {
+		assert_eq!(Robux(price).after_marketplace_fee(), Robux(earned), "price {price}");
+		assert_eq!(Robux(price).marketplace_fee(), Robux(price - earned), "price {price}");
+	}
+
+	assert_eq!(Robux(u64::MAX).after_marketplace_fee(), Robux(u64::MAX / 10 * 7 + 3));
+}
+
+#[test]
+fn robux_arithmetic() {
+	assert_eq!(Robux(5).checked_add(Robux(10)), Some(Robux(15)));
+	assert_eq!(Robux(u64::MAX).checked_add(Robux(1)), None);
+	assert_eq!(Robux(5).checked_sub(Robux(10)), None);
+	assert_eq!(Robux(5).saturating_sub(Robux(10)), Robux(0));
+	assert_eq!(Robux(u64::MAX).saturating_add(Robux(1)), Robux(u64::MAX));
+}
+
+#[test]
+fn robux_display() {
+	assert_eq!(Robux(0).to_string(), "0");
+	assert_eq!(Robux(999).to_string(), "999");
+	assert_eq!(Robux(1000).to_string(), "1,000");
+	assert_eq!(Robux(1234567).to_string(), "1,234,567");
+	assert_eq!(format!("{:>7}", Robux(1000)), "  1,000");
+}
+
+#[test]
+fn robux_serde() {
+	assert_eq!(serde_json::from_str::<Robux>("1200").unwrap(), Robux(1200));
+	assert_eq!(serde_json::from_str::<Robux>(r#""1200""#).unwrap(), Robux(1200));
+	assert!(serde_json::from_str::<Robux>(r#""1,200""#).is_err());
+	assert!(serde_json::from_str::<Robux>("-5").is_err());
+	assert_eq!(serde_json::to_string(&Robux(1200)).unwrap(), "1200");
+}
+
+#[test]
+fn resale_data_price_points() {
+	let resale_data: ResaleData = serde_json::from_str(include_str!("fixtures/economy/resale_data.json")).unwrap();
+
+	assert_eq!(resale_data.asset_stock, None);
+	assert_eq!(resale_data.recent_average_price, Robux(1210));
+	assert_eq!(resale_data.price_points(), vec![
+		PricePoint {
+			date: "2023-05-01T05:00:00Z".to_string(),
+			price: Robux(1000),
+			volume: 12,
+		},
+		PricePoint {
+			date: "2023-05-02T05:00:00Z".to_string(),
+			price: Robux(1100),
+			volume: 3,
+		},
+		PricePoint {
+			date: "2023-05-03T05:00:00Z".to_string(),
+			price: Robux(1200),
+			volume: 0,
+		},
+		PricePoint {
+			date: "2023-05-04T05:00:00Z".to_string(),
+			price: Robux(1300),
+			volume: 7,
+		},
+	]);
+}
+
+#[test]
+fn empty_resale_data() {
+	let resale_data: ResaleData = serde_json::from_str(r#"{"sales": 0}"#).unwrap();
+	assert!(resale_data.price_points().is_empty());
+}
+
+#[test]
+fn transaction_details() {
+	let transactions: Page<Transaction> =
+		serde_json::from_str(include_str!("fixtures/economy/transactions.json")).unwrap();
+
+	assert_eq!(
+		transactions.data[0].item(),
+		Some(TransactionItem {
+			id: 1034719863,
+			name: "VIP".to_string(),
+			item_type: "GamePass".to_string(),
+		})
+	);
+	assert_eq!(
+		transactions.data[0].place(),
+		Some(TransactionPlace {
+			place_id: 6516141723,
+			universe_id: 2440500124,
+			name: "DOORS".to_string(),
+		})
+	);
+	assert_eq!(transactions.data[1].item(), None);
+	assert_eq!(transactions.data[1].place(), None);
+	assert_eq!(transactions.data[1].currency.amount, -250);
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "test-util")]
+
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use robolt::api::Limit;
+use robolt::errors::{NotFoundReason, ResourceKind, RoboltError, RoboltErrorKind};
+use robolt::test_util::{MockResponse, MockTransport};
+use robolt::Robolt;
+
+async fn fetch_error(url: &str, response: MockResponse) -> RoboltError {
+	let client = Robolt::new().mock_transport(MockTransport::new().route(Method::GET, url, response));
+	let host = url.trim_start_matches("https://").split('/').next().unwrap();
+
+	let res = match host {
+		"users.roblox.com" => client.user(1).await.map(drop),
+		"friends.roblox.com" => client.follower_count(1).await.map(drop),
+		"groups.roblox.com" => client.group(2).await.map(drop),
+		"badges.roblox.com" => client.badge(3).await.map(drop),
+		"catalog.roblox.com" if url.contains("/users/") => client.user_bundles(1).await.map(drop),
+		"catalog.roblox.com" => client.bundle(4).await.map(drop),
+		"avatar.roblox.com" => client.outfit(5).await.map(drop),
+		"games.roblox.com" => client.game_servers(6, Limit::Min, None).await.map(drop),
+		"apis.roblox.com" => client.cloud_user(1).await.map(drop),
+		_ => unreachable!("no wrapper for {url}"),
+	};
+
+	res.unwrap_err()
+}
+
+fn not_found(resource: ResourceKind, id: u64, reason: NotFoundReason) -> RoboltErrorKind {
+	RoboltErrorKind::NotFound {
+		resource,
+		id,
+		reason,
+	}
+}
+
+#[tokio::test]
+async fn not_found_reasons() {
+	let cases = [
+		(
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+			not_found(ResourceKind::User, 1, NotFoundReason::Invalid),
+		),
+		(
+			"https://friends.roblox.com/v1/users/1/followers/count",
+			MockResponse::error(
+				StatusCode::BAD_REQUEST,
+				1,
+				"The target user is invalid or does not exist.",
+			),
+			not_found(ResourceKind::User, 1, NotFoundReason::Invalid),
+		),
+		(
+			"https://groups.roblox.com/v1/groups/2",
+			MockResponse::error(StatusCode::BAD_REQUEST, 1, "Group is invalid or does not exist."),
+			not_found(ResourceKind::Group, 2, NotFoundReason::Invalid),
+		),
+		(
+			"https://badges.roblox.com/v1/badges/3",
+			MockResponse::error(StatusCode::NOT_FOUND, 1, "Badge is invalid or does not exist."),
+			not_found(ResourceKind::Badge, 3, NotFoundReason::Invalid),
+		),
+		(
+			"https://catalog.roblox.com/v1/bundles/4/details",
+			MockResponse::error(StatusCode::BAD_REQUEST, 1, "Invalid bundle"),
+			not_found(ResourceKind::Bundle, 4, NotFoundReason::Invalid),
+		),
+		(
+			"https://catalog.roblox.com/v1/users/1/bundles",
+			MockResponse::error(StatusCode::FORBIDDEN, 11, "The target user's inventory is private."),
+			not_found(ResourceKind::User, 1, NotFoundReason::Hidden),
+		),
+		(
+			"https://avatar.roblox.com/v1/outfits/5/details",
+			MockResponse::error(StatusCode::BAD_REQUEST, 1, "The specified userOutfit does not exist!"),
+			not_found(ResourceKind::Outfit, 5, NotFoundReason::Invalid),
+		),
+		(
+			"https://games.roblox.com/v1/games/6/servers/Public?limit=10",
+			MockResponse::error(StatusCode::BAD_REQUEST, 1, "The place is invalid."),
+			not_found(ResourceKind::Place, 6, NotFoundReason::Invalid),
+		),
+		(
+			"https://badges.roblox.com/v1/badges/3",
+			MockResponse::error(StatusCode::GONE, 0, "Gone"),
+			not_found(ResourceKind::Badge, 3, NotFoundReason::Deleted),
+		),
+		(
+			"https://groups.roblox.com/v1/groups/2",
+			MockResponse::error(StatusCode::NOT_FOUND, 0, "NotFound"),
+			not_found(ResourceKind::Group, 2, NotFoundReason::Unknown),
+		),
+		(
+			"https://apis.roblox.com/cloud/v2/users/1",
+			MockResponse::json(&json!({ "code": "NOT_FOUND", "message": "User not found." }))
+				.status(StatusCode::NOT_FOUND),
+			not_found(ResourceKind::User, 1, NotFoundReason::Unknown),
+		),
+		// Codes of other resources or APIs are not a missing resource
+		(
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::error(StatusCode::BAD_REQUEST, 1, "Too many ids."),
+			RoboltErrorKind::Api,
+		),
+		(
+			"https://groups.roblox.com/v1/groups/2",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed"),
+			RoboltErrorKind::PermissionDenied,
+		),
+	];
+
+	for (url, response, kind) in cases {
+		let err = fetch_error(url, response).await;
+		assert_eq!(err.kind(), &kind, "{url}");
+	}
+}
+
+#[tokio::test]
+async fn not_found_keeps_the_api_error() {
+	let err = fetch_error(
+		"https://users.roblox.com/v1/users/1",
+		MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+	)
+	.await;
+
+	assert_eq!(err.code, 3);
+	assert_eq!(err.status(), Some(404));
+	assert_eq!(
+		err.to_string(),
+		"[Robolt] User 1 Not Found (Invalid): The user id is invalid."
+	);
+}
+
+#[test]
+fn permanent_not_found_reasons() {
+	assert!(NotFoundReason::Invalid.is_permanent());
+	assert!(NotFoundReason::Deleted.is_permanent());
+	assert!(!NotFoundReason::Hidden.is_permanent());
+	assert!(!NotFoundReason::Unknown.is_permanent());
+}
+
+#[tokio::test]
+async fn error_kinds_by_cause() {
+	let err = fetch_error(
+		"https://users.roblox.com/v1/users/1",
+		MockResponse::json(&json!({ "id": "not a number" })),
+	)
+	.await;
+	assert_eq!(err.kind(), &RoboltErrorKind::Deserialize);
+
+	let err = fetch_error(
+		"https://users.roblox.com/v1/users/1",
+		MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests"),
+	)
+	.await;
+	assert_eq!(err.kind(), &RoboltErrorKind::RateLimited);
+	assert_eq!(err.status(), Some(429));
+
+	let err = Robolt::from("".to_string()).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::InvalidCookie);
+}
+
+#[tokio::test]
+async fn errors_propagate_as_std_errors() {
+	async fn user_name(client: &Robolt) -> Result<String, Box<dyn std::error::Error>> {
+		Ok(client.user(1).await?.username)
+	}
+
+	let client = Robolt::new().mock_transport(MockTransport::new().route(
+		Method::GET,
+		"https://users.roblox.com/v1/users/1",
+		MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+	));
+	let err = user_name(&client).await.unwrap_err();
+
+	assert_eq!(
+		err.to_string(),
+		"[Robolt] User 1 Not Found (Invalid): The user id is invalid."
+	);
+}
+
+#[tokio::test]
+async fn invalid_responses_keep_their_context() {
+	let url = "https://users.roblox.com/v1/users/1";
+	let err = fetch_error(url, MockResponse::json(&json!({ "id": "not a number" }))).await;
+
+	assert_eq!(err.kind(), &RoboltErrorKind::Deserialize);
+	assert_eq!(err.status(), Some(200));
+	assert_eq!(err.url(), Some(url));
+	assert!(err.message.starts_with(&format!("Invalid response from GET {url}: ")));
+	assert_eq!(err.body(), None);
+}
+
+#[tokio::test]
+async fn captured_error_bodies() {
+	let url = "https://users.roblox.com/v1/users/1";
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			url,
+			MockResponse::json(&"<html>Bad Request</html>").status(StatusCode::BAD_REQUEST),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/2",
+			MockResponse::json(&"a".repeat(2000)),
+		);
+	let client = Robolt::new().capture_error_bodies(true).mock_transport(transport);
+
+	let err = client.user(1).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::Api);
+	assert_eq!(err.status(), Some(400));
+	assert_eq!(err.url(), Some(url));
+	assert_eq!(err.body(), Some(r#""<html>Bad Request</html>""#));
+
+	let err = client.user(2).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::Deserialize);
+	assert_eq!(err.body().map(str::len), Some(1024));
+}
+
+#[tokio::test]
+async fn large_error_codes_keep_their_message() {
+	let err = fetch_error(
+		"https://badges.roblox.com/v1/badges/3",
+		MockResponse::error(StatusCode::BAD_REQUEST, 1000, "Badge is not available."),
+	)
+	.await;
+
+	assert_eq!(err.kind(), &RoboltErrorKind::Api);
+	assert_eq!(err.code, 1000);
+	assert_eq!(err.message, "Badge is not available.");
+}
@@ -1,18 +1,19 @@
 use tokio_test::assert_ok;
 
-use robolt::api::Limit;
+use robolt::api::friends::{FriendRequest, FriendRequestOrigin};
+use robolt::api::{Limit, Page};
 use robolt::Robolt;
 
 #[tokio::test]
 async fn followers() {
 	let client = Robolt::new();
-	assert_ok!(client.followers(1, Limit::Min).await);
+	assert_ok!(client.followers(1, Limit::Min, None).await);
 }
 
 #[tokio::test]
 async fn followings() {
 	let client = Robolt::new();
-	assert_ok!(client.followings(1, Limit::Min).await);
+	assert_ok!(client.followings(1, Limit::Min, None).await);
 }
 
 #[tokio::test]
@@ -38,3 +39,142 @@ async fn following_count() {
 	let client = Robolt::new();
 	assert_ok!(client.following_count(1).await);
 }
+
+#[test]
+fn friend_request_metadata() {
+	let page: Page<FriendRequest> = serde_json::from_str(include_str!("fixtures/friends/requests.json")).unwrap();
+	let requests = page.data;
+
+	assert_eq!(requests[0].user.id, 156);
+	assert_eq!(requests[0].user.display_name, "Builder Man");
+	assert_eq!(requests[0].origin, FriendRequestOrigin::InGame);
+	assert_eq!(requests[0].source_universe_id, Some(13058));
+	assert_eq!(requests[0].sent_at, "2023-06-01T12:00:00.000Z");
+	assert!(requests[0].mutual_friends.is_empty());
+
+	assert_eq!(requests[1].origin, FriendRequestOrigin::UserProfile);
+	assert_eq!(requests[1].mutual_friends, vec!["Roblox", "builderman"]);
+
+	assert_eq!(requests[2].origin, FriendRequestOrigin::Other {
+		source_type: "ContactImporter".to_string(),
+	});
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn followers_pages() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let follower = |id: u64, name: &str| json!({ "id": id, "name": name, "displayName": name, "created": "0001-01-01T05:51:00Z", "isOnline": false });
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://friends.roblox.com/v1/users/1/followers?limit=10",
+			MockResponse::json(&json!({
+				"previousPageCursor": null,
+				"nextPageCursor": "eyJrZXkiOjJ9",
+				"data": [follower(156, "builderman")],
+			})),
+		)
+		.route(
+			Method::GET,
+			"https://friends.roblox.com/v1/users/1/followers?limit=10&cursor=eyJrZXkiOjJ9",
+			MockResponse::json(&json!({
+				"previousPageCursor": "eyJrZXkiOjF9",
+				"nextPageCursor": null,
+				"data": [follower(261, "Shedletsky")],
+			})),
+		);
+	let client = Robolt::new().mock_transport(transport);
+
+	let page = client.followers(1, Limit::Min, None).await.unwrap();
+	assert_eq!(page.data[0].username, "builderman");
+
+	let cursor = page.next_page_cursor.unwrap();
+	let page = client.followers(1, Limit::Min, Some(&cursor)).await.unwrap();
+	assert_eq!(page.data[0].id, 261);
+	assert_eq!(page.next_page_cursor, None);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn friend_request_errors_are_api_errors() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::POST,
+			"https://friends.roblox.com/v1/users/156/request-friendship",
+			MockResponse::error(StatusCode::BAD_REQUEST, 5, "The target user is already a friend."),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let err = client.send_friend_request(156).await.unwrap_err();
+
+	assert_eq!(err.kind(), &RoboltErrorKind::Api);
+	assert_eq!(err.code, 5);
+	assert_eq!(err.message, "The target user is already a friend.");
+	assert_eq!(
+		transport.requests()[1].body,
+		Some(json!({ "friendshipOriginSourceType": "UserProfile" }))
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn dry_run_unfollow_many_reports_followed_users() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::POST,
+			"https://friends.roblox.com/v1/user/following-exists",
+			MockResponse::json(&json!({
+				"followings": [
+					{ "isFollowing": true, "isFollowed": false, "userId": 1 },
+					{ "isFollowing": false, "isFollowed": true, "userId": 2 },
+					{ "isFollowing": true, "isFollowed": true, "userId": 3 },
+				]
+			})),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let report = client.unfollow_many(vec![1, 2, 3], 2, true).await.unwrap();
+
+	assert!(report.dry_run);
+	assert_eq!(report.succeeded, vec![1, 3]);
+	assert_eq!(report.skipped, vec![2]);
+
+	let requests = transport.requests();
+	assert_eq!(requests.len(), 2);
+	assert_eq!(requests[1].body, Some(json!({ "targetUserIds": [1, 2, 3] })));
+}
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use tokio_test::assert_ok;
+
+use robolt::api::games::{
+	crawl_servers,
+	DeveloperProduct,
+	DeveloperProductId,
+	GameDetails,
+	GamePass,
+	GameServer,
+	Playability,
+	PlayabilityStatus,
+	ProductId,
+	ServerCrawlOptions,
+	SocialLink,
+	SocialLinkRequest,
+	SocialLinkType,
+};
+use robolt::api::{Creator, Limit, Page, Robux};
+use robolt::Robolt;
+
+#[tokio::test]
+async fn game_servers() {
+	let client = Robolt::new();
+	assert_ok!(client.game_servers(1818, Limit::Min, None).await);
+}
+
+#[tokio::test]
+async fn universe_details() {
+	let client = Robolt::new();
+	let details = client.universe_details(vec![13058]).await.unwrap();
+
+	assert_eq!(details[0].root_place_id, 1818);
+}
+
+#[tokio::test]
+async fn social_links() {
+	let client = Robolt::new();
+	assert_ok!(client.social_links(1818).await);
+}
+
+#[test]
+fn social_link_types() {
+	let links: Page<SocialLink> = serde_json::from_str(include_str!("fixtures/games/social_links.json")).unwrap();
+	let types = links
+		.data
+		.iter()
+		.map(|link| link.link_type.clone())
+		.collect::<Vec<SocialLinkType>>();
+
+	assert_eq!(types, vec![
+		SocialLinkType::Discord,
+		SocialLinkType::YouTube,
+		SocialLinkType::Other
+	]);
+	assert_eq!(links.data[0].title, "Community Discord");
+}
+
+#[test]
+fn social_link_request_body() {
+	let link = SocialLinkRequest {
+		link_type: SocialLinkType::RobloxGroup,
+		title: "Fan group".to_string(),
+		url: "https://www.roblox.com/groups/7".to_string(),
+	};
+
+	assert_eq!(
+		serde_json::to_value(&link).unwrap(),
+		serde_json::json!({
+			"type": "RobloxGroup",
+			"title": "Fan group",
+			"url": "https://www.roblox.com/groups/7",
+		})
+	);
+}
+
+fn server(id: &str) -> GameServer {
+	GameServer {
+		id: id.to_string(),
+		max_players: 10,
+		playing: 1,
+		..Default::default()
+	}
+}
+
+fn page(servers: &[&str], next: Option<&str>) -> Page<GameServer> {
+	Page {
+		previous_page_cursor: None,
+		next_page_cursor: next.map(String::from),
+		data: servers.iter().map(|id| server(id)).collect(),
+	}
+}
+
+/// Serves pages keyed by the cursor used to request them
+fn pages(pages: Vec<(Option<&str>, Page<GameServer>)>) -> HashMap<Option<String>, Page<GameServer>> {
+	pages
+		.into_iter()
+		.map(|(cursor, page)| (cursor.map(String::from), page))
+		.collect()
+}
+
+#[tokio::test]
+async fn crawl_drops_duplicate_servers() {
+	let pages = pages(vec![
+		(None, page(&["a", "b"], Some("1"))),
+		(Some("1"), page(&["b", "c"], Some("2"))),
+		(Some("2"), page(&["a", "d"], None)),
+	]);
+
+	let snapshot = crawl_servers(ServerCrawlOptions::new(), |cursor| {
+		let page = pages[&cursor].clone();
+		async move { Ok(page) }
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(
+		snapshot
+			.servers
+			.iter()
+			.map(|server| server.id.as_str())
+			.collect::<Vec<_>>(),
+		vec!["a", "b", "c", "d"]
+	);
+	assert_eq!(snapshot.pages_fetched, 3);
+	assert_eq!(snapshot.duplicates_dropped, 2);
+	assert!(!snapshot.truncated);
+	assert!(!snapshot.cursor_loop);
+}
+
+#[tokio::test]
+async fn crawl_detects_cursor_loops() {
+	let pages = pages(vec![
+		(None, page(&["a"], Some("1"))),
+		(Some("1"), page(&["b"], Some("2"))),
+		(Some("2"), page(&["c"], Some("1"))),
+	]);
+
+	let snapshot = crawl_servers(ServerCrawlOptions::new(), |cursor| {
+		let page = pages[&cursor].clone();
+		async move { Ok(page) }
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(snapshot.servers.len(), 3);
+	assert_eq!(snapshot.pages_fetched, 3);
+	assert!(snapshot.cursor_loop);
+	assert!(snapshot.truncated);
+}
+
+#[tokio::test]
+async fn crawl_respects_page_budget() {
+	let snapshot = crawl_servers(ServerCrawlOptions::new().max_pages(2), |cursor| async move {
+		let n = cursor.map_or(0, |cursor| cursor.parse::<u32>().unwrap());
+		let id = n.to_string();
+		let next = (n + 1).to_string();
+		Ok(page(&[id.as_str()], Some(next.as_str())))
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(snapshot.pages_fetched, 2);
+	assert!(snapshot.truncated);
+	assert!(!snapshot.cursor_loop);
+}
+
+#[tokio::test]
+async fn crawl_stops_when_server_matches() {
+	let pages = pages(vec![
+		(None, page(&["a", "b"], Some("1"))),
+		(Some("1"), page(&["c", "d"], Some("2"))),
+		(Some("2"), page(&["e"], None)),
+	]);
+
+	let options = ServerCrawlOptions::new().stop_when(|server| server.id == "c");
+	let snapshot = crawl_servers(options, |cursor| {
+		let page = pages[&cursor].clone();
+		async move { Ok(page) }
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(snapshot.servers.last().unwrap().id, "c");
+	assert_eq!(snapshot.pages_fetched, 2);
+	assert!(snapshot.stopped_early);
+	assert!(!snapshot.truncated);
+}
+
+#[test]
+fn developer_products() {
+	let products: Vec<DeveloperProduct> =
+		serde_json::from_str(include_str!("fixtures/games/developer_products.json")).unwrap();
+
+	assert_eq!(products[0].product_id, ProductId(1301252944));
+	assert_eq!(products[0].developer_product_id, DeveloperProductId(16113004));
+	assert_eq!(products[0].price_in_robux, Some(Robux(25)));
+	assert_eq!(products[1].price_in_robux, None);
+	assert_eq!(products[1].icon_image_asset_id, None);
+
+	let product: DeveloperProduct =
+		serde_json::from_str(include_str!("fixtures/games/developer_product.json")).unwrap();
+	assert_eq!(product, products[0]);
+}
+
+#[test]
+fn playability_statuses() {
+	let statuses: Vec<Playability> =
+		serde_json::from_str(include_str!("fixtures/games/playability.json")).unwrap();
+	let statuses = statuses
+		.into_iter()
+		.map(|status| (status.universe_id, status))
+		.collect::<HashMap<u64, Playability>>();
+
+	assert!(statuses[&13058].is_playable);
+	assert_eq!(statuses[&13058].status, PlayabilityStatus::Playable);
+	assert_eq!(statuses[&13058].unplayable_display_text, None);
+	assert_eq!(statuses[&2440500124].status, PlayabilityStatus::PurchaseRequired {
+		price: Some(Robux(25)),
+	});
+	assert_eq!(statuses[&3317771874].status, PlayabilityStatus::GuestProhibited);
+	assert_eq!(
+		statuses[&3317771874].unplayable_display_text.as_deref(),
+		Some("Log in to play")
+	);
+	assert_eq!(statuses[&4922741943].status, PlayabilityStatus::UnderReview);
+	assert!(!statuses[&5166944221].is_playable);
+	assert_eq!(statuses[&5166944221].status, PlayabilityStatus::Other {
+		status: "TemporarilyUnavailable".to_string(),
+	});
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn playability_is_chunked() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let ids = (1..=60).collect::<Vec<u64>>();
+	let url = |ids: &[u64]| {
+		let ids = ids.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+		format!("https://games.roblox.com/v1/games/multiget-playability-status?universeIds={ids}")
+	};
+	let statuses = |ids: &[u64]| {
+		let statuses = ids
+			.iter()
+			.map(|id| json!({ "playabilityStatus": "Playable", "isPlayable": true, "universeId": id }))
+			.collect::<Vec<_>>();
+
+		MockResponse::json(&statuses)
+	};
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(Method::GET, &url(&ids[..50]), statuses(&ids[..50]))
+		.route(Method::GET, &url(&ids[50..]), statuses(&ids[50..]));
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let playability = client.playability(&ids).await.unwrap();
+
+	assert_eq!(playability.len(), 60);
+	assert!(playability.values().all(|status| status.is_playable));
+	assert_eq!(
+		transport
+			.requests()
+			.iter()
+			.filter(|request| request.method == Method::GET)
+			.count(),
+		2
+	);
+}
+
+#[test]
+fn game_details_creators() {
+	let details: Page<GameDetails> = serde_json::from_str(include_str!("fixtures/games/universes.json")).unwrap();
+
+	assert_eq!(details.data[0].creator, Creator::User {
+		id: 1,
+		name: "Roblox".to_string(),
+	});
+	assert_eq!(
+		details.data[0].description.as_deref(),
+		Some("The classic ROBLOX level is back!")
+	);
+	assert_eq!(details.data[0].price, None);
+	assert_eq!(details.data[1].creator, Creator::Group {
+		id: 5304356,
+		name: "LSPLASH".to_string(),
+	});
+	assert_eq!(details.data[1].description, None);
+	assert_eq!(details.data[1].price, Some(Robux(25)));
+	assert_eq!(details.data[1].max_players, 12);
+}
+
+#[test]
+fn game_passes() {
+	let passes: Page<GamePass> = serde_json::from_str(include_str!("fixtures/games/game_passes.json")).unwrap();
+
+	assert_eq!(passes.data[0].price, Some(Robux(199)));
+	assert_eq!(passes.data[1].price, None);
+	assert_eq!(passes.data[1].product_id, None);
+	assert_eq!(passes.next_page_cursor.as_deref(), Some("eyJzdGFydEluZGV4IjoyfQ"));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn details_urls() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::games::ServerType;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::{json, Value};
+
+	let universes: Value = serde_json::from_str(include_str!("fixtures/games/universes.json")).unwrap();
+	let places: Value = serde_json::from_str(include_str!("fixtures/games/place_details.json")).unwrap();
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://games.roblox.com/v1/games?universeIds=13058,2440500124",
+			MockResponse::json(&universes),
+		)
+		.route(
+			Method::GET,
+			"https://games.roblox.com/v1/games/multiget-place-details?placeIds=1818&placeIds=6516141723",
+			MockResponse::json(&places),
+		)
+		.route(
+			Method::GET,
+			"https://games.roblox.com/v1/games/1818/servers/Friend?limit=10",
+			MockResponse::json(&json!({ "previousPageCursor": null, "nextPageCursor": null, "data": [] })),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let universes = client.universe_details(vec![13058, 2440500124]).await.unwrap();
+	assert_eq!(universes.len(), 2);
+
+	let places = client.place_details(vec![1818, 6516141723]).await.unwrap();
+	assert_eq!(places[0].universe_id, 13058);
+	assert_eq!(places[0].reason_prohibited.as_deref(), Some("None"));
+
+	let servers = client
+		.servers(1818, ServerType::Friend, Limit::Min, None)
+		.await
+		.unwrap();
+	assert!(servers.data.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn votes_and_favorites() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::games::Votes;
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let favorite_url = "https://games.roblox.com/v1/games/13058/favorites";
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://games.roblox.com/v1/games/votes?universeIds=13058,1",
+			MockResponse::json(&json!({ "data": [{ "id": 13058, "upVotes": 120, "downVotes": 30 }] })),
+		)
+		.route(
+			Method::GET,
+			"https://games.roblox.com/v1/games/13058/favorites/count",
+			MockResponse::json(&json!({ "favoritesCount": 4500 })),
+		)
+		.route(Method::POST, favorite_url, MockResponse::json(&json!({})))
+		.route(
+			Method::GET,
+			"https://games.roblox.com/v1/games/13058/social-links/list",
+			MockResponse::error(
+				StatusCode::FORBIDDEN,
+				0,
+				"Authorization has been denied for this request.",
+			),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let votes = client.votes(vec![13058, 1]).await.unwrap();
+	assert_eq!(votes.len(), 1);
+	assert_eq!(votes[&13058], Votes {
+		id: 13058,
+		up_votes: 120,
+		down_votes: 30,
+	});
+
+	assert_eq!(client.favorites_count(13058).await.unwrap(), 4500);
+	client.set_favorite(13058, true).await.unwrap();
+
+	let favorite = transport
+		.requests()
+		.into_iter()
+		.find(|req| req.url == favorite_url)
+		.unwrap();
+	assert_eq!(favorite.body, Some(json!({ "isFavorited": true })));
+
+	let err = client.social_links(13058).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::PermissionDenied);
+}
+
+#[test]
+fn x_social_links_are_twitter() {
+	let link: SocialLink =
+		serde_json::from_str(r#"{ "id": 104, "type": "X", "url": "https://x.com/roblox", "title": "News" }"#)
+			.unwrap();
+
+	assert_eq!(link.link_type, SocialLinkType::Twitter);
+}
@@ -0,0 +1,1361 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio_test::assert_ok;
+
+#[cfg(feature = "test-util")]
+use robolt::api::groups::GroupSource;
+use robolt::api::groups::{
+	AnnounceChannel,
+	Announcement,
+	AuditLogEntry,
+	ChannelOutcome,
+	ComplianceReport,
+	Group,
+	GroupMember,
+	GroupSearchResult,
+	GroupShout,
+	JoinRequest,
+	ReportFormat,
+	RoleCountDelta,
+};
+use robolt::api::{Limit, Page, SearchResults};
+use robolt::errors::{RoboltError, RoboltErrorKind};
+use robolt::middleware::{Middleware, RequestParts};
+use robolt::Robolt;
+
+#[tokio::test]
+async fn group_roles() {
+	let client = Robolt::new();
+	assert_ok!(client.group_roles(1).await);
+}
+
+#[tokio::test]
+async fn group_members() {
+	let client = Robolt::new();
+	assert_ok!(client.group_members(1, Limit::Min, None).await);
+}
+
+#[tokio::test]
+async fn group_role_counts() {
+	let client = Robolt::new();
+	assert_ok!(client.group_role_counts(1).await);
+}
+
+#[test]
+fn role_count_deltas() {
+	let previous = HashMap::from([(1, 10), (2, 5), (3, 7)]);
+	let current = HashMap::from([(1, 12), (2, 5), (4, 1)]);
+	let deltas = RoleCountDelta::between(&previous, &current);
+
+	assert_eq!(deltas, vec![
+		RoleCountDelta {
+			role_id: 1,
+			previous: Some(10),
+			current: Some(12),
+		},
+		RoleCountDelta {
+			role_id: 3,
+			previous: Some(7),
+			current: None,
+		},
+		RoleCountDelta {
+			role_id: 4,
+			previous: None,
+			current: Some(1),
+		},
+	]);
+
+	assert_eq!(deltas[0].delta(), 2);
+	assert!(deltas[1].is_deleted());
+	assert_eq!(deltas[1].delta(), -7);
+	assert!(deltas[2].is_added());
+}
+
+#[test]
+fn role_count_deltas_unchanged() {
+	let counts = HashMap::from([(1, 10), (2, 5)]);
+	assert!(RoleCountDelta::between(&counts, &counts).is_empty());
+}
+
+#[test]
+fn shouts_from_audit_log() {
+	let audit_log: Page<AuditLogEntry> =
+		serde_json::from_str(include_str!("fixtures/groups/shout_audit_log.json")).unwrap();
+
+	let shouts = audit_log
+		.data
+		.iter()
+		.filter_map(AuditLogEntry::shout)
+		.map(|shout| (shout.body, shout.poster.username, shout.created))
+		.collect::<Vec<_>>();
+
+	assert_eq!(shouts, vec![
+		(
+			String::new(),
+			"Moderator".to_string(),
+			"2023-05-03T08:00:00.000Z".to_string()
+		),
+		(
+			"Event tonight at 8, see you there!".to_string(),
+			"GroupOwner".to_string(),
+			"2023-05-01T12:00:00.000Z".to_string()
+		),
+	]);
+	assert_eq!(audit_log.data[2].shout(), None);
+}
+
+#[test]
+fn only_post_status_entries_are_shouts() {
+	let audit_log: Page<AuditLogEntry> =
+		serde_json::from_str(include_str!("fixtures/groups/shout_audit_log.json")).unwrap();
+
+	let mut entry = audit_log.data[1].clone();
+	assert!(entry.shout().is_some());
+
+	entry.action_type = "PostStatus".to_string();
+	assert!(entry.shout().is_some());
+
+	entry.action_type = "Update Group Description".to_string();
+	assert_eq!(entry.shout(), None);
+}
+
+#[test]
+fn rank_changes_are_not_shouts() {
+	let audit_log: Page<AuditLogEntry> =
+		serde_json::from_str(include_str!("fixtures/groups/audit_log.json")).unwrap();
+	assert!(audit_log.data.iter().all(|entry| entry.shout().is_none()));
+}
+
+fn compliance_report() -> ComplianceReport {
+	let members: Page<GroupMember> = serde_json::from_str(include_str!("fixtures/groups/members.json")).unwrap();
+	let audit_log: Page<AuditLogEntry> =
+		serde_json::from_str(include_str!("fixtures/groups/audit_log.json")).unwrap();
+	let join_requests: Page<JoinRequest> =
+		serde_json::from_str(include_str!("fixtures/groups/join_requests.json")).unwrap();
+
+	ComplianceReport::from_sources(1, members.data, &audit_log.data, join_requests.data)
+}
+
+#[test]
+fn compliance_report_attribution() {
+	let report = compliance_report();
+
+	let attributions = report
+		.members
+		.iter()
+		.map(|member| {
+			(
+				member.user_id,
+				member
+					.ranked_by
+					.as_ref()
+					.map(|ranked_by| (ranked_by.actor_id, ranked_by.changed_at.as_str())),
+			)
+		})
+		.collect::<Vec<_>>();
+
+	assert_eq!(attributions, vec![
+		(100, None),
+		(200, Some((100, "2023-04-28T18:30:00.000Z"))),
+		(300, Some((100, "2023-05-02T10:00:00.000Z"))),
+	]);
+	assert_eq!(report.unattributed().count(), 1);
+	assert_eq!(report.pending_requests[0].user_id, 400);
+}
+
+#[test]
+fn compliance_report_csv() {
+	let mut csv = Vec::new();
+	compliance_report().write_to(&mut csv, ReportFormat::Csv).unwrap();
+
+	assert_eq!(
+		String::from_utf8(csv).unwrap(),
+		"type,user_id,username,role_id,role_name,rank,attributed,ranked_by_id,ranked_by_name,changed_at\n\
+		 member,100,GroupOwner,3,Owner,255,false,,,\n\
+		 member,200,Moderator,2,\"Moderator, Senior\",100,true,100,GroupOwner,2023-04-28T18:30:00.000Z\n\
+		 member,300,Member,1,Member,1,true,100,GroupOwner,2023-05-02T10:00:00.000Z\n\
+		 join_request,400,Applicant,,,,false,,,2023-05-03T12:00:00.000Z\n"
+	);
+}
+
+#[test]
+fn compliance_report_json() {
+	let mut json = Vec::new();
+	compliance_report().write_to(&mut json, ReportFormat::Json).unwrap();
+
+	let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+	assert_eq!(value["members"][0]["rankedBy"], serde_json::Value::Null);
+	assert_eq!(value["members"][1]["rankedBy"]["actorName"], "GroupOwner");
+	assert_eq!(value["pendingRequests"][0]["username"], "Applicant");
+}
+
+#[test]
+fn locked_group() {
+	let locked: Group = serde_json::from_str(include_str!("fixtures/groups/locked_group.json")).unwrap();
+	assert_eq!(locked.is_locked, Some(true));
+	assert_eq!(locked.owner, None);
+
+	let group: Group = serde_json::from_str(include_str!("fixtures/groups/group.json")).unwrap();
+	assert_eq!(group.is_locked, None);
+	assert_eq!(group.shout.unwrap().body, "Event tonight!");
+}
+
+/// Counts requests and aborts them before they reach the network
+struct AbortCounter(Arc<AtomicUsize>);
+
+impl Middleware for AbortCounter {
+	fn before_send(&self, _parts: &mut RequestParts) -> Result<(), RoboltError> {
+		self.0.fetch_add(1, Ordering::SeqCst);
+		Err("aborted".to_string().into())
+	}
+}
+
+#[tokio::test]
+async fn locked_guard_disabled_skips_fetch() {
+	let count = Arc::new(AtomicUsize::new(0));
+	let client = Robolt::new().middleware(AbortCounter(count.clone()));
+
+	assert!(client.ensure_group_unlocked(1).await.is_ok());
+	assert_eq!(count.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn locked_guard_does_not_cache_failures() {
+	let count = Arc::new(AtomicUsize::new(0));
+	let client = Robolt::new()
+		.middleware(AbortCounter(count.clone()))
+		.guard_locked_groups(true);
+
+	for _ in 0..2 {
+		let err = client.ensure_group_unlocked(1).await.unwrap_err();
+		assert_ne!(err.kind(), &RoboltErrorKind::GroupLocked);
+	}
+
+	assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn group_search_results() {
+	let results: SearchResults<GroupSearchResult> =
+		serde_json::from_str(include_str!("fixtures/groups/search.json")).unwrap();
+
+	assert_eq!(results.items.len(), 2);
+	assert_eq!(results.items[1].previous_name.as_deref(), Some("BC Fans"));
+	assert_eq!(results.next_page_cursor.as_deref(), Some("1_1_b9c3f2a4"));
+	assert_eq!(results.keyword(), Some("builders"));
+	assert_eq!(results.prioritizes_exact_match(), None);
+}
+
+#[tokio::test]
+async fn announce_continues_after_failure() {
+	let report = Announcement::new()
+		.shout("Event tonight!")
+		.wall_post("Join us for the event tonight!")
+		.send(|channel, text| async move {
+			match channel {
+				AnnounceChannel::Shout => Err("shout failed".to_string().into()),
+				AnnounceChannel::Wall => Ok(text.replace("tonight", "#######")),
+			}
+		})
+		.await;
+
+	assert!(matches!(report.shout, Some(ChannelOutcome::Failed(_))));
+	match report.wall {
+		Some(ChannelOutcome::Sent {
+			text,
+			truncated,
+			filtered,
+		}) => {
+			assert_eq!(text, "Join us for the event #######!");
+			assert!(!truncated);
+			assert!(filtered);
+		},
+		outcome => panic!("expected the wall post to be sent, got {outcome:?}"),
+	}
+}
+
+#[tokio::test]
+async fn announce_aborts_on_failure() {
+	let calls = AtomicUsize::new(0);
+
+	let report = Announcement::new()
+		.shout("Event tonight!")
+		.wall_post("Join us for the event tonight!")
+		.abort_on_failure(true)
+		.send(|_, _| async {
+			calls.fetch_add(1, Ordering::SeqCst);
+			Err("failed".to_string().into())
+		})
+		.await;
+
+	assert_eq!(calls.load(Ordering::SeqCst), 1);
+	assert!(matches!(report.shout, Some(ChannelOutcome::Failed(_))));
+	assert!(matches!(report.wall, Some(ChannelOutcome::Skipped)));
+	assert!(!report.is_success());
+}
+
+#[tokio::test]
+async fn announce_fits_text_per_channel() {
+	let text = "a".repeat(600);
+
+	let report = Announcement::new()
+		.shout(&text)
+		.wall_post(&text)
+		.send(|_, text| async move { Ok(text) })
+		.await;
+
+	for (outcome, max_length) in [(report.shout, 255), (report.wall, 500)] {
+		match outcome {
+			Some(ChannelOutcome::Sent {
+				text,
+				truncated,
+				..
+			}) => {
+				assert_eq!(text.chars().count(), max_length);
+				assert!(text.ends_with('…'));
+				assert!(truncated);
+			},
+			outcome => panic!("expected the announcement to be sent, got {outcome:?}"),
+		}
+	}
+}
+
+#[tokio::test]
+async fn announce_skips_unset_channels() {
+	let report = Announcement::new()
+		.wall_post("Hello")
+		.send(|channel, text| async move {
+			assert_eq!(channel, AnnounceChannel::Wall);
+			Ok(text)
+		})
+		.await;
+
+	assert!(report.shout.is_none());
+	assert!(report.is_success());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn wall_posts_enriched_batches_lookups() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let fixture = |json: &str| MockResponse::json(&serde_json::from_str::<serde_json::Value>(json).unwrap());
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v2/groups/1/wall/posts?limit=10&sortOrder=Desc",
+			fixture(include_str!("fixtures/groups/wall/posts.json")),
+		)
+		.route(
+			Method::GET,
+			"https://apis.roblox.com/cloud/v2/groups/1/memberships?maxPageSize=100&filter=user%20in%20%5B%27users%2F100%27%2C%20%27users%2F200%27%2C%20%27users%2F300%27%5D",
+			fixture(include_str!("fixtures/groups/wall/memberships.json")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/roles",
+			fixture(include_str!("fixtures/groups/wall/roles.json")),
+		);
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let page = client.wall_posts_enriched(1, Limit::Min, None).await.unwrap();
+
+	assert_eq!(transport.requests().len(), 3);
+	assert_eq!(page.next_page_cursor.as_deref(), Some("2_1_f00d"));
+	assert_eq!(
+		page.data
+			.iter()
+			.map(|post| (post.post.id, post.current_role.as_ref().map(|role| role.name.as_str())))
+			.collect::<Vec<_>>(),
+		vec![
+			(4, Some("Member")),
+			(3, Some("Moderator")),
+			(2, None),
+			(1, Some("Member"))
+		]
+	);
+	assert_eq!(
+		page.data.iter().map(|post| post.role_changed()).collect::<Vec<_>>(),
+		vec![false, true, true, false]
+	);
+}
+
+#[cfg(feature = "test-util")]
+fn group_full_client(v1: bool, v2: bool) -> Robolt {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let fixture = |json: &str| MockResponse::json(&serde_json::from_str::<serde_json::Value>(json).unwrap());
+	let failure = || MockResponse::error(StatusCode::SERVICE_UNAVAILABLE, 0, "Service unavailable");
+
+	let transport = MockTransport::new()
+		.route(Method::GET, "https://groups.roblox.com/v1/groups/3000002", match v1 {
+			true => fixture(include_str!("fixtures/groups/group.json")),
+			false => failure(),
+		})
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v2/groups?groupIds=3000002",
+			match v2 {
+				true => fixture(include_str!("fixtures/groups/group_v2.json")),
+				false => failure(),
+			},
+		);
+
+	Robolt::new().mock_transport(transport)
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn group_full_merges_both_endpoints() {
+	let group = group_full_client(true, true).group_full(3000002).await.unwrap();
+
+	assert!(!group.is_partial());
+	assert_eq!(group.name, "Example Group");
+	assert_eq!(group.created.as_deref(), Some("2017-08-22T14:09:33.717Z"));
+	assert_eq!(group.member_count, Some(120));
+	assert_eq!(group.owner.unwrap().username, "GroupOwner");
+	assert_eq!(group.shout.unwrap().body, "Event tonight!");
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn group_full_without_v2() {
+	let group = group_full_client(true, false).group_full(3000002).await.unwrap();
+	let partial = group.partial.as_ref().unwrap();
+
+	assert_eq!(partial.missing, GroupSource::V2);
+	assert_eq!(partial.error.status(), Some(503));
+	assert_eq!(group.created, None);
+	assert_eq!(group.member_count, Some(120));
+	assert_eq!(group.owner_id, Some(100));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn group_full_without_v1() {
+	let group = group_full_client(false, true).group_full(3000002).await.unwrap();
+
+	assert_eq!(group.partial.as_ref().unwrap().missing, GroupSource::V1);
+	assert_eq!(group.created.as_deref(), Some("2017-08-22T14:09:33.717Z"));
+	assert_eq!(group.member_count, None);
+	assert_eq!(group.owner, None);
+	assert_eq!(group.owner_id, Some(100));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn group_full_fails_when_both_fail() {
+	let err = group_full_client(false, false).group_full(3000002).await.unwrap_err();
+	assert_eq!(err.status(), Some(503));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn role_members_pages() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let fixture = |json: &str| MockResponse::json(&serde_json::from_str::<serde_json::Value>(json).unwrap());
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/3000002/roles/2/users?limit=100&sortOrder=Asc",
+			fixture(include_str!("fixtures/groups/role_members.json")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/3000002/roles/2/users?limit=100&sortOrder=Asc&cursor=2_1_e3b0c442",
+			MockResponse::json(&serde_json::json!({ "previousPageCursor": "2_1_e3b0c442", "nextPageCursor": null, "data": [] })),
+		);
+	let client = Robolt::new().mock_transport(transport);
+
+	let page = client.role_members(3000002, 2, Limit::Max, None).await.unwrap();
+	assert_eq!(page.data.iter().map(|user| user.id).collect::<Vec<u64>>(), [200, 201]);
+	assert_eq!(page.data[1].display_name, "Friendly Helper");
+
+	let cursor = page.next_page_cursor.unwrap();
+	let page = client
+		.role_members(3000002, 2, Limit::Max, Some(&cursor))
+		.await
+		.unwrap();
+	assert!(page.data.is_empty());
+	assert_eq!(page.next_page_cursor, None);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn user_groups_with_roles() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let transport = MockTransport::new().route(
+		Method::GET,
+		"https://groups.roblox.com/v2/users/200/groups/roles",
+		MockResponse::json(
+			&serde_json::from_str::<serde_json::Value>(include_str!("fixtures/groups/user_groups.json")).unwrap(),
+		),
+	);
+	let client = Robolt::new().mock_transport(transport);
+
+	let groups = client.user_groups(200).await.unwrap();
+
+	assert_eq!(groups.len(), 2);
+	assert_eq!(groups[0].group.id, 3000002);
+	assert_eq!(groups[0].role.name, "Moderator");
+	assert_eq!(groups[0].role.rank, 100);
+	assert_eq!(groups[1].group.owner, None);
+	assert_eq!(groups[1].role.id, 41);
+}
+
+#[cfg(feature = "test-util")]
+fn rank_transport() -> robolt::test_util::MockTransport {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/roles",
+			MockResponse::json(
+				&serde_json::from_str::<serde_json::Value>(include_str!("fixtures/groups/wall/roles.json"))
+					.unwrap(),
+			),
+		)
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn rank_many_waits_out_throttles() {
+	use std::time::Duration;
+
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::groups::{RankOptions, RoleTarget};
+	use robolt::test_util::{MockResponse, VirtualClock};
+	use serde_json::json;
+
+	let transport = rank_transport()
+		.route(
+			Method::GET,
+			"https://apis.roblox.com/cloud/v2/groups/1/memberships?maxPageSize=100&filter=user%20in%20%5B%27users%2F100%27%2C%20%27users%2F200%27%2C%20%27users%2F300%27%2C%20%27users%2F400%27%5D",
+			MockResponse::json(&json!({
+				"groupMemberships": [
+					{ "path": "groups/1/memberships/MTAw", "user": "users/100", "role": "groups/1/roles/2" },
+					{ "path": "groups/1/memberships/MjAw", "user": "users/200", "role": "groups/1/roles/1" },
+					{ "path": "groups/1/memberships/MzAw", "user": "users/300", "role": "groups/1/roles/1" },
+				],
+				"nextPageToken": "",
+			})),
+		)
+		.route(
+			Method::PATCH,
+			"https://groups.roblox.com/v1/groups/1/users/200",
+			MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests").header("retry-after", "30"),
+		)
+		.route(Method::PATCH, "https://groups.roblox.com/v1/groups/1/users/200", MockResponse::empty())
+		.route(Method::PATCH, "https://groups.roblox.com/v1/groups/1/users/300", MockResponse::empty());
+	let clock = VirtualClock::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.sleeper(clock.clone())
+		.set_api_key("key".to_string())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let changes = vec![
+		(100, RoleTarget::Rank(100)),
+		(200, RoleTarget::Name("moderator".to_string())),
+		(300, RoleTarget::Id(3)),
+		(400, RoleTarget::Name("Admin".to_string())),
+	];
+	let report = client.rank_many(1, changes, &RankOptions::default()).await;
+
+	assert!(report.error.is_none());
+	assert!(report.progress.is_done());
+	assert_eq!(report.rate_limited, 1);
+	assert_eq!(report.report.skipped, [100]);
+	assert_eq!(report.report.succeeded, [200, 300]);
+	assert_eq!(report.report.failed.len(), 1);
+	assert_eq!(report.report.failed[0].0, 400);
+	assert_eq!(clock.sleeps(), [Duration::from_secs(30), Duration::from_secs(4)]);
+
+	let role_changes = transport
+		.requests()
+		.into_iter()
+		.filter(|request| request.method == Method::PATCH)
+		.map(|request| (request.url, request.body.unwrap()))
+		.collect::<Vec<_>>();
+
+	assert_eq!(role_changes, [
+		(
+			"https://groups.roblox.com/v1/groups/1/users/200".to_string(),
+			json!({ "roleId": 2 })
+		),
+		(
+			"https://groups.roblox.com/v1/groups/1/users/200".to_string(),
+			json!({ "roleId": 2 })
+		),
+		(
+			"https://groups.roblox.com/v1/groups/1/users/300".to_string(),
+			json!({ "roleId": 3 })
+		),
+	]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn rank_many_resumes_from_saved_progress() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::groups::{RankOptions, RankProgress, RoleTarget};
+	use robolt::test_util::{MockResponse, VirtualClock};
+
+	let options = RankOptions {
+		max_rate_limit_pauses: 2,
+		..Default::default()
+	};
+	let changes = vec![(200, RoleTarget::Rank(100)), (300, RoleTarget::Rank(255))];
+
+	let throttled = rank_transport()
+		.route(
+			Method::PATCH,
+			"https://groups.roblox.com/v1/groups/1/users/200",
+			MockResponse::empty(),
+		)
+		.route(
+			Method::PATCH,
+			"https://groups.roblox.com/v1/groups/1/users/300",
+			MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests"),
+		);
+	let client = Robolt::new()
+		.mock_transport(throttled)
+		.sleeper(VirtualClock::new())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let report = client.rank_many(1, changes, &options).await;
+
+	assert_eq!(report.report.succeeded, [200]);
+	assert_eq!(report.rate_limited, 3);
+	assert_eq!(report.error.unwrap().kind(), &RoboltErrorKind::RateLimited);
+
+	let saved = serde_json::to_string(&report.progress).unwrap();
+	let progress = serde_json::from_str::<RankProgress>(&saved).unwrap();
+	assert_eq!(progress, RankProgress::new(1, vec![(300, RoleTarget::Rank(255))]));
+
+	let recovered = rank_transport().route(
+		Method::PATCH,
+		"https://groups.roblox.com/v1/groups/1/users/300",
+		MockResponse::empty(),
+	);
+	let client = Robolt::new()
+		.mock_transport(recovered)
+		.sleeper(VirtualClock::new())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let report = client.resume_rank_many(progress, &options).await;
+
+	assert!(report.error.is_none());
+	assert!(report.progress.is_done());
+	assert_eq!(report.report.succeeded, [300]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn dry_run_rank_many_plans_every_change() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::groups::{RankOptions, RoleTarget};
+	use robolt::dry_run::{PlannedRequest, PlannedRequests};
+	use robolt::test_util::VirtualClock;
+	use serde_json::json;
+
+	let transport = rank_transport();
+	let clock = VirtualClock::new();
+	let planned = PlannedRequests::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.sleeper(clock.clone())
+		.dry_run(planned.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let changes = vec![
+		(100, RoleTarget::Rank(100)),
+		(200, RoleTarget::Name("owner".to_string())),
+		(300, RoleTarget::Name("Admin".to_string())),
+	];
+	let report = client.rank_many(1, changes, &RankOptions::default()).await;
+
+	assert!(report.error.is_none());
+	assert!(report.progress.is_done());
+	assert!(report.report.dry_run);
+	assert_eq!(report.report.succeeded, [100, 200]);
+	assert_eq!(report.report.failed[0].0, 300);
+	assert!(clock.sleeps().is_empty());
+	assert!(transport
+		.requests()
+		.iter()
+		.all(|request| request.method == Method::GET || request.url == "https://auth.roblox.com/v2/logout"));
+	assert_eq!(planned.requests(), [
+		PlannedRequest {
+			method: Method::PATCH,
+			url: "https://groups.roblox.com/v1/groups/1/users/100".to_string(),
+			body: Some(json!({ "roleId": 2 })),
+		},
+		PlannedRequest {
+			method: Method::PATCH,
+			url: "https://groups.roblox.com/v1/groups/1/users/200".to_string(),
+			body: Some(json!({ "roleId": 3 })),
+		},
+	]);
+	assert_eq!(
+		planned.requests()[0].to_string(),
+		r#"PATCH https://groups.roblox.com/v1/groups/1/users/100 {"roleId":2}"#
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone)]
+/// Keeps every recorded action along with the number of requests sent before
+/// it was recorded
+struct RecordingLog {
+	transport: robolt::test_util::MockTransport,
+	actions: Arc<std::sync::Mutex<Vec<(robolt::moderation::ModerationAction, usize)>>>,
+}
+
+#[cfg(feature = "test-util")]
+impl robolt::moderation::ModerationLog for RecordingLog {
+	fn record(&self, action: robolt::moderation::ModerationAction) -> Result<(), RoboltError> {
+		let sent = self.transport.requests().len();
+		self.actions.lock().unwrap().push((action, sent));
+		Ok(())
+	}
+}
+
+#[cfg(feature = "test-util")]
+fn wall_transport() -> robolt::test_util::MockTransport {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let post = |id: u64, user_id: u64, body: &str| {
+		json!({
+			"id": id,
+			"poster": {
+				"user": { "userId": user_id, "username": format!("User{user_id}"), "displayName": "User" },
+				"role": { "id": 1, "name": "Member", "rank": 1 }
+			},
+			"body": body,
+			"created": "2023-05-01T12:00:00.000Z",
+			"updated": "2023-05-01T12:00:00.000Z"
+		})
+	};
+
+	MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 1, "name": "Moderator", "displayName": "Moderator" })),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v2/groups/1/wall/posts?limit=100&sortOrder=Desc",
+			MockResponse::json(&json!({
+				"previousPageCursor": null,
+				"nextPageCursor": "next",
+				"data": [post(4, 300, "spam"), post(3, 200, "hello")]
+			})),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v2/groups/1/wall/posts?limit=100&sortOrder=Desc&cursor=next",
+			MockResponse::json(&json!({
+				"previousPageCursor": "next",
+				"nextPageCursor": null,
+				"data": [post(2, 300, "more spam")]
+			})),
+		)
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn delete_wall_posts_by_user_records_posts_first() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::moderation::{ModerationOutcome, ModerationTarget};
+	use robolt::test_util::MockResponse;
+
+	let transport = wall_transport().route(
+		Method::DELETE,
+		"https://groups.roblox.com/v1/groups/1/wall/users/300/posts",
+		MockResponse::empty(),
+	);
+	let log = RecordingLog {
+		transport: transport.clone(),
+		actions: Arc::default(),
+	};
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.moderation_log(log.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.delete_wall_posts_by_user(1, 300).await.unwrap();
+
+	let requests = transport.requests();
+	let deletion = requests
+		.iter()
+		.position(|request| request.method == Method::DELETE)
+		.unwrap();
+	let actions = log.actions.lock().unwrap();
+	let recorded = actions
+		.iter()
+		.map(|(action, sent)| {
+			let ModerationTarget::DeletedWallPost(post) = &action.target;
+			(post.id, post.body.as_str(), action.outcome.clone(), *sent <= deletion)
+		})
+		.collect::<Vec<_>>();
+
+	assert_eq!(recorded, [
+		(4, "spam", ModerationOutcome::Pending, true),
+		(2, "more spam", ModerationOutcome::Pending, true),
+		(4, "spam", ModerationOutcome::Succeeded, false),
+		(2, "more spam", ModerationOutcome::Succeeded, false),
+	]);
+	assert!(actions
+		.iter()
+		.all(|(action, _)| action.group_id == 1 && action.actor.username == "Moderator"));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn delete_wall_post_records_failures() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::moderation::ModerationOutcome;
+	use robolt::test_util::MockResponse;
+
+	let transport = wall_transport().route(
+		Method::DELETE,
+		"https://groups.roblox.com/v1/groups/1/wall/posts/2",
+		MockResponse::error(
+			StatusCode::FORBIDDEN,
+			3,
+			"You do not have permission to manage this group's wall.",
+		),
+	);
+	let log = RecordingLog {
+		transport: transport.clone(),
+		actions: Arc::default(),
+	};
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.moderation_log(log.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	assert!(client.delete_wall_post(1, 2).await.is_err());
+
+	let err = client.delete_wall_post(1, 99).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::NotFound {
+		resource: robolt::errors::ResourceKind::WallPost,
+		id: 99,
+		reason: robolt::errors::NotFoundReason::Unknown,
+	});
+
+	let outcomes = log
+		.actions
+		.lock()
+		.unwrap()
+		.iter()
+		.map(|(action, _)| action.outcome.clone())
+		.collect::<Vec<_>>();
+
+	assert_eq!(outcomes.len(), 2);
+	assert_eq!(outcomes[0], ModerationOutcome::Pending);
+	assert!(matches!(&outcomes[1], ModerationOutcome::Failed(message) if message.contains("permission")));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn delete_wall_post_without_log_skips_lookup() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::MockResponse;
+
+	let transport = wall_transport().route(
+		Method::DELETE,
+		"https://groups.roblox.com/v1/groups/1/wall/posts/2",
+		MockResponse::empty(),
+	);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.delete_wall_post(1, 2).await.unwrap();
+
+	assert!(transport.requests().iter().all(|request| request.method != Method::GET));
+}
+
+#[test]
+fn jsonl_moderation_log_appends_lines() {
+	use robolt::api::groups::WallPost;
+	use robolt::api::users::PartialUser;
+	use robolt::moderation::{
+		JsonlModerationLog,
+		ModerationAction,
+		ModerationLog,
+		ModerationOutcome,
+		ModerationTarget,
+	};
+
+	let path = std::env::temp_dir().join(format!("robolt-moderation-{}.jsonl", std::process::id()));
+	let _ = std::fs::remove_file(&path);
+
+	let log = JsonlModerationLog::open(&path).unwrap();
+	let action = |outcome| ModerationAction {
+		group_id: 1,
+		actor: PartialUser {
+			id: 2,
+			username: "Moderator".to_string(),
+			display_name: "Moderator".to_string(),
+		},
+		timestamp: 1700000000,
+		target: ModerationTarget::DeletedWallPost(WallPost {
+			id: 3,
+			poster: None,
+			body: "spam".to_string(),
+			created: "2023-05-01T12:00:00.000Z".to_string(),
+			updated: "2023-05-01T12:00:00.000Z".to_string(),
+		}),
+		outcome,
+	};
+
+	log.record(action(ModerationOutcome::Pending)).unwrap();
+	log.record(action(ModerationOutcome::Failed("Forbidden".to_string())))
+		.unwrap();
+
+	let lines = std::fs::read_to_string(&path)
+		.unwrap()
+		.lines()
+		.map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+		.collect::<Vec<_>>();
+	let _ = std::fs::remove_file(&path);
+
+	assert_eq!(lines.len(), 2);
+	assert_eq!(lines[0]["action"], "deleteWallPost");
+	assert_eq!(lines[0]["outcome"], "pending");
+	assert_eq!(lines[0]["actor"]["username"], "Moderator");
+	assert_eq!(lines[0]["post"]["body"], "spam");
+	assert_eq!(lines[1]["outcome"], "failed");
+	assert_eq!(lines[1]["error"], "Forbidden");
+}
+
+#[test]
+fn deleted_posters_and_cleared_shouts() {
+	use robolt::api::groups::WallPost;
+
+	let post: WallPost = serde_json::from_str(
+		r#"{ "id": 5, "poster": null, "body": "Hi", "created": "2023-05-04T12:00:00Z", "updated": "2023-05-04T12:00:00Z" }"#,
+	)
+	.unwrap();
+	assert_eq!(post.poster, None);
+
+	let shout: GroupShout = serde_json::from_str(
+		r#"{ "body": null, "poster": null, "created": "2023-05-04T12:00:00Z", "updated": "2023-05-04T12:00:00Z" }"#,
+	)
+	.unwrap();
+	assert_eq!(shout.body, "");
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn group_shouts() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let fixture = |json: &str| MockResponse::json(&serde_json::from_str::<serde_json::Value>(json).unwrap());
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1",
+			fixture(include_str!("fixtures/groups/group.json")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/2",
+			fixture(include_str!("fixtures/groups/locked_group.json")),
+		);
+	let client = Robolt::new().mock_transport(transport);
+
+	assert_eq!(client.group_shout(1).await.unwrap().unwrap().body, "Event tonight!");
+	assert_eq!(client.group_shout(2).await.unwrap(), None);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn join_request_management() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::MockResponse;
+	use serde_json::json;
+
+	let transport = rank_transport()
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests/users/100",
+			MockResponse::json(&json!({
+				"requester": { "userId": 100, "username": "Applicant", "displayName": "Applicant" },
+				"created": "2023-05-01T12:00:00Z"
+			})),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests/users/200",
+			MockResponse::json(&json!(null)),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests/users/300",
+			MockResponse::error(StatusCode::NOT_FOUND, 0, "NotFound"),
+		)
+		.route(
+			Method::POST,
+			"https://groups.roblox.com/v1/groups/1/join-requests",
+			MockResponse::empty(),
+		)
+		.route(
+			Method::DELETE,
+			"https://groups.roblox.com/v1/groups/1/join-requests/users/100",
+			MockResponse::error(
+				StatusCode::FORBIDDEN,
+				18,
+				"You do not have permission to manage this member.",
+			),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let request = client.join_request(1, 100).await.unwrap().unwrap();
+	assert_eq!(request.requester.username, "Applicant");
+	assert_eq!(client.join_request(1, 200).await.unwrap(), None);
+	assert_eq!(client.join_request(1, 300).await.unwrap(), None);
+
+	client.accept_join_requests(1, (1..=150).collect()).await.unwrap();
+	let sizes = transport
+		.requests()
+		.iter()
+		.filter(|request| request.url == "https://groups.roblox.com/v1/groups/1/join-requests")
+		.map(|request| request.body.as_ref().unwrap()["UserIds"].as_array().unwrap().len())
+		.collect::<Vec<usize>>();
+	assert_eq!(sizes, vec![100, 50]);
+
+	let err = client.decline_join_request(1, 100).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::PermissionDenied);
+	assert_eq!(err.code, 18);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn crawls_report_truncation() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::groups::ComplianceOptions;
+	use robolt::test_util::MockResponse;
+
+	let with_next_page = |json: &str| {
+		let mut page = serde_json::from_str::<serde_json::Value>(json).unwrap();
+		page["nextPageCursor"] = "next".into();
+		MockResponse::json(&page)
+	};
+	let transport = rank_transport()
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/users?limit=100&sortOrder=Asc",
+			with_next_page(include_str!("fixtures/groups/members.json")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/audit-log?limit=100&sortOrder=Desc&actionType=ChangeRank",
+			with_next_page(include_str!("fixtures/groups/audit_log.json")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests?limit=100",
+			with_next_page(include_str!("fixtures/groups/join_requests.json")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/audit-log?limit=100&sortOrder=Desc&actionType=PostStatus",
+			with_next_page(include_str!("fixtures/groups/shout_audit_log.json")),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.max_pages_per_crawl(1)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let report = client.compliance_report(1, ComplianceOptions::default()).await.unwrap();
+	assert!(report.truncated);
+	assert_eq!(report.members.len(), 3);
+	assert_eq!(report.unattributed().count(), 1);
+
+	let history = client.shout_history(1, 100).await.unwrap();
+	assert!(history.truncated);
+	assert!(!history.truncated_by_retention);
+
+	// The page limit is not reached once enough shouts have been found
+	let history = client.shout_history(1, 1).await.unwrap();
+	assert!(!history.truncated);
+	assert_eq!(history.records.len(), 1);
+
+	let requests = transport.requests();
+	assert!(requests.iter().all(|request| !request.url.contains("cursor=next")));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn sweep_join_requests_batches_each_page() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::groups::Decision;
+	use robolt::test_util::MockResponse;
+	use serde_json::json;
+
+	let page = |user_ids: std::ops::RangeInclusive<u64>, next: Option<&str>| {
+		let data = user_ids
+			.map(|user_id| {
+				json!({
+					"requester": { "userId": user_id, "username": format!("User{user_id}"), "displayName": "User" },
+					"created": "2023-05-03T12:00:00.000Z"
+				})
+			})
+			.collect::<Vec<_>>();
+
+		MockResponse::json(&json!({ "previousPageCursor": null, "nextPageCursor": next, "data": data }))
+	};
+	let transport = rank_transport()
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests?limit=100",
+			page(1..=7, Some("page2")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests?limit=100&cursor=page2",
+			page(8..=10, Some("page3")),
+		)
+		.route(
+			Method::GET,
+			"https://groups.roblox.com/v1/groups/1/join-requests?limit=100&cursor=page3",
+			page(11..=16, None),
+		)
+		.route(
+			Method::POST,
+			"https://groups.roblox.com/v1/groups/1/join-requests",
+			MockResponse::empty(),
+		)
+		.route(
+			Method::DELETE,
+			"https://groups.roblox.com/v1/groups/1/join-requests",
+			MockResponse::empty(),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let report = client
+		.sweep_join_requests(1, None, 2, |request| match request.requester.id {
+			user_id if user_id % 5 == 0 => Decision::Skip,
+			user_id if user_id % 2 == 1 => Decision::Accept,
+			_ => Decision::Decline,
+		})
+		.await;
+
+	assert_eq!((report.accepted, report.declined, report.skipped), (6, 7, 3));
+	assert!(report.failed.is_empty());
+	assert_eq!(report.cursor, None);
+	assert!(report.error.is_none());
+
+	let batches = transport
+		.requests()
+		.into_iter()
+		.filter(|request| request.url == "https://groups.roblox.com/v1/groups/1/join-requests")
+		.map(|request| {
+			let user_ids = request.body.unwrap()["UserIds"].clone();
+			(request.method, serde_json::from_value::<Vec<u64>>(user_ids).unwrap())
+		})
+		.collect::<Vec<_>>();
+
+	assert_eq!(batches, vec![
+		(Method::POST, vec![1, 3]),
+		(Method::POST, vec![7]),
+		(Method::DELETE, vec![2, 4]),
+		(Method::DELETE, vec![6]),
+		(Method::POST, vec![9]),
+		(Method::DELETE, vec![8]),
+		(Method::POST, vec![11, 13]),
+		(Method::DELETE, vec![12, 14]),
+		(Method::DELETE, vec![16]),
+	]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn delete_wall_post_reports_unrecorded_deletions() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::moderation::{ModerationAction, ModerationLog, ModerationOutcome};
+	use robolt::test_util::MockResponse;
+
+	/// Accepts pending actions and fails to record their outcome
+	#[derive(Debug)]
+	struct FailingLog;
+
+	impl ModerationLog for FailingLog {
+		fn record(&self, action: ModerationAction) -> Result<(), RoboltError> {
+			match action.outcome {
+				ModerationOutcome::Pending => Ok(()),
+				_ => Err("disk full".to_string().into()),
+			}
+		}
+	}
+
+	let transport = wall_transport().route(
+		Method::DELETE,
+		"https://groups.roblox.com/v1/groups/1/wall/posts/2",
+		MockResponse::empty(),
+	);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.moderation_log(FailingLog)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let err = client.delete_wall_post(1, 2).await.unwrap_err();
+
+	assert_eq!(err.kind(), &RoboltErrorKind::NotRecorded);
+	assert_eq!(err.message, "disk full");
+	assert!(transport
+		.requests()
+		.iter()
+		.any(|request| request.method == Method::DELETE));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn delete_wall_post_stops_paging_once_found() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::MockResponse;
+
+	let transport = wall_transport().route(
+		Method::DELETE,
+		"https://groups.roblox.com/v1/groups/1/wall/posts/3",
+		MockResponse::empty(),
+	);
+	let log = RecordingLog {
+		transport: transport.clone(),
+		actions: Arc::default(),
+	};
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.moderation_log(log)
+		.max_pages_per_crawl(1)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.delete_wall_post(1, 3).await.unwrap();
+
+	let wall_pages = transport
+		.requests()
+		.iter()
+		.filter(|request| request.url.contains("/wall/posts?"))
+		.count();
+	assert_eq!(wall_pages, 1);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn join_request_declines_are_retried() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::retry::RetryPolicy;
+	use robolt::test_util::{MockResponse, VirtualClock};
+
+	let bad_gateway = || MockResponse::error(StatusCode::BAD_GATEWAY, 0, "Bad Gateway");
+	let transport = rank_transport()
+		.route(
+			Method::DELETE,
+			"https://groups.roblox.com/v1/groups/1/join-requests/users/100",
+			bad_gateway(),
+		)
+		.route(
+			Method::DELETE,
+			"https://groups.roblox.com/v1/groups/1/join-requests/users/100",
+			MockResponse::empty(),
+		)
+		.route(
+			Method::DELETE,
+			"https://groups.roblox.com/v1/groups/1/join-requests",
+			bad_gateway(),
+		)
+		.route(
+			Method::DELETE,
+			"https://groups.roblox.com/v1/groups/1/join-requests",
+			MockResponse::empty(),
+		);
+	let clock = VirtualClock::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.retry_policy(RetryPolicy::new().max_retries(1))
+		.sleeper(clock.clone())
+		.clock(clock)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.decline_join_request(1, 100).await.unwrap();
+	client.decline_join_requests(1, vec![100, 200]).await.unwrap();
+
+	let deletes = transport
+		.requests()
+		.iter()
+		.filter(|request| request.method == Method::DELETE)
+		.count();
+	assert_eq!(deletes, 4);
+}
@@ -0,0 +1,118 @@
+use tokio_test::assert_ok;
+
+use robolt::api::{Limit, SortOrder};
+use robolt::Robolt;
+
+#[tokio::test]
+async fn owns_asset() {
+	let client = Robolt::new();
+	assert_ok!(client.owns_asset(1, 1365767).await);
+}
+
+#[tokio::test]
+async fn collectibles() {
+	let client = Robolt::new();
+	assert_ok!(client.collectibles(1, Limit::Min, SortOrder::Desc, None).await);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn ownership_checks_read_bare_booleans() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://inventory.roblox.com/v1/users/1/items/Asset/10/is-owned",
+			MockResponse::json(&json!(true)),
+		)
+		.route(
+			Method::GET,
+			"https://inventory.roblox.com/v1/users/1/items/Badge/20/is-owned",
+			MockResponse::json(&json!(false)),
+		)
+		.route(
+			Method::GET,
+			"https://inventory.roblox.com/v1/users/1/items/GamePass/30/is-owned",
+			MockResponse::json(&json!(true)),
+		);
+	let client = Robolt::new().mock_transport(transport);
+
+	assert!(client.owns_asset(1, 10).await.unwrap());
+	assert!(!client.owns_badge(1, 20).await.unwrap());
+	assert!(client.owns_gamepass(1, 30).await.unwrap());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn private_inventories_are_hidden() {
+	use reqwest::{Method, StatusCode};
+	use robolt::errors::{NotFoundReason, ResourceKind, RoboltErrorKind};
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let private = || {
+		MockResponse::error(
+			StatusCode::FORBIDDEN,
+			4,
+			"You don't have permissions to view the specified user's inventory.",
+		)
+	};
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://inventory.roblox.com/v1/users/2/items/Asset/10/is-owned",
+			private(),
+		)
+		.route(
+			Method::GET,
+			"https://inventory.roblox.com/v1/users/2/assets/collectibles?limit=10&sortOrder=Asc",
+			private(),
+		);
+	let client = Robolt::new().mock_transport(transport);
+	let hidden = RoboltErrorKind::NotFound {
+		resource: ResourceKind::User,
+		id: 2,
+		reason: NotFoundReason::Hidden,
+	};
+
+	assert_eq!(client.owns_asset(2, 10).await.unwrap_err().kind(), &hidden);
+	assert_eq!(
+		client
+			.collectibles(2, Limit::Min, SortOrder::Asc, None)
+			.await
+			.unwrap_err()
+			.kind(),
+		&hidden
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn collectibles_page() {
+	use reqwest::Method;
+	use robolt::api::Robux;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let transport = MockTransport::new().route(
+		Method::GET,
+		"https://inventory.roblox.com/v1/users/1/assets/collectibles?limit=100&sortOrder=Desc&cursor=1_1_abc",
+		MockResponse::json(
+			&serde_json::from_str::<serde_json::Value>(include_str!("fixtures/inventory/collectibles.json"))
+				.unwrap(),
+		),
+	);
+	let client = Robolt::new().mock_transport(transport);
+	let page = client
+		.collectibles(1, Limit::Max, SortOrder::Desc, Some("1_1_abc"))
+		.await
+		.unwrap();
+
+	assert_eq!(page.next_page_cursor.as_deref(), Some("2_1_abc"));
+	assert_eq!(page.data[0].serial_number, None);
+	assert_eq!(page.data[0].recent_average_price, Robux(98000));
+	assert_eq!(page.data[1].serial_number, Some(42));
+	assert_eq!(page.data[1].original_price, Some(Robux(500)));
+	assert_eq!(page.data[1].asset_stock, Some(1000));
+}
@@ -0,0 +1,174 @@
+use robolt::api::messaging::{
+	decode_message,
+	encode_message,
+	validate_topic,
+	COMPRESSED_PREFIX,
+	MESSAGE_MAX_BYTES,
+	TOPIC_MAX_LENGTH,
+};
+use robolt::errors::RoboltErrorKind;
+
+/// A JSON string of `length` bytes, quotes included
+fn json_string(body: &str, length: usize) -> String {
+	body.repeat((length - 2) / body.len())
+}
+
+/// Characters from a fixed-seed generator, which zlib barely compresses
+fn noise(length: usize) -> String {
+	const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+	let mut seed = 0x2545_F491_u64;
+
+	(0..length)
+		.map(|_| {
+			seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			ALPHABET[(seed >> 58) as usize] as char
+		})
+		.collect()
+}
+
+#[test]
+fn message_at_limit_is_sent_as_is() {
+	let payload = json_string("a", MESSAGE_MAX_BYTES);
+	let message = encode_message(&payload, true).unwrap();
+
+	assert_eq!(message.len(), MESSAGE_MAX_BYTES);
+	assert_eq!(message, format!("\"{payload}\""));
+}
+
+#[test]
+fn message_over_limit_is_rejected() {
+	let payload = json_string("a", MESSAGE_MAX_BYTES + 1);
+	let err = encode_message(&payload, false).unwrap_err();
+
+	assert_eq!(*err.kind(), RoboltErrorKind::MessageTooLarge {
+		size: 1025,
+		compressed: None,
+		limit: 1024,
+	});
+	assert!(err.message.contains("1025 bytes"));
+}
+
+#[test]
+fn message_size_counts_utf8_bytes() {
+	// "é" is 2 bytes and "🎉" is 4 bytes in UTF-8
+	let fits = json_string("é", MESSAGE_MAX_BYTES);
+	let over = format!("{}🎉", json_string("é", MESSAGE_MAX_BYTES - 2));
+
+	assert_eq!(fits.chars().count(), 511);
+	assert!(encode_message(&fits, false).is_ok());
+	assert!(matches!(
+		encode_message(&over, false).unwrap_err().kind(),
+		RoboltErrorKind::MessageTooLarge {
+			size: 1026,
+			..
+		}
+	));
+}
+
+#[test]
+fn compressed_message_round_trips() {
+	let payload = vec!["repeated"; 200];
+	let json = serde_json::to_string(&payload).unwrap();
+	let message = encode_message(&payload, true).unwrap();
+
+	assert!(json.len() > MESSAGE_MAX_BYTES);
+	assert!(message.starts_with(COMPRESSED_PREFIX));
+	assert!(message.len() <= MESSAGE_MAX_BYTES);
+	assert_eq!(decode_message(&message).unwrap(), json);
+}
+
+#[test]
+fn compressed_size_includes_base64_expansion() {
+	let payload = noise(900) + &"a".repeat(600);
+	let message = encode_message(&payload, true).unwrap();
+	let encoded = &message[COMPRESSED_PREFIX.len()..];
+
+	// Base64 turns every 3 bytes of the zlib stream into 4 characters, which
+	// must fit within the limit along with the prefix
+	assert_eq!(encoded.len() % 4, 0);
+	assert!(message.len() <= MESSAGE_MAX_BYTES);
+	assert_eq!(decode_message(&message).unwrap(), format!("\"{payload}\""));
+}
+
+#[test]
+fn incompressible_message_is_rejected() {
+	let err = encode_message(&noise(1500), true).unwrap_err();
+
+	match err.kind() {
+		RoboltErrorKind::MessageTooLarge {
+			size,
+			compressed: Some(compressed),
+			limit,
+		} => {
+			assert_eq!(*size, 1502);
+			assert!(compressed > limit);
+			assert_eq!((compressed - COMPRESSED_PREFIX.len()) % 4, 0);
+		},
+		kind => panic!("unexpected error kind {kind:?}"),
+	}
+
+	assert!(err.message.contains("bytes compressed"));
+}
+
+#[test]
+fn plain_message_decodes_to_itself() {
+	assert_eq!(decode_message("{\"a\":1}").unwrap(), "{\"a\":1}");
+	assert!(decode_message("zlib:not base64").is_err());
+}
+
+#[test]
+fn topic_length() {
+	assert!(validate_topic(&"a".repeat(TOPIC_MAX_LENGTH)).is_ok());
+	assert!(validate_topic(&"é".repeat(TOPIC_MAX_LENGTH)).is_ok());
+	assert!(validate_topic(&"a".repeat(TOPIC_MAX_LENGTH + 1)).is_err());
+	assert!(validate_topic("").is_err());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn publish_json() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::Robolt;
+	use serde_json::json;
+
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://apis.roblox.com/cloud/v2/universes/1:publishMessage",
+		MockResponse::empty(),
+	);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_api_key("key".to_string());
+
+	client
+		.publish_json(1, "announcements", &json!({ "text": "hello" }), true)
+		.await
+		.unwrap();
+
+	let requests = transport.requests();
+
+	assert_eq!(requests.len(), 1);
+	assert_eq!(
+		requests[0].body,
+		Some(json!({ "topic": "announcements", "message": "{\"text\":\"hello\"}" }))
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn publish_message_validates_before_sending() {
+	use robolt::test_util::MockTransport;
+	use robolt::Robolt;
+
+	let transport = MockTransport::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_api_key("key".to_string());
+
+	let oversized = "a".repeat(MESSAGE_MAX_BYTES + 1);
+
+	assert!(client.publish_message(1, "topic", &oversized).await.is_err());
+	assert!(client.publish_message(1, "", "message").await.is_err());
+	assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,206 @@
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use robolt::errors::RoboltError;
+use robolt::middleware::{HeaderInjector, Middleware, RequestParts};
+use robolt::Robolt;
+
+struct Recorder {
+	name: &'static str,
+	log: Arc<Mutex<Vec<String>>>,
+	abort: bool,
+}
+
+impl Middleware for Recorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		let header = parts
+			.headers
+			.get("x-test")
+			.map(|value| value.to_str().unwrap().to_string())
+			.unwrap_or_default();
+
+		self.log.lock().unwrap().push(format!("{}:{header}", self.name));
+
+		if self.abort {
+			return Err(format!("aborted by {}", self.name).into());
+		}
+
+		Ok(())
+	}
+}
+
+#[tokio::test]
+async fn middleware_chain_order_and_abort() {
+	let log = Arc::new(Mutex::new(Vec::new()));
+	let recorder = |name, abort| Recorder {
+		name,
+		log: log.clone(),
+		abort,
+	};
+
+	let mut headers = HeaderMap::new();
+	headers.insert("x-test", HeaderValue::from_static("injected"));
+
+	let client = Robolt::new()
+		.middleware(recorder("first", false))
+		.middleware(HeaderInjector::new(headers))
+		.middleware(recorder("second", true))
+		.middleware(recorder("third", false));
+
+	let err = client.user(1).await.unwrap_err();
+
+	assert_eq!(err.message, "aborted by second");
+	assert_eq!(*log.lock().unwrap(), vec!["first:", "second:injected"]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn middleware_chain_around_retries() {
+	use reqwest::{Method, StatusCode};
+	use robolt::middleware::ResponseMeta;
+	use robolt::retry::RetryPolicy;
+	use robolt::test_util::{MockResponse, MockTransport, VirtualClock};
+
+	/// Records both hooks of every request, along with their retry flag
+	struct ChainRecorder {
+		name: &'static str,
+		log: Arc<Mutex<Vec<String>>>,
+	}
+
+	impl Middleware for ChainRecorder {
+		fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+			let entry = format!("{}:before:{}", self.name, parts.is_retry);
+			self.log.lock().unwrap().push(entry);
+			Ok(())
+		}
+
+		fn after_receive(&self, meta: &ResponseMeta) {
+			let entry = format!("{}:after:{}:{}", self.name, meta.is_retry, meta.status.as_u16());
+			self.log.lock().unwrap().push(entry);
+		}
+	}
+
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::error(StatusCode::BAD_GATEWAY, 0, "Bad Gateway"),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::json(
+				&serde_json::from_str::<serde_json::Value>(include_str!("fixtures/users/active.json")).unwrap(),
+			),
+		);
+	let log = Arc::new(Mutex::new(Vec::new()));
+	let recorder = |name| ChainRecorder {
+		name,
+		log: log.clone(),
+	};
+	let clock = VirtualClock::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.retry_policy(RetryPolicy::new().max_retries(1))
+		.sleeper(clock.clone())
+		.clock(clock)
+		.middleware(recorder("first"))
+		.middleware(recorder("second"))
+		.middleware(recorder("third"));
+
+	client.user(1).await.unwrap();
+
+	assert_eq!(transport.requests().len(), 2);
+	assert_eq!(*log.lock().unwrap(), vec![
+		"first:before:false",
+		"second:before:false",
+		"third:before:false",
+		"third:after:false:502",
+		"second:after:false:502",
+		"first:after:false:502",
+		"first:before:true",
+		"second:before:true",
+		"third:before:true",
+		"third:after:true:200",
+		"second:after:true:200",
+		"first:after:true:200",
+	]);
+}
+
+type RecordedRequest = Option<(String, Option<serde_json::Value>)>;
+
+struct UrlRecorder(Arc<Mutex<RecordedRequest>>);
+
+impl Middleware for UrlRecorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		*self.0.lock().unwrap() = Some((parts.url.clone(), parts.body.clone()));
+		Err("recorded".to_string().into())
+	}
+}
+
+#[tokio::test]
+async fn request_urls_and_bodies() {
+	let recorded = Arc::new(Mutex::new(None));
+	let client = Robolt::new().middleware(UrlRecorder(recorded.clone()));
+
+	let _ = client.user(1).await;
+	assert_eq!(
+		recorded.lock().unwrap().take(),
+		Some(("https://users.roblox.com/v1/users/1".to_string(), None))
+	);
+
+	let _ = client.users_from_ids(vec![1, 2], true).await;
+	assert_eq!(
+		recorded.lock().unwrap().take(),
+		Some((
+			"https://users.roblox.com/v1/users".to_string(),
+			Some(serde_json::json!({ "excludeBannedUsers": true, "userIds": [1, 2] }))
+		))
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn request_stats_per_api() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::routes::RobloxApi;
+	use robolt::middleware::RequestStats;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::json(
+				&serde_json::from_str::<serde_json::Value>(include_str!("fixtures/users/active.json")).unwrap(),
+			),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/2",
+			MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+		)
+		.route(
+			Method::GET,
+			"https://friends.roblox.com/v1/users/1/friends/count",
+			MockResponse::json(&json!({ "count": 3 })),
+		);
+	let stats = RequestStats::new();
+	let client = Robolt::new().mock_transport(transport).middleware(stats.clone());
+
+	client.user(1).await.unwrap();
+	client.user(2).await.unwrap_err();
+	client.friend_count(1).await.unwrap();
+
+	let users = stats.api(RobloxApi::Users);
+	assert_eq!(users.requests, 2);
+	assert_eq!(users.failures, 1);
+	assert_eq!(users.retries, 0);
+	assert_eq!(stats.host("friends.roblox.com").requests, 1);
+	assert_eq!(stats.api(RobloxApi::Groups).requests, 0);
+	assert_eq!(stats.snapshot().len(), 2);
+
+	stats.reset();
+	assert!(stats.snapshot().is_empty());
+}
@@ -0,0 +1,153 @@
+use robolt::api::avatar::Avatar;
+use robolt::api::badges::Badge;
+use robolt::api::catalog::{Bundle, CatalogSearchResponse};
+use robolt::api::friends::{FriendRequest, FriendRequestOrigin};
+use robolt::api::groups::GroupRole;
+use robolt::api::presence::UserPresence;
+use robolt::api::users::{PartialUser, User};
+use serde::de::DeserializeOwned;
+
+fn parse<T: DeserializeOwned>(json: &str) -> T {
+	serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn user_minimal() {
+	let user: User =
+		parse(r#"{ "id": 1, "name": "Roblox", "displayName": "Roblox", "created": "2006-02-27T21:06:40.3Z" }"#);
+
+	assert_eq!(user.description, None);
+	assert_eq!(user.external_app_display_name, None);
+	assert!(!user.is_banned);
+	assert!(!user.has_verified_badge);
+}
+
+#[test]
+fn partial_user_minimal() {
+	let user: PartialUser = parse(r#"{ "userId": 1, "username": "Roblox" }"#);
+	assert_eq!(user.display_name, "");
+}
+
+#[test]
+fn badge_minimal() {
+	let badge: Badge = parse(
+		r#"{
+			"id": 1,
+			"name": "Badge",
+			"displayName": "Badge",
+			"iconImageId": 2,
+			"displayIconImageId": 2,
+			"created": "2020-01-01T00:00:00Z",
+			"updated": "2020-01-01T00:00:00Z"
+		}"#,
+	);
+
+	assert!(!badge.enabled);
+	assert_eq!(badge.statistics.awarded_count, 0);
+	assert_eq!(badge.awarding_universe, None);
+}
+
+#[test]
+fn catalog_item_minimal() {
+	let item: CatalogSearchResponse = parse(
+		r#"{
+			"id": 1,
+			"itemType": "Asset",
+			"name": "Hat",
+			"productId": 2,
+			"creatorType": "User",
+			"creatorTargetId": 1,
+			"creatorName": "Roblox",
+			"saleLocationType": "NotApplicable"
+		}"#,
+	);
+
+	assert!(item.item_status.is_empty());
+	assert_eq!(item.favorite_count, 0);
+}
+
+#[test]
+fn bundle_minimal() {
+	let bundle: Bundle = parse(
+		r#"{
+			"id": 1,
+			"name": "Bundle",
+			"bundleType": "BodyParts",
+			"creator": { "id": 1, "name": "Roblox", "type": "User" },
+			"product": { "id": 2, "type": "Bundle" }
+		}"#,
+	);
+
+	assert!(bundle.items.is_empty());
+	assert!(!bundle.product.is_for_sale);
+}
+
+#[test]
+fn avatar_minimal() {
+	let avatar: Avatar = parse(
+		r#"{
+			"playerAvatarType": "R15",
+			"scales": { "head": 1, "depth": 1, "height": 1, "width": 1, "proportion": 0, "bodyType": 0 },
+			"bodyColors": {
+				"headColorId": 1,
+				"torsoColorId": 1,
+				"rightArmColorId": 1,
+				"leftArmColorId": 1,
+				"rightLegColorId": 1,
+				"leftLegColorId": 1
+			}
+		}"#,
+	);
+
+	assert!(avatar.assets.is_empty());
+	assert!(avatar.emotes.is_empty());
+}
+
+#[test]
+fn friend_request_minimal() {
+	let request: FriendRequest = parse(
+		r#"{
+			"id": 1,
+			"name": "Roblox",
+			"displayName": "Roblox",
+			"created": "2006-02-27T21:06:40.3Z",
+			"friendRequest": { "sentAt": "2023-01-01T00:00:00Z", "senderId": 1 }
+		}"#,
+	);
+
+	assert!(request.mutual_friends.is_empty());
+	assert_eq!(request.source_universe_id, None);
+	assert_eq!(request.origin, FriendRequestOrigin::Unknown);
+}
+
+#[test]
+fn presence_minimal() {
+	let presence: UserPresence = parse(r#"{ "userPresenceType": 0, "lastOnline": "2023-01-01T00:00:00Z" }"#);
+
+	assert_eq!(presence.last_location, "");
+	assert_eq!(presence.place_id, None);
+}
+
+#[test]
+fn group_role_minimal() {
+	let role: GroupRole = parse(r#"{ "id": 1, "name": "Guest", "rank": 0 }"#);
+	assert_eq!(role.member_count, 0);
+}
+
+#[test]
+fn unknown_fields_are_ignored() {
+	let user: User = parse(
+		r#"{
+			"id": 1,
+			"name": "Roblox",
+			"displayName": "Roblox",
+			"created": "2006-02-27T21:06:40.3Z",
+			"someFieldAddedLater": { "nested": [1, 2, 3] }
+		}"#,
+	);
+
+	assert_eq!(user.id, 1);
+
+	let role: GroupRole = parse(r#"{ "id": 1, "name": "Guest", "rank": 0, "color": "red" }"#);
+	assert_eq!(role.rank, 0);
+}
@@ -0,0 +1,304 @@
+use std::sync::{Arc, Mutex};
+
+use robolt::api::cloud::CloudPage;
+use robolt::api::Page;
+use robolt::errors::RoboltError;
+use robolt::paging::{CursorPaging, ExhaustionReason, NumberPaging, NumberedPage, PageIterator, TokenPaging};
+
+/// Seven items served three per page, in each paging style
+const ITEMS: [u64; 7] = [1, 2, 3, 4, 5, 6, 7];
+const PAGE_SIZE: usize = 3;
+
+/// Records the cursors a mock endpoint was called with, failing once at
+/// `fail_at` if set
+#[derive(Clone, Default)]
+struct Calls {
+	cursors: Arc<Mutex<Vec<Option<String>>>>,
+	fail_at: Option<String>,
+}
+
+impl Calls {
+	fn record(&mut self, cursor: &Option<String>) -> Result<(), RoboltError> {
+		self.cursors.lock().unwrap().push(cursor.clone());
+
+		if self.fail_at.is_some() && *cursor == self.fail_at {
+			self.fail_at = None;
+			return Err("Internal server error".to_string().into());
+		}
+
+		Ok(())
+	}
+
+	fn count(&self) -> usize {
+		self.cursors.lock().unwrap().len()
+	}
+}
+
+fn chunk(index: usize) -> (Vec<u64>, bool) {
+	let start = index * PAGE_SIZE;
+	let end = (start + PAGE_SIZE).min(ITEMS.len());
+
+	(ITEMS[start..end].to_vec(), end < ITEMS.len())
+}
+
+fn cursor_iterator(mut calls: Calls) -> PageIterator<'static, u64> {
+	let cursors = ["", "cursor_a", "cursor_b"];
+
+	PageIterator::new(CursorPaging::new(), move |cursor| {
+		let res = calls.record(&cursor).map(|_| {
+			let index = cursors
+				.iter()
+				.position(|c| *c == cursor.as_deref().unwrap_or(""))
+				.unwrap();
+			let (data, more) = chunk(index);
+
+			Page {
+				previous_page_cursor: None,
+				next_page_cursor: more.then(|| cursors[index + 1].to_string()),
+				data,
+			}
+		});
+
+		async move { res }
+	})
+}
+
+fn token_iterator(mut calls: Calls) -> PageIterator<'static, u64> {
+	let tokens = ["", "token_a", "token_b"];
+
+	PageIterator::new(TokenPaging::new(), move |cursor| {
+		let res = calls.record(&cursor).map(|_| {
+			let index = tokens
+				.iter()
+				.position(|t| *t == cursor.as_deref().unwrap_or(""))
+				.unwrap();
+			let (items, more) = chunk(index);
+
+			CloudPage {
+				items,
+				next_page_token: more.then(|| tokens[index + 1].to_string()),
+			}
+		});
+
+		async move { res }
+	})
+}
+
+fn number_iterator(mut calls: Calls) -> PageIterator<'static, u64> {
+	let paging = NumberPaging::new(PAGE_SIZE as u64);
+
+	PageIterator::new(paging.clone(), move |cursor| {
+		let res = calls.record(&cursor).and_then(|_| {
+			let page = paging.page_number(cursor.as_deref())?;
+
+			Ok(NumberedPage {
+				items: chunk(page as usize - 1).0,
+				total: Some(ITEMS.len() as u64),
+				final_page: None,
+			})
+		});
+
+		async move { res }
+	})
+}
+
+type Factory = fn(Calls) -> PageIterator<'static, u64>;
+
+const STYLES: [(&str, Factory); 3] = [
+	("cursor", cursor_iterator),
+	("token", token_iterator),
+	("number", number_iterator),
+];
+
+#[tokio::test]
+async fn collect_all() {
+	for (style, iterator) in STYLES {
+		let calls = Calls::default();
+		let collected = iterator(calls.clone()).collect_all().await.unwrap();
+
+		assert_eq!(collected.items, ITEMS, "{style}");
+		assert!(!collected.truncated, "{style}");
+		assert_eq!(calls.count(), 3, "{style}");
+	}
+}
+
+#[tokio::test]
+async fn take_stops_fetching_early() {
+	for (style, iterator) in STYLES {
+		let calls = Calls::default();
+		let items = iterator(calls.clone()).take(4).await.unwrap();
+
+		assert_eq!(items, [1, 2, 3, 4], "{style}");
+		assert_eq!(calls.count(), 2, "{style}");
+	}
+}
+
+#[tokio::test]
+async fn next_page_until_done() {
+	for (style, iterator) in STYLES {
+		let mut pages = iterator(Calls::default());
+		let mut sizes = Vec::new();
+
+		while let Some(page) = pages.next_page().await {
+			sizes.push(page.unwrap().len());
+		}
+
+		assert_eq!(sizes, [3, 3, 1], "{style}");
+		assert_eq!(pages.pages_fetched(), 3, "{style}");
+		assert!(pages.is_done(), "{style}");
+		assert_eq!(pages.exhaustion_reason(), Some(ExhaustionReason::Completed), "{style}");
+		assert_eq!(pages.cursor(), None, "{style}");
+	}
+}
+
+#[tokio::test]
+async fn failed_pages_can_be_resumed() {
+	for (style, iterator) in STYLES {
+		let mut pages = iterator(Calls::default());
+		pages.next_page().await.unwrap().unwrap();
+		let cursor = pages.cursor().unwrap().to_string();
+
+		// A second iterator fails on the same page, keeping its cursor
+		let calls = Calls {
+			fail_at: Some(cursor.clone()),
+			..Calls::default()
+		};
+		let mut resumed = iterator(calls.clone()).resume_from(&cursor);
+
+		assert!(resumed.next_page().await.unwrap().is_err(), "{style}");
+		assert_eq!(resumed.cursor(), Some(cursor.as_str()), "{style}");
+		assert_eq!(resumed.collect_all().await.unwrap().items, [4, 5, 6, 7], "{style}");
+		assert_eq!(calls.count(), 3, "{style}");
+	}
+}
+
+#[tokio::test]
+async fn repeated_cursors_end_the_iteration() {
+	let calls = Calls::default();
+	let mut inner = calls.clone();
+	let mut pages = PageIterator::new(CursorPaging::new(), move |cursor| {
+		let res = inner.record(&cursor).map(|_| Page {
+			previous_page_cursor: None,
+			next_page_cursor: Some("loop".to_string()),
+			data: vec![1],
+		});
+
+		async move { res }
+	});
+
+	let mut items = Vec::new();
+
+	while let Some(page) = pages.next_page().await {
+		items.extend(page.unwrap());
+	}
+
+	assert_eq!(items, [1, 1]);
+	assert_eq!(pages.exhaustion_reason(), Some(ExhaustionReason::CursorLoop));
+	assert_eq!(calls.count(), 2);
+}
+
+/// An endpoint that hands out a new cursor on every page
+fn endless_iterator(calls: Calls) -> PageIterator<'static, u64> {
+	let mut inner = calls;
+
+	PageIterator::new(CursorPaging::new(), move |cursor| {
+		let page = cursor.as_deref().map_or(0, |cursor| cursor.parse::<u64>().unwrap());
+		let res = inner.record(&cursor).map(|_| Page {
+			previous_page_cursor: None,
+			next_page_cursor: Some((page + 1).to_string()),
+			data: vec![page],
+		});
+
+		async move { res }
+	})
+}
+
+#[tokio::test]
+async fn page_limit_truncates_endless_crawls() {
+	let calls = Calls::default();
+	let collected = endless_iterator(calls.clone())
+		.max_pages(5)
+		.collect_all()
+		.await
+		.unwrap();
+
+	assert_eq!(collected.items, [0, 1, 2, 3, 4]);
+	assert!(collected.truncated);
+	assert_eq!(calls.count(), 5);
+}
+
+#[tokio::test]
+async fn truncated_crawls_can_be_resumed() {
+	let mut pages = endless_iterator(Calls::default()).max_pages(2);
+
+	while let Some(page) = pages.next_page().await {
+		page.unwrap();
+	}
+
+	assert_eq!(pages.exhaustion_reason(), Some(ExhaustionReason::CrawlTruncated));
+	assert_eq!(pages.pages_fetched(), 2);
+	assert_eq!(pages.cursor(), Some("2"));
+
+	let resumed = endless_iterator(Calls::default()).max_pages(2).resume_from("2");
+	assert_eq!(resumed.take(3).await.unwrap(), [2, 3]);
+}
+
+#[test]
+fn number_paging_rejects_invalid_cursors() {
+	let paging = NumberPaging::<u64>::new(10).first_page(3);
+
+	assert_eq!(paging.page_number(None).unwrap(), 3);
+	assert_eq!(paging.page_number(Some("5")).unwrap(), 5);
+	assert!(paging.page_number(Some("0")).is_err());
+	assert!(paging.page_number(Some("next")).is_err());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn outfits_pages() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::Robolt;
+	use serde_json::json;
+
+	let outfit = |id: u64| json!({ "id": id, "name": format!("Outfit {id}"), "isEditable": true });
+	let url = |page: u8| {
+		format!("https://avatar.roblox.com/v1/users/1/outfits?page={page}&itemsPerPage=10&isEditable=true")
+	};
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			&url(1),
+			MockResponse::json(&json!({
+				"filteredCount": 0,
+				"data": (1..=10).map(outfit).collect::<Vec<_>>(),
+				"total": 12,
+			})),
+		)
+		.route(
+			Method::GET,
+			&url(2),
+			MockResponse::json(&json!({
+				"filteredCount": 0,
+				"data": [outfit(11), outfit(12)],
+				"total": 12,
+			})),
+		);
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let outfits = client.outfits(1).pages().collect_all().await.unwrap();
+
+	assert_eq!(
+		outfits.items.iter().map(|outfit| outfit.id).collect::<Vec<_>>(),
+		(1..=12).collect::<Vec<_>>()
+	);
+	assert!(!outfits.truncated);
+	assert_eq!(transport.requests().len(), 2);
+
+	let client = Robolt::new().mock_transport(transport.clone()).max_pages_per_crawl(1);
+	let outfits = client.outfits(1).pages().collect_all().await.unwrap();
+
+	assert_eq!(outfits.items.len(), 10);
+	assert!(outfits.truncated);
+	assert_eq!(transport.requests().len(), 3);
+}
@@ -1,5 +1,9 @@
+use std::time::Duration;
+
+use serde_json::json;
 use tokio_test::assert_ok;
 
+use robolt::api::presence::{PresenceTracker, UserPresence, UserPresenceType};
 use robolt::Robolt;
 
 #[tokio::test]
@@ -15,3 +19,162 @@ async fn partial_presences() {
 	let user_ids = vec![1, 2, 3];
 	assert_ok!(client.partial_presences(user_ids).await);
 }
+
+fn presence(user_id: u64, presence_type: u8, universe_id: Option<u64>) -> UserPresence {
+	serde_json::from_value(json!({
+		"userPresenceType": presence_type,
+		"lastLocation": universe_id.map_or("Website", |_| "Jailbreak"),
+		"placeId": universe_id.map(|id| id * 10),
+		"rootPlaceId": universe_id.map(|id| id * 10),
+		"gameId": null,
+		"universeId": universe_id,
+		"userId": user_id,
+		"lastOnline": "2023-05-01T12:00:00.000Z"
+	}))
+	.unwrap()
+}
+
+#[test]
+fn presence_tracker_records_changes() {
+	let mut tracker = PresenceTracker::new(vec![1], Duration::from_secs(60));
+	let script = [
+		(0, presence(1, 1, None)),
+		(60_000, presence(1, 1, None)),
+		(120_000, presence(1, 2, Some(606849621))),
+		(180_000, presence(1, 2, Some(606849621))),
+		(240_000, presence(1, 0, None)),
+		(300_000, presence(2, 2, Some(1))),
+	];
+
+	for (observed_at, presence) in script {
+		tracker.observe(&[presence], observed_at);
+	}
+
+	let history = tracker.history(1).collect::<Vec<_>>();
+	assert_eq!(history.len(), 3);
+	assert_eq!(history[0].from, None);
+	assert_eq!(history[1].to.presence_type, UserPresenceType::InGame);
+	assert_eq!(history[1].observed_at, 120_000);
+
+	let last_seen = tracker.last_seen(1).unwrap();
+	assert_eq!(last_seen.observed_at, 240_000);
+	assert_eq!(last_seen.from.as_ref().unwrap().last_location, "Jailbreak");
+	assert_eq!(tracker.current(1).unwrap().presence_type, UserPresenceType::Offline);
+	assert_eq!(tracker.history(2).count(), 0);
+
+	let events = tracker.drain_events();
+	assert_eq!(events.len(), 3);
+	assert!(tracker.drain_events().is_empty());
+	assert_eq!(serde_json::to_value(&events[1]).unwrap()["to"]["presenceType"], 2);
+}
+
+#[test]
+fn presence_tracker_coalesces_flapping() {
+	let mut tracker = PresenceTracker::new(vec![1], Duration::from_secs(10)).grace(Duration::from_secs(30));
+	let script = [
+		(0, presence(1, 1, None)),
+		(10_000, presence(1, 0, None)),
+		(20_000, presence(1, 1, None)),
+		(30_000, presence(1, 0, None)),
+		(40_000, presence(1, 2, Some(1))),
+		(50_000, presence(1, 2, Some(1))),
+		(70_000, presence(1, 2, Some(1))),
+	];
+
+	for (observed_at, presence) in script {
+		tracker.observe(&[presence], observed_at);
+	}
+
+	let transitions = tracker
+		.history(1)
+		.map(|transition| (transition.to.presence_type.clone(), transition.observed_at))
+		.collect::<Vec<_>>();
+
+	assert_eq!(transitions, vec![
+		(UserPresenceType::Online, 0),
+		(UserPresenceType::InGame, 40_000)
+	]);
+}
+
+#[test]
+fn presence_tracker_is_bounded() {
+	let mut tracker = PresenceTracker::new(vec![1, 2], Duration::from_secs(60)).depth(3);
+
+	for i in 0..100 {
+		let presence_type = (i % 2) as u8;
+		tracker.observe(
+			&[presence(1, presence_type, None), presence(2, presence_type, None)],
+			i * 1000,
+		);
+	}
+
+	assert_eq!(tracker.history(1).count(), 3);
+	assert_eq!(tracker.history(1).last().unwrap().observed_at, 99_000);
+
+	let events = tracker.drain_events();
+	assert_eq!(events.len(), 6);
+	assert_eq!(events.last().unwrap().observed_at, 99_000);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn presence_tracker_polls_on_client_clock() {
+	use std::time::UNIX_EPOCH;
+
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport, VirtualClock};
+
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://presence.roblox.com/v1/presence/users",
+		MockResponse::json(&json!({
+			"userPresences": [{
+				"userPresenceType": 1,
+				"lastLocation": "Website",
+				"userId": 1,
+				"lastOnline": "2023-05-01T12:00:00.000Z"
+			}]
+		})),
+	);
+	let clock = VirtualClock::starting_at(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+	let client = Robolt::new().mock_transport(transport).clock(clock.clone());
+	let mut tracker = PresenceTracker::new(vec![1], Duration::from_secs(60));
+
+	clock.advance(Duration::from_secs(60));
+	tracker.poll(&client).await.unwrap();
+
+	assert_eq!(tracker.history(1).next().unwrap().observed_at, 1_700_000_060_000);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn presences_with_hidden_locations() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://presence.roblox.com/v1/presence/users",
+		MockResponse::json(
+			&serde_json::from_str::<serde_json::Value>(include_str!("fixtures/presence/users.json")).unwrap(),
+		),
+	);
+	let client = Robolt::new().mock_transport(transport);
+	let presences = client.presences(vec![1, 2, 3]).await.unwrap();
+
+	assert_eq!(presences[0].user_presence_type, UserPresenceType::InGame);
+	assert_eq!(presences[0].place_id, Some(606849621));
+	assert_eq!(
+		presences[0].game_id.as_deref(),
+		Some("5d4a1f3c-7f2b-4c1e-9a7d-2b1f0c3e8d64")
+	);
+	assert!(!presences[0].is_location_hidden());
+
+	assert_eq!(presences[1].user_presence_type, UserPresenceType::InGame);
+	assert!(presences[1].is_location_hidden());
+
+	assert_eq!(presences[2].user_presence_type, UserPresenceType::Offline);
+	assert_eq!(presences[2].last_location, "");
+	assert_eq!(presences[2].last_online, None);
+	assert!(!presences[2].is_location_hidden());
+}
@@ -0,0 +1,154 @@
+#[cfg(feature = "test-util")]
+fn message(id: u64) -> serde_json::Value {
+	serde_json::json!({
+		"id": id,
+		"sender": { "hasVerifiedBadge": false, "id": 2, "name": "sender", "displayName": "Sender" },
+		"recipient": { "hasVerifiedBadge": false, "id": 7, "name": "builder", "displayName": "Builder" },
+		"subject": "Hello",
+		"body": "Hi there",
+		"created": "2023-05-01T12:00:00.123Z",
+		"updated": "2023-05-01T12:00:00.123Z",
+		"isRead": false,
+		"isSystemMessage": false,
+		"isReportAbuseDisplayed": true
+	})
+}
+
+#[cfg(feature = "test-util")]
+fn messages_transport() -> robolt::test_util::MockTransport {
+	use reqwest::{Method, StatusCode};
+	use robolt::test_util::{MockResponse, MockTransport};
+
+	MockTransport::new().route(
+		Method::POST,
+		"https://auth.roblox.com/v2/logout",
+		MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed").header("x-csrf-token", "token"),
+	)
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn messages_are_paged_by_number() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::private_messages::MessageTab;
+	use robolt::test_util::MockResponse;
+	use robolt::Robolt;
+	use serde_json::json;
+
+	let transport = messages_transport()
+		.route(
+			Method::GET,
+			"https://privatemessages.roblox.com/v1/messages?messageTab=Inbox&pageNumber=0&pageSize=2",
+			MockResponse::json(&json!({
+				"collection": [message(1), message(2)],
+				"totalCollectionSize": 3,
+				"totalPages": 2,
+				"pageNumber": 0
+			})),
+		)
+		.route(
+			Method::GET,
+			"https://privatemessages.roblox.com/v1/messages?messageTab=Inbox&pageNumber=1&pageSize=2",
+			MockResponse::json(&json!({
+				"collection": [message(3)],
+				"totalCollectionSize": 3,
+				"totalPages": 2,
+				"pageNumber": 1
+			})),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let page = client.messages(MessageTab::Inbox, 0, 2).await.unwrap();
+	assert_eq!(page.total_pages, 2);
+	assert_eq!(page.collection[0].sender.username, "sender");
+	assert_eq!(page.collection[0].recipient.id, 7);
+	assert!(!page.collection[0].is_read);
+
+	let messages = client.messages_pages(MessageTab::Inbox, 2).collect_all().await.unwrap();
+	let ids = messages.items.iter().map(|message| message.id).collect::<Vec<u64>>();
+	assert_eq!(ids, vec![1, 2, 3]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn sending_and_updating_messages() {
+	use reqwest::Method;
+	use robolt::api::auth::RobloxCookie;
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::MockResponse;
+	use robolt::Robolt;
+	use serde_json::json;
+
+	let send_url = "https://privatemessages.roblox.com/v1/messages/send";
+	let transport = messages_transport()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		)
+		.route(
+			Method::POST,
+			send_url,
+			MockResponse::json(
+				&json!({ "success": true, "shortMessage": "Success", "message": "Your message has been sent" }),
+			),
+		)
+		.route(
+			Method::POST,
+			send_url,
+			MockResponse::json(&json!({
+				"success": false,
+				"shortMessage": "Failure",
+				"message": "This user does not accept messages from you"
+			})),
+		)
+		.route(
+			Method::GET,
+			"https://privatemessages.roblox.com/v1/messages/unread/count",
+			MockResponse::json(&json!({ "count": 4 })),
+		)
+		.route(
+			Method::POST,
+			"https://privatemessages.roblox.com/v1/messages/mark-read",
+			MockResponse::json(&json!({ "failedMessages": [] })),
+		)
+		.route(
+			Method::POST,
+			"https://privatemessages.roblox.com/v1/messages/archive",
+			MockResponse::json(&json!({
+				"failedMessages": [{ "messageId": 2, "errorMessage": "Message not found" }]
+			})),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.send_message(2, "Hello", "Hi there").await.unwrap();
+	let err = client.send_message(2, "Hello", "Hi again").await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::MessageNotSent);
+	assert_eq!(err.message, "This user does not accept messages from you");
+
+	assert_eq!(client.unread_messages_count().await.unwrap(), 4);
+	assert!(client.mark_messages_read(vec![1]).await.unwrap().is_empty());
+
+	let failed = client.archive_messages(vec![1, 2]).await.unwrap();
+	assert_eq!(failed.len(), 1);
+	assert_eq!(failed[0].message_id, 2);
+
+	let sent = transport
+		.requests()
+		.into_iter()
+		.find(|req| req.url == send_url)
+		.unwrap();
+	assert_eq!(
+		sent.body,
+		Some(json!({ "userId": 7, "recipientId": 2, "subject": "Hello", "body": "Hi there" }))
+	);
+}
@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex};
+
+use robolt::errors::{RoboltError, RoboltErrorKind};
+use robolt::middleware::{Middleware, RequestParts};
+use robolt::retry::Idempotency;
+use robolt::Robolt;
+
+/// Records the URL and idempotency of requests and aborts them before they
+/// reach the network
+#[derive(Default)]
+struct RequestRecorder(Arc<Mutex<Vec<(String, Idempotency)>>>);
+
+impl Middleware for RequestRecorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		self.0.lock().unwrap().push((parts.url.clone(), parts.idempotency));
+		Err("aborted".to_string().into())
+	}
+}
+
+#[tokio::test]
+async fn raw_rejects_other_hosts() {
+	let requests = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new().middleware(RequestRecorder(requests.clone()));
+
+	for url in [
+		"https://example.com/v1/users/1",
+		"https://roblox.com.example.com/v1/users/1",
+		"https://notroblox.com/v1/users/1",
+		"http://users.roblox.com/v1/users/1",
+	] {
+		let err = client.raw().get(url).send().await.unwrap_err();
+		assert_eq!(err.kind(), &RoboltErrorKind::HostNotAllowed, "{url}");
+	}
+
+	assert!(requests.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn raw_allows_configured_hosts() {
+	let requests = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new()
+		.middleware(RequestRecorder(requests.clone()))
+		.allow_raw_host("Example.com");
+
+	let _ = client.raw().get("https://api.example.com/v1/status").send().await;
+	let _ = client
+		.raw()
+		.get("https://roblox.com/v1/status?a=1")
+		.query("keyword", "a b&c")
+		.idempotency(Idempotency::Idempotent)
+		.send()
+		.await;
+
+	assert_eq!(*requests.lock().unwrap(), vec![
+		("https://api.example.com/v1/status".to_string(), Idempotency::Unknown),
+		(
+			"https://roblox.com/v1/status?a=1&keyword=a%20b%26c".to_string(),
+			Idempotency::Idempotent
+		),
+	]);
+}
+
+#[tokio::test]
+async fn raw_body_serialization_errors_are_returned() {
+	use std::collections::HashMap;
+
+	let requests = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new().middleware(RequestRecorder(requests.clone()));
+
+	// JSON object keys must be strings
+	let body = HashMap::from([(vec![1], 1)]);
+	let err = client
+		.raw()
+		.post("https://groups.roblox.com/v1/groups/1/audit-log")
+		.json(&body)
+		.send()
+		.await
+		.unwrap_err();
+
+	assert!(err.message.starts_with("Failed to serialize request body"));
+	assert!(requests.lock().unwrap().is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn raw_request_through_middlewares() {
+	use reqwest::header::{HeaderMap, HeaderValue};
+	use reqwest::Method;
+	use robolt::middleware::HeaderInjector;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde::Deserialize;
+
+	#[derive(Deserialize)]
+	struct AuditLog {
+		data: Vec<serde_json::Value>,
+	}
+
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://groups.roblox.com/v1/groups/123/audit-log?limit=10",
+		MockResponse::json(&serde_json::json!({ "data": [{}, {}] })),
+	);
+	let headers = HeaderMap::from_iter([("x-test".parse().unwrap(), HeaderValue::from_static("1"))]);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.middleware(HeaderInjector::new(headers));
+
+	let log = client
+		.raw()
+		.post("https://groups.roblox.com/v1/groups/123/audit-log")
+		.query("limit", "10")
+		.json(&serde_json::json!({ "actionType": "ChangeRank" }))
+		.send_json::<AuditLog>()
+		.await
+		.unwrap();
+
+	assert_eq!(log.data.len(), 2);
+	assert_eq!(transport.requests()[0].headers["x-test"], "1");
+	assert_eq!(
+		transport.requests()[0].body,
+		Some(serde_json::json!({ "actionType": "ChangeRank" }))
+	);
+
+	let err = client
+		.raw()
+		.get("https://groups.roblox.com/v1/groups/123/audit-log")
+		.send()
+		.await
+		.unwrap_err();
+	assert!(err.message.contains("No mock response"));
+}
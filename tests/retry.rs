@@ -0,0 +1,195 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+
+use robolt::errors::RoboltError;
+use robolt::middleware::{Middleware, RequestParts};
+use robolt::retry::{Idempotency, RetryPolicy};
+use robolt::Robolt;
+
+#[test]
+fn retry_delays_back_off() {
+	let policy = RetryPolicy::new()
+		.max_retries(5)
+		.base_delay(Duration::from_secs(2))
+		.max_delay(Duration::from_secs(10));
+
+	assert_eq!(
+		(1..=5).map(|attempt| policy.delay(attempt)).collect::<Vec<Duration>>(),
+		vec![
+			Duration::from_secs(2),
+			Duration::from_secs(4),
+			Duration::from_secs(8),
+			Duration::from_secs(10),
+			Duration::from_secs(10),
+		]
+	);
+}
+
+#[test]
+fn retry_delay_does_not_overflow() {
+	let policy = RetryPolicy::new().max_delay(Duration::from_secs(60));
+	assert_eq!(policy.delay(u32::MAX), Duration::from_secs(60));
+}
+
+#[test]
+fn retry_policy_is_kept_on_the_client() {
+	let client = Robolt::new().retry_policy(RetryPolicy::new().max_retries(3).on_retry(|attempt, err, delay| {
+		eprintln!("{err}, retrying in {delay:?} (attempt {attempt}/3)");
+	}));
+
+	assert!(format!("{client:?}").contains("max_retries: 3"));
+	assert!(format!("{client:?}").contains("on_retry: true"));
+}
+
+#[test]
+fn retries_depend_on_idempotency() {
+	let policy = RetryPolicy::new().max_retries(2);
+
+	for status in [
+		StatusCode::INTERNAL_SERVER_ERROR,
+		StatusCode::BAD_GATEWAY,
+		StatusCode::SERVICE_UNAVAILABLE,
+		StatusCode::GATEWAY_TIMEOUT,
+	] {
+		assert!(policy.should_retry(status, Idempotency::Idempotent, 1), "{status}");
+		assert!(!policy.should_retry(status, Idempotency::NonIdempotent, 1), "{status}");
+		assert!(!policy.should_retry(status, Idempotency::Unknown, 1), "{status}");
+	}
+
+	assert!(policy.should_retry(StatusCode::TOO_MANY_REQUESTS, Idempotency::NonIdempotent, 2));
+	assert!(!policy.should_retry(StatusCode::TOO_MANY_REQUESTS, Idempotency::Idempotent, 3));
+	assert!(!policy.should_retry(StatusCode::BAD_REQUEST, Idempotency::Idempotent, 1));
+	assert!(!RetryPolicy::new().should_retry(StatusCode::BAD_GATEWAY, Idempotency::Idempotent, 1));
+}
+
+/// Records the method and idempotency of requests and aborts them before
+/// they reach the network
+#[derive(Default)]
+struct IdempotencyRecorder(Arc<Mutex<Vec<(Method, Idempotency)>>>);
+
+impl Middleware for IdempotencyRecorder {
+	fn before_send(&self, parts: &mut RequestParts) -> Result<(), RoboltError> {
+		self.0.lock().unwrap().push((parts.method.clone(), parts.idempotency));
+		Err("aborted".to_string().into())
+	}
+}
+
+#[tokio::test]
+async fn batch_reads_are_idempotent() {
+	let requests = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new().middleware(IdempotencyRecorder(requests.clone()));
+	let policy = RetryPolicy::new().max_retries(1);
+
+	let _ = client.users_from_ids(vec![1, 2], false).await;
+	let _ = client.presences(vec![1, 2]).await;
+	let _ = client.user(1).await;
+
+	for (method, idempotency) in requests.lock().unwrap().iter() {
+		assert_eq!(idempotency, &Idempotency::Idempotent, "{method}");
+		assert!(policy.should_retry(StatusCode::BAD_GATEWAY, *idempotency, 1));
+	}
+
+	assert_eq!(requests.lock().unwrap().len(), 3);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn batch_reads_retry_on_bad_gateway() {
+	use robolt::test_util::{MockResponse, MockTransport, VirtualClock};
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://users.roblox.com/v1/users",
+			MockResponse::error(StatusCode::BAD_GATEWAY, 0, "Bad Gateway"),
+		)
+		.route(
+			Method::POST,
+			"https://users.roblox.com/v1/users",
+			MockResponse::json(
+				&serde_json::json!({ "data": [{ "id": 1, "name": "Roblox", "displayName": "Roblox" }] }),
+			),
+		);
+	let clock = VirtualClock::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.sleeper(clock.clone())
+		.retry_policy(RetryPolicy::new().max_retries(1).base_delay(Duration::from_secs(3)));
+
+	let users = client.users_from_ids(vec![1], false).await.unwrap();
+
+	assert_eq!(users[0].username, "Roblox");
+	assert_eq!(transport.requests().len(), 2);
+	assert_eq!(clock.sleeps(), vec![Duration::from_secs(3)]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn rate_limited_requests_notify_each_retry() {
+	use robolt::test_util::{MockResponse, MockTransport, VirtualClock};
+
+	let url = "https://users.roblox.com/v1/users/1";
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			url,
+			MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests"),
+		)
+		.route(
+			Method::GET,
+			url,
+			MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests"),
+		)
+		.route(
+			Method::GET,
+			url,
+			MockResponse::json(&serde_json::json!({
+				"name": "Roblox",
+				"displayName": "Roblox",
+				"description": "",
+				"created": "2006-02-27T21:06:40.3Z",
+				"isBanned": false,
+				"hasVerifiedBadge": true,
+				"id": 1
+			})),
+		);
+	let retries = Arc::new(Mutex::new(Vec::new()));
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.sleeper(VirtualClock::new())
+		.retry_policy(RetryPolicy::new().max_retries(3).on_retry({
+			let retries = retries.clone();
+			move |attempt, err, delay| retries.lock().unwrap().push((attempt, err.status(), delay))
+		}));
+
+	assert_eq!(client.user(1).await.unwrap().id, 1);
+	assert_eq!(transport.requests().len(), 3);
+	assert_eq!(*retries.lock().unwrap(), vec![
+		(1, Some(429), Duration::from_secs(1)),
+		(2, Some(429), Duration::from_secs(2)),
+	]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn retry_after_is_capped_at_max_delay() {
+	use robolt::test_util::{MockResponse, MockTransport, VirtualClock};
+
+	let transport = MockTransport::new().route(
+		Method::GET,
+		"https://users.roblox.com/v1/users/1",
+		MockResponse::error(StatusCode::TOO_MANY_REQUESTS, 0, "Too many requests").header("retry-after", "3600"),
+	);
+	let clock = VirtualClock::new();
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.sleeper(clock.clone())
+		.retry_policy(RetryPolicy::new().max_retries(1).max_delay(Duration::from_secs(10)));
+
+	let err = client.user(1).await.unwrap_err();
+
+	assert_eq!(err.retry_after(), Some(Duration::from_secs(3600)));
+	assert_eq!(clock.sleeps(), vec![Duration::from_secs(10)]);
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "test-util")]
+
+use std::sync::{Arc, Mutex};
+
+use reqwest::Method;
+use serde_json::json;
+
+use robolt::schema::{SchemaDrift, SchemaWatch};
+use robolt::test_util::{MockResponse, MockTransport};
+use robolt::Robolt;
+
+fn user(id: u64, extra: bool) -> MockResponse {
+	let mut body = json!({
+		"id": id,
+		"name": "builderman",
+		"displayName": "builderman",
+		"description": null,
+		"externalAppDisplayName": null,
+		"created": "2006-02-27T21:06:40.3Z",
+		"isBanned": false,
+		"hasVerifiedBadge": true,
+	});
+
+	if extra {
+		body["profileTheme"] = json!("dark");
+	}
+
+	MockResponse::json(&body)
+}
+
+fn watched_client(transport: MockTransport) -> (Robolt, Arc<Mutex<Vec<SchemaDrift>>>) {
+	let reports = Arc::new(Mutex::new(Vec::new()));
+	let watch = SchemaWatch::new({
+		let reports = reports.clone();
+		move |drift| reports.lock().unwrap().push(drift.clone())
+	});
+
+	(Robolt::new().mock_transport(transport).schema_watch(watch), reports)
+}
+
+#[tokio::test]
+async fn reports_unknown_field_once_per_endpoint() {
+	let transport = MockTransport::new()
+		.route(Method::GET, "https://users.roblox.com/v1/users/156", user(156, true))
+		.route(Method::GET, "https://users.roblox.com/v1/users/261", user(261, true));
+	let (client, reports) = watched_client(transport);
+
+	assert_eq!(client.user(156).await.unwrap().id, 156);
+	assert_eq!(client.user(261).await.unwrap().id, 261);
+	assert_eq!(client.clone().user(156).await.unwrap().id, 156);
+
+	assert_eq!(*reports.lock().unwrap(), vec![SchemaDrift {
+		endpoint: "GET https://users.roblox.com/v1/users/{id}".to_string(),
+		fields: vec!["profileTheme".to_string()],
+	}]);
+}
+
+#[tokio::test]
+async fn known_fields_are_not_reported() {
+	let transport =
+		MockTransport::new().route(Method::GET, "https://users.roblox.com/v1/users/156", user(156, false));
+	let (client, reports) = watched_client(transport);
+
+	client.user(156).await.unwrap();
+
+	assert!(reports.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn nested_fields_are_reported_by_path() {
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://users.roblox.com/v1/users",
+		MockResponse::json(&json!({
+			"data": [
+				{ "hasVerifiedBadge": false, "id": 156, "name": "builderman", "displayName": "builderman" },
+				{ "hasVerifiedBadge": true, "id": 1, "name": "Roblox", "displayName": "Roblox" },
+			],
+		})),
+	);
+	let (client, reports) = watched_client(transport);
+
+	client.users_from_ids(vec![156, 1], false).await.unwrap();
+
+	let reports = reports.lock().unwrap();
+	assert_eq!(reports.len(), 1);
+	assert_eq!(reports[0].endpoint, "POST https://users.roblox.com/v1/users");
+	assert_eq!(reports[0].fields, vec!["data[].hasVerifiedBadge".to_string()]);
+}
+
+#[tokio::test]
+async fn requested_usernames_are_not_reported() {
+	let transport = MockTransport::new().route(
+		Method::POST,
+		"https://users.roblox.com/v1/usernames/users",
+		MockResponse::json(&json!({
+			"data": [
+				{ "requestedUsername": "builderman", "id": 156, "name": "builderman", "displayName": "builderman" },
+			],
+		})),
+	);
+	let (client, reports) = watched_client(transport);
+
+	let lookup = client.lookup_usernames(vec!["Builderman"], false).await.unwrap();
+
+	assert_eq!(lookup.found["Builderman"].id, 156);
+	assert!(reports.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn panicking_callback_does_not_fail_the_request() {
+	let transport =
+		MockTransport::new().route(Method::GET, "https://users.roblox.com/v1/users/156", user(156, true));
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.schema_watch(SchemaWatch::new(|_| panic!("drift")));
+
+	assert_eq!(client.user(156).await.unwrap().id, 156);
+}
+
+#[test]
+fn drift_display() {
+	let drift = SchemaDrift {
+		endpoint: "GET https://users.roblox.com/v1/users/{id}".to_string(),
+		fields: vec!["profileTheme".to_string(), "badges[].rank".to_string()],
+	};
+
+	assert_eq!(
+		drift.to_string(),
+		"Unknown fields in GET https://users.roblox.com/v1/users/{id}: profileTheme, badges[].rank"
+	);
+}
@@ -0,0 +1,187 @@
+use robolt::api::avatar::{Avatar, BrickColor};
+use robolt::api::screening::{ScreeningExtras, ScreeningProfile, ScreeningRule};
+use robolt::api::users::User;
+
+/// 2023-05-04T00:00:00Z, three days after the fixture user was created
+const NOW: u64 = 1_683_158_400;
+
+fn user() -> User {
+	serde_json::from_str(include_str!("fixtures/screening/user.json")).unwrap()
+}
+
+fn avatar() -> Avatar {
+	serde_json::from_str(include_str!("fixtures/screening/avatar.json")).unwrap()
+}
+
+fn extras() -> ScreeningExtras {
+	ScreeningExtras {
+		avatar: Some(avatar()),
+		friend_count: Some(0),
+		previous_usernames: Some(vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()]),
+	}
+}
+
+#[test]
+fn default_profile_flags_new_accounts() {
+	let verdict = ScreeningProfile::new().evaluate_at(&user(), &extras(), NOW);
+
+	assert_eq!(verdict.fired.iter().map(|hit| hit.rule).collect::<Vec<_>>(), vec![
+		ScreeningRule::AccountAge,
+		ScreeningRule::DefaultAvatar,
+		ScreeningRule::FewFriends,
+		ScreeningRule::UsernameChurn
+	]);
+	assert_eq!(verdict.fired[0].reason, "Account is 3 days old");
+	assert_eq!(verdict.score, 100);
+	assert!(verdict.flagged);
+	assert!(verdict.skipped.is_empty());
+}
+
+#[test]
+fn account_age() {
+	let profile = ScreeningProfile::new().min_account_age(3);
+	let verdict = profile.evaluate_at(&user(), &ScreeningExtras::default(), NOW);
+
+	assert!(!verdict.has_fired(ScreeningRule::AccountAge));
+
+	let verdict = profile
+		.min_account_age(4)
+		.evaluate_at(&user(), &ScreeningExtras::default(), NOW);
+
+	assert!(verdict.has_fired(ScreeningRule::AccountAge));
+	assert!(!verdict.flagged);
+}
+
+#[test]
+fn default_avatar() {
+	let profile = ScreeningProfile::new();
+	let mut customized = extras();
+	customized.avatar.as_mut().unwrap().body_colors.head_color_id = BrickColor::DarkGreen;
+
+	assert!(profile
+		.evaluate_at(&user(), &extras(), NOW)
+		.has_fired(ScreeningRule::DefaultAvatar));
+	assert!(!profile
+		.evaluate_at(&user(), &customized, NOW)
+		.has_fired(ScreeningRule::DefaultAvatar));
+}
+
+#[test]
+fn few_friends() {
+	let profile = ScreeningProfile::new().min_friends(5);
+	let extras = |friend_count| ScreeningExtras {
+		friend_count: Some(friend_count),
+		..ScreeningExtras::default()
+	};
+
+	assert!(profile
+		.evaluate_at(&user(), &extras(4), NOW)
+		.has_fired(ScreeningRule::FewFriends));
+	assert!(!profile
+		.evaluate_at(&user(), &extras(5), NOW)
+		.has_fired(ScreeningRule::FewFriends));
+}
+
+#[test]
+fn username_patterns() {
+	let user = user();
+	let cases = [
+		("*free*robux*", true),
+		("freerobux_####", true),
+		("freerobux_###", false),
+		("?reeRobux*", true),
+		("robux*", false),
+		("*", true),
+	];
+
+	for (pattern, fires) in cases {
+		let verdict = ScreeningProfile::new()
+			.username_patterns(&["builderman", pattern])
+			.evaluate_at(&user, &ScreeningExtras::default(), NOW);
+
+		assert_eq!(verdict.has_fired(ScreeningRule::UsernamePattern), fires, "{pattern}");
+	}
+}
+
+#[test]
+fn username_patterns_are_disabled_by_default() {
+	let profile = ScreeningProfile::new();
+
+	assert!(!profile.is_enabled(ScreeningRule::UsernamePattern));
+	assert!(profile
+		.username_patterns(&["*"])
+		.is_enabled(ScreeningRule::UsernamePattern));
+}
+
+#[test]
+fn username_churn() {
+	let profile = ScreeningProfile::new().max_previous_usernames(4);
+
+	assert!(!profile
+		.evaluate_at(&user(), &extras(), NOW)
+		.has_fired(ScreeningRule::UsernameChurn));
+	assert!(profile
+		.max_previous_usernames(3)
+		.evaluate_at(&user(), &extras(), NOW)
+		.has_fired(ScreeningRule::UsernameChurn));
+}
+
+#[test]
+fn missing_extras_are_skipped() {
+	let profile = ScreeningProfile::new().disable(ScreeningRule::FewFriends);
+	let verdict = profile.evaluate_at(&user(), &ScreeningExtras::default(), NOW);
+
+	assert_eq!(verdict.skipped, vec![
+		ScreeningRule::DefaultAvatar,
+		ScreeningRule::UsernameChurn
+	]);
+	assert_eq!(verdict.fired.len(), 1);
+}
+
+#[test]
+fn scores_and_threshold() {
+	let profile = ScreeningProfile::new()
+		.score(ScreeningRule::AccountAge, 5)
+		.score(ScreeningRule::FewFriends, 5)
+		.threshold(10);
+
+	let extras = ScreeningExtras {
+		friend_count: Some(0),
+		..ScreeningExtras::default()
+	};
+	let verdict = profile.evaluate_at(&user(), &extras, NOW);
+
+	assert_eq!(verdict.score, 10);
+	assert!(verdict.flagged);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn screen_only_fetches_needed_extras() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::Robolt;
+
+	let fixture = |json: &str| MockResponse::json(&serde_json::from_str::<serde_json::Value>(json).unwrap());
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			fixture(include_str!("fixtures/screening/user.json")),
+		)
+		.route(
+			Method::GET,
+			"https://friends.roblox.com/v1/users/1/friends/count",
+			MockResponse::json(&serde_json::json!({ "count": 0 })),
+		);
+	let client = Robolt::new().mock_transport(transport.clone());
+	let profile = ScreeningProfile::new()
+		.disable(ScreeningRule::DefaultAvatar)
+		.disable(ScreeningRule::UsernameChurn);
+
+	let verdict = client.screen(1, &profile).await.unwrap();
+
+	assert_eq!(transport.requests().len(), 2);
+	assert!(verdict.has_fired(ScreeningRule::FewFriends));
+	assert!(verdict.skipped.is_empty());
+}
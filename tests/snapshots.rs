@@ -0,0 +1,87 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use robolt::api::groups::Group;
+use robolt::api::users::User;
+use robolt::snapshots::{GroupSnapshot, UserSnapshot, UserSnapshotV1, UserSnapshotV2};
+
+#[test]
+fn user_snapshot_captures_user() {
+	let user: User = serde_json::from_str(include_str!("fixtures/users/active.json")).unwrap();
+	let snapshot = UserSnapshot::captured(user.clone(), UNIX_EPOCH + Duration::from_secs(1704067200));
+
+	assert_eq!(snapshot.schema_version, UserSnapshotV2::SCHEMA_VERSION);
+	assert_eq!(snapshot.captured_at, 1704067200);
+	assert_eq!(snapshot.id, user.id);
+	assert_eq!(snapshot.username, user.username);
+	assert_eq!(snapshot.description, user.description);
+}
+
+#[test]
+fn user_snapshot_round_trips() {
+	let user: User = serde_json::from_str(include_str!("fixtures/users/active.json")).unwrap();
+	let snapshot = UserSnapshot::from(user);
+	let loaded = UserSnapshot::load_any(&snapshot.to_bytes().unwrap()).unwrap();
+
+	assert_eq!(loaded, snapshot);
+}
+
+#[test]
+fn user_snapshot_v1_is_upgraded() {
+	let stored = include_bytes!("fixtures/snapshots/user_v1.json");
+	let v1: UserSnapshotV1 = serde_json::from_slice(stored).unwrap();
+	let snapshot = UserSnapshot::load_any(stored).unwrap();
+
+	assert_eq!(snapshot, UserSnapshot {
+		schema_version: 2,
+		captured_at: 1609459200,
+		id: 100000001,
+		username: "ExampleUser".to_string(),
+		display_name: "Example".to_string(),
+		description: Some("Welcome to my profile!".to_string()),
+		created: "2015-03-14T09:26:53.58Z".to_string(),
+		is_banned: false,
+		has_verified_badge: false,
+		external_app_display_name: None,
+	});
+	assert_eq!(snapshot, UserSnapshotV2::from(v1));
+}
+
+#[test]
+fn user_snapshot_v2_is_loaded() {
+	let snapshot = UserSnapshot::load_any(include_bytes!("fixtures/snapshots/user_v2.json")).unwrap();
+
+	assert_eq!(snapshot.schema_version, 2);
+	assert!(snapshot.has_verified_badge);
+	assert_eq!(snapshot.external_app_display_name.as_deref(), Some("Example App"));
+}
+
+#[test]
+fn unknown_schema_versions_are_rejected() {
+	let future = br#"{ "schemaVersion": 3, "capturedAt": 0, "id": 1 }"#;
+	let err = UserSnapshot::load_any(future).unwrap_err();
+
+	assert!(err.message.contains("schema version 3"));
+	assert!(UserSnapshot::load_any(br#"{ "id": 1 }"#).is_err());
+	assert!(GroupSnapshot::load_any(br#"{ "schemaVersion": 2 }"#).is_err());
+}
+
+#[test]
+fn group_snapshot_captures_group() {
+	let group: Group = serde_json::from_str(include_str!("fixtures/groups/group.json")).unwrap();
+	let snapshot = GroupSnapshot::captured(group, UNIX_EPOCH);
+
+	assert_eq!(snapshot.schema_version, 1);
+	assert_eq!(snapshot.owner_id, Some(100));
+	assert_eq!(snapshot.owner_username.as_deref(), Some("GroupOwner"));
+	assert_eq!(snapshot.shout.as_deref(), Some("Event tonight!"));
+	assert!(!snapshot.is_locked);
+}
+
+#[test]
+fn group_snapshot_v1_is_loaded() {
+	let snapshot = GroupSnapshot::load_any(include_bytes!("fixtures/snapshots/group_v1.json")).unwrap();
+	let loaded = GroupSnapshot::load_any(&snapshot.to_bytes().unwrap()).unwrap();
+
+	assert_eq!(snapshot.member_count, 42);
+	assert_eq!(loaded, snapshot);
+}
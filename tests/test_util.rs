@@ -0,0 +1,85 @@
+#![cfg(feature = "test-util")]
+
+use reqwest::{Method, StatusCode};
+
+use robolt::api::Limit;
+use robolt::errors::RoboltErrorKind;
+use robolt::test_util::{client_with_fixtures, FixtureSet, MockResponse, MockTransport};
+use robolt::Robolt;
+
+#[tokio::test]
+async fn fixture_group_crawl() {
+	let client = client_with_fixtures("tests/fixtures/group_123");
+
+	let group = client.group(123).await.unwrap();
+	assert_eq!(group.name, "Fixture Group");
+
+	let mut members = Vec::new();
+	let mut cursor = None;
+
+	loop {
+		let page = client.group_members(123, Limit::Max, cursor.as_deref()).await.unwrap();
+		members.extend(page.data.into_iter().map(|member| member.user.id));
+
+		cursor = match page.next_page_cursor {
+			Some(next) => Some(next),
+			None => break,
+		};
+	}
+
+	assert_eq!(members, vec![100, 200, 300]);
+	assert_eq!(members.len() as u64, group.member_count);
+}
+
+#[test]
+fn fixture_set_load() {
+	let fixtures = FixtureSet::load("tests/fixtures/group_123").unwrap();
+
+	assert_eq!(fixtures.fixtures.len(), 3);
+	assert_eq!(fixtures.fixtures[0].method, "GET");
+	assert_eq!(fixtures.fixtures[0].status, 200);
+	assert!(fixtures.fixtures[2].url.ends_with("&cursor=2_1_a1b2c3"));
+}
+
+#[tokio::test]
+async fn mock_transport_serves_responses_in_order() {
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/1",
+			MockResponse::json(&serde_json::json!({
+				"description": "",
+				"created": "2006-02-27T21:06:40.3Z",
+				"id": 1,
+				"name": "Roblox",
+				"displayName": "Roblox"
+			})),
+		);
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let err = client.user(1).await.unwrap_err();
+	assert_eq!(err.status(), Some(404));
+	assert_eq!(err.message, "The user id is invalid.");
+
+	for _ in 0..2 {
+		assert_eq!(client.user(1).await.unwrap().username, "Roblox");
+	}
+
+	assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn mock_transport_rejects_unknown_routes() {
+	let transport = MockTransport::new();
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let err = client.user(1).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::Unknown);
+	assert!(err.message.contains("GET https://users.roblox.com/v1/users/1"));
+	assert_eq!(transport.requests()[0].method, Method::GET);
+}
@@ -0,0 +1,331 @@
+#[cfg(feature = "test-util")]
+use std::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "test-util")]
+use std::time::Duration;
+
+use tokio_test::assert_ok;
+
+use robolt::api::avatar::Avatar;
+#[cfg(feature = "test-util")]
+use robolt::api::thumbnails::{poll_thumbnail_with, ThumbnailRequest, ThumbnailType};
+use robolt::api::thumbnails::{Thumbnail, ThumbnailFormat, ThumbnailSize, ThumbnailState, TryOnRequest};
+#[cfg(feature = "test-util")]
+use robolt::test_util::VirtualClock;
+use robolt::Robolt;
+
+#[tokio::test]
+async fn asset_thumbnails() {
+	let client = Robolt::new();
+	assert_ok!(
+		client
+			.asset_thumbnails(&[1818], ThumbnailSize(420, 420), ThumbnailFormat::Png)
+			.await
+	);
+}
+
+#[tokio::test]
+async fn avatar_thumbnails() {
+	let client = Robolt::new();
+	assert_ok!(
+		client
+			.avatar_thumbnails(&[1], ThumbnailSize(420, 420), ThumbnailFormat::Png, false)
+			.await
+	);
+}
+
+#[tokio::test]
+async fn avatar_headshots() {
+	let client = Robolt::new();
+	assert_ok!(
+		client
+			.avatar_headshots(&[1], ThumbnailSize(150, 150), ThumbnailFormat::Webp, true)
+			.await
+	);
+}
+
+#[tokio::test]
+async fn bundle_thumbnails() {
+	let client = Robolt::new();
+	assert_ok!(client.bundle_thumbnails(&[192], ThumbnailSize(150, 150)).await);
+}
+
+#[tokio::test]
+async fn outfit_thumbnails() {
+	let client = Robolt::new();
+	assert_ok!(client.outfit_thumbnails(&[11713089], ThumbnailSize(420, 420)).await);
+}
+
+#[tokio::test]
+async fn badge_icons() {
+	let client = Robolt::new();
+	assert_ok!(client.badge_icons(&[2124789031], ThumbnailSize(150, 150)).await);
+}
+
+#[tokio::test]
+async fn invalid_thumbnail_size() {
+	let client = Robolt::new();
+	let err = client
+		.bundle_thumbnails(&[192], ThumbnailSize(30, 30))
+		.await
+		.unwrap_err();
+
+	assert_eq!(
+		err.message,
+		"Invalid thumbnail size 30x30 for /v1/bundles/thumbnails, expected one of: 150x150, 420x420"
+	);
+}
+
+#[tokio::test]
+async fn invalid_headshot_size() {
+	let client = Robolt::new();
+	let err = client
+		.avatar_headshots(&[1], ThumbnailSize(30, 30), ThumbnailFormat::Png, false)
+		.await
+		.unwrap_err();
+
+	assert!(err
+		.message
+		.starts_with("Invalid thumbnail size 30x30 for /v1/users/avatar-headshot, expected one of: 48x48"));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn avatar_headshots_are_chunked() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let ids = (1..=150).collect::<Vec<u64>>();
+	let url = |ids: &[u64]| {
+		let ids = ids.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+		format!("https://thumbnails.roblox.com/v1/users/avatar-headshot?isCircular=true&userIds={ids}&size=48x48&format=Png")
+	};
+	let page = |ids: &[u64]| {
+		let data = ids
+			.iter()
+			.map(|id| match id {
+				150 => json!({ "targetId": id, "state": "Pending", "imageUrl": null }),
+				_ => {
+					json!({ "targetId": id, "state": "Completed", "imageUrl": format!("https://tr.rbxcdn.com/{id}") })
+				},
+			})
+			.collect::<Vec<_>>();
+
+		MockResponse::json(&json!({ "data": data }))
+	};
+	let transport = MockTransport::new()
+		.route(Method::GET, &url(&ids[..100]), page(&ids[..100]))
+		.route(Method::GET, &url(&ids[100..]), page(&ids[100..]));
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let thumbnails = client
+		.avatar_headshots(&ids, ThumbnailSize(48, 48), ThumbnailFormat::Png, true)
+		.await
+		.unwrap();
+
+	assert_eq!(transport.requests().len(), 2);
+	assert_eq!(thumbnails.len(), 150);
+	assert_eq!(thumbnails[0].image_url.as_deref(), Some("https://tr.rbxcdn.com/1"));
+	assert_eq!(thumbnails[149].state, ThumbnailState::Pending);
+	assert_eq!(thumbnails[149].image_url, None);
+}
+
+#[test]
+fn pending_thumbnail() {
+	let thumbnail: Thumbnail =
+		serde_json::from_str(r#"{ "targetId": 1818, "state": "Pending", "imageUrl": null, "version": "" }"#)
+			.unwrap();
+
+	assert_eq!(thumbnail.state, ThumbnailState::Pending);
+	assert_eq!(thumbnail.image_url, None);
+}
+
+#[test]
+fn try_on_request_body() {
+	let avatar: Avatar = serde_json::from_str(include_str!("fixtures/thumbnails/avatar.json")).unwrap();
+	let body = TryOnRequest::new(1, &avatar, &[1818, 63690008], ThumbnailSize(420, 420));
+
+	let expected: serde_json::Value =
+		serde_json::from_str(include_str!("fixtures/thumbnails/try_on_request.json")).unwrap();
+	assert_eq!(serde_json::to_value(body).unwrap(), expected);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn poll_until_completed() {
+	let calls = AtomicU32::new(0);
+	let clock = VirtualClock::new();
+
+	let thumbnail = poll_thumbnail_with(5, Duration::from_secs(2), &clock, || async {
+		let state = match calls.fetch_add(1, Ordering::SeqCst) {
+			0 | 1 => ThumbnailState::Pending,
+			_ => ThumbnailState::Completed,
+		};
+
+		Ok(Thumbnail {
+			target_id: 1,
+			state,
+			..Default::default()
+		})
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(thumbnail.state, ThumbnailState::Completed);
+	assert_eq!(calls.load(Ordering::SeqCst), 3);
+	assert_eq!(clock.sleeps(), vec![Duration::from_secs(2); 2]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn poll_gives_up_while_pending() {
+	let calls = AtomicU32::new(0);
+	let clock = VirtualClock::new();
+
+	let thumbnail = poll_thumbnail_with(3, Duration::from_secs(2), &clock, || async {
+		calls.fetch_add(1, Ordering::SeqCst);
+		Ok(Thumbnail::default())
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(thumbnail.state, ThumbnailState::Pending);
+	assert_eq!(calls.load(Ordering::SeqCst), 3);
+	assert_eq!(clock.elapsed(), Duration::from_secs(4));
+}
+
+#[cfg(feature = "test-util")]
+/// Answers each batch request with the next state list, where every entry is a
+/// target ID and the state it is returned in
+fn batch_client(ticks: &[&[(u64, &str)]]) -> (Robolt, robolt::test_util::MockTransport, VirtualClock) {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = ticks.iter().fold(MockTransport::new(), |transport, states| {
+		let data = states
+			.iter()
+			.map(|(target_id, state)| {
+				json!({
+					"requestId": format!("{target_id}:AvatarHeadShot:150x150:Png"),
+					"errorCode": 0,
+					"errorMessage": "",
+					"targetId": target_id,
+					"state": state,
+					"imageUrl": (*state == "Completed").then(|| format!("https://tr.rbxcdn.com/{target_id}")),
+					"version": "TN3",
+				})
+			})
+			.collect::<Vec<_>>();
+
+		transport.route(
+			Method::POST,
+			"https://thumbnails.roblox.com/v1/batch",
+			MockResponse::json(&json!({ "data": data })),
+		)
+	});
+
+	let clock = VirtualClock::new();
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.clock(clock.clone())
+		.sleeper(clock.clone());
+
+	(client, transport, clock)
+}
+
+#[cfg(feature = "test-util")]
+fn headshots(target_ids: &[u64]) -> Vec<ThumbnailRequest> {
+	target_ids
+		.iter()
+		.map(|target_id| ThumbnailRequest::new(ThumbnailType::AvatarHeadShot, *target_id, ThumbnailSize(150, 150)))
+		.collect()
+}
+
+#[cfg(feature = "test-util")]
+fn requested_ids(transport: &robolt::test_util::MockTransport) -> Vec<Vec<u64>> {
+	transport
+		.requests()
+		.iter()
+		.map(|request| {
+			request
+				.body
+				.as_ref()
+				.unwrap()
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|entry| entry["targetId"].as_u64().unwrap())
+				.collect()
+		})
+		.collect()
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn resolve_thumbnails_requests_only_pending() {
+	let (client, transport, clock) = batch_client(&[
+		&[(1, "Completed"), (2, "Pending"), (3, "Completed"), (4, "Pending")],
+		&[(2, "Pending"), (4, "Completed")],
+		&[(2, "Pending")],
+		&[(2, "Completed")],
+	]);
+	let requests = headshots(&[1, 2, 3, 4]);
+
+	let thumbnails = client
+		.resolve_thumbnails(&requests, Duration::from_secs(1), Duration::from_secs(5))
+		.await
+		.unwrap();
+
+	assert_eq!(clock.sleeps(), vec![Duration::from_secs(1); 3]);
+	assert_eq!(requested_ids(&transport), vec![
+		vec![1, 2, 3, 4],
+		vec![2, 4],
+		vec![2],
+		vec![2]
+	]);
+	assert!(thumbnails
+		.values()
+		.all(|thumbnail| thumbnail.state == ThumbnailState::Completed));
+	assert_eq!(
+		thumbnails[&requests[1]].image_url.as_deref(),
+		Some("https://tr.rbxcdn.com/2")
+	);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn resolve_thumbnails_returns_pending_at_deadline() {
+	let (client, transport, clock) = batch_client(&[
+		&[(1, "Completed"), (2, "Pending"), (3, "Pending")],
+		&[(2, "Pending"), (3, "Completed")],
+		&[(2, "Completed")],
+	]);
+	let requests = headshots(&[1, 2, 3]);
+
+	let thumbnails = client
+		.resolve_thumbnails(&requests, Duration::from_secs(2), Duration::from_secs(3))
+		.await
+		.unwrap();
+
+	assert_eq!(clock.elapsed(), Duration::from_secs(2));
+	assert_eq!(requested_ids(&transport), vec![vec![1, 2, 3], vec![2, 3]]);
+	assert_eq!(thumbnails[&requests[1]].state, ThumbnailState::Pending);
+	assert_eq!(thumbnails[&requests[2]].state, ThumbnailState::Completed);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn resolve_thumbnails_does_not_retry_blocked() {
+	let (client, transport, _) = batch_client(&[&[(1, "Blocked"), (2, "Pending")], &[(2, "Completed")]]);
+	let requests = headshots(&[1, 2]);
+
+	let thumbnails = client
+		.resolve_thumbnails(&requests, Duration::from_secs(1), Duration::from_secs(5))
+		.await
+		.unwrap();
+
+	assert_eq!(requested_ids(&transport), vec![vec![1, 2], vec![2]]);
+	assert_eq!(thumbnails[&requests[0]].state, ThumbnailState::Blocked);
+	assert_eq!(thumbnails[&requests[1]].state, ThumbnailState::Completed);
+}
@@ -0,0 +1,70 @@
+#[cfg(feature = "chrono")]
+#[test]
+fn parses_roblox_timestamps() {
+	use chrono::{TimeZone, Utc};
+	use robolt::timestamp;
+
+	let second = Utc.with_ymd_and_hms(2015, 3, 14, 9, 26, 53).unwrap();
+
+	assert_eq!(timestamp::parse("2015-03-14T09:26:53Z").unwrap(), second);
+	assert_eq!(timestamp::parse("2015-03-14T09:26:53").unwrap(), second);
+	assert_eq!(
+		timestamp::parse("2015-03-14T09:26:53.58Z").unwrap(),
+		second + chrono::Duration::milliseconds(580)
+	);
+	assert_eq!(
+		timestamp::parse("2015-03-14T10:26:53.5830000+01:00").unwrap(),
+		second + chrono::Duration::milliseconds(583)
+	);
+	assert!(timestamp::parse("2015-03-14").is_err());
+	assert!(timestamp::parse("").is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn model_timestamps() {
+	use chrono::{Datelike, TimeZone, Utc};
+	use robolt::api::presence::UserPresence;
+	use robolt::api::users::User;
+	use serde_json::json;
+
+	let user: User = serde_json::from_str(include_str!("fixtures/users/active.json")).unwrap();
+	assert_eq!(user.created_at().unwrap().year(), 2015);
+
+	let presence: UserPresence = serde_json::from_value(json!({
+		"userPresenceType": 0,
+		"lastOnline": "2023-05-01T12:00:00.000Z",
+	}))
+	.unwrap();
+	assert_eq!(
+		presence.last_online_at().unwrap(),
+		Some(Utc.with_ymd_and_hms(2023, 5, 1, 12, 0, 0).unwrap())
+	);
+
+	let presence: UserPresence = serde_json::from_value(json!({ "userPresenceType": 0 })).unwrap();
+	assert_eq!(presence.last_online_at().unwrap(), None);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn deserializes_nullable_timestamps() {
+	use chrono::{DateTime, Utc};
+	use serde::Deserialize;
+
+	#[derive(Deserialize)]
+	struct Post {
+		#[serde(deserialize_with = "robolt::timestamp::deserialize")]
+		created: DateTime<Utc>,
+		#[serde(default, deserialize_with = "robolt::timestamp::deserialize_option")]
+		deleted: Option<DateTime<Utc>>,
+	}
+
+	let post: Post = serde_json::from_str(r#"{ "created": "2023-05-01T12:00:00Z", "deleted": null }"#).unwrap();
+	assert_eq!(post.created.timestamp(), 1682942400);
+	assert_eq!(post.deleted, None);
+
+	let post: Post = serde_json::from_str(r#"{ "created": "2023-05-01T12:00:00Z" }"#).unwrap();
+	assert_eq!(post.deleted, None);
+
+	assert!(serde_json::from_str::<Post>(r#"{ "created": "yesterday" }"#).is_err());
+}
@@ -0,0 +1,216 @@
+use robolt::api::trades::{
+	TradeDetails,
+	TradeEligibility,
+	TradeMetadata,
+	TradeOfferIssue,
+	TradeOfferItem,
+	TradeOfferSide,
+	TradeSide,
+};
+use robolt::api::Robux;
+
+fn metadata() -> TradeMetadata {
+	serde_json::from_str(include_str!("fixtures/trades/metadata.json")).unwrap()
+}
+
+fn side(values: &[u64], robux: u64) -> TradeOfferSide {
+	TradeOfferSide {
+		items: values
+			.iter()
+			.enumerate()
+			.map(|(i, value)| TradeOfferItem {
+				user_asset_id: i as u64 + 1,
+				value: Robux(*value),
+			})
+			.collect(),
+		robux: Robux(robux),
+	}
+}
+
+#[test]
+fn trade_eligibility_statuses() {
+	let eligibility = |json: &str| serde_json::from_str::<TradeEligibility>(json).unwrap();
+
+	assert!(eligibility(r#"{ "canTrade": true, "status": "CanTrade" }"#).is_eligible());
+	assert_eq!(
+		eligibility(r#"{ "canTrade": false, "status": "SenderCannotTrade" }"#),
+		TradeEligibility::SenderCannotTrade
+	);
+	assert_eq!(
+		eligibility(r#"{ "canTrade": false, "status": "ReceiverCannotTrade" }"#),
+		TradeEligibility::CannotTrade
+	);
+	assert_eq!(
+		eligibility(r#"{ "canTrade": false, "status": "ReceiverPrivacySettingsTooStrict" }"#),
+		TradeEligibility::Privacy
+	);
+	assert_eq!(
+		eligibility(r#"{ "canTrade": false, "status": "CannotTradeWithSelf" }"#),
+		TradeEligibility::CannotTradeWithSelf
+	);
+	assert_eq!(
+		eligibility(r#"{ "canTrade": false, "status": "UserUnderReview" }"#),
+		TradeEligibility::Unknown {
+			status: "UserUnderReview".to_string()
+		}
+	);
+}
+
+#[test]
+fn trade_metadata() {
+	let metadata = metadata();
+
+	assert_eq!(metadata.max_items_per_side, 4);
+	assert_eq!(metadata.min_value_ratio, 0.5);
+	assert_eq!(metadata.trade_system_max_robux_percent, Some(0.5));
+}
+
+#[test]
+fn valid_offer() {
+	assert_eq!(metadata().validate(&side(&[1000], 0), &side(&[1500], 0)), vec![]);
+}
+
+#[test]
+fn offer_breaking_item_and_robux_limits() {
+	let issues = metadata().validate(&side(&[100; 5], 300), &side(&[], 0));
+
+	assert_eq!(issues, vec![
+		TradeOfferIssue::TooManyItems {
+			side: TradeSide::Offer,
+			count: 5,
+			max: 4,
+		},
+		TradeOfferIssue::TooMuchRobux {
+			side: TradeSide::Offer,
+			robux: Robux(300),
+			max: Robux(250),
+		},
+		TradeOfferIssue::NoItems {
+			side: TradeSide::Request,
+		},
+	]);
+	assert_eq!(
+		issues[1].to_string(),
+		"The Offer side adds 300 Robux, at most 250 are allowed"
+	);
+}
+
+#[test]
+fn offer_worth_too_little() {
+	// 400 + 200 Robux after the 30% fee, against 1,200
+	let issues = metadata().validate(&side(&[400], 200), &side(&[1200], 0));
+
+	match issues.as_slice() {
+		[TradeOfferIssue::ValueRatioTooLow {
+			ratio,
+			min,
+		}] => {
+			assert!((ratio - 0.45).abs() < 1e-9);
+			assert_eq!(*min, 0.5);
+		},
+		issues => panic!("unexpected issues: {issues:?}"),
+	}
+}
+
+#[test]
+fn trade_details() {
+	let trade: TradeDetails = serde_json::from_str(include_str!("fixtures/trades/trade.json")).unwrap();
+
+	assert_eq!(trade.trade.id, 123456);
+	assert_eq!(trade.trade.user.username, "trader");
+	assert!(trade.trade.is_active);
+	assert_eq!(trade.offers.len(), 2);
+
+	let helm = &trade.offers[0].user_assets[0];
+	assert_eq!(helm.user_asset_id, 1001);
+	assert_eq!(helm.serial_number, Some(42));
+	assert_eq!(helm.recent_average_price, Robux(120000));
+	assert_eq!(trade.offers[0].user_assets[1].serial_number, None);
+	assert_eq!(trade.offers[1].robux, Robux(1000));
+
+	let offer = TradeOfferSide::from(&trade.offers[0]);
+	assert_eq!(offer.item_value(), Robux(123500));
+	assert!(metadata()
+		.validate(&offer, &TradeOfferSide::from(&trade.offers[1]))
+		.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn sending_and_answering_trades() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::api::trades::TradeOffer;
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use robolt::Robolt;
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		)
+		.route(
+			Method::POST,
+			"https://trades.roblox.com/v1/trades/send",
+			MockResponse::json(&json!({ "id": 123456 })),
+		)
+		.route(
+			Method::POST,
+			"https://trades.roblox.com/v1/trades/123456/decline",
+			MockResponse::json(&json!({})),
+		)
+		.route(
+			Method::POST,
+			"https://trades.roblox.com/v1/trades/654321/accept",
+			MockResponse::error(
+				StatusCode::FORBIDDEN,
+				0,
+				"Challenge is required to authorize the request",
+			)
+			.header("rblx-challenge-id", "challenge")
+			.header("rblx-challenge-type", "twostepverification"),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let trade = TradeOffer {
+		user_id: 2,
+		offer: side(&[1000], 100),
+		request: side(&[1500, 500], 0),
+	};
+	assert_eq!(client.send_trade(&trade).await.unwrap(), 123456);
+	client.decline_trade(123456).await.unwrap();
+
+	let err = client.accept_trade(654321).await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::ChallengeRequired {
+		id: "challenge".to_string(),
+		challenge_type: Some("twostepverification".to_string()),
+	});
+
+	let sent = transport
+		.requests()
+		.into_iter()
+		.find(|req| req.url.ends_with("/v1/trades/send"))
+		.unwrap();
+	assert_eq!(
+		sent.body,
+		Some(json!({
+			"offers": [
+				{ "userId": 7, "userAssetIds": [1], "robux": 100 },
+				{ "userId": 2, "userAssetIds": [1, 2], "robux": 0 }
+			]
+		}))
+	);
+}
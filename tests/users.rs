@@ -1,6 +1,7 @@
 use tokio_test::assert_ok;
 
-use robolt::api::Limit;
+use robolt::api::users::{is_placeholder_name, ModerationState, PartialUser, User};
+use robolt::api::{Limit, SearchResults, SortOrder};
 use robolt::Robolt;
 
 #[tokio::test]
@@ -21,10 +22,57 @@ async fn username_history() {
 	assert_ok!(client.username_history(1).await);
 }
 
+#[tokio::test]
+async fn username_history_page() {
+	let client = Robolt::new();
+	assert_ok!(client.username_history_page(1, Limit::Min, SortOrder::Desc, None).await);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn username_history_walks_every_page() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let url = "https://users.roblox.com/v1/users/1/username-history?limit=100&sortOrder=Asc";
+	let transport = MockTransport::new()
+		.route(
+			Method::GET,
+			url,
+			MockResponse::json(&json!({
+				"previousPageCursor": null,
+				"nextPageCursor": "next",
+				"data": ["first", "second"]
+			})),
+		)
+		.route(
+			Method::GET,
+			&format!("{url}&cursor=next"),
+			MockResponse::json(&json!({
+				"previousPageCursor": "next",
+				"nextPageCursor": null,
+				"data": ["third"]
+			})),
+		);
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	assert_eq!(client.username_history(1).await.unwrap(), ["first", "second", "third"]);
+	assert_eq!(transport.requests().len(), 2);
+
+	let mut pages = client
+		.username_history_pages(1, Limit::Max, SortOrder::Asc)
+		.max_pages(1);
+
+	assert_eq!(pages.next_page().await.unwrap().unwrap(), ["first", "second"]);
+	assert!(pages.next_page().await.is_none());
+	assert_eq!(pages.cursor(), Some("next"));
+}
+
 #[tokio::test]
 async fn search_users() {
 	let client = Robolt::new();
-	assert_ok!(client.search_users("test", Limit::Min).await);
+	assert_ok!(client.search_users("test", Limit::Min, None).await);
 }
 
 #[tokio::test]
@@ -38,3 +86,216 @@ async fn validate_display_name() {
 	let client = Robolt::new();
 	assert_ok!(client.validate_display_name("test", "01-01-1999").await);
 }
+
+#[test]
+fn moderation_state() {
+	let active: User = serde_json::from_str(include_str!("fixtures/users/active.json")).unwrap();
+	let banned: User = serde_json::from_str(include_str!("fixtures/users/banned.json")).unwrap();
+	let deleted: User = serde_json::from_str(include_str!("fixtures/users/content_deleted.json")).unwrap();
+
+	assert_eq!(active.moderation_state(), ModerationState::Active);
+	assert_eq!(banned.moderation_state(), ModerationState::Banned);
+	assert_eq!(deleted.moderation_state(), ModerationState::ContentDeleted);
+}
+
+#[test]
+fn placeholder_names() {
+	assert!(is_placeholder_name("[ Content Deleted ]"));
+	assert!(is_placeholder_name("[Content Deleted]"));
+	assert!(is_placeholder_name(" [ Content Deleted 123 ] "));
+	assert!(!is_placeholder_name("Content Deleted"));
+	assert!(!is_placeholder_name("[Builderman]"));
+	assert!(!is_placeholder_name(""));
+}
+
+#[test]
+fn user_search_results() {
+	let results: SearchResults<PartialUser> =
+		serde_json::from_str(include_str!("fixtures/users/search.json")).unwrap();
+
+	assert_eq!(results.items.len(), 2);
+	assert_eq!(results.items[1].display_name, "Tester");
+	assert!(results.next_page_cursor.is_some());
+	assert_eq!(results.keyword(), None);
+	assert!(results.extras.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn lookup_users_is_chunked() {
+	use reqwest::Method;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let users = |ids: &[u64]| {
+		let users = ids
+			.iter()
+			.map(|id| json!({ "id": id, "name": format!("user{id}"), "displayName": format!("User {id}") }))
+			.collect::<Vec<_>>();
+
+		MockResponse::json(&json!({ "data": users }))
+	};
+	let first = (1..=100).filter(|id| *id != 7).collect::<Vec<u64>>();
+	let second = (101..150).collect::<Vec<u64>>();
+	let transport = MockTransport::new()
+		.route(Method::POST, "https://users.roblox.com/v1/users", users(&first))
+		.route(Method::POST, "https://users.roblox.com/v1/users", users(&second));
+	let client = Robolt::new().mock_transport(transport.clone());
+
+	let lookup = client.lookup_users((1..=150).collect(), true).await.unwrap();
+
+	assert_eq!(lookup.found.len(), 148);
+	assert_eq!(lookup.found[&42].display_name, "User 42");
+	assert_eq!(lookup.not_found, vec![7, 150]);
+
+	let sizes = transport
+		.requests()
+		.iter()
+		.map(|request| request.body.as_ref().unwrap()["userIds"].as_array().unwrap().len())
+		.collect::<Vec<usize>>();
+	assert_eq!(sizes, vec![100, 50]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn lookup_usernames_keeps_requested_names() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::POST,
+			"https://users.roblox.com/v1/usernames/users",
+			MockResponse::json(&json!({
+				"data": [
+					{ "requestedUsername": "ROBLOX", "hasVerifiedBadge": true, "id": 1, "name": "Roblox", "displayName": "Roblox" }
+				]
+			})),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport)
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	let lookup = client
+		.lookup_usernames(vec!["ROBLOX", "nobody", "nobody"], false)
+		.await
+		.unwrap();
+
+	assert_eq!(lookup.found["ROBLOX"].id, 1);
+	assert_eq!(lookup.found["ROBLOX"].username, "Roblox");
+	assert_eq!(lookup.not_found, vec!["nobody".to_string()]);
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn display_name_changes() {
+	use reqwest::{Method, StatusCode};
+	use robolt::api::auth::RobloxCookie;
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let url = "https://users.roblox.com/v1/users/7/display-names";
+	let transport = MockTransport::new()
+		.route(
+			Method::POST,
+			"https://auth.roblox.com/v2/logout",
+			MockResponse::error(StatusCode::FORBIDDEN, 0, "Token Validation Failed")
+				.header("x-csrf-token", "token"),
+		)
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/authenticated",
+			MockResponse::json(&json!({ "id": 7, "name": "builder", "displayName": "Builder" })),
+		)
+		.route(Method::PATCH, url, MockResponse::json(&json!({})))
+		.route(
+			Method::PATCH,
+			url,
+			MockResponse::error(
+				StatusCode::FORBIDDEN,
+				5,
+				"Display name updates for this user have been throttled.",
+			),
+		);
+	let client = Robolt::new()
+		.mock_transport(transport.clone())
+		.set_cookie(RobloxCookie::parse("token").unwrap())
+		.await
+		.unwrap();
+
+	client.set_display_name("New Name").await.unwrap();
+	let err = client.set_display_name("Newer Name").await.unwrap_err();
+	assert_eq!(err.kind(), &RoboltErrorKind::DisplayNameCooldown);
+
+	let requests = transport.requests();
+	let lookups = requests
+		.iter()
+		.filter(|req| req.url.ends_with("/v1/users/authenticated"))
+		.count();
+	assert_eq!(lookups, 1);
+
+	let change = requests.iter().find(|req| req.url == url).unwrap();
+	assert_eq!(change.body, Some(json!({ "newDisplayName": "New Name" })));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn users_keep_order_and_failures() {
+	use std::sync::Mutex;
+
+	use reqwest::{Method, StatusCode};
+	use robolt::errors::RoboltErrorKind;
+	use robolt::test_util::{MockResponse, MockTransport};
+	use serde_json::json;
+
+	let user = |id: u64| {
+		MockResponse::json(&json!({
+			"name": format!("user{id}"),
+			"displayName": format!("User {id}"),
+			"description": "",
+			"created": "2006-02-27T21:06:40.3Z",
+			"isBanned": false,
+			"hasVerifiedBadge": false,
+			"id": id
+		}))
+	};
+	let transport = MockTransport::new()
+		.route(Method::GET, "https://users.roblox.com/v1/users/1", user(1))
+		.route(
+			Method::GET,
+			"https://users.roblox.com/v1/users/2",
+			MockResponse::error(StatusCode::NOT_FOUND, 3, "The user id is invalid."),
+		)
+		.route(Method::GET, "https://users.roblox.com/v1/users/3", user(3));
+	let client = Robolt::new().mock_transport(transport);
+	let progress = Mutex::new(Vec::new());
+
+	let users = client
+		.users_with_progress(&[3, 2, 1], 2, |done, total| {
+			progress.lock().unwrap().push((done, total));
+		})
+		.await;
+
+	assert_eq!(users.len(), 3);
+	assert_eq!(users[0].as_ref().unwrap().id, 3);
+	assert!(matches!(
+		users[1].as_ref().unwrap_err().kind(),
+		RoboltErrorKind::NotFound {
+			id: 2,
+			..
+		}
+	));
+	assert_eq!(users[2].as_ref().unwrap().username, "user1");
+	assert_eq!(*progress.lock().unwrap(), vec![(1, 3), (2, 3), (3, 3)]);
+}